@@ -1,12 +1,12 @@
 use composer::blob;
 use criterion::{criterion_group, criterion_main, Criterion};
 use ewasm::{Execute, Runtime};
+use sheth::config::Config;
 
-static SHETH_BINARY: &'static [u8] =
-    include_bytes!("../target/wasm32-unknown-unknown/release/sheth.wasm");
+static SHETH_BINARY: &[u8] = include_bytes!("../target/wasm32-unknown-unknown/release/sheth.wasm");
 
 fn large_proof(c: &mut Criterion) {
-    let (blob, pre_state, _) = blob::generate_with_roots(2, 1, 256);
+    let (blob, pre_state, _) = blob::generate_with_roots(2, 1, &Config::default());
     let blob = blob.to_bytes();
 
     c.bench_function("execute(2, 1, 256)", |b| {
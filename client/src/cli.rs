@@ -0,0 +1,252 @@
+use clap::{App, Arg, Shell, SubCommand};
+
+pub const NAME: &str = "sheth-client";
+pub const BIN: &str = "client";
+pub const VERSION: &str = "0.0.1";
+
+/// Description of a subcommand, used to build both the `clap` app and the output of
+/// `--help-json` so the two can't drift apart.
+pub struct Command {
+    pub name: &'static str,
+    pub about: &'static str,
+    pub args: &'static [Argument],
+}
+
+pub struct Argument {
+    pub name: &'static str,
+    pub long: Option<&'static str>,
+    pub short: Option<&'static str>,
+    pub default: Option<&'static str>,
+    pub values: &'static [&'static str],
+    pub takes_value: bool,
+    pub help: &'static str,
+}
+
+impl Argument {
+    const fn positional(name: &'static str, help: &'static str) -> Self {
+        Argument {
+            name,
+            long: None,
+            short: None,
+            default: None,
+            values: &[],
+            takes_value: true,
+            help,
+        }
+    }
+
+    const fn option(
+        name: &'static str,
+        short: &'static str,
+        default: &'static str,
+        help: &'static str,
+    ) -> Self {
+        Argument {
+            name,
+            long: Some(name),
+            short: Some(short),
+            default: Some(default),
+            values: &[],
+            takes_value: true,
+            help,
+        }
+    }
+
+    const fn flag(name: &'static str, help: &'static str) -> Self {
+        Argument {
+            name,
+            long: Some(name),
+            short: None,
+            default: None,
+            values: &[],
+            takes_value: false,
+            help,
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match (self.long, self.takes_value) {
+            (None, _) => "positional",
+            (Some(_), true) => "option",
+            (Some(_), false) => "flag",
+        }
+    }
+}
+
+const ACCOUNTS: Argument = Argument::positional(
+    "accounts",
+    "number of accounts that will be represented in the proof",
+);
+
+const HEIGHT: Argument = Argument::option(
+    "height",
+    "d",
+    "256",
+    "defines the height of sparse state structure",
+);
+
+pub const COMMANDS: &[Command] = &[
+    Command {
+        name: "package",
+        about: "Builds a random transaction package",
+        args: &[
+            ACCOUNTS,
+            Argument::positional("transactions", "number of transactions to be generated"),
+            HEIGHT,
+            Argument::flag(
+                "scout",
+                "When set, the output will be in the format of a Scout YAML file",
+            ),
+            Argument::flag(
+                "compress",
+                "When set, the blob is compressed and its sizes are printed to stderr",
+            ),
+        ],
+    },
+    Command {
+        name: "vanity",
+        about: "Searches for public keys whose addresses begin with a prefix",
+        args: &[
+            Argument::positional("prefix", "most significant bits of the address, e.g. 0110"),
+            Argument::positional("count", "number of public keys to find"),
+            HEIGHT,
+            Argument::option(
+                "seed",
+                "s",
+                "42",
+                "seed used to generate candidate public keys",
+            ),
+            Argument::option("threads", "t", "4", "number of workers to search with"),
+        ],
+    },
+    Command {
+        name: "start",
+        about: "Starts a Sheth client",
+        args: &[
+            ACCOUNTS,
+            HEIGHT,
+            Argument {
+                default: None,
+                ..Argument::option("labels", "l", "", "file of `0x<address> <label>` lines")
+            },
+        ],
+    },
+    Command {
+        name: "completions",
+        about: "Generates a shell completion script",
+        args: &[Argument {
+            values: &["bash", "elvish", "fish", "powershell", "zsh"],
+            ..Argument::positional("shell", "shell to generate the completion script for")
+        }],
+    },
+];
+
+const HELP_JSON: Argument = Argument::flag(
+    "help-json",
+    "Prints a description of the subcommands and their arguments as JSON",
+);
+
+pub fn app() -> App<'static, 'static> {
+    COMMANDS.iter().fold(
+        App::new(NAME)
+            .version(VERSION)
+            .author("Matt G. <git@garnett.dev>")
+            .about("Builds transaction packages for the Sheth EE")
+            .arg(arg(&HELP_JSON)),
+        |app, command| {
+            app.subcommand(
+                command.args.iter().fold(
+                    SubCommand::with_name(command.name)
+                        .about(command.about)
+                        .version(VERSION),
+                    |subcommand, a| subcommand.arg(arg(a)),
+                ),
+            )
+        },
+    )
+}
+
+fn arg(argument: &'static Argument) -> Arg<'static, 'static> {
+    let mut arg = Arg::with_name(argument.name).help(argument.help);
+
+    match argument.long {
+        Some(long) => arg = arg.long(long).takes_value(argument.takes_value),
+        None => arg = arg.required(true),
+    }
+
+    if let Some(short) = argument.short {
+        arg = arg.short(short);
+    }
+
+    if let Some(default) = argument.default {
+        arg = arg.default_value(default);
+    }
+
+    if !argument.values.is_empty() {
+        arg = arg.possible_values(argument.values);
+    }
+
+    arg
+}
+
+/// Writes the completion script for `shell` to stdout.
+pub fn completions(shell: Shell) {
+    app().gen_completions_to(BIN, shell, &mut std::io::stdout());
+}
+
+/// Returns a description of the CLI as JSON, so wrapper scripts can discover its subcommands and
+/// arguments without parsing the help text.
+pub fn help_json() -> String {
+    let commands: Vec<String> = COMMANDS
+        .iter()
+        .map(|command| {
+            let args: Vec<String> = command.args.iter().map(argument_json).collect();
+
+            format!(
+                "{{\"name\":{},\"about\":{},\"args\":[{}]}}",
+                string(command.name),
+                string(command.about),
+                args.join(",")
+            )
+        })
+        .collect();
+
+    format!(
+        "{{\"name\":{},\"version\":{},\"flags\":[{}],\"subcommands\":[{}]}}",
+        string(NAME),
+        string(VERSION),
+        argument_json(&HELP_JSON),
+        commands.join(",")
+    )
+}
+
+fn argument_json(argument: &Argument) -> String {
+    let optional = |s: Option<&str>| s.map(string).unwrap_or_else(|| "null".to_string());
+    let values: Vec<String> = argument.values.iter().map(|v| string(v)).collect();
+
+    format!(
+        "{{\"name\":{},\"kind\":{},\"long\":{},\"short\":{},\"default\":{},\"values\":[{}],\
+         \"required\":{},\"help\":{}}}",
+        string(argument.name),
+        string(argument.kind()),
+        optional(argument.long),
+        optional(argument.short),
+        optional(argument.default),
+        values.join(","),
+        argument.long.is_none(),
+        string(argument.help)
+    )
+}
+
+fn string(s: &str) -> String {
+    let escaped: String = s
+        .chars()
+        .flat_map(|c| match c {
+            '"' => vec!['\\', '"'],
+            '\\' => vec!['\\', '\\'],
+            c => vec![c],
+        })
+        .collect();
+
+    format!("\"{}\"", escaped)
+}
@@ -1,8 +1,10 @@
 use super::error::Error;
 use bigint::U256;
 use composer::accounts::AddressedAccount;
+use composer::labels::Labels;
 use composer::transactions::serialize;
 use imp::Imp;
+use sheth::amount::Amount;
 use sheth::process::process_transactions;
 use sheth::state::State;
 use sheth::transaction::{Transaction, Transfer};
@@ -30,7 +32,7 @@ pub struct BalanceCmd {
 pub struct TransferCmd {
     pub(crate) from: U256,
     pub(crate) to: U256,
-    pub(crate) amount: u64,
+    pub(crate) amount: Amount,
 }
 
 /// The accounts command will list the accounts managed by the client.
@@ -60,10 +62,12 @@ impl TransferCmd {
             from: self.from.into(),
             nonce,
             amount: self.amount,
+            fee: Amount::default(),
+            valid_until: None,
             signature: [0u8; 96],
         });
 
-        let mut body = serialize(&vec![tx.clone()]);
+        let mut body = serialize(self.from.into(), 0, &vec![tx.clone()]);
         body.extend(imp_to_bytes(db));
         let mut request: HashMap<String, String> = HashMap::new();
         request.insert("block_body".to_string(), hex::encode(body));
@@ -82,11 +86,15 @@ impl TransferCmd {
 }
 
 impl AccountsCmd {
-    pub fn execute(&self, accounts: &Vec<AddressedAccount>) -> Result<(), Error> {
+    pub fn execute(&self, accounts: &Vec<AddressedAccount>, labels: &Labels) -> Result<(), Error> {
         for account in accounts {
             let mut buf = [0u8; 32];
             account.0.to_big_endian(&mut buf);
-            println!("0x{}", hex::encode(buf));
+
+            match labels.label(account.0) {
+                Some(label) => println!("0x{} {}", hex::encode(buf), label),
+                None => println!("0x{}", hex::encode(buf)),
+            }
         }
 
         Ok(())
@@ -129,7 +137,7 @@ mod test {
         TransferCmd {
             from: accounts[0].0,
             to: accounts[1].0,
-            amount: 45,
+            amount: 45.into(),
         }
     }
 
@@ -161,7 +169,10 @@ mod test {
     #[test]
     fn accounts_ok() {
         create_db!(blob, _db, 2, 256);
-        assert_eq!(Ok(()), AccountsCmd {}.execute(&blob.accounts));
+        assert_eq!(
+            Ok(()),
+            AccountsCmd {}.execute(&blob.accounts, &Labels::new())
+        );
     }
 
     #[test]
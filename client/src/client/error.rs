@@ -4,7 +4,7 @@ pub enum Error {
     /// Unable to parse command string into a new command
     CommandUnknown(String),
 
-    /// The amount could not be parsed into an integer
+    /// The amount could not be parsed into an integer below `Amount::MAX`
     AmountInvalid(String),
 
     /// The address could not be parsed into a 32 byte value
@@ -4,6 +4,7 @@ mod parse;
 
 use command::Command;
 use composer::blob;
+use composer::labels::Labels;
 use dialoguer::{theme::CustomPromptCharacterTheme, Input};
 use imp::Imp;
 use parse::parse_command;
@@ -11,7 +12,7 @@ use sheth::u264::U264;
 use std::io;
 use std::io::prelude::*;
 
-pub fn start(accounts: usize, tree_height: usize) {
+pub fn start(accounts: usize, tree_height: usize, labels: Labels) {
     println!("Starting sheth client");
 
     print!("Initializing database ... ");
@@ -32,13 +33,15 @@ pub fn start(accounts: usize, tree_height: usize) {
             .interact()
             .unwrap();
 
-        let command = parse_command(command_str);
+        // Labels are replaced by their addresses before parsing, so they can be used anywhere an
+        // address is expected.
+        let command = parse_command(labels.expand(&command_str));
 
         let result = match command {
             Ok(Command::Balance(b)) => b.execute(&db),
             Ok(Command::Transfer(t)) => t.execute(&mut db),
             Ok(Command::Exit) => std::process::exit(0),
-            Ok(Command::Accounts(a)) => a.execute(&accounts),
+            Ok(Command::Accounts(a)) => a.execute(&accounts, &labels),
             Err(e) => Err(e),
         };
 
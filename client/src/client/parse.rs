@@ -1,7 +1,9 @@
 use super::command::{AccountsCmd, BalanceCmd, Command, TransferCmd};
 use super::error::Error;
-use arrayref::array_ref;
 use bigint::U256;
+use sheth::address::Address;
+use sheth::amount::Amount;
+use std::convert::TryFrom;
 
 /// Parse a given string and return the resulting `Command` or `Error`.
 pub fn parse_command(command: String) -> Result<Command, Error> {
@@ -38,7 +40,9 @@ pub fn parse_transfer(transfer_args: Vec<&str>) -> Result<TransferCmd, Error> {
     let to = parse_address(transfer_args[1])?;
     let amount = transfer_args[2]
         .parse::<u64>()
-        .map_err(|_| Error::AmountInvalid(transfer_args[2].to_string()))?;
+        .ok()
+        .and_then(|amount| Amount::new(amount).ok())
+        .ok_or_else(|| Error::AmountInvalid(transfer_args[2].to_string()))?;
 
     Ok(TransferCmd { from, to, amount })
 }
@@ -54,27 +58,16 @@ pub fn parse_accounts(accounts_args: Vec<&str>) -> Result<AccountsCmd, Error> {
 
 /// Parse a hex string into a U256 value or return `Error`.
 pub fn parse_address(s: &str) -> Result<U256, Error> {
-    // If the address is prepended by `"0x"`, strip it.
-    let s = if s.len() > 2 && &s[0..2] == "0x" {
-        &s[2..]
-    } else {
-        &s
-    };
-
-    let bytes = hex::decode(s).map_err(|_| Error::AddressInvalid(s.to_string()))?;
-
-    if bytes.len() != 32 {
-        Err(Error::AddressInvalid(s.to_string()))
-    } else {
-        Ok(U256::from(array_ref![bytes, 0, 32]))
-    }
+    Address::try_from(s)
+        .map(U256::from)
+        .map_err(|_| Error::AddressInvalid(s.to_string()))
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const ADDRESS: &str = "b94F5eA0ba39494cE839613fffBA74279579268A742795792683424567896523";
+    const ADDRESS: &str = "b94F5eA0BA39494cE839613fFfBa74279579268A742795792683424567896523";
     const PARSED_ADDRESS: [u8; 32] = [
         185, 79, 94, 160, 186, 57, 73, 76, 232, 57, 97, 63, 255, 186, 116, 39, 149, 121, 38, 138,
         116, 39, 149, 121, 38, 131, 66, 69, 103, 137, 101, 35,
@@ -96,7 +89,7 @@ mod test {
         TransferCmd {
             from: from_to,
             to: from_to,
-            amount: 34,
+            amount: 34.into(),
         }
     }
 
@@ -248,6 +241,13 @@ mod test {
             parse_transfer(wrong_arguments).unwrap_err()
         );
 
+        let mut large_arguments = create_correct_transfer_arguments();
+        large_arguments[2] = "18446744073709551615";
+        assert_eq!(
+            Error::AmountInvalid("18446744073709551615".to_string()),
+            parse_transfer(large_arguments).unwrap_err()
+        );
+
         let mut long_arguments = create_correct_transfer_arguments();
         long_arguments.push("a");
         let check_long_arguments = long_arguments.clone();
@@ -294,4 +294,9 @@ mod test {
 
         assert_address_invalid!(&long_address);
     }
+
+    #[test]
+    fn parse_address_bad_checksum_ko() {
+        assert_address_invalid!("b94F5eA0ba39494cE839613fffBA74279579268A742795792683424567896523");
+    }
 }
@@ -1,62 +1,23 @@
 #[macro_use]
 extern crate clap;
 
+mod cli;
 mod client;
 mod package;
+mod vanity;
 
-use clap::{App, Arg, SubCommand};
+use clap::Shell;
+use composer::labels::Labels;
+use composer::vanity::Prefix;
+use sheth::config::Config;
 
 fn main() {
-    let matches = App::new("sheth-client")
-        .version("0.0.1")
-        .author("Matt G. <git@garnett.dev>")
-        .about("Builds transaction packages for the Sheth EE")
-        .subcommand(
-            SubCommand::with_name("package")
-                .about("Builds a random transaction package")
-                .version("0.0.1")
-                .arg(
-                    Arg::with_name("accounts")
-                        .required(true)
-                        .help("number of accounts that will be represented in the proof"),
-                )
-                .arg(
-                    Arg::with_name("transactions")
-                        .required(true)
-                        .help("number of transactions to be generated"),
-                )
-                .arg(
-                    Arg::with_name("height")
-                        .long("height")
-                        .short("d")
-                        .takes_value(true)
-                        .default_value("256")
-                        .help("defines the height of sparse state structure"),
-                )
-                .arg(
-                    Arg::with_name("scout")
-                        .long("scout")
-                        .help("When set, the output will be in the format of a Scout YAML file"),
-                ),
-        )
-        .subcommand(
-            SubCommand::with_name("start")
-                .about("Starts a Sheth client")
-                .arg(
-                    Arg::with_name("accounts")
-                        .required(true)
-                        .help("number of accounts that will be represented in the proof"),
-                )
-                .arg(
-                    Arg::with_name("height")
-                        .long("height")
-                        .short("d")
-                        .takes_value(true)
-                        .default_value("256")
-                        .help("defines the height of sparse state structure"),
-                ),
-        )
-        .get_matches();
+    let matches = cli::app().get_matches();
+
+    if matches.is_present("help-json") {
+        println!("{}", cli::help_json());
+        return;
+    }
 
     // Run packager
     if let Some(matches) = matches.subcommand_matches("package") {
@@ -64,8 +25,24 @@ fn main() {
         let txs = value_t!(matches.value_of("transactions"), usize).unwrap_or_else(|e| e.exit());
         let height = value_t!(matches.value_of("height"), usize).unwrap_or_else(|e| e.exit());
         let scout = matches.is_present("scout");
+        let compress = matches.is_present("compress");
+
+        let (output, stats) = package::build(accounts, txs, &Config::new(height), scout, compress);
+        if let Some(stats) = stats {
+            eprintln!("{}", stats);
+        }
+        println!("{}", output);
+    }
 
-        let output = package::build(accounts, txs, height, scout);
+    // Run vanity search
+    if let Some(matches) = matches.subcommand_matches("vanity") {
+        let prefix = value_t!(matches.value_of("prefix"), Prefix).unwrap_or_else(|e| e.exit());
+        let count = value_t!(matches.value_of("count"), usize).unwrap_or_else(|e| e.exit());
+        let height = value_t!(matches.value_of("height"), usize).unwrap_or_else(|e| e.exit());
+        let seed = value_t!(matches.value_of("seed"), u64).unwrap_or_else(|e| e.exit());
+        let threads = value_t!(matches.value_of("threads"), usize).unwrap_or_else(|e| e.exit());
+
+        let output = vanity::search(prefix, count, height, seed, threads);
         println!("{}", output);
     }
 
@@ -73,7 +50,27 @@ fn main() {
     if let Some(matches) = matches.subcommand_matches("start") {
         let accounts = value_t!(matches.value_of("accounts"), usize).unwrap_or_else(|e| e.exit());
         let height = value_t!(matches.value_of("height"), usize).unwrap_or_else(|e| e.exit());
+        let labels = match matches.value_of("labels") {
+            Some(path) => {
+                let labels = std::fs::read_to_string(path).unwrap_or_else(|e| {
+                    eprintln!("Unable to read labels: {}", e);
+                    std::process::exit(1)
+                });
+
+                labels.parse::<Labels>().unwrap_or_else(|e| {
+                    eprintln!("Unable to parse labels: {:?}", e);
+                    std::process::exit(1)
+                })
+            }
+            None => Labels::new(),
+        };
+
+        client::start(accounts, height, labels);
+    }
 
-        client::start(accounts, height);
+    // Generate shell completions
+    if let Some(matches) = matches.subcommand_matches("completions") {
+        let shell = value_t!(matches.value_of("shell"), Shell).unwrap_or_else(|e| e.exit());
+        cli::completions(shell);
     }
 }
@@ -1,9 +1,50 @@
 use composer::blob;
+use sheth::compress::compress;
+use sheth::config::Config;
+use std::fmt;
 
-pub fn build(accounts: usize, transactions: usize, height: usize, scout: bool) -> String {
-    let (blob, pre_state, post_state) = blob::generate_with_roots(accounts, transactions, height);
+/// Sizes of a package's blob before and after compression.
+#[derive(Debug, PartialEq)]
+pub struct Stats {
+    pub raw: usize,
+    pub compressed: usize,
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "raw: {} bytes, compressed: {} bytes ({:.1}%)",
+            self.raw,
+            self.compressed,
+            100.0 * self.compressed as f64 / self.raw as f64
+        )
+    }
+}
+
+/// Builds a random package. If `compressed` is set, the blob is compressed with
+/// `sheth::compress` and the sizes before and after are returned with the package.
+pub fn build(
+    accounts: usize,
+    transactions: usize,
+    config: &Config,
+    scout: bool,
+    compressed: bool,
+) -> (String, Option<Stats>) {
+    let (blob, pre_state, post_state) = blob::generate_with_roots(accounts, transactions, config);
+
+    let mut data = blob.to_bytes();
+    let mut stats = None;
+    if compressed {
+        let raw = data.len();
+        data = compress(&data);
+        stats = Some(Stats {
+            raw,
+            compressed: data.len(),
+        });
+    }
 
-    if scout {
+    let output = if scout {
         format!(
             "\
 beacon_state:
@@ -19,7 +60,7 @@ shard_post_state:
     exec_env_states:
         - \"{}\"",
             hex::encode(pre_state),
-            hex::encode(blob.to_bytes()),
+            hex::encode(&data),
             hex::encode(post_state)
         )
     } else {
@@ -27,7 +68,36 @@ shard_post_state:
             "{} {} {}",
             hex::encode(pre_state),
             hex::encode(post_state),
-            hex::encode(blob.to_bytes()),
+            hex::encode(&data),
         )
+    };
+
+    (output, stats)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use sheth::compress::process_compressed_data_blob;
+
+    #[test]
+    fn compressed_package() {
+        let config = Config::new(16);
+        let (output, stats) = build(10, 5, &config, false, true);
+        let fields: Vec<Vec<u8>> = output
+            .split(' ')
+            .map(|field| hex::decode(field).unwrap())
+            .collect();
+
+        let stats = stats.unwrap();
+        assert_eq!(stats.compressed, fields[2].len());
+        assert!(stats.compressed < stats.raw);
+
+        let mut pre_state = [0u8; 32];
+        pre_state.copy_from_slice(&fields[0]);
+        assert_eq!(
+            process_compressed_data_blob(&fields[2], &pre_state, &config),
+            Ok(*arrayref::array_ref![fields[1], 0, 32])
+        );
     }
 }
@@ -0,0 +1,18 @@
+use composer::accounts::AddressedAccount;
+use composer::vanity::{self, Prefix};
+
+pub fn search(prefix: Prefix, n: usize, height: usize, seed: u64, threads: usize) -> String {
+    vanity::search(prefix, n, height, seed, threads)
+        .iter()
+        .map(|AddressedAccount(address, account)| {
+            let mut buf = [0u8; 32];
+            address.to_big_endian(&mut buf);
+            format!(
+                "0x{} 0x{}",
+                hex::encode(buf),
+                hex::encode(&account.pubkey.as_bytes()[..])
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
@@ -1,34 +1,29 @@
+use crate::rng::DeterministicRng;
 use bigint::U256;
-use rand::{rngs::StdRng, Rng, SeedableRng};
-use sha2::{Digest, Sha256};
 use sheth::account::Account;
+use sheth::address::Address;
 use sheth::bls::PublicKey;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// A tuple consisting of an `Account` and its address.
 ///
 /// The address is important for other stages in the `Blob` generation process since it defines
 /// where in the multi-proof the account resides.
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct AddressedAccount(pub U256, pub Account);
 
 pub fn random_accounts(n: usize, height: usize) -> Vec<AddressedAccount> {
     // TODO: check that the number of accounts can be generated from the tree
 
-    let mut rng = StdRng::seed_from_u64(42);
+    let mut rng = DeterministicRng::new(42);
     let mut map: HashMap<U256, bool> = HashMap::new();
 
     (0..n).fold(vec![], |mut acc, _| {
         let mut pubkey = [0u8; 48];
         let address = loop {
-            rng.fill(&mut pubkey[..]);
+            rng.fill(&mut pubkey);
 
-            // Hash public key to get address
-            let mut address = U256::from(Sha256::digest(&pubkey).as_ref());
-
-            if height < 256 {
-                address = address % (U256::one() << height);
-            }
+            let address = address_from_pubkey(&pubkey, height);
 
             if !map.contains_key(&address) {
                 map.insert(address, true);
@@ -40,8 +35,8 @@ pub fn random_accounts(n: usize, height: usize) -> Vec<AddressedAccount> {
             address,
             Account {
                 pubkey: PublicKey::new(pubkey),
-                nonce: rng.gen(),
-                value: rng.gen_range(1, 1000),
+                nonce: rng.next_u64(),
+                value: rng.range(1, 1000),
             },
         ));
 
@@ -52,9 +47,77 @@ pub fn random_accounts(n: usize, height: usize) -> Vec<AddressedAccount> {
     })
 }
 
+/// Generate `n` accounts whose addresses share the same `shared_bits` most significant bits, so
+/// their branches in the state tree overlap until a depth of `shared_bits`.
+///
+/// Finding public keys which hash to such addresses is infeasible for long prefixes, so unlike
+/// `random_accounts` the addresses are chosen directly and are not derived from the public keys.
+/// Use `vanity::search` if the addresses must match the public keys.
+pub fn adjacent_accounts(n: usize, height: usize, shared_bits: usize) -> Vec<AddressedAccount> {
+    assert!(
+        shared_bits <= height,
+        "shared prefix is longer than the tree height"
+    );
+
+    let free_bits = height - shared_bits;
+    if free_bits < 64 {
+        assert!(
+            n as u64 <= 1u64 << free_bits,
+            "not enough addresses share the prefix"
+        );
+    }
+
+    let mut rng = DeterministicRng::new(42);
+    let mut seen: HashSet<U256> = HashSet::new();
+
+    let mut buf = [0u8; 32];
+    rng.fill(&mut buf);
+    let prefix = (truncate(U256::from(buf), height) >> free_bits) << free_bits;
+
+    (0..n).fold(vec![], |mut acc, _| {
+        let address = loop {
+            rng.fill(&mut buf);
+            let address = prefix + truncate(U256::from(buf), free_bits);
+
+            if seen.insert(address) {
+                break address;
+            }
+        };
+
+        let mut pubkey = [0u8; 48];
+        rng.fill(&mut pubkey);
+
+        acc.push(AddressedAccount(
+            address,
+            Account {
+                pubkey: PublicKey::new(pubkey),
+                nonce: rng.next_u64(),
+                value: rng.range(1, 1000),
+            },
+        ));
+
+        acc
+    })
+}
+
+/// Hash a public key to determine its address in a tree of `height`.
+pub fn address_from_pubkey(pubkey: &[u8; 48], height: usize) -> U256 {
+    Address::from_pubkey(&PublicKey::new(*pubkey), height).into()
+}
+
+/// Keep only the `bits` least significant bits of `n`.
+fn truncate(n: U256, bits: usize) -> U256 {
+    if bits < 256 {
+        n % (U256::one() << bits)
+    } else {
+        n
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use sha2::{Digest, Sha256};
 
     #[test]
     fn generates_random_accounts() {
@@ -71,4 +134,34 @@ mod test {
             assert_ne!(account.value, 0);
         }
     }
+
+    #[test]
+    fn generates_adjacent_accounts() {
+        let accounts = adjacent_accounts(8, 256, 250);
+        let prefix = accounts[0].0 >> 6;
+
+        let mut addresses: Vec<U256> = accounts.iter().map(|a| a.0).collect();
+        addresses.sort();
+        addresses.dedup();
+        assert_eq!(addresses.len(), 8);
+
+        for AddressedAccount(address, account) in accounts {
+            assert_eq!(address >> 6, prefix);
+            assert_ne!(account.value, 0);
+        }
+    }
+
+    #[test]
+    fn generates_adjacent_accounts_filling_subtree() {
+        let mut addresses: Vec<U256> = adjacent_accounts(4, 8, 6).iter().map(|a| a.0).collect();
+
+        addresses.sort();
+
+        let first = addresses[0];
+        assert_eq!(first % 4.into(), 0.into());
+        assert_eq!(
+            addresses,
+            vec![first, first + 1.into(), first + 2.into(), first + 3.into()]
+        );
+    }
 }
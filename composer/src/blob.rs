@@ -1,9 +1,11 @@
 use crate::accounts::{random_accounts, AddressedAccount};
-use crate::proof::offsets::calculate as calculate_offsets;
 use crate::proof::uncompressed::generate as generate_uncompressed_proof;
 use crate::transactions;
 use imp::Imp;
-use sheth::process::process_transactions;
+use sheth::address::Address;
+use sheth::config::Config;
+use sheth::process::execute;
+use sheth::process::settle_fees;
 use sheth::transaction::Transaction;
 use sheth::u264::U264;
 
@@ -11,6 +13,8 @@ use sheth::u264::U264;
 #[derive(Clone)]
 pub struct Blob {
     pub proof: Vec<u8>,
+    pub proposer: Address,
+    pub slot: u64,
     pub transactions: Vec<Transaction>,
     pub accounts: Vec<AddressedAccount>,
 }
@@ -18,7 +22,7 @@ pub struct Blob {
 impl Blob {
     /// Returns a serialized blob that can be used as input to `sheth`.
     pub fn to_bytes(&self) -> Vec<u8> {
-        let mut ret = transactions::serialize(&self.transactions);
+        let mut ret = transactions::serialize(self.proposer, self.slot, &self.transactions);
         ret.extend(&self.proof);
         ret
     }
@@ -26,23 +30,32 @@ impl Blob {
 
 /// Build a blob with specified tree height, accounts, and transactions.
 pub fn generate(accounts: usize, transactions: usize, tree_height: usize) -> Blob {
-    let accounts = random_accounts(accounts, tree_height);
+    generate_from_accounts(
+        random_accounts(accounts, tree_height),
+        transactions,
+        tree_height,
+    )
+}
+
+/// Build a blob with specified tree height and transactions between the given accounts.
+pub fn generate_from_accounts(
+    accounts: Vec<AddressedAccount>,
+    transactions: usize,
+    tree_height: usize,
+) -> Blob {
     let proof = generate_uncompressed_proof(accounts.clone(), tree_height);
-    let offsets = calculate_offsets(proof.indexes);
     let transactions = transactions::generate(transactions, accounts.clone());
 
-    let mut compressed_proof = offsets.iter().fold(vec![], |mut acc, x| {
-        acc.extend(&x.to_le_bytes());
-        acc
-    });
-
-    compressed_proof = proof.values.iter().fold(compressed_proof, |mut acc, x| {
-        acc.extend(x.as_bytes());
-        acc
-    });
+    // The proposer is one of the accounts in the proof, so it can be paid if there are fees.
+    let proposer = accounts
+        .first()
+        .map(|a| Address::from(a.0))
+        .unwrap_or_else(|| 0.into());
 
     Blob {
-        proof: compressed_proof,
+        proof: proof.compress(),
+        proposer,
+        slot: 0,
         transactions,
         accounts,
     }
@@ -52,16 +65,17 @@ pub fn generate(accounts: usize, transactions: usize, tree_height: usize) -> Blo
 pub fn generate_with_roots(
     accounts: usize,
     transactions: usize,
-    tree_height: usize,
+    config: &Config,
 ) -> (Blob, [u8; 32], [u8; 32]) {
-    let mut blob = generate(accounts, transactions, tree_height);
+    let mut blob = generate(accounts, transactions, config.height);
     let ret_blob = blob.clone();
 
-    let mut mem = Imp::<U264>::new(&mut blob.proof, tree_height + 3);
+    let mut mem = Imp::<U264>::new(&mut blob.proof, config.proof_height());
 
-    let pre_state = mem.root();
-    assert_eq!(process_transactions(&mut mem, &blob.transactions), Ok(()));
-    let post_state = mem.root();
+    let pre_state = config.root(&mem);
+    let result = execute(&mut mem, &blob.transactions, &config.limits).expect("block to execute");
+    settle_fees(&mut mem, blob.proposer, result.fees, config.fees).expect("fees to be settled");
+    let post_state = config.root(&mem);
 
     (ret_blob, pre_state, post_state)
 }
@@ -70,22 +84,29 @@ pub fn generate_with_roots(
 mod test {
     use super::*;
     use arrayref::array_ref;
+    use bigint::U256;
+    use sheth::account::Account;
+    use sheth::amount::Amount;
+    use sheth::bls::PublicKey;
+    use sheth::process::process_transactions;
+    use sheth::state::root::imp_root;
 
     #[test]
     fn generate_small_tree() {
-        // Indexes = [16, 17, 9, 10, 11, 3]
+        // Header = [0 transactions, proposer 1, slot 0], indexes = [16, 17, 9, 10, 11, 3]
         let mut proof = vec![
-            0, 0, 0, 0, 6, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 2,
-            0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 218, 109, 128,
-            123, 247, 149, 16, 97, 70, 229, 130, 39, 117, 217, 20, 176, 39, 122, 101, 36, 15, 101,
-            14, 212, 200, 167, 202, 119, 130, 78, 90, 223, 120, 72, 181, 215, 17, 188, 152, 131,
-            153, 99, 23, 163, 249, 201, 2, 105, 213, 103, 113, 0, 93, 84, 10, 25, 24, 73, 57, 201,
-            232, 208, 219, 42, 85, 242, 146, 169, 167, 93, 196, 41, 170, 134, 245, 251, 132, 117,
-            101, 88, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 197, 33, 10, 45, 228, 168,
-            212, 211, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 192,
-            1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
             0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-            0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 6, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0,
+            0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0,
+            0, 0, 0, 0, 0, 218, 109, 128, 123, 247, 149, 16, 97, 70, 229, 130, 39, 117, 217, 20,
+            176, 39, 122, 101, 36, 15, 101, 14, 212, 200, 167, 202, 119, 130, 78, 90, 223, 120, 72,
+            181, 215, 17, 188, 152, 131, 153, 99, 23, 163, 249, 201, 2, 105, 213, 103, 113, 0, 93,
+            84, 10, 25, 24, 73, 57, 201, 232, 208, 219, 42, 85, 242, 146, 169, 167, 93, 196, 41,
+            170, 134, 245, 251, 132, 117, 101, 88, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            197, 33, 10, 45, 228, 168, 212, 211, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 192, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         ];
 
         let root = vec![
@@ -94,7 +115,619 @@ mod test {
         ];
 
         assert_eq!(generate(1, 0, 1).to_bytes(), proof);
-        let mut mem = Imp::<U264>::new(&mut proof[4..], 4);
-        assert_eq!(mem.root(), *array_ref![root, 0, 32]);
+        let mem = Imp::<U264>::new(&mut proof[44..], 4);
+        assert_eq!(imp_root(&mem), *array_ref![root, 0, 32]);
+    }
+
+    /// Executes a blob against its multi-proof and applies the same transactions to the full set
+    /// of accounts, then checks that the multi-proof rebuilt from the resulting accounts matches
+    /// the executed one chunk for chunk.
+    fn assert_round_trip(accounts: Vec<AddressedAccount>, transactions: usize, height: usize) {
+        let mut blob = generate_from_accounts(accounts, transactions, height);
+
+        let mut executed = blob.proof.clone();
+        let mut mem = Imp::<U264>::new(&mut executed, height + 3);
+        assert_eq!(process_transactions(&mut mem, &blob.transactions), Ok(()));
+        let post_root = imp_root(&mem);
+
+        assert_eq!(
+            crate::transactions::apply(
+                &blob.transactions,
+                blob.proposer,
+                blob.slot,
+                &mut blob.accounts
+            ),
+            Ok(())
+        );
+
+        let mut rebuilt = generate_from_accounts(blob.accounts, 0, height).proof;
+        let mem = Imp::<U264>::new(&mut rebuilt, height + 3);
+        assert_eq!(imp_root(&mem), post_root);
+
+        assert_eq!(rebuilt, executed);
+    }
+
+    #[test]
+    fn round_trip_random_accounts() {
+        for &height in &[1, 4, 16, 160, 256] {
+            for &(accounts, transactions) in &[(1, 1), (2, 1), (2, 10), (8, 30)] {
+                if accounts > 1 << height.min(16) {
+                    continue;
+                }
+
+                assert_round_trip(random_accounts(accounts, height), transactions, height);
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip_adjacent_accounts() {
+        use crate::accounts::adjacent_accounts;
+
+        for &shared_bits in &[0, 128, 250, 254] {
+            assert_round_trip(adjacent_accounts(4, 256, shared_bits), 20, 256);
+        }
+    }
+
+    #[test]
+    fn round_trip_first_and_last_leaves() {
+        // At full height the last account's chunks are the deepest in `U264`.
+        let accounts = [U256::zero(), U256::max_value()]
+            .iter()
+            .map(|address| {
+                AddressedAccount(
+                    *address,
+                    Account {
+                        pubkey: PublicKey::zero(),
+                        nonce: 0,
+                        value: 100,
+                    },
+                )
+            })
+            .collect();
+
+        assert_round_trip(accounts, 10, sheth::account::MAX_HEIGHT);
+    }
+
+    #[test]
+    fn short_addresses_shrink_proofs() {
+        use sheth::account::{MAX_HEIGHT, SHORT_ADDRESS_HEIGHT};
+
+        let short = generate(8, 10, SHORT_ADDRESS_HEIGHT);
+        let full = generate(8, 10, MAX_HEIGHT);
+
+        assert!(short.proof.len() < full.proof.len());
+        assert!(short
+            .accounts
+            .iter()
+            .all(|account| account.0 >> SHORT_ADDRESS_HEIGHT == U256::zero()));
+    }
+
+    #[test]
+    fn generate_deeply_shared_branches() {
+        use crate::accounts::adjacent_accounts;
+        use sheth::state::State;
+
+        let accounts = adjacent_accounts(16, 256, 252);
+        let mut blob = generate_from_accounts(accounts.clone(), 0, 256);
+        let mem = Imp::<U264>::new(&mut blob.proof, 259);
+
+        for AddressedAccount(address, account) in accounts {
+            assert_eq!(
+                mem.value(address.into()),
+                Ok(Amount::new(account.value).unwrap())
+            );
+            assert_eq!(mem.nonce(address.into()), Ok(account.nonce.into()));
+        }
+    }
+
+    /// A state description: (address, nonce, value, byte the pubkey is filled with).
+    type GoldenAccount = (&'static str, u64, u64, u8);
+
+    fn golden_root(height: usize, accounts: &[GoldenAccount]) -> String {
+        let accounts = accounts
+            .iter()
+            .map(|(address, nonce, value, pubkey)| {
+                let address = hex::decode(format!("{:0>64}", address)).unwrap();
+                AddressedAccount(
+                    U256::from(&address[..]),
+                    Account {
+                        pubkey: PublicKey::new([*pubkey; 48]),
+                        nonce: *nonce,
+                        value: *value,
+                    },
+                )
+            })
+            .collect();
+
+        let mut proof = generate_from_accounts(accounts, 0, height).proof;
+        hex::encode(imp_root(&Imp::<U264>::new(&mut proof, height + 3)))
+    }
+
+    /// Roots of known states, so any change to hashing or index math which alters the root of
+    /// the state is caught.
+    const GOLDEN_ROOTS: &[(usize, &[GoldenAccount], &str)] = &[
+        // zero account at first leaf
+        (
+            1,
+            &[("0", 0, 0, 0)],
+            "c356a96eb1790f3a8562c70d6f90ae5484ec1955fbdce4654b6f2ab192bacb99",
+        ),
+        // zero account at last leaf
+        (
+            1,
+            &[("1", 0, 0, 0)],
+            "c356a96eb1790f3a8562c70d6f90ae5484ec1955fbdce4654b6f2ab192bacb99",
+        ),
+        // single account
+        (
+            1,
+            &[("0", 7, 1000, 170)],
+            "53f7b894a01f1efb4f1a786d6fab077127b39f9be4de588ac7cb302374e52545",
+        ),
+        // full tree
+        (
+            1,
+            &[("0", 1, 2, 1), ("1", 3, 4, 2)],
+            "9a57488648340a0da131cefd1c7e2bf4245f58db9e579d536409b48aba378b12",
+        ),
+        // zero account at first leaf
+        (
+            2,
+            &[("0", 0, 0, 0)],
+            "7b7a40ea65e15b5d6722d88b7d5ebc8198747bafe7980e6c1aeef8ffdf0d41cd",
+        ),
+        // zero account at last leaf
+        (
+            2,
+            &[("3", 0, 0, 0)],
+            "7b7a40ea65e15b5d6722d88b7d5ebc8198747bafe7980e6c1aeef8ffdf0d41cd",
+        ),
+        // single account
+        (
+            2,
+            &[("1", 7, 1000, 170)],
+            "bfeb6d7ce2395338c43127d7529ddea865a58fdf85d56cad410bf5aedfa5fa9d",
+        ),
+        // packed accounts
+        (
+            2,
+            &[
+                ("0", 1, 2, 1),
+                ("1", 3, 4, 2),
+                ("2", 5, 6, 3),
+                ("3", 7, 8, 4),
+            ],
+            "731e13087e3fd406843254887e19b0585a7e1b5063fc83c629bb797e3bae6a50",
+        ),
+        // unpacked accounts
+        (
+            2,
+            &[("0", 1, 2, 1), ("2", 3, 4, 2), ("3", 5, 6, 3)],
+            "c3a4dd2c3b7407351252cbfe563c97a8b569e48b8e00924720daf4f9ed17a7e9",
+        ),
+        // zero account at first leaf
+        (
+            3,
+            &[("0", 0, 0, 0)],
+            "91f399a40fd67517603988394751e9c039ae8d4f7f89800626973aa1b8a6f56e",
+        ),
+        // zero account at last leaf
+        (
+            3,
+            &[("7", 0, 0, 0)],
+            "91f399a40fd67517603988394751e9c039ae8d4f7f89800626973aa1b8a6f56e",
+        ),
+        // single account
+        (
+            3,
+            &[("1", 7, 1000, 170)],
+            "9db7da867cf5665ff130e4baad9403b0672e1cf7f1a1a2402e96296885c26a5f",
+        ),
+        // packed accounts
+        (
+            3,
+            &[
+                ("0", 1, 2, 1),
+                ("1", 3, 4, 2),
+                ("2", 5, 6, 3),
+                ("3", 7, 8, 4),
+            ],
+            "3715b7cb58ec0e8b128a6fd02ae92df4940afc4dcbc41b3315c15694c2722aa4",
+        ),
+        // unpacked accounts
+        (
+            3,
+            &[("0", 1, 2, 1), ("4", 3, 4, 2), ("7", 5, 6, 3)],
+            "cad96b49a28771010ade2196262cd5f4a929e3e33431021a2a981da9768d5dc5",
+        ),
+        // zero account at first leaf
+        (
+            4,
+            &[("0", 0, 0, 0)],
+            "73b07bc89da2d669a8ffa1858c77a9aa9d33e7a515ddc134ff67b3773c631abb",
+        ),
+        // zero account at last leaf
+        (
+            4,
+            &[("f", 0, 0, 0)],
+            "73b07bc89da2d669a8ffa1858c77a9aa9d33e7a515ddc134ff67b3773c631abb",
+        ),
+        // single account
+        (
+            4,
+            &[("1", 7, 1000, 170)],
+            "0fc1ea84ccda9c63dbb0256a26563a664c5e9abc90dff660238d315691208649",
+        ),
+        // packed accounts
+        (
+            4,
+            &[
+                ("0", 1, 2, 1),
+                ("1", 3, 4, 2),
+                ("2", 5, 6, 3),
+                ("3", 7, 8, 4),
+            ],
+            "4c5618ed7cd10fc1787a9605343c3d88903b7e66846f1f8bdadeeb8faa665033",
+        ),
+        // unpacked accounts
+        (
+            4,
+            &[("0", 1, 2, 1), ("8", 3, 4, 2), ("f", 5, 6, 3)],
+            "6879f07f22cdd2dc690a2fa0791a29885805bc95bd8cd5a260aaff931b0a5e8d",
+        ),
+        // zero account at first leaf
+        (
+            8,
+            &[("0", 0, 0, 0)],
+            "5a5497375ed207cdb96372459c2f35dd0949e891caafc2148402c96456b9bc87",
+        ),
+        // zero account at last leaf
+        (
+            8,
+            &[("ff", 0, 0, 0)],
+            "5a5497375ed207cdb96372459c2f35dd0949e891caafc2148402c96456b9bc87",
+        ),
+        // single account
+        (
+            8,
+            &[("1", 7, 1000, 170)],
+            "a7d040c352ce1eff15446bb237e35c2e57f7e2f07bea27841bc621132e6f3544",
+        ),
+        // packed accounts
+        (
+            8,
+            &[
+                ("0", 1, 2, 1),
+                ("1", 3, 4, 2),
+                ("2", 5, 6, 3),
+                ("3", 7, 8, 4),
+            ],
+            "e579a4028df57d576b5be2b9bd701d541263cb3bc2e5814e20bae70344f1f7d7",
+        ),
+        // unpacked accounts
+        (
+            8,
+            &[("0", 1, 2, 1), ("80", 3, 4, 2), ("ff", 5, 6, 3)],
+            "f63abf46d864fceb1be49881021310e4654b09badb9fdcc482cc17ff9ea620bf",
+        ),
+        // zero and non-zero siblings
+        (
+            8,
+            &[("0", 0, 0, 0), ("1", 0, 18446744073709551615, 255)],
+            "9169dbd0db69fd19f4b5bdb910d2cfe4eab378b72e9a9b6463811bca10b2fa33",
+        ),
+        // zero account at first leaf
+        (
+            16,
+            &[("0", 0, 0, 0)],
+            "ca40c0af7a15aabc30765ca350dc85d6e1e917b9f5acecf90f2f4b4ff90e9e1e",
+        ),
+        // zero account at last leaf
+        (
+            16,
+            &[("ffff", 0, 0, 0)],
+            "ca40c0af7a15aabc30765ca350dc85d6e1e917b9f5acecf90f2f4b4ff90e9e1e",
+        ),
+        // single account
+        (
+            16,
+            &[("1", 7, 1000, 170)],
+            "6efa53ba080cd156a82a2d4838b5914feeab11ccab40fd8f08c68669b70200d8",
+        ),
+        // packed accounts
+        (
+            16,
+            &[
+                ("0", 1, 2, 1),
+                ("1", 3, 4, 2),
+                ("2", 5, 6, 3),
+                ("3", 7, 8, 4),
+            ],
+            "af89454a503445c0dfdd264ac89c2b71cdd9362b0944a7e4719baee457cb3e30",
+        ),
+        // unpacked accounts
+        (
+            16,
+            &[("0", 1, 2, 1), ("8000", 3, 4, 2), ("ffff", 5, 6, 3)],
+            "9c127f92d7a0719bc67b0cb147804b022f052f96590bb48509959b5ed115610b",
+        ),
+        // zero and non-zero siblings
+        (
+            16,
+            &[("0", 0, 0, 0), ("1", 0, 18446744073709551615, 255)],
+            "e87cd273bcb73a6076eac799f8f45c08942f34c486e7d41c07ef4992488a9e1a",
+        ),
+        // zero account at first leaf
+        (
+            32,
+            &[("0", 0, 0, 0)],
+            "dc4b7253a1ecf655ee5ef843473e117de5bbf8fd31ad87c9e17f90f7319c95d3",
+        ),
+        // zero account at last leaf
+        (
+            32,
+            &[("ffffffff", 0, 0, 0)],
+            "dc4b7253a1ecf655ee5ef843473e117de5bbf8fd31ad87c9e17f90f7319c95d3",
+        ),
+        // single account
+        (
+            32,
+            &[("1", 7, 1000, 170)],
+            "9389b955e80a9ee8fd1731acbbc2b34e5557d2337da87768c63d7dab4d8d37b4",
+        ),
+        // packed accounts
+        (
+            32,
+            &[
+                ("0", 1, 2, 1),
+                ("1", 3, 4, 2),
+                ("2", 5, 6, 3),
+                ("3", 7, 8, 4),
+            ],
+            "cb5f1e5707a11d0d03a0e9a4413cd95592fa2377c85aeef686e8fb32589a51bf",
+        ),
+        // unpacked accounts
+        (
+            32,
+            &[("0", 1, 2, 1), ("80000000", 3, 4, 2), ("ffffffff", 5, 6, 3)],
+            "d5a2cee4c25c92752d9879ab170dca6e2fa40647de8358dedcbb3ce844231eb5",
+        ),
+        // zero and non-zero siblings
+        (
+            32,
+            &[("0", 0, 0, 0), ("1", 0, 18446744073709551615, 255)],
+            "0c75234573ba6b7c96445ef6827edee8d7ebee8a6d60bd678e470c99ac169ef0",
+        ),
+        // zero account at first leaf
+        (
+            64,
+            &[("0", 0, 0, 0)],
+            "49e1ef23866ac4bbbe412c53faf58bfaa03a1da6c0cb61487038a488cff48fc9",
+        ),
+        // zero account at last leaf
+        (
+            64,
+            &[("ffffffffffffffff", 0, 0, 0)],
+            "49e1ef23866ac4bbbe412c53faf58bfaa03a1da6c0cb61487038a488cff48fc9",
+        ),
+        // single account
+        (
+            64,
+            &[("1", 7, 1000, 170)],
+            "589eef7532f6c96f902a61f2a328700c72400498fa65c38e6d7716c3f60c4127",
+        ),
+        // packed accounts
+        (
+            64,
+            &[
+                ("0", 1, 2, 1),
+                ("1", 3, 4, 2),
+                ("2", 5, 6, 3),
+                ("3", 7, 8, 4),
+            ],
+            "cb44f1353787caa46ac2882ac7e99c6081d7b8d55dbc88fb5eb65ea0305ad523",
+        ),
+        // unpacked accounts
+        (
+            64,
+            &[
+                ("0", 1, 2, 1),
+                ("8000000000000000", 3, 4, 2),
+                ("ffffffffffffffff", 5, 6, 3),
+            ],
+            "f27b2d86dc58c10015f4f88228b0024328dcf80cfd7d72ea5d55e92638e428e3",
+        ),
+        // zero and non-zero siblings
+        (
+            64,
+            &[("0", 0, 0, 0), ("1", 0, 18446744073709551615, 255)],
+            "269366a09b28e7590b1265ef2003a4fef984c9a3acbb374a0551dfcec1654324",
+        ),
+        // zero account at first leaf
+        (
+            128,
+            &[("0", 0, 0, 0)],
+            "10e4581909d59783fd7d083b53031056addad151edff6d8d01c4930b710d8978",
+        ),
+        // zero account at last leaf
+        (
+            128,
+            &[("ffffffffffffffffffffffffffffffff", 0, 0, 0)],
+            "10e4581909d59783fd7d083b53031056addad151edff6d8d01c4930b710d8978",
+        ),
+        // single account
+        (
+            128,
+            &[("1", 7, 1000, 170)],
+            "3bb710c9e4df744fdffea66a3d7ee0a9e9bbfea664f555bdc24f47d361129972",
+        ),
+        // packed accounts
+        (
+            128,
+            &[
+                ("0", 1, 2, 1),
+                ("1", 3, 4, 2),
+                ("2", 5, 6, 3),
+                ("3", 7, 8, 4),
+            ],
+            "07768d0a1d02c9f537fc7f1a0338857b0abe63ad6bf039911f43c70613d09eb4",
+        ),
+        // unpacked accounts
+        (
+            128,
+            &[
+                ("0", 1, 2, 1),
+                ("80000000000000000000000000000000", 3, 4, 2),
+                ("ffffffffffffffffffffffffffffffff", 5, 6, 3),
+            ],
+            "908584e77a2b1309fe03c271779e6b777ba2efc297bb849b9c002a8ff830c556",
+        ),
+        // zero and non-zero siblings
+        (
+            128,
+            &[("0", 0, 0, 0), ("1", 0, 18446744073709551615, 255)],
+            "cfb687ab419e1a2e83d76444b156d9e41d47fbf743d3f60b2df77d4ddf633b55",
+        ),
+        // zero account at first leaf
+        (
+            255,
+            &[("0", 0, 0, 0)],
+            "a9de57bc708ed47e494b29a13c9399449812eb84e00b47d9eb41159906c3a4e5",
+        ),
+        // zero account at last leaf
+        (
+            255,
+            &[(
+                "7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+                0,
+                0,
+                0,
+            )],
+            "a9de57bc708ed47e494b29a13c9399449812eb84e00b47d9eb41159906c3a4e5",
+        ),
+        // single account
+        (
+            255,
+            &[("1", 7, 1000, 170)],
+            "8df4ad6c0ad4fbdbec474def59a10a5c13cc0c9c2f51f8633dd0447cd55571a0",
+        ),
+        // packed accounts
+        (
+            255,
+            &[
+                ("0", 1, 2, 1),
+                ("1", 3, 4, 2),
+                ("2", 5, 6, 3),
+                ("3", 7, 8, 4),
+            ],
+            "ad246ce33ce49d6234b81a3da396cdfc8d9977b05d87f87542065d6039d517e7",
+        ),
+        // unpacked accounts
+        (
+            255,
+            &[
+                ("0", 1, 2, 1),
+                (
+                    "4000000000000000000000000000000000000000000000000000000000000000",
+                    3,
+                    4,
+                    2,
+                ),
+                (
+                    "7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+                    5,
+                    6,
+                    3,
+                ),
+            ],
+            "941f677517a636eeeb2f9191cf004e2548757c34dde2861fa062e1c5f76f79db",
+        ),
+        // zero and non-zero siblings
+        (
+            255,
+            &[("0", 0, 0, 0), ("1", 0, 18446744073709551615, 255)],
+            "dbd3d2df39991e43d7ab45aeb4222932d35f6f956022ce977c913b9e90aba49a",
+        ),
+        // zero account at first leaf
+        (
+            256,
+            &[("0", 0, 0, 0)],
+            "36169d2d28c3a8970f6b8bf59f54f4164316e8a3b8083e55c729adb8d014fd3b",
+        ),
+        // zero account at last leaf
+        (
+            256,
+            &[(
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+                0,
+                0,
+                0,
+            )],
+            "36169d2d28c3a8970f6b8bf59f54f4164316e8a3b8083e55c729adb8d014fd3b",
+        ),
+        // single account
+        (
+            256,
+            &[("1", 7, 1000, 170)],
+            "011e30d2706c861bd1bb679481f13116e59db7ad2355ddcefe9efea587323e2a",
+        ),
+        // packed accounts
+        (
+            256,
+            &[
+                ("0", 1, 2, 1),
+                ("1", 3, 4, 2),
+                ("2", 5, 6, 3),
+                ("3", 7, 8, 4),
+            ],
+            "d0997ea88c766513948234ba68b77380e7bf997097bfd5dd23589d3351b22e77",
+        ),
+        // unpacked accounts
+        (
+            256,
+            &[
+                ("0", 1, 2, 1),
+                (
+                    "8000000000000000000000000000000000000000000000000000000000000000",
+                    3,
+                    4,
+                    2,
+                ),
+                (
+                    "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+                    5,
+                    6,
+                    3,
+                ),
+            ],
+            "eca59e917fdd061c0a13a817ab17dd094f7469eeec50ff2ccbaf461af4f0df27",
+        ),
+        // zero and non-zero siblings
+        (
+            256,
+            &[("0", 0, 0, 0), ("1", 0, 18446744073709551615, 255)],
+            "709dcd44216d92fd49b1193911742ef9ec416a5ae8662fff6b87b13757ffd230",
+        ),
+    ];
+
+    #[test]
+    fn golden_roots() {
+        for (height, accounts, root) in GOLDEN_ROOTS {
+            assert_eq!(golden_root(*height, accounts), *root, "height {}", height);
+        }
+    }
+
+    #[test]
+    fn golden_roots_of_zero_accounts() {
+        // A tree of accounts with a balance of zero has the same root as the zero subtree.
+        for (height, accounts, root) in GOLDEN_ROOTS {
+            if accounts
+                .iter()
+                .all(|(_, n, v, p)| *n == 0 && *v == 0 && *p == 0)
+            {
+                assert_eq!(hex::encode(sheth::hash::ZERO_HASHES[*height]), *root);
+            }
+        }
     }
 }
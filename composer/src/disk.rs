@@ -0,0 +1,269 @@
+use crate::accounts::AddressedAccount;
+use crate::proof::cache::{StateCache, Store};
+use crate::proof::h256::H256;
+use arrayref::array_ref;
+use bigint::U256;
+use sheth::account::Account;
+use sheth::bls::PublicKey;
+use sheth::u264::U264;
+use std::path::Path;
+
+/// Size of a serialized account: pubkey, nonce, and value.
+const ACCOUNT_LENGTH: usize = 48 + 8 + 8;
+
+const HEIGHT_KEY: &[u8] = b"height";
+
+/// An enum of errors that can occur while opening a disk store.
+#[derive(Debug)]
+pub enum Error {
+    /// The underlying database returned an error
+    Database(sled::Error),
+
+    /// The store was created for a tree of a different height
+    HeightMismatch(usize),
+}
+
+impl From<sled::Error> for Error {
+    fn from(e: sled::Error) -> Self {
+        Error::Database(e)
+    }
+}
+
+/// Persists the accounts and nodes of a `StateCache` with `sled`, so a long-lived state provider
+/// can be restarted without rebuilding the tree.
+///
+/// The accounts changed since the last rehash are persisted too, so a restart picks up where the
+/// previous process left off. Errors from the database after the store has been opened are
+/// treated as fatal.
+pub struct DiskStore {
+    db: sled::Db,
+    accounts: sled::Tree,
+    nodes: sled::Tree,
+    dirty: sled::Tree,
+}
+
+impl DiskStore {
+    /// Open the store at `path`, creating it for a tree of `height` if it doesn't exist.
+    pub fn open<P: AsRef<Path>>(path: P, height: usize) -> Result<Self, Error> {
+        let db = sled::open(path)?;
+
+        match db.get(HEIGHT_KEY)? {
+            Some(h) => {
+                let h = u64::from_le_bytes(*array_ref![h, 0, 8]) as usize;
+                if h != height {
+                    return Err(Error::HeightMismatch(h));
+                }
+            }
+            None => {
+                db.insert(HEIGHT_KEY, &(height as u64).to_le_bytes())?;
+            }
+        }
+
+        Ok(Self {
+            accounts: db.open_tree("accounts")?,
+            nodes: db.open_tree("nodes")?,
+            dirty: db.open_tree("dirty")?,
+            db,
+        })
+    }
+
+    /// Block until every change has been written to disk.
+    pub fn flush(&self) -> Result<(), Error> {
+        self.db.flush()?;
+        Ok(())
+    }
+}
+
+/// Open the full state persisted at `path`.
+pub fn open<P: AsRef<Path>>(path: P, height: usize) -> Result<StateCache<DiskStore>, Error> {
+    Ok(StateCache::with_store(
+        DiskStore::open(path, height)?,
+        height,
+    ))
+}
+
+impl Store for DiskStore {
+    fn node(&self, index: &U264) -> Option<H256> {
+        self.nodes
+            .get(index_key(index))
+            .expect("unable to read node")
+            .map(|n| H256::new(array_ref![n, 0, 32]))
+    }
+
+    fn set_node(&mut self, index: U264, node: H256) {
+        self.nodes
+            .insert(index_key(&index), &node.as_bytes()[..])
+            .expect("unable to write node");
+    }
+
+    fn remove_node(&mut self, index: &U264) {
+        self.nodes
+            .remove(index_key(index))
+            .expect("unable to remove node");
+    }
+
+    fn account(&self, address: &U256) -> Option<Account> {
+        self.accounts
+            .get(address_key(address))
+            .expect("unable to read account")
+            .map(|a| decode_account(&a))
+    }
+
+    fn set_account(&mut self, address: U256, account: Account) {
+        self.accounts
+            .insert(address_key(&address), &encode_account(&account)[..])
+            .expect("unable to write account");
+    }
+
+    fn remove_account(&mut self, address: &U256) -> Option<Account> {
+        self.accounts
+            .remove(address_key(address))
+            .expect("unable to remove account")
+            .map(|a| decode_account(&a))
+    }
+
+    fn accounts(&self) -> Vec<AddressedAccount> {
+        // Keys are big endian, so the tree is already ordered by address.
+        self.accounts
+            .iter()
+            .map(|entry| {
+                let (address, account) = entry.expect("unable to read account");
+                AddressedAccount(U256::from(&address[..]), decode_account(&account))
+            })
+            .collect()
+    }
+
+    fn mark_dirty(&mut self, index: U264) {
+        self.dirty
+            .insert(index_key(&index), &[])
+            .expect("unable to write dirty account");
+    }
+
+    fn take_dirty(&mut self) -> Vec<U264> {
+        let dirty = self
+            .dirty
+            .iter()
+            .keys()
+            .map(|k| {
+                let k = k.expect("unable to read dirty account");
+                U264::from_be_bytes(*array_ref![k, 31, 33])
+            })
+            .collect();
+
+        self.dirty.clear().expect("unable to clear dirty accounts");
+
+        dirty
+    }
+}
+
+// Keys are left padded to 64 bytes, matching the layout of databases written with `U512` indexes.
+fn index_key(index: &U264) -> [u8; 64] {
+    let mut buf = [0u8; 64];
+    buf[31..64].copy_from_slice(&index.to_be_bytes());
+    buf
+}
+
+fn address_key(address: &U256) -> [u8; 32] {
+    let mut buf = [0u8; 32];
+    address.to_big_endian(&mut buf);
+    buf
+}
+
+fn encode_account(account: &Account) -> [u8; ACCOUNT_LENGTH] {
+    let mut buf = [0u8; ACCOUNT_LENGTH];
+    buf[0..48].copy_from_slice(&account.pubkey.as_bytes());
+    buf[48..56].copy_from_slice(&account.nonce.to_le_bytes());
+    buf[56..64].copy_from_slice(&account.value.to_le_bytes());
+    buf
+}
+
+fn decode_account(buf: &[u8]) -> Account {
+    Account {
+        pubkey: PublicKey::new(*array_ref![buf, 0, 48]),
+        nonce: u64::from_le_bytes(*array_ref![buf, 48, 8]),
+        value: u64::from_le_bytes(*array_ref![buf, 56, 8]),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::accounts::random_accounts;
+    use crate::transactions;
+    use sheth::process::process_transactions;
+    use std::path::PathBuf;
+
+    fn path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("sheth-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        path
+    }
+
+    /// sled releases the lock on its directory from a background thread once the last handle is
+    /// dropped, so reopening it straight away can briefly fail.
+    fn reopen<T>(f: impl Fn() -> Result<T, Error>) -> Result<T, Error> {
+        for _ in 0..100 {
+            match f() {
+                Err(Error::Database(_)) => std::thread::sleep(std::time::Duration::from_millis(10)),
+                result => return result,
+            }
+        }
+
+        f()
+    }
+
+    #[test]
+    fn roots_match_memory_store() {
+        let path = path("roots");
+        let accounts = random_accounts(8, 16);
+        let txs = transactions::generate(4, accounts.clone());
+
+        let mut memory = StateCache::new(accounts.clone(), 16);
+        let mut disk = open(&path, 16).unwrap();
+        for account in accounts.iter() {
+            disk.update(account);
+        }
+
+        assert_eq!(disk.root(), memory.root());
+
+        process_transactions(&mut memory, &txs).unwrap();
+        process_transactions(&mut disk, &txs).unwrap();
+        assert_eq!(disk.root(), memory.root());
+        assert_eq!(disk.accounts(), memory.accounts());
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+
+    #[test]
+    fn reopen_after_restart() {
+        let path = path("reopen");
+        let accounts = random_accounts(4, 16);
+        let mut memory = StateCache::new(accounts.clone(), 16);
+
+        {
+            let mut disk = open(&path, 16).unwrap();
+            for account in accounts.iter() {
+                disk.update(account);
+            }
+
+            // Rehash only some of the changes, so the rest must be picked up after the restart.
+            disk.root();
+            disk.remove(accounts[0].0);
+            disk.store().flush().unwrap();
+        }
+
+        memory.remove(accounts[0].0);
+
+        let mut disk = reopen(|| open(&path, 16)).unwrap();
+        assert_eq!(disk.root(), memory.root());
+        assert_eq!(disk.accounts().len(), 3);
+        drop(disk);
+
+        assert!(matches!(
+            reopen(|| DiskStore::open(&path, 8)),
+            Err(Error::HeightMismatch(16))
+        ));
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+}
@@ -0,0 +1,325 @@
+use crate::accounts::AddressedAccount;
+use crate::proof::uncompressed::{generate, generate_subset};
+use crate::proof::verify::verify_account;
+use arrayref::array_ref;
+use bigint::U256;
+use imp::Imp;
+use sheth::account::Account;
+use sheth::state::root::imp_root;
+use sheth::u264::U264;
+use std::collections::BTreeMap;
+
+/// An enum of errors that can occur while verifying a historical balance proof.
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// The root history does not contain the block
+    BlockUnknown(u64),
+
+    /// The proof's root does not match the root of the block in the history
+    RootMismatch(u64),
+
+    /// The proof does not authenticate the account against the root
+    ProofInvalid(u64),
+
+    /// The root history could not be deserialized
+    HistoryMalformed,
+}
+
+/// A proof of an account's balance as of a particular block.
+///
+/// Bundles only need to be generated for the accounts someone may later ask about, so the full
+/// state at each block can be discarded once its bundles are built.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BalanceProof {
+    pub block: u64,
+    pub root: [u8; 32],
+    pub account: AddressedAccount,
+    pub proof: Vec<u8>,
+}
+
+impl BalanceProof {
+    /// Build a proof of the account at `address` in the state made up of `accounts` at `block`.
+    pub fn generate(
+        accounts: &[AddressedAccount],
+        address: U256,
+        block: u64,
+        root: [u8; 32],
+        height: usize,
+    ) -> Self {
+        let account = accounts
+            .iter()
+            .find(|a| a.0 == address)
+            .expect("address is not in the state")
+            .clone();
+
+        BalanceProof {
+            block,
+            root,
+            account,
+            proof: generate_subset(accounts.to_vec(), &[address], height).compress(),
+        }
+    }
+
+    pub fn value(&self) -> u64 {
+        self.account.1.value
+    }
+}
+
+/// The post-state root of every block, indexed by block number.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RootHistory {
+    pub roots: Vec<[u8; 32]>,
+}
+
+impl RootHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the post-state root of the next block.
+    pub fn push(&mut self, root: [u8; 32]) {
+        self.roots.push(root);
+    }
+
+    pub fn root(&self, block: u64) -> Option<[u8; 32]> {
+        self.roots.get(block as usize).cloned()
+    }
+
+    /// Export the history as the concatenation of its roots.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.roots.iter().fold(vec![], |mut acc, root| {
+            acc.extend(root);
+            acc
+        })
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if !bytes.len().is_multiple_of(32) {
+            return Err(Error::HistoryMalformed);
+        }
+
+        Ok(RootHistory {
+            roots: bytes.chunks(32).map(|r| *array_ref![r, 0, 32]).collect(),
+        })
+    }
+
+    /// Checks `proof` against the root recorded for its block and returns the proven balance.
+    pub fn verify(&self, proof: &BalanceProof, height: usize) -> Result<u64, Error> {
+        let root = self
+            .root(proof.block)
+            .ok_or(Error::BlockUnknown(proof.block))?;
+
+        if root != proof.root {
+            return Err(Error::RootMismatch(proof.block));
+        }
+
+        if !verify_account(&proof.proof, &root, &proof.account, height) {
+            return Err(Error::ProofInvalid(proof.block));
+        }
+
+        Ok(proof.value())
+    }
+}
+
+/// Returns the root of the state made up of `accounts`.
+fn state_root(accounts: &[AddressedAccount], height: usize) -> [u8; 32] {
+    let mut proof = generate(accounts.to_vec(), height).compress();
+    imp_root(&Imp::<U264>::new(&mut proof, height + 3))
+}
+
+/// Records the post-state of every block, so balance proofs can be served as of any block as well
+/// as verified.
+///
+/// Only the accounts each block changed are kept, and the state as of a block is rebuilt from them
+/// when a proof of it is asked for, so the archive grows with the accounts blocks touch rather
+/// than with the size of the state.
+#[derive(Clone, Debug)]
+pub struct RootArchive {
+    history: RootHistory,
+    height: usize,
+
+    /// The accounts each block changed, by block number. An account which was removed is recorded
+    /// as empty.
+    changes: Vec<Vec<AddressedAccount>>,
+
+    /// The state after the last block.
+    state: BTreeMap<U256, Account>,
+}
+
+impl RootArchive {
+    pub fn new(height: usize) -> Self {
+        RootArchive {
+            history: RootHistory::new(),
+            height,
+            changes: vec![],
+            state: BTreeMap::new(),
+        }
+    }
+
+    /// The post-state root of every recorded block.
+    pub fn history(&self) -> &RootHistory {
+        &self.history
+    }
+
+    /// Record `accounts`, the whole state after the next block, returning its root.
+    pub fn record(&mut self, accounts: &[AddressedAccount]) -> [u8; 32] {
+        let state: BTreeMap<U256, Account> = accounts.iter().map(|a| (a.0, a.1.clone())).collect();
+
+        let mut changes: Vec<AddressedAccount> = state
+            .iter()
+            .filter(|(address, account)| self.state.get(address) != Some(account))
+            .map(|(address, account)| AddressedAccount(*address, account.clone()))
+            .collect();
+        changes.extend(
+            self.state
+                .keys()
+                .filter(|address| !state.contains_key(address))
+                .map(|address| AddressedAccount(*address, Account::zero())),
+        );
+
+        let root = state_root(accounts, self.height);
+        self.history.push(root);
+        self.changes.push(changes);
+        self.state = state;
+
+        root
+    }
+
+    /// Returns the state after `block`, or `None` if it hasn't been recorded.
+    pub fn state(&self, block: u64) -> Option<Vec<AddressedAccount>> {
+        let changes = self.changes.get(..=block as usize)?;
+        let mut state: BTreeMap<U256, Account> = BTreeMap::new();
+
+        for account in changes.iter().flatten() {
+            if account.1 == Account::zero() {
+                state.remove(&account.0);
+            } else {
+                state.insert(account.0, account.1.clone());
+            }
+        }
+
+        Some(
+            state
+                .into_iter()
+                .map(|(address, account)| AddressedAccount(address, account))
+                .collect(),
+        )
+    }
+
+    /// Returns a proof of the account at `address` as of `block`, or `None` if the block hasn't
+    /// been recorded or the account didn't exist after it.
+    pub fn prove(&self, address: U256, block: u64) -> Option<BalanceProof> {
+        let state = self.state(block)?;
+        if state.iter().all(|a| a.0 != address) {
+            return None;
+        }
+
+        let root = self.history.root(block)?;
+        Some(BalanceProof::generate(
+            &state,
+            address,
+            block,
+            root,
+            self.height,
+        ))
+    }
+
+    /// Checks `proof` against the root recorded for its block, see `RootHistory::verify`.
+    pub fn verify(&self, proof: &BalanceProof) -> Result<u64, Error> {
+        self.history.verify(proof, self.height)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::accounts::random_accounts;
+    use crate::transactions;
+
+    /// Run a few blocks, keeping only the root history and a balance proof per block.
+    fn build_history() -> (RootHistory, Vec<BalanceProof>, Vec<u64>) {
+        let mut accounts = random_accounts(3, 256);
+        let address = accounts[0].0;
+
+        let mut history = RootHistory::new();
+        let mut proofs = vec![];
+        let mut balances = vec![];
+
+        for block in 0..3 {
+            let txs = transactions::generate(4, accounts.clone());
+            transactions::apply(&txs, address.into(), 0, &mut accounts).unwrap();
+
+            let root = state_root(&accounts, 256);
+            history.push(root);
+            proofs.push(BalanceProof::generate(&accounts, address, block, root, 256));
+            balances.push(accounts[0].1.value);
+        }
+
+        (history, proofs, balances)
+    }
+
+    #[test]
+    fn verify_historical_balances() {
+        let (history, proofs, balances) = build_history();
+        let history = RootHistory::from_bytes(&history.to_bytes()).unwrap();
+
+        for (proof, balance) in proofs.iter().zip(balances) {
+            assert_eq!(history.verify(proof, 256), Ok(balance));
+        }
+    }
+
+    #[test]
+    fn reject_invalid_proofs() {
+        let (history, proofs, _) = build_history();
+
+        let mut proof = proofs[1].clone();
+        proof.block = 2;
+        assert_eq!(history.verify(&proof, 256), Err(Error::RootMismatch(2)));
+
+        proof.block = 3;
+        assert_eq!(history.verify(&proof, 256), Err(Error::BlockUnknown(3)));
+
+        let mut proof = proofs[1].clone();
+        proof.account.1.value += 1;
+        assert_eq!(history.verify(&proof, 256), Err(Error::ProofInvalid(1)));
+
+        assert_eq!(
+            RootHistory::from_bytes(&[0u8; 33]),
+            Err(Error::HistoryMalformed)
+        );
+    }
+
+    #[test]
+    fn archive() {
+        let mut accounts = random_accounts(3, 256);
+        let address = accounts[0].0;
+
+        let mut archive = RootArchive::new(256);
+        let mut balances = vec![];
+
+        for _ in 0..3 {
+            let txs = transactions::generate(4, accounts.clone());
+            transactions::apply(&txs, address.into(), 0, &mut accounts).unwrap();
+
+            let root = archive.record(&accounts);
+            assert_eq!(root, state_root(&accounts, 256));
+            balances.push(accounts[0].1.value);
+        }
+
+        // Proofs are served as of every block, including the states which have since changed.
+        for (block, balance) in balances.iter().enumerate() {
+            let proof = archive.prove(address, block as u64).unwrap();
+            assert_eq!(archive.verify(&proof), Ok(*balance));
+        }
+
+        // An account which is removed can only be proven before it was.
+        let removed = accounts.pop().unwrap();
+        archive.record(&accounts);
+        assert!(archive.prove(removed.0, 2).is_some());
+        assert_eq!(archive.prove(removed.0, 3), None);
+        assert_eq!(archive.state(3).unwrap(), accounts);
+
+        assert_eq!(archive.prove(address, 4), None);
+        assert_eq!(archive.history().roots.len(), 4);
+    }
+}
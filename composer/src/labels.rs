@@ -0,0 +1,159 @@
+use bigint::U256;
+use sheth::address::Address;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+/// An enum of errors that can occur while loading labels.
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// The line was not of the form `<address> <label>`
+    LineInvalid(usize),
+
+    /// The address on the line could not be parsed into a 32 byte value
+    AddressInvalid(usize),
+
+    /// The label has already been given to another address
+    LabelDuplicate(String),
+}
+
+/// A registry of human-readable labels for addresses, so tooling can refer to accounts as e.g.
+/// `alice` and `bob` instead of their 32 byte addresses.
+///
+/// Labels are loaded from lines of the form `0x<address> <label>`. Blank lines and lines starting
+/// with `#` are ignored.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Labels {
+    labels: HashMap<U256, String>,
+    addresses: HashMap<String, U256>,
+}
+
+impl Labels {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Give `address` the label `label`, replacing any label it already had.
+    pub fn insert(&mut self, address: U256, label: &str) -> Result<(), Error> {
+        match self.addresses.get(label) {
+            Some(a) if *a != address => return Err(Error::LabelDuplicate(label.to_string())),
+            _ => (),
+        }
+
+        if let Some(old) = self.labels.insert(address, label.to_string()) {
+            self.addresses.remove(&old);
+        }
+        self.addresses.insert(label.to_string(), address);
+
+        Ok(())
+    }
+
+    pub fn label(&self, address: U256) -> Option<&str> {
+        self.labels.get(&address).map(|l| l.as_str())
+    }
+
+    pub fn address(&self, label: &str) -> Option<U256> {
+        self.addresses.get(label).cloned()
+    }
+
+    /// Returns the label of `address`, or the address in hex if it doesn't have one.
+    pub fn name(&self, address: U256) -> String {
+        match self.label(address) {
+            Some(label) => label.to_string(),
+            None => {
+                let mut buf = [0u8; 32];
+                address.to_big_endian(&mut buf);
+                format!("0x{}", hex::encode(buf))
+            }
+        }
+    }
+
+    /// Replace each word of `s` which is a label with the address it refers to.
+    pub fn expand(&self, s: &str) -> String {
+        s.split_whitespace()
+            .map(|word| match self.address(word) {
+                Some(address) => {
+                    let mut buf = [0u8; 32];
+                    address.to_big_endian(&mut buf);
+                    format!("0x{}", hex::encode(buf))
+                }
+                None => word.to_string(),
+            })
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+}
+
+impl FromStr for Labels {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut labels = Labels::new();
+
+        for (n, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let words: Vec<&str> = line.split_whitespace().collect();
+            if words.len() != 2 {
+                return Err(Error::LineInvalid(n + 1));
+            }
+
+            let address = Address::try_from(words[0]).map_err(|_| Error::AddressInvalid(n + 1))?;
+
+            labels.insert(address.into(), words[1])?;
+        }
+
+        Ok(labels)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const FILE: &str = "
+        # accounts used in the demo
+        0x0000000000000000000000000000000000000000000000000000000000000001 alice
+        0000000000000000000000000000000000000000000000000000000000000002 bob
+    ";
+
+    #[test]
+    fn parse_labels() {
+        let labels: Labels = FILE.parse().unwrap();
+
+        assert_eq!(labels.label(1.into()), Some("alice"));
+        assert_eq!(labels.address("bob"), Some(2.into()));
+        assert_eq!(labels.name(2.into()), "bob");
+        assert_eq!(
+            labels.name(3.into()),
+            "0x0000000000000000000000000000000000000000000000000000000000000003"
+        );
+        assert_eq!(
+            labels.expand("transfer alice bob 5"),
+            "transfer 0x0000000000000000000000000000000000000000000000000000000000000001 \
+             0x0000000000000000000000000000000000000000000000000000000000000002 5"
+        );
+    }
+
+    #[test]
+    fn parse_invalid_labels() {
+        assert_eq!("0x01".parse::<Labels>(), Err(Error::LineInvalid(1)));
+        assert_eq!(
+            "\n0x01 alice".parse::<Labels>(),
+            Err(Error::AddressInvalid(2))
+        );
+
+        let mut labels: Labels = FILE.parse().unwrap();
+        assert_eq!(
+            labels.insert(3.into(), "alice"),
+            Err(Error::LabelDuplicate("alice".to_string()))
+        );
+
+        labels.insert(1.into(), "carol").unwrap();
+        assert_eq!(labels.address("alice"), None);
+        assert_eq!(labels.label(1.into()), Some("carol"));
+    }
+}
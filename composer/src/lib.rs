@@ -1,4 +1,16 @@
 pub mod accounts;
 pub mod blob;
+#[cfg(feature = "disk")]
+pub mod disk;
+pub mod history;
+pub mod labels;
+#[cfg(feature = "mmap")]
+pub mod mmap;
 pub mod proof;
+pub mod rng;
+pub mod snapshot;
+#[cfg(feature = "stealth")]
+pub mod stealth;
 pub mod transactions;
+pub mod vanity;
+pub mod watch;
@@ -0,0 +1,203 @@
+use arrayref::array_ref;
+use imp::Imp;
+use sheth::u264::U264;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::ops::{Deref, DerefMut};
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+
+/// An enum of errors that can occur while mapping a proof file.
+#[derive(Debug)]
+pub enum Error {
+    /// The file could not be opened or mapped
+    Io(io::Error),
+
+    /// The file's length does not match the number of chunks in its header
+    LengthInvalid(usize),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+/// A compressed multi-proof mapped directly from a file, so witnesses of hundreds of megabytes
+/// can be read and updated through `Imp` without copying them into an owned buffer.
+///
+/// The file has the same layout as any other compressed proof: the number of chunks, the
+/// offsets, and then the chunks themselves. The mapping is page aligned, which also satisfies
+/// `Imp`'s requirement that the offsets are aligned to 8 bytes.
+pub struct MappedProof {
+    ptr: *mut u8,
+    len: usize,
+    _file: File,
+}
+
+impl MappedProof {
+    /// Map the proof at `path`. Changes made through the mapping are written back to the file.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let file = OpenOptions::new().read(true).write(true).open(path)?;
+        Self::map(file, libc::MAP_SHARED)
+    }
+
+    /// Map the proof at `path` copy-on-write, so it can be processed speculatively (e.g. by a
+    /// verifier) without ever modifying the file.
+    pub fn open_private<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let file = File::open(path)?;
+        Self::map(file, libc::MAP_PRIVATE)
+    }
+
+    fn map(file: File, flags: libc::c_int) -> Result<Self, Error> {
+        let len = file.metadata()?.len() as usize;
+        if len < 8 {
+            return Err(Error::LengthInvalid(len));
+        }
+
+        // Safety: the file is kept open for as long as the mapping exists, and the mapping is
+        // only accessed through `&self` and `&mut self`.
+        let ptr = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                flags,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error().into());
+        }
+
+        let proof = Self {
+            ptr: ptr as *mut u8,
+            len,
+            _file: file,
+        };
+
+        // Each chunk is 32 bytes and has an 8 byte offset, with the count standing in for the
+        // offset of the first chunk.
+        let chunks = u64::from_le_bytes(*array_ref![proof, 0, 8]) as usize;
+        if chunks.checked_mul(40) != Some(len) {
+            return Err(Error::LengthInvalid(len));
+        }
+
+        Ok(proof)
+    }
+
+    /// Returns an `Imp` over the mapped proof for a tree of `height`, not including the account
+    /// subtrees.
+    pub fn imp(&mut self, height: usize) -> Imp<'_, U264> {
+        Imp::new(self, height + 3)
+    }
+
+    /// Block until every change made through a shared mapping has been written to the file.
+    pub fn flush(&self) -> Result<(), Error> {
+        if unsafe { libc::msync(self.ptr as *mut libc::c_void, self.len, libc::MS_SYNC) } != 0 {
+            return Err(io::Error::last_os_error().into());
+        }
+
+        Ok(())
+    }
+}
+
+impl Deref for MappedProof {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.ptr, self.len) }
+    }
+}
+
+impl DerefMut for MappedProof {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        unsafe { std::slice::from_raw_parts_mut(self.ptr, self.len) }
+    }
+}
+
+impl Drop for MappedProof {
+    fn drop(&mut self) {
+        unsafe {
+            libc::munmap(self.ptr as *mut libc::c_void, self.len);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::accounts::random_accounts;
+    use crate::proof::uncompressed::generate;
+    use crate::proof::verify::BatchVerifier;
+    use crate::transactions;
+    use sheth::process::process_transactions;
+    use sheth::state::State;
+    use std::path::PathBuf;
+
+    fn path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("sheth-mmap-{}-{}", name, std::process::id()))
+    }
+
+    #[test]
+    fn process_mapped_proof() {
+        let path = path("process");
+        let accounts = random_accounts(16, 32);
+        let txs = transactions::generate(8, accounts.clone());
+
+        let mut proof = generate(accounts, 32).compress();
+        std::fs::write(&path, &proof).unwrap();
+
+        let mut expected = Imp::<U264>::new(&mut proof, 32 + 3);
+        let pre_root = State::root(&mut expected).unwrap();
+        process_transactions(&mut expected, &txs).unwrap();
+        let post_root = State::root(&mut expected).unwrap();
+
+        // A private mapping can be processed against without touching the file.
+        {
+            let mut mapped = MappedProof::open_private(&path).unwrap();
+            let mut mem = mapped.imp(32);
+            assert_eq!(State::root(&mut mem), Ok(pre_root));
+            process_transactions(&mut mem, &txs).unwrap();
+            assert_eq!(State::root(&mut mem), Ok(post_root));
+        }
+
+        let mut mapped = MappedProof::open(&path).unwrap();
+        assert_eq!(
+            BatchVerifier::new(32).verify_all(&[(pre_root, &mapped)]),
+            vec![true]
+        );
+        process_transactions(&mut mapped.imp(32), &txs).unwrap();
+        mapped.flush().unwrap();
+        drop(mapped);
+
+        let mut mapped = MappedProof::open(&path).unwrap();
+        assert_eq!(State::root(&mut mapped.imp(32)), Ok(post_root));
+        drop(mapped);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn reject_invalid_length() {
+        let path = path("length");
+
+        std::fs::write(&path, &[0u8; 4]).unwrap();
+        assert!(matches!(
+            MappedProof::open(&path),
+            Err(Error::LengthInvalid(4))
+        ));
+
+        let mut proof = 2u64.to_le_bytes().to_vec();
+        proof.resize(64, 0);
+        std::fs::write(&path, &proof).unwrap();
+        assert!(matches!(
+            MappedProof::open_private(&path),
+            Err(Error::LengthInvalid(64))
+        ));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,471 @@
+use crate::accounts::AddressedAccount;
+use crate::proof::h256::H256;
+use crate::proof::sort::alpha_sort;
+use crate::proof::uncompressed::{account_chunks, account_leaves, UncompressedProof};
+use arrayref::array_ref;
+use bigint::U256;
+use sheth::account::Account;
+use sheth::address::Address;
+use sheth::amount::Amount;
+use sheth::bls::PublicKey;
+use sheth::error::Error;
+use sheth::hash::{hash, ZERO_HASHES};
+use sheth::nonce::Nonce;
+use sheth::state::State;
+use sheth::u264::U264;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+/// Storage for the accounts and nodes of a `StateCache`.
+pub trait Store {
+    /// Returns the node at `index`, if it has been written.
+    fn node(&self, index: &U264) -> Option<H256>;
+
+    fn set_node(&mut self, index: U264, node: H256);
+
+    fn remove_node(&mut self, index: &U264);
+
+    fn account(&self, address: &U256) -> Option<Account>;
+
+    fn set_account(&mut self, address: U256, account: Account);
+
+    fn remove_account(&mut self, address: &U256) -> Option<Account>;
+
+    /// Returns every account in the store, ordered by address.
+    fn accounts(&self) -> Vec<AddressedAccount>;
+
+    /// Record that the account whose root is at `index` has changed since the last rehash.
+    fn mark_dirty(&mut self, index: U264);
+
+    /// Returns the root indexes of the accounts which have changed since the last rehash, and
+    /// forgets them.
+    fn take_dirty(&mut self) -> Vec<U264>;
+}
+
+/// Keeps the whole state in memory.
+#[derive(Default)]
+pub struct MemoryStore {
+    accounts: BTreeMap<U256, Account>,
+    nodes: HashMap<U264, H256>,
+    dirty: HashSet<U264>,
+}
+
+impl Store for MemoryStore {
+    fn node(&self, index: &U264) -> Option<H256> {
+        self.nodes.get(index).cloned()
+    }
+
+    fn set_node(&mut self, index: U264, node: H256) {
+        self.nodes.insert(index, node);
+    }
+
+    fn remove_node(&mut self, index: &U264) {
+        self.nodes.remove(index);
+    }
+
+    fn account(&self, address: &U256) -> Option<Account> {
+        self.accounts.get(address).cloned()
+    }
+
+    fn set_account(&mut self, address: U256, account: Account) {
+        self.accounts.insert(address, account);
+    }
+
+    fn remove_account(&mut self, address: &U256) -> Option<Account> {
+        self.accounts.remove(address)
+    }
+
+    fn accounts(&self) -> Vec<AddressedAccount> {
+        self.accounts
+            .iter()
+            .map(|(address, account)| AddressedAccount(*address, account.clone()))
+            .collect()
+    }
+
+    fn mark_dirty(&mut self, index: U264) {
+        self.dirty.insert(index);
+    }
+
+    fn take_dirty(&mut self) -> Vec<U264> {
+        self.dirty.drain().collect()
+    }
+}
+
+/// The full state, with the root of every account subtree and the internal nodes above them
+/// cached between blocks.
+///
+/// Updating an account only marks it as dirty, so building a witness rehashes the paths that
+/// have been touched since the last witness rather than the entire state.
+///
+/// Since it holds every account, it also implements `State`, so block producers can execute
+/// transactions directly against it and then export witnesses for stateless validators.
+pub struct StateCache<S: Store = MemoryStore> {
+    height: usize,
+    store: S,
+    zeros: Vec<H256>,
+}
+
+impl StateCache {
+    pub fn new(accounts: Vec<AddressedAccount>, height: usize) -> Self {
+        let mut cache = StateCache::with_store(MemoryStore::default(), height);
+
+        for account in accounts.iter() {
+            cache.update(account);
+        }
+
+        cache
+    }
+}
+
+impl<S: Store> StateCache<S> {
+    /// Use the state already held in `store`, which must have been built for a tree of `height`.
+    pub fn with_store(store: S, height: usize) -> Self {
+        let zeros = ZERO_HASHES[..=height].iter().map(H256::new).collect();
+
+        StateCache {
+            height,
+            store,
+            zeros,
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Overwrite the account at `account.0`, creating it if it doesn't exist.
+    pub fn update(&mut self, account: &AddressedAccount) {
+        for (index, chunk) in account_chunks(account, self.height) {
+            self.store.set_node(index, chunk);
+        }
+
+        self.store.set_account(account.0, account.1.clone());
+        self.store.mark_dirty(self.account_index(account.0));
+    }
+
+    /// Remove the account at `address`, leaving an empty subtree in its place.
+    pub fn remove(&mut self, address: U256) -> Option<Account> {
+        let index = self.account_index(address);
+        for leaf in account_leaves(index) {
+            self.store.remove_node(&leaf);
+        }
+
+        self.store.mark_dirty(index);
+        self.store.remove_account(&address)
+    }
+
+    /// Returns every account in the state, ordered by address.
+    pub fn accounts(&self) -> Vec<AddressedAccount> {
+        self.store.accounts()
+    }
+
+    pub fn root(&mut self) -> H256 {
+        self.rehash();
+        self.node(U264::one())
+    }
+
+    /// Generate a proof of only the accounts at `addresses`, authenticated against the root of
+    /// the full state.
+    pub fn witness(&mut self, addresses: &[U256]) -> UncompressedProof {
+        self.rehash();
+
+        let leaves: HashSet<U264> = addresses
+            .iter()
+            .flat_map(|address| {
+                let index = self.account_index(*address);
+                assert!(
+                    self.store.node(&(index << 3usize)).is_some(),
+                    "address is not in the state"
+                );
+                account_leaves(index)
+            })
+            .collect();
+
+        // Every node on the path from a leaf to the root can be calculated, so only the siblings
+        // of those nodes which can't be calculated need to be included.
+        let mut path: HashSet<U264> = HashSet::new();
+        for leaf in leaves.iter() {
+            let mut index = *leaf;
+            while index > U264::zero() {
+                path.insert(index);
+                index = index >> 1usize;
+            }
+        }
+
+        let indexes: Vec<U264> = path
+            .iter()
+            .filter(|i| **i > U264::one())
+            .map(|i| *i ^ U264::one())
+            .filter(|i| !path.contains(i))
+            .chain(leaves.iter().cloned())
+            .collect::<HashSet<U264>>()
+            .into_iter()
+            .collect();
+
+        let indexes = alpha_sort(&indexes);
+
+        UncompressedProof {
+            indexes: indexes.clone(),
+            values: indexes.iter().map(|i| self.node(*i)).collect(),
+        }
+    }
+
+    fn account_index(&self, address: U256) -> U264 {
+        (U264::one() << self.height) + U264::from(address)
+    }
+
+    /// Returns the node at `index`, or the hash of an empty subtree if nothing has been written
+    /// below it.
+    fn node(&self, index: U264) -> H256 {
+        match self.store.node(&index) {
+            Some(x) => x,
+            None => self.zeros[self.height + 1 - index.bits()],
+        }
+    }
+
+    /// Recalculate the internal nodes of each dirty account and every node above them.
+    fn rehash(&mut self) {
+        let mut dirty: BTreeSet<U264> = BTreeSet::new();
+
+        for index in self.store.take_dirty() {
+            let subtree = [
+                index,
+                index << 1usize,
+                (index << 1usize) + 1.into(),
+                index << 2usize,
+            ];
+
+            if self.store.node(&(index << 3usize)).is_some() {
+                dirty.extend(subtree.iter());
+            } else {
+                // The account was removed, so its subtree is empty again.
+                for node in subtree.iter() {
+                    self.store.remove_node(node);
+                }
+            }
+
+            let mut index = index >> 1usize;
+            while index > U264::zero() {
+                dirty.insert(index);
+                index = index >> 1usize;
+            }
+        }
+
+        // A node always has a greater index than its parent, so working backwards from the
+        // greatest index hashes both children of a node before the node itself.
+        for index in dirty.iter().rev() {
+            let mut buf = [0u8; 64];
+            buf[0..32].copy_from_slice(self.node(*index << 1usize).as_bytes());
+            buf[32..64].copy_from_slice(self.node((*index << 1usize) + 1.into()).as_bytes());
+            hash(&mut buf);
+
+            self.store
+                .set_node(*index, H256::new(array_ref![buf, 0, 32]));
+        }
+    }
+
+    fn get(&self, address: Address) -> Account {
+        let address = U256::from(<[u8; 32]>::from(address));
+        self.store.account(&address).unwrap_or_else(Account::zero)
+    }
+
+    fn set(&mut self, address: Address, account: Account) {
+        let address = U256::from(<[u8; 32]>::from(address));
+        self.update(&AddressedAccount(address, account));
+    }
+}
+
+/// Every account is part of the full state, so accounts which don't exist are read as empty
+/// rather than incomplete.
+impl<S: Store> State for StateCache<S> {
+    fn root(&mut self) -> Result<[u8; 32], Error> {
+        Ok(*StateCache::root(self).as_bytes())
+    }
+
+    fn value(&self, address: Address) -> Result<Amount, Error> {
+        Amount::new(self.get(address).value)
+    }
+
+    fn nonce(&self, address: Address) -> Result<Nonce, Error> {
+        Ok(self.get(address).nonce.into())
+    }
+
+    fn pubkey(&self, address: Address) -> Result<PublicKey, Error> {
+        Ok(self.get(address).pubkey)
+    }
+
+    fn add_value(&mut self, address: Address, amount: Amount) -> Result<Amount, Error> {
+        let mut account = self.get(address);
+        let value = Amount::new(account.value)?.checked_add(amount)?;
+
+        account.value = value.get();
+        self.set(address, account);
+
+        Ok(value)
+    }
+
+    fn sub_value(&mut self, address: Address, amount: Amount) -> Result<Amount, Error> {
+        let mut account = self.get(address);
+        let value = Amount::new(account.value)?
+            .checked_sub(amount)
+            .map_err(|_| Error::BalanceInsufficient)?;
+
+        account.value = value.get();
+        self.set(address, account);
+
+        Ok(value)
+    }
+
+    fn inc_nonce(&mut self, address: Address) -> Result<Nonce, Error> {
+        let mut account = self.get(address);
+        let nonce = Nonce::from(account.nonce).next()?;
+
+        account.nonce = nonce.get();
+        self.set(address, account);
+
+        Ok(nonce)
+    }
+
+    fn set_pubkey(&mut self, address: Address, pubkey: PublicKey) -> Result<(), Error> {
+        let mut account = self.get(address);
+        account.pubkey = pubkey;
+        self.set(address, account);
+
+        Ok(())
+    }
+
+    fn create_account(&mut self, address: Address, pubkey: PublicKey) -> Result<(), Error> {
+        if self.get(address) != Account::zero() {
+            return Err(Error::AccountExists);
+        }
+
+        self.set(
+            address,
+            Account {
+                pubkey,
+                nonce: 0,
+                value: 0,
+            },
+        );
+
+        Ok(())
+    }
+
+    fn delete_account(&mut self, address: Address) -> Result<Amount, Error> {
+        let value = Amount::new(self.get(address).value)?;
+        self.remove(U256::from(<[u8; 32]>::from(address)));
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::accounts::random_accounts;
+    use crate::proof::uncompressed::generate;
+    use crate::transactions;
+    use imp::Imp;
+    use sheth::process::process_transactions;
+    use sheth::state::root::imp_root;
+    use sheth::u264::U264;
+
+    fn proof_root(proof: &UncompressedProof, height: usize) -> [u8; 32] {
+        let mut proof = proof.compress();
+        imp_root(&Imp::<U264>::new(&mut proof, height + 3))
+    }
+
+    #[test]
+    fn root_matches_full_proof() {
+        let accounts = random_accounts(8, 16);
+        let proof = generate(accounts.clone(), 16);
+
+        assert_eq!(
+            *StateCache::new(accounts, 16).root().as_bytes(),
+            proof_root(&proof, 16)
+        );
+    }
+
+    #[test]
+    fn update_rehashes_dirty_paths() {
+        let mut accounts = random_accounts(8, 16);
+        let mut cache = StateCache::new(accounts.clone(), 16);
+        let pre_root = cache.root();
+
+        accounts[3].1.value += 1;
+        accounts[5].1.nonce += 1;
+        cache.update(&accounts[3]);
+        cache.update(&accounts[5]);
+
+        let post_root = cache.root();
+        assert_ne!(pre_root, post_root);
+        assert_eq!(post_root, StateCache::new(accounts, 16).root());
+    }
+
+    #[test]
+    fn witness_matches_fresh_state() {
+        let mut accounts = random_accounts(8, 16);
+        let mut cache = StateCache::new(accounts.clone(), 16);
+        cache.root();
+
+        accounts[1].1.value -= 1;
+        cache.update(&accounts[1]);
+
+        let addresses = [accounts[1].0, accounts[6].0];
+        let witness = cache.witness(&addresses);
+
+        assert_eq!(
+            witness,
+            StateCache::new(accounts.clone(), 16).witness(&addresses)
+        );
+        assert_eq!(
+            proof_root(&witness, 16),
+            proof_root(&generate(accounts, 16), 16)
+        );
+    }
+
+    #[test]
+    fn execute_against_full_state() {
+        let accounts = random_accounts(8, 16);
+        let txs = transactions::generate(4, accounts.clone());
+        let addresses: Vec<U256> = accounts.iter().map(|a| a.0).collect();
+
+        let mut cache = StateCache::new(accounts.clone(), 16);
+        let witness = cache.witness(&addresses).compress();
+        assert_eq!(process_transactions(&mut cache, &txs), Ok(()));
+
+        let mut proof = witness;
+        let mut mem = Imp::<U264>::new(&mut proof, 16 + 3);
+        assert_eq!(process_transactions(&mut mem, &txs), Ok(()));
+        assert_eq!(State::root(&mut cache), Ok(imp_root(&mem)));
+
+        // The post state's witness authenticates against the post state root.
+        let post = cache.witness(&addresses[0..2]);
+        assert_eq!(proof_root(&post, 16), imp_root(&mem));
+    }
+
+    #[test]
+    fn delete_restores_empty_subtree() {
+        let accounts = random_accounts(2, 16);
+        let mut cache = StateCache::new(accounts.clone(), 16);
+        let root = cache.root();
+
+        let address = Address::from(U256::from(7));
+        cache.create_account(address, PublicKey::one()).unwrap();
+        assert_eq!(cache.add_value(address, 3.into()), Ok(3.into()));
+        assert_eq!(cache.accounts().len(), 3);
+        assert_ne!(cache.root(), root);
+
+        assert_eq!(cache.delete_account(address), Ok(3.into()));
+        assert_eq!(cache.root(), root);
+        assert_eq!(cache.accounts(), accounts_by_address(accounts));
+    }
+
+    fn accounts_by_address(mut accounts: Vec<AddressedAccount>) -> Vec<AddressedAccount> {
+        accounts.sort_by_key(|a| a.0);
+        accounts
+    }
+}
@@ -1,5 +1,14 @@
 #[cfg(test)]
 use arrayref::array_ref;
+use std::fmt;
+use std::str::FromStr;
+
+/// An enum of errors that can occur while parsing an `H256`.
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// The string is not 64 hex digits, with or without a `0x` prefix
+    HexInvalid(String),
+}
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct H256([u8; 32]);
@@ -9,9 +18,78 @@ impl H256 {
         H256(arr.clone())
     }
 
+    pub fn zero() -> Self {
+        H256([0u8; 32])
+    }
+
     pub fn as_bytes(&self) -> &[u8; 32] {
         &self.0
     }
+
+    /// Compare against `other` in time which doesn't depend on where they differ, for
+    /// comparisons next to signatures and secrets where a timing leak might matter.
+    pub fn ct_eq(&self, other: &H256) -> bool {
+        let diff = self
+            .0
+            .iter()
+            .zip(other.0.iter())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b));
+
+        std::hint::black_box(diff) == 0
+    }
+}
+
+impl From<[u8; 32]> for H256 {
+    fn from(arr: [u8; 32]) -> Self {
+        H256(arr)
+    }
+}
+
+impl From<H256> for [u8; 32] {
+    fn from(h: H256) -> Self {
+        h.0
+    }
+}
+
+impl AsRef<[u8]> for H256 {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl fmt::LowerHex for H256 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+
+        write!(f, "{}", hex::encode(self.0))
+    }
+}
+
+/// Writes the hash as `0x` prefixed hex.
+impl fmt::Display for H256 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:#x}", self)
+    }
+}
+
+/// Parses 64 hex digits, with or without a `0x` prefix.
+impl FromStr for H256 {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(s.trim_start_matches("0x"))
+            .map_err(|_| Error::HexInvalid(s.to_string()))?;
+
+        if bytes.len() != 32 {
+            return Err(Error::HexInvalid(s.to_string()));
+        }
+
+        let mut ret = [0u8; 32];
+        ret.copy_from_slice(&bytes);
+        Ok(H256(ret))
+    }
 }
 
 #[cfg(test)]
@@ -20,3 +98,45 @@ pub fn zh(depth: usize) -> H256 {
     sheth::hash::zh(depth, &mut buf);
     H256::new(array_ref![buf, 0, 32])
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const HASH: &str = "0x00000000000000000000000000000000000000000000000000000000000abcde";
+
+    #[test]
+    fn format_hex() {
+        let mut arr = [0u8; 32];
+        arr[29..32].copy_from_slice(&[0x0a, 0xbc, 0xde]);
+        let h = H256::from(arr);
+
+        assert_eq!(h.to_string(), HASH);
+        assert_eq!(format!("{:x}", h), &HASH[2..]);
+        assert_eq!(format!("{:#x}", h), HASH);
+    }
+
+    #[test]
+    fn parse_hex() {
+        let h: H256 = HASH.parse().unwrap();
+
+        assert_eq!(h.as_ref()[29..32], [0x0a, 0xbc, 0xde]);
+        assert_eq!(HASH[2..].parse(), Ok(h));
+        assert_eq!(
+            HASH[..65].parse::<H256>(),
+            Err(Error::HexInvalid(HASH[..65].to_string()))
+        );
+        assert!("0xzz".parse::<H256>().is_err());
+    }
+
+    #[test]
+    fn constant_time_equality() {
+        let h: H256 = HASH.parse().unwrap();
+        let mut arr = <[u8; 32]>::from(h);
+        arr[0] ^= 1;
+
+        assert!(h.ct_eq(&h));
+        assert!(!h.ct_eq(&H256::from(arr)));
+        assert!(H256::zero().ct_eq(&H256::new(&[0u8; 32])));
+    }
+}
@@ -1,4 +1,6 @@
+pub mod cache;
 pub mod h256;
 pub mod offsets;
 pub mod sort;
 pub mod uncompressed;
+pub mod verify;
@@ -1,10 +1,10 @@
-use bigint::U512;
+use sheth::u264::U264;
 
 /// Returns a vector of offsets that is read by an in-place lookup algorithm to determine the
 /// location of a particular 32 byte value in the multiproof.
 ///
 /// For more info, see: https://github.com/protolambda/eth-merkle-trees
-pub fn calculate(indexes: Vec<U512>) -> Vec<u64> {
+pub fn calculate(indexes: Vec<U264>) -> Vec<u64> {
     let mut raw_indexes = vec![];
 
     // Convert indexes into arrays of bits
@@ -77,13 +77,13 @@ mod test {
 
     #[test]
     fn offset_4_bit_left() {
-        let indexes: Vec<U512> = vec![8.into(), 9.into(), 5.into(), 12.into(), 13.into(), 7.into()];
+        let indexes: Vec<U264> = vec![8.into(), 9.into(), 5.into(), 12.into(), 13.into(), 7.into()];
         assert_eq!(calculate(indexes), vec![6, 3, 2, 1, 2, 1]);
     }
 
     #[test]
     fn offset_4_bit_right() {
-        let indexes: Vec<U512> = vec![
+        let indexes: Vec<U264> = vec![
             4.into(),
             10.into(),
             11.into(),
@@ -97,7 +97,7 @@ mod test {
 
     #[test]
     fn offset_4_bit_full() {
-        let indexes: Vec<U512> = vec![
+        let indexes: Vec<U264> = vec![
             8.into(),
             9.into(),
             10.into(),
@@ -113,25 +113,25 @@ mod test {
 
     #[test]
     fn offset_4_bit_left_small_branch() {
-        let indexes: Vec<U512> = vec![4.into(), 10.into(), 11.into(), 3.into()];
+        let indexes: Vec<U264> = vec![4.into(), 10.into(), 11.into(), 3.into()];
         assert_eq!(calculate(indexes), vec![4, 3, 1, 1]);
     }
 
     #[test]
     fn offset_4_bit_right_small_branch() {
-        let indexes: Vec<U512> = vec![2.into(), 12.into(), 13.into(), 7.into()];
+        let indexes: Vec<U264> = vec![2.into(), 12.into(), 13.into(), 7.into()];
         assert_eq!(calculate(indexes), vec![4, 1, 2, 1]);
     }
 
     #[test]
     fn offset_5_bit_right_small_branch() {
-        let indexes: Vec<U512> = vec![16.into(), 17.into(), 9.into(), 5.into(), 3.into()];
+        let indexes: Vec<U264> = vec![16.into(), 17.into(), 9.into(), 5.into(), 3.into()];
         assert_eq!(calculate(indexes), vec![5, 4, 3, 2, 1]);
     }
 
     #[test]
     fn offset_5_bit_left_branch() {
-        let indexes: Vec<U512> = vec![
+        let indexes: Vec<U264> = vec![
             16.into(),
             17.into(),
             9.into(),
@@ -145,13 +145,13 @@ mod test {
 
     #[test]
     fn offset_5_bit_right_branch() {
-        let indexes: Vec<U512> = vec![4.into(), 10.into(), 22.into(), 23.into(), 3.into()];
+        let indexes: Vec<U264> = vec![4.into(), 10.into(), 22.into(), 23.into(), 3.into()];
         assert_eq!(calculate(indexes), vec![5, 4, 1, 1, 1]);
     }
 
     #[test]
     fn offset_5_bit_full() {
-        let mut indexes: Vec<U512> = vec![];
+        let mut indexes: Vec<U264> = vec![];
 
         for i in 16..32 {
             indexes.push(i.into());
@@ -1,10 +1,10 @@
-use bigint::U512;
+use sheth::u264::U264;
 use std::ops::Shl;
 
 /// Sort a vector bit-alphabetically
 ///
 /// For more info, see: https://github.com/ethereum/eth2.0-specs/issues/1303
-pub fn alpha_sort(n: &Vec<U512>) -> Vec<U512> {
+pub fn alpha_sort(n: &Vec<U264>) -> Vec<U264> {
     let mut ret = n.clone();
 
     ret.sort_by(|a, b| {
@@ -19,7 +19,7 @@ pub fn alpha_sort(n: &Vec<U512>) -> Vec<U512> {
     ret
 }
 
-fn normalize(a: U512, b: U512) -> (U512, usize, U512, usize) {
+fn normalize(a: U264, b: U264) -> (U264, usize, U264, usize) {
     // Normalize (e.g. right pad until the the most significant bit in `a` and `b` align)
     let max = std::cmp::max(a.bits(), b.bits());
 
@@ -46,12 +46,12 @@ mod test {
 
     #[test]
     fn normalize_numbers() {
-        let one = U512::from(1);
-        let two = U512::from(2);
-        let big = U512::from(std::u64::MAX);
+        let one = U264::from(1);
+        let two = U264::from(2);
+        let big = (U264::one() << 64usize) - one;
 
         assert_eq!(normalize(one, two), (two, 1, two, 0));
-        assert_eq!(normalize(big, one), (big, 0, U512::from(2u64.pow(63)), 63));
+        assert_eq!(normalize(big, one), (big, 0, one << 63usize, 63));
     }
 
     #[test]
@@ -64,14 +64,14 @@ mod test {
 
     #[test]
     fn alphas_sort_branch() {
-        let unsorted: Vec<U512> = vec![20, 21, 11, 4, 3]
+        let unsorted: Vec<U264> = vec![20, 21, 11, 4, 3]
             .into_iter()
             .fold(vec![], |mut acc, n| {
                 acc.push(n.into());
                 acc
             });
 
-        let sorted: Vec<U512> = vec![4, 20, 21, 11, 3]
+        let sorted: Vec<U264> = vec![4, 20, 21, 11, 3]
             .into_iter()
             .fold(vec![], |mut acc, n| {
                 acc.push(n.into());
@@ -84,12 +84,12 @@ mod test {
     #[ignore] // Current implementation only works on branches
     #[test]
     fn alpha_sort_many_numbers() {
-        let unsorted: Vec<U512> = (1..4).fold(vec![], |mut acc, n| {
+        let unsorted: Vec<U264> = (1..4).fold(vec![], |mut acc, n| {
             acc.push(n.into());
             acc
         });
 
-        let sorted: Vec<U512> = vec![
+        let sorted: Vec<U264> = vec![
             16, 8, 17, 4, 18, 9, 19, 2, 20, 10, 21, 5, 22, 11, 23, 1, 24, 12, 25, 6, 26, 13, 27, 3,
             28, 14, 29, 7, 30, 15, 31,
         ]
@@ -1,14 +1,34 @@
 use crate::accounts::AddressedAccount;
+use crate::proof::cache::StateCache;
 use crate::proof::h256::H256;
+use crate::proof::offsets::calculate as calculate_offsets;
 use crate::proof::sort::alpha_sort;
 use arrayref::array_ref;
-use bigint::U512;
-use sheth::hash::{hash, zh};
-use std::collections::HashMap;
+use bigint::U256;
+use sheth::hash::{hash, ZERO_HASHES};
+use sheth::state::imp::validate;
+use sheth::u264::U264;
+use std::collections::{BTreeSet, HashMap};
+
+/// An enum of errors that can occur while patching a proof.
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// The index is not one of the proof's chunks
+    LeafUnknown(U264),
+
+    /// The sibling of a node is missing, so the root can't be calculated
+    ProofIncomplete(U264),
+
+    /// The serialized proof's offsets and chunks don't form a multi-proof
+    ProofInvalid(sheth::error::Error),
+
+    /// The proof at this position authenticates against a different root than the first
+    RootMismatch(usize),
+}
 
 #[derive(Debug, PartialEq)]
 pub struct UncompressedProof {
-    pub indexes: Vec<U512>,
+    pub indexes: Vec<U264>,
     pub values: Vec<H256>,
 }
 
@@ -26,45 +46,246 @@ pub fn generate(accounts: Vec<AddressedAccount>, height: usize) -> UncompressedP
     }
 }
 
-pub fn init_multiproof(accounts: Vec<AddressedAccount>, height: usize) -> HashMap<U512, H256> {
-    let mut map: HashMap<U512, H256> = HashMap::new();
+impl UncompressedProof {
+    /// Returns the proof in the format read by `Imp`: the number of offsets, the offsets, and then
+    /// the values.
+    pub fn compress(&self) -> Vec<u8> {
+        let offsets = calculate_offsets(self.indexes.clone());
 
-    for account in accounts.into_iter() {
-        let (address, account) = (account.0, account.1);
+        let ret = offsets.iter().fold(vec![], |mut acc, x| {
+            acc.extend(&x.to_le_bytes());
+            acc
+        });
 
-        // Calulate the root index of the account (e.g. `first_leaf` + address)
-        let index = (U512::one() << height) + U512::from(address);
+        self.values.iter().fold(ret, |mut acc, x| {
+            acc.extend(x.as_bytes());
+            acc
+        })
+    }
 
-        // Copy the values of the account into a buffer
-        let mut buf = [0u8; 128];
-        buf[0..48].copy_from_slice(&account.pubkey.as_bytes());
-        buf[64..72].copy_from_slice(&account.nonce.to_le_bytes());
-        buf[96..104].copy_from_slice(&account.value.to_le_bytes());
+    /// Parses a proof in the format written by `compress`, finding the general index of each
+    /// chunk by walking the offsets from the root.
+    pub fn decompress(proof: &[u8]) -> Result<Self, Error> {
+        if proof.len() < 8 {
+            return Err(Error::ProofInvalid(sheth::error::Error::ProofTruncated));
+        }
+
+        let len = u64::from_le_bytes(*array_ref![proof, 0, 8]) as usize;
+        if len == 0 || proof.len() < len.saturating_mul(8) {
+            return Err(Error::ProofInvalid(sheth::error::Error::ProofTruncated));
+        }
+
+        let (offsets, chunks) = (&proof[8..len * 8], &proof[len * 8..]);
+        validate(offsets, chunks).map_err(Error::ProofInvalid)?;
+
+        let mut indexes = vec![U264::zero(); len];
+
+        // Each entry is the general index, offset position, number of chunks below and first
+        // chunk of a subtree.
+        let mut stack = vec![(U264::one(), 0, len, 0)];
+        while let Some((index, position, size, first)) = stack.pop() {
+            if size == 1 {
+                indexes[first] = index;
+                continue;
+            }
+
+            let left = u64::from_le_bytes(*array_ref![offsets, position * 8, 8]) as usize;
+            stack.push((index << 1usize, position + 1, left, first));
+            stack.push((
+                (index << 1usize) + 1.into(),
+                position + left,
+                size - left,
+                first + left,
+            ));
+        }
 
-        // Insert children nodes of the account, where structure looks like:
-        map.insert(index << 3, H256::new(array_ref![buf, 0, 32]));
-        map.insert((index << 3) + 1.into(), H256::new(array_ref![buf, 32, 32]));
-        map.insert((index << 2) + 1.into(), H256::new(array_ref![buf, 64, 32]));
-        map.insert((index << 2) + 2.into(), H256::new(array_ref![buf, 96, 32]));
-        map.insert((index << 2) + 3.into(), H256::new(&[0u8; 32]));
+        Ok(UncompressedProof {
+            indexes,
+            values: chunks
+                .chunks(32)
+                .map(|chunk| H256::new(array_ref![chunk, 0, 32]))
+                .collect(),
+        })
+    }
+
+    /// Calculate the root that the proof authenticates against.
+    pub fn root(&self) -> Result<H256, Error> {
+        let mut nodes: HashMap<U264, H256> = self
+            .indexes
+            .iter()
+            .cloned()
+            .zip(self.values.iter().cloned())
+            .collect();
+
+        // A node always has a greater index than its parent, so taking the greatest index first
+        // means the sibling of each node has been calculated before it is needed.
+        let mut pending: BTreeSet<U264> = self.indexes.iter().cloned().collect();
+        while let Some(index) = pending.iter().next_back().cloned() {
+            pending.remove(&index);
+
+            if index <= U264::one() {
+                break;
+            }
+
+            let sibling = index ^ U264::one();
+            let parent = index >> 1usize;
+            pending.remove(&sibling);
+
+            let (left, right) = if index < sibling {
+                (index, sibling)
+            } else {
+                (sibling, index)
+            };
+
+            let mut buf = [0u8; 64];
+            for (i, node) in [left, right].iter().enumerate() {
+                let value = nodes.get(node).ok_or(Error::ProofIncomplete(*node))?;
+                buf[i * 32..(i + 1) * 32].copy_from_slice(value.as_bytes());
+            }
+            hash(&mut buf);
+
+            nodes.insert(parent, H256::new(array_ref![buf, 0, 32]));
+            pending.insert(parent);
+        }
+
+        nodes
+            .get(&U264::one())
+            .cloned()
+            .ok_or(Error::ProofIncomplete(U264::one()))
+    }
+
+    /// Overwrite the chunk at `index` and return the proof's new root, so fixtures can be adjusted
+    /// (e.g. bumping a balance) without regenerating the proof from the full state.
+    pub fn patch_leaf(&mut self, index: U264, chunk: H256) -> Result<H256, Error> {
+        let position = self
+            .indexes
+            .iter()
+            .position(|i| *i == index)
+            .ok_or(Error::LeafUnknown(index))?;
+
+        self.values[position] = chunk;
+        self.root()
+    }
+}
+
+/// Generate a proof of only the accounts at `addresses`, authenticated against the root of the
+/// state made up of all of `accounts`.
+pub fn generate_subset(
+    accounts: Vec<AddressedAccount>,
+    addresses: &[U256],
+    height: usize,
+) -> UncompressedProof {
+    StateCache::new(accounts, height).witness(addresses)
+}
+
+/// Merge multi-proofs against the same root into a single proof of every chunk any of them
+/// proves.
+///
+/// A node which one proof includes and another proves the children of is an ancestor of chunks
+/// in the union, so it is dropped in favour of the deeper chunks, and branches shared between
+/// the proofs appear once.
+pub fn merge(proofs: &[UncompressedProof]) -> Result<UncompressedProof, Error> {
+    let root = proofs
+        .first()
+        .ok_or(Error::ProofIncomplete(U264::one()))?
+        .root()?;
+
+    for (i, proof) in proofs.iter().enumerate().skip(1) {
+        if proof.root()? != root {
+            return Err(Error::RootMismatch(i));
+        }
+    }
+
+    let mut map: HashMap<U264, H256> = HashMap::new();
+    for proof in proofs {
+        map.extend(
+            proof
+                .indexes
+                .iter()
+                .cloned()
+                .zip(proof.values.iter().cloned()),
+        );
+    }
+
+    let mut ancestors: BTreeSet<U264> = BTreeSet::new();
+    for index in map.keys() {
+        let mut parent = *index >> 1usize;
+        while parent >= U264::one() && ancestors.insert(parent) {
+            parent = parent >> 1usize;
+        }
+    }
+
+    let indexes: Vec<U264> = map
+        .keys()
+        .filter(|index| !ancestors.contains(index))
+        .cloned()
+        .collect();
+    let indexes = alpha_sort(&indexes);
+
+    Ok(UncompressedProof {
+        values: indexes.iter().map(|i| map[i]).collect(),
+        indexes,
+    })
+}
+
+/// Returns the indexes of the chunks which make up the account whose root is at `index`.
+pub(crate) fn account_leaves(index: U264) -> Vec<U264> {
+    vec![
+        index << 3usize,
+        (index << 3usize) + 1.into(),
+        (index << 2usize) + 1.into(),
+        (index << 2usize) + 2.into(),
+        (index << 2usize) + 3.into(),
+    ]
+}
+
+/// Returns the index and value of each chunk of `account`.
+pub(crate) fn account_chunks(account: &AddressedAccount, height: usize) -> Vec<(U264, H256)> {
+    let (address, account) = (account.0, &account.1);
+
+    // Calulate the root index of the account (e.g. `first_leaf` + address)
+    let index = (U264::one() << height) + U264::from(address);
+
+    // Copy the values of the account into a buffer
+    let mut buf = [0u8; 128];
+    buf[0..48].copy_from_slice(&account.pubkey.as_bytes());
+    buf[64..72].copy_from_slice(&account.nonce.to_le_bytes());
+    buf[96..104].copy_from_slice(&account.value.to_le_bytes());
+
+    // Children nodes of the account, where structure looks like:
+    let leaves = account_leaves(index);
+    vec![
+        (leaves[0], H256::new(array_ref![buf, 0, 32])),
+        (leaves[1], H256::new(array_ref![buf, 32, 32])),
+        (leaves[2], H256::new(array_ref![buf, 64, 32])),
+        (leaves[3], H256::new(array_ref![buf, 96, 32])),
+        (leaves[4], H256::new(&[0u8; 32])),
+    ]
+}
+
+pub fn init_multiproof(accounts: Vec<AddressedAccount>, height: usize) -> HashMap<U264, H256> {
+    let mut map: HashMap<U264, H256> = HashMap::new();
+
+    for account in accounts.iter() {
+        map.extend(account_chunks(account, height));
     }
 
     map
 }
 
-fn fill_proof(map: &mut HashMap<U512, H256>, height: usize) -> Vec<U512> {
-    let mut indexes: Vec<U512> = map.keys().map(|x| x.to_owned()).collect();
+fn fill_proof(map: &mut HashMap<U264, H256>, height: usize) -> Vec<U264> {
+    let mut indexes: Vec<U264> = map.keys().map(|x| x.to_owned()).collect();
     indexes.sort();
     indexes.reverse();
 
-    let mut proof_indexes: Vec<U512> = indexes.clone();
+    let mut proof_indexes: Vec<U264> = indexes.clone();
 
     let mut position = 0;
 
-    while indexes[position] > U512::from(1) {
-        let left = indexes[position] & (!U512::zero() - U512::one());
+    while indexes[position] > U264::from(1) {
+        let left = indexes[position] & (!U264::zero() - U264::one());
         let right = left + 1.into();
-        let parent = left / 2.into();
+        let parent = left >> 1usize;
 
         if !map.contains_key(&parent) {
             let left = get_or_generate(
@@ -105,18 +326,16 @@ fn fill_proof(map: &mut HashMap<U512, H256>, height: usize) -> Vec<U512> {
 }
 
 fn get_or_generate(
-    map: &mut HashMap<U512, H256>,
-    proof_indexes: &mut Vec<U512>,
+    map: &mut HashMap<U264, H256>,
+    proof_indexes: &mut Vec<U264>,
     height: usize,
-    index: U512,
+    index: U264,
     zero_bits: usize,
 ) -> H256 {
     match map.get(&index) {
         Some(x) => x.clone(),
         None => {
-            let mut buf = [0u8; 64];
-            zh(height + 1 - zero_bits, &mut buf);
-            let buf = H256::new(array_ref![buf, 0, 32]);
+            let buf = H256::new(&ZERO_HASHES[height + 1 - zero_bits]);
             proof_indexes.push(index);
             map.insert(index, buf.clone());
             buf
@@ -130,6 +349,7 @@ mod test {
     use crate::proof::h256::zh;
     use sheth::account::Account;
     use sheth::bls::PublicKey;
+    use sheth::state::root::imp_root;
 
     #[test]
     fn one_bit_tree() {
@@ -205,6 +425,45 @@ mod test {
         );
     }
 
+    #[test]
+    fn subset_of_single_account_matches_full_proof() {
+        let account = Account {
+            pubkey: PublicKey::one(),
+            nonce: 42,
+            value: 123,
+        };
+
+        let accounts = vec![AddressedAccount(9.into(), account)];
+
+        assert_eq!(
+            generate_subset(accounts.clone(), &[9.into()], 4),
+            generate(accounts, 4)
+        );
+    }
+
+    #[test]
+    fn subset_uses_hashes_of_omitted_accounts() {
+        let account = Account {
+            pubkey: PublicKey::one(),
+            nonce: 42,
+            value: 123,
+        };
+
+        let accounts = vec![
+            AddressedAccount(0.into(), account.clone()),
+            AddressedAccount(1.into(), account),
+        ];
+
+        let full = generate(accounts.clone(), 1);
+        let subset = generate_subset(accounts, &[0.into()], 1);
+
+        // Account 1 is replaced by its root instead of the zero hash.
+        assert_eq!(subset.indexes.last(), Some(&3.into()));
+        assert_ne!(subset.values.last(), Some(&zh(0)));
+        assert_eq!(subset.indexes.len(), 6);
+        assert_eq!(full.indexes.len(), 10);
+    }
+
     #[test]
     fn four_bit_tree_single_account() {
         //
@@ -269,4 +528,103 @@ mod test {
             }
         );
     }
+
+    #[test]
+    fn patch_leaf_matches_regenerated_proof() {
+        let mut account = Account {
+            pubkey: PublicKey::one(),
+            nonce: 42,
+            value: 123,
+        };
+
+        let mut proof = generate(vec![AddressedAccount(9.into(), account.clone())], 4);
+        let pre_root = proof.root().unwrap();
+
+        account.value = 124;
+        let expected = generate(vec![AddressedAccount(9.into(), account)], 4);
+
+        let mut chunk = [0u8; 32];
+        chunk[0..8].copy_from_slice(&124u64.to_le_bytes());
+        let post_root = proof.patch_leaf(102.into(), H256::new(&chunk)).unwrap();
+
+        assert_ne!(pre_root, post_root);
+        assert_eq!(proof, expected);
+        assert_eq!(post_root, expected.root().unwrap());
+
+        let mut compressed = proof.compress();
+        let mem = imp::Imp::<sheth::u264::U264>::new(&mut compressed, 4 + 3);
+        assert_eq!(*post_root.as_bytes(), imp_root(&mem));
+    }
+
+    #[test]
+    fn patch_unknown_leaf() {
+        let account = Account {
+            pubkey: PublicKey::one(),
+            nonce: 42,
+            value: 123,
+        };
+
+        let mut proof = generate(vec![AddressedAccount(9.into(), account)], 4);
+        assert_eq!(
+            proof.patch_leaf(12.into(), zh(0)),
+            Err(Error::LeafUnknown(12.into()))
+        );
+
+        proof.indexes.remove(0);
+        proof.values.remove(0);
+        assert_eq!(proof.root(), Err(Error::ProofIncomplete(2.into())));
+    }
+
+    #[test]
+    fn decompress_round_trip() {
+        let account = Account {
+            pubkey: PublicKey::one(),
+            nonce: 42,
+            value: 123,
+        };
+
+        let proof = generate(vec![AddressedAccount(9.into(), account)], 4);
+        assert_eq!(UncompressedProof::decompress(&proof.compress()), Ok(proof));
+
+        assert_eq!(
+            UncompressedProof::decompress(&[0u8; 4]),
+            Err(Error::ProofInvalid(sheth::error::Error::ProofTruncated))
+        );
+    }
+
+    #[test]
+    fn merge_matches_joint_proof() {
+        let account = Account {
+            pubkey: PublicKey::one(),
+            nonce: 42,
+            value: 123,
+        };
+
+        let accounts: Vec<AddressedAccount> = [0, 1, 9, 14]
+            .iter()
+            .map(|a| AddressedAccount((*a).into(), account.clone()))
+            .collect();
+
+        let left = generate_subset(accounts.clone(), &[0.into(), 9.into()], 4);
+        let right = generate_subset(accounts.clone(), &[1.into(), 9.into()], 4);
+        let joint = generate_subset(accounts, &[0.into(), 1.into(), 9.into()], 4);
+
+        let merged = merge(&[left, right]).unwrap();
+        assert_eq!(merged, joint);
+    }
+
+    #[test]
+    fn merge_different_roots() {
+        let account = Account {
+            pubkey: PublicKey::one(),
+            nonce: 42,
+            value: 123,
+        };
+
+        let proof = generate(vec![AddressedAccount(9.into(), account.clone())], 4);
+        let other = generate(vec![AddressedAccount(3.into(), account)], 4);
+
+        assert_eq!(merge(&[]), Err(Error::ProofIncomplete(U264::one())));
+        assert_eq!(merge(&[proof, other]), Err(Error::RootMismatch(1)));
+    }
 }
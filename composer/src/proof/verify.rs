@@ -0,0 +1,197 @@
+use crate::accounts::AddressedAccount;
+use arrayref::array_ref;
+use imp::Imp;
+use sheth::address::Address;
+use sheth::hash::{hash, ZERO_HASHES};
+use sheth::state::imp::new_checked;
+use sheth::state::root::imp_root;
+use sheth::state::State;
+use sheth::u264::U264;
+
+/// Returns `true` if the compressed `proof` authenticates against `root` and its leaves for the
+/// account's address match the account.
+pub fn verify_account(
+    proof: &[u8],
+    root: &[u8; 32],
+    account: &AddressedAccount,
+    height: usize,
+) -> bool {
+    let mut proof = proof.to_vec();
+    let mem = match new_checked(&mut proof, height + 3) {
+        Ok(mem) => mem,
+        Err(_) => return false,
+    };
+
+    if imp_root(&mem) != *root {
+        return false;
+    }
+
+    verify_leaves(&mem, account)
+}
+
+/// Returns `true` if the leaves of `account` in `mem` match the account.
+pub fn verify_leaves(mem: &Imp<U264>, account: &AddressedAccount) -> bool {
+    let AddressedAccount(address, account) = account;
+    let address = Address::from(*address);
+
+    mem.value(address).map(u64::from) == Ok(account.value)
+        && mem.nonce(address) == Ok(account.nonce.into())
+        && mem.pubkey(address).as_ref() == Ok(&account.pubkey)
+}
+
+/// Verifies many `(root, witness)` pairs for trees of the same height, e.g. witnesses targeted at
+/// several recent state roots.
+///
+/// The table of empty subtree roots and the scratch space used to decode offsets are shared
+/// across every witness, and the parent of two empty subtrees is looked up in the table rather
+/// than hashed.
+pub struct BatchVerifier {
+    height: usize,
+    zeros: Vec<[u8; 32]>,
+    offsets: Vec<u64>,
+}
+
+impl BatchVerifier {
+    pub fn new(height: usize) -> Self {
+        let zeros = ZERO_HASHES[..=height].to_vec();
+
+        Self {
+            height,
+            zeros,
+            offsets: vec![],
+        }
+    }
+
+    /// Returns whether each witness authenticates against the root it is paired with.
+    pub fn verify_all(&mut self, pairs: &[([u8; 32], &[u8])]) -> Vec<bool> {
+        pairs
+            .iter()
+            .map(|(root, witness)| self.verify(root, witness))
+            .collect()
+    }
+
+    /// Returns `true` if the compressed `witness` authenticates against `root`.
+    pub fn verify(&mut self, root: &[u8; 32], witness: &[u8]) -> bool {
+        if witness.len() < 8 {
+            return false;
+        }
+
+        let length = u64::from_le_bytes(*array_ref![witness, 0, 8]) as usize;
+        if length == 0 || witness.len() as u128 != length as u128 * 40 {
+            return false;
+        }
+
+        self.offsets.clear();
+        self.offsets.extend(
+            witness[8..length * 8]
+                .chunks(8)
+                .map(|c| u64::from_le_bytes(*array_ref![c, 0, 8])),
+        );
+
+        let chunks = &witness[length * 8..];
+        match self.node(chunks, &self.offsets, 0, 0) {
+            Some(computed) => computed == *root,
+            None => false,
+        }
+    }
+
+    /// Calculates the root of the subtree at `depth` described by `offsets`, whose first chunk is
+    /// `offset`. Returns `None` if the offsets are malformed.
+    fn node(&self, chunks: &[u8], offsets: &[u64], offset: u64, depth: usize) -> Option<[u8; 32]> {
+        let chunk = |i: u64| {
+            let i = (i as usize).checked_mul(32)?;
+            chunks.get(i..i + 32).map(|c| *array_ref![c, 0, 32])
+        };
+
+        if offsets.is_empty() {
+            return chunk(offset);
+        }
+
+        let split = offsets[0];
+        if split == 0 || split as usize > offsets.len() {
+            return None;
+        }
+
+        let left = if split != 1 {
+            self.node(chunks, &offsets[1..split as usize], offset, depth + 1)?
+        } else {
+            chunk(offset)?
+        };
+
+        let right = if offsets.len() != 1 {
+            self.node(
+                chunks,
+                &offsets[split as usize..],
+                offset + split,
+                depth + 1,
+            )?
+        } else {
+            chunk(offset + 1)?
+        };
+
+        // Both children are empty subtrees, so their parent is too.
+        if depth < self.height {
+            let zero = &self.zeros[self.height - depth - 1];
+            if left == *zero && right == *zero {
+                return Some(self.zeros[self.height - depth]);
+            }
+        }
+
+        let mut buf = [0u8; 64];
+        buf[0..32].copy_from_slice(&left);
+        buf[32..64].copy_from_slice(&right);
+        hash(&mut buf);
+
+        Some(*array_ref![buf, 0, 32])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::accounts::random_accounts;
+    use crate::blob::generate_from_accounts;
+
+    #[test]
+    fn verify_witnesses_against_several_roots() {
+        let witnesses: Vec<Vec<u8>> = (1..4)
+            .map(|n| generate_from_accounts(random_accounts(n, 16), 0, 16).proof)
+            .collect();
+
+        let roots: Vec<[u8; 32]> = witnesses
+            .iter()
+            .map(|w| {
+                let mut w = w.clone();
+                imp_root(&Imp::<U264>::new(&mut w, 16 + 3))
+            })
+            .collect();
+
+        let mut verifier = BatchVerifier::new(16);
+
+        let pairs: Vec<([u8; 32], &[u8])> = roots
+            .iter()
+            .zip(witnesses.iter())
+            .map(|(r, w)| (*r, &w[..]))
+            .collect();
+        assert_eq!(verifier.verify_all(&pairs), vec![true, true, true]);
+
+        let swapped = [(roots[1], &witnesses[0][..]), (roots[0], &witnesses[1][..])];
+        assert_eq!(verifier.verify_all(&swapped), vec![false, false]);
+    }
+
+    #[test]
+    fn reject_malformed_witnesses() {
+        let witness = generate_from_accounts(random_accounts(2, 8), 0, 8).proof;
+        let mut w = witness.clone();
+        let root = imp_root(&Imp::<U264>::new(&mut w, 8 + 3));
+
+        let mut verifier = BatchVerifier::new(8);
+        assert!(verifier.verify(&root, &witness));
+        assert!(!verifier.verify(&root, &witness[..witness.len() - 1]));
+        assert!(!verifier.verify(&root, &[]));
+
+        let mut bad_offset = witness.clone();
+        bad_offset[8..16].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(!verifier.verify(&root, &bad_offset));
+    }
+}
@@ -0,0 +1,97 @@
+use rand_chacha::rand_core::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+
+/// Version of the algorithm used by `DeterministicRng`. It must be bumped whenever the output for
+/// a seed changes, since the golden corpora are generated from it.
+pub const VERSION: u32 = 1;
+
+/// The generator used by the packager, so corpora generated on different platforms and with
+/// different releases of `rand` are byte-identical.
+///
+/// `StdRng`'s algorithm is not guaranteed to be stable, so every step is defined here instead.
+/// Version 1 expands the seed with PCG32 into a ChaCha20 key and samples ranges by widening
+/// multiplication with rejection, like `rand` 0.7 does on 64-bit targets. It therefore
+/// reproduces the corpora which were generated with `StdRng` before the generator was pinned.
+pub struct DeterministicRng(ChaCha20Rng);
+
+impl DeterministicRng {
+    pub fn new(seed: u64) -> Self {
+        const MUL: u64 = 6_364_136_223_846_793_005;
+        const INC: u64 = 11_634_580_027_462_260_723;
+
+        let mut state = seed;
+        let mut key = [0u8; 32];
+
+        for chunk in key.chunks_mut(4) {
+            state = state.wrapping_mul(MUL).wrapping_add(INC);
+
+            let xorshifted = (((state >> 18) ^ state) >> 27) as u32;
+            let rot = (state >> 59) as u32;
+            chunk.copy_from_slice(&xorshifted.rotate_right(rot).to_le_bytes());
+        }
+
+        DeterministicRng(ChaCha20Rng::from_seed(key))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    pub fn fill(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    /// Returns a value in `[low, high)`.
+    pub fn range(&mut self, low: u64, high: u64) -> u64 {
+        assert!(low < high, "range is empty");
+
+        let range = high - low;
+        let zone = (range << range.leading_zeros()).wrapping_sub(1);
+
+        loop {
+            let product = u128::from(self.next_u64()) * u128::from(range);
+            if product as u64 <= zone {
+                return low + (product >> 64) as u64;
+            }
+        }
+    }
+
+    /// Returns an index into a collection of `len` items.
+    pub fn index(&mut self, len: usize) -> usize {
+        self.range(0, len as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::{rngs::StdRng, Rng};
+
+    #[test]
+    fn stable_output() {
+        let mut rng = DeterministicRng::new(42);
+
+        let mut buf = [0u8; 8];
+        rng.fill(&mut buf);
+        assert_eq!(hex::encode(buf), "7848b5d711bc9883");
+        assert_eq!(rng.next_u64(), 7566832397956113305);
+        assert_eq!(rng.range(1, 1000), 98);
+        assert_eq!(rng.index(10), 3);
+    }
+
+    #[test]
+    fn matches_std_rng() {
+        let mut rng = DeterministicRng::new(7);
+        let mut std = StdRng::seed_from_u64(7);
+
+        for i in 1..1000u64 {
+            let (mut a, mut b) = ([0u8; 48], [0u8; 48]);
+            rng.fill(&mut a);
+            std.fill(&mut b[..]);
+
+            assert_eq!(a[..], b[..]);
+            assert_eq!(rng.next_u64(), std.gen::<u64>());
+            assert_eq!(rng.range(0, i), std.gen_range(0, i));
+        }
+    }
+}
@@ -0,0 +1,311 @@
+use crate::accounts::AddressedAccount;
+use crate::blob::generate_from_accounts;
+use crate::proof::cache::StateCache;
+use crate::proof::verify::verify_leaves;
+use arrayref::array_ref;
+use bigint::U256;
+use imp::Imp;
+use sheth::account::Account;
+use sheth::bls::PublicKey;
+use sheth::state::imp::new_checked;
+use sheth::state::root::imp_root;
+use sheth::u264::U264;
+use std::collections::BTreeMap;
+
+/// Size of a serialized account within a chunk: address, pubkey, nonce, and value.
+const ACCOUNT_LENGTH: usize = 32 + 48 + 8 + 8;
+
+/// An enum of errors that can occur while importing a snapshot.
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// The chunk's index is outside of the snapshot
+    ChunkUnknown(usize),
+
+    /// The chunk's multi-proof does not authenticate against the trusted root
+    RootMismatch(usize),
+
+    /// An account in the chunk does not match the leaves of the chunk's multi-proof
+    AccountMismatch(usize),
+
+    /// The chunk could not be deserialized
+    ChunkMalformed,
+
+    /// Not every chunk has been imported, or the chunks do not make up the whole state
+    SnapshotIncomplete,
+}
+
+/// A contiguous range of accounts from the state, along with a multi-proof of those accounts.
+///
+/// Since the multi-proof is authenticated against the state root, each chunk can be verified on
+/// its own as it arrives without trusting the peer that sent it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SnapshotChunk {
+    pub index: usize,
+    pub accounts: Vec<AddressedAccount>,
+    pub proof: Vec<u8>,
+}
+
+impl SnapshotChunk {
+    /// Serialize the chunk as `index || count || accounts || proof`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut ret = (self.index as u64).to_le_bytes().to_vec();
+        ret.extend(&(self.accounts.len() as u32).to_le_bytes());
+
+        for AddressedAccount(address, account) in &self.accounts {
+            let mut buf = [0u8; 32];
+            address.to_big_endian(&mut buf);
+            ret.extend(&buf);
+            ret.extend(&account.pubkey.as_bytes()[..]);
+            ret.extend(&account.nonce.to_le_bytes());
+            ret.extend(&account.value.to_le_bytes());
+        }
+
+        ret.extend(&self.proof);
+        ret
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() < 12 {
+            return Err(Error::ChunkMalformed);
+        }
+
+        let index = u64::from_le_bytes(*array_ref![bytes, 0, 8]) as usize;
+        let count = u32::from_le_bytes(*array_ref![bytes, 8, 4]) as usize;
+
+        let end = count
+            .checked_mul(ACCOUNT_LENGTH)
+            .and_then(|n| n.checked_add(12))
+            .ok_or(Error::ChunkMalformed)?;
+
+        if bytes.len() < end {
+            return Err(Error::ChunkMalformed);
+        }
+
+        let accounts = bytes[12..end]
+            .chunks(ACCOUNT_LENGTH)
+            .map(|buf| {
+                AddressedAccount(
+                    U256::from(array_ref![buf, 0, 32]),
+                    Account {
+                        pubkey: PublicKey::new(*array_ref![buf, 32, 48]),
+                        nonce: u64::from_le_bytes(*array_ref![buf, 80, 8]),
+                        value: u64::from_le_bytes(*array_ref![buf, 88, 8]),
+                    },
+                )
+            })
+            .collect();
+
+        Ok(SnapshotChunk {
+            index,
+            accounts,
+            proof: bytes[end..].to_vec(),
+        })
+    }
+}
+
+/// Split the full state into chunks of at most `chunk_size` accounts, ordered by address.
+pub fn export(
+    accounts: &[AddressedAccount],
+    height: usize,
+    chunk_size: usize,
+) -> Vec<SnapshotChunk> {
+    assert!(chunk_size > 0, "chunks must contain at least one account");
+
+    let mut accounts = accounts.to_vec();
+    accounts.sort_by_key(|a| a.0);
+
+    let mut cache = StateCache::new(accounts.clone(), height);
+
+    accounts
+        .chunks(chunk_size)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let addresses: Vec<U256> = chunk.iter().map(|a| a.0).collect();
+
+            SnapshotChunk {
+                index,
+                accounts: chunk.to_vec(),
+                proof: cache.witness(&addresses).compress(),
+            }
+        })
+        .collect()
+}
+
+/// Rebuilds the full state from snapshot chunks, verifying each against a trusted state root.
+///
+/// Chunks may be imported in any order and the import can be resumed by requesting the chunk
+/// returned from `next_missing`.
+pub struct Importer {
+    root: [u8; 32],
+    height: usize,
+    received: Vec<bool>,
+    accounts: BTreeMap<U256, Account>,
+}
+
+impl Importer {
+    pub fn new(root: [u8; 32], height: usize, chunks: usize) -> Self {
+        Importer {
+            root,
+            height,
+            received: vec![false; chunks],
+            accounts: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the index of the next chunk which still needs to be imported.
+    pub fn next_missing(&self) -> Option<usize> {
+        self.received.iter().position(|r| !r)
+    }
+
+    /// Verify `chunk` against the trusted root and add its accounts to the state.
+    pub fn import(&mut self, chunk: SnapshotChunk) -> Result<(), Error> {
+        if chunk.index >= self.received.len() {
+            return Err(Error::ChunkUnknown(chunk.index));
+        }
+
+        let index = chunk.index;
+        let mut proof = chunk.proof;
+        let mem =
+            new_checked(&mut proof, self.height + 3).map_err(|_| Error::RootMismatch(index))?;
+        if imp_root(&mem) != self.root {
+            return Err(Error::RootMismatch(chunk.index));
+        }
+
+        for account in &chunk.accounts {
+            if !verify_leaves(&mem, account) {
+                return Err(Error::AccountMismatch(chunk.index));
+            }
+        }
+
+        for AddressedAccount(address, account) in chunk.accounts {
+            self.accounts.insert(address, account);
+        }
+
+        self.received[chunk.index] = true;
+
+        Ok(())
+    }
+
+    /// Returns the full state once every chunk has been imported and the accounts make up the
+    /// entire state committed to by the trusted root.
+    pub fn finish(self) -> Result<Vec<AddressedAccount>, Error> {
+        if self.next_missing().is_some() || self.accounts.is_empty() {
+            return Err(Error::SnapshotIncomplete);
+        }
+
+        let accounts: Vec<AddressedAccount> = self
+            .accounts
+            .into_iter()
+            .map(|(address, account)| AddressedAccount(address, account))
+            .collect();
+
+        let mut proof = generate_from_accounts(accounts.clone(), 0, self.height).proof;
+        let mem = Imp::<U264>::new(&mut proof, self.height + 3);
+
+        if imp_root(&mem) != self.root {
+            return Err(Error::SnapshotIncomplete);
+        }
+
+        Ok(accounts)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::accounts::random_accounts;
+
+    fn state_root(accounts: &[AddressedAccount], height: usize) -> [u8; 32] {
+        let mut proof = generate_from_accounts(accounts.to_vec(), 0, height).proof;
+        imp_root(&Imp::<U264>::new(&mut proof, height + 3))
+    }
+
+    #[test]
+    fn export_and_import() {
+        let accounts = random_accounts(10, 256);
+        let root = state_root(&accounts, 256);
+        let chunks = export(&accounts, 256, 3);
+        assert_eq!(chunks.len(), 4);
+
+        let mut importer = Importer::new(root, 256, chunks.len());
+
+        // Import out of order over the wire, then resume from the first missing chunk.
+        for chunk in chunks.iter().rev().take(2) {
+            let chunk = SnapshotChunk::from_bytes(&chunk.to_bytes()).unwrap();
+            assert_eq!(importer.import(chunk), Ok(()));
+        }
+
+        while let Some(index) = importer.next_missing() {
+            assert_eq!(importer.import(chunks[index].clone()), Ok(()));
+        }
+
+        let mut expected = accounts.clone();
+        expected.sort_by_key(|a| a.0);
+
+        let imported = importer.finish().unwrap();
+        assert_eq!(
+            imported.iter().map(|a| a.0).collect::<Vec<U256>>(),
+            expected.iter().map(|a| a.0).collect::<Vec<U256>>()
+        );
+    }
+
+    #[test]
+    fn reject_tampered_chunk() {
+        let accounts = random_accounts(4, 256);
+        let root = state_root(&accounts, 256);
+        let mut chunks = export(&accounts, 256, 2);
+
+        let mut importer = Importer::new(root, 256, chunks.len());
+
+        chunks[0].accounts[0].1.value += 1;
+        assert_eq!(
+            importer.import(chunks[0].clone()),
+            Err(Error::AccountMismatch(0))
+        );
+
+        let last = chunks[1].proof.len() - 1;
+        chunks[1].proof[last] ^= 1;
+        assert_eq!(
+            importer.import(chunks[1].clone()),
+            Err(Error::RootMismatch(1))
+        );
+
+        assert_eq!(importer.next_missing(), Some(0));
+    }
+
+    #[test]
+    fn reject_incomplete_snapshot() {
+        let accounts = random_accounts(4, 256);
+        let root = state_root(&accounts, 256);
+        let mut chunks = export(&accounts, 256, 2);
+
+        // A peer omitting an account still produces a chunk that authenticates.
+        chunks[1].accounts.pop();
+
+        let mut importer = Importer::new(root, 256, chunks.len());
+        for chunk in chunks {
+            assert_eq!(importer.import(chunk), Ok(()));
+        }
+
+        assert_eq!(importer.finish(), Err(Error::SnapshotIncomplete));
+    }
+
+    #[test]
+    fn reject_unknown_and_malformed_chunks() {
+        let accounts = random_accounts(2, 256);
+        let root = state_root(&accounts, 256);
+        let mut chunks = export(&accounts, 256, 2);
+
+        let mut importer = Importer::new(root, 256, 1);
+        chunks[0].index = 1;
+        assert_eq!(
+            importer.import(chunks[0].clone()),
+            Err(Error::ChunkUnknown(1))
+        );
+
+        assert_eq!(
+            SnapshotChunk::from_bytes(&[0u8; 4]),
+            Err(Error::ChunkMalformed)
+        );
+    }
+}
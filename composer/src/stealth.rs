@@ -0,0 +1,75 @@
+use crate::accounts::AddressedAccount;
+use crate::proof::cache::{StateCache, Store};
+use crate::proof::uncompressed::UncompressedProof;
+use bigint::U256;
+use sheth::account::Account;
+use sheth::amount::Amount;
+use sheth::bls::PublicKey;
+use sheth::state::State;
+use sheth::stealth::StealthTransfer;
+
+/// Build a stealth transfer of `amount` from `from` to `recipient` for the block with `salt`,
+/// along with a witness of the sender and the recipient's empty one-time account.
+pub fn generate<S: Store>(
+    state: &mut StateCache<S>,
+    from: U256,
+    recipient: PublicKey,
+    amount: Amount,
+    salt: &[u8; 32],
+) -> (StealthTransfer, UncompressedProof) {
+    let tx = StealthTransfer {
+        from: from.into(),
+        recipient,
+        nonce: State::nonce(state, from.into()).expect("full state to contain every account"),
+        amount,
+        signature: [0u8; 96],
+    };
+
+    // An empty account hashes to the same root as an empty subtree, so it can be written to the
+    // full state without changing it. This makes its chunks part of the witness.
+    let to = U256::from(<[u8; 32]>::from(tx.to(salt, state.height())));
+    if state.store().account(&to).is_none() {
+        state.update(&AddressedAccount(to, Account::zero()));
+    }
+
+    let witness = state.witness(&[from, to]);
+
+    (tx, witness)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::accounts::random_accounts;
+    use imp::Imp;
+    use sheth::state::root::imp_root;
+    use sheth::stealth::apply;
+    use sheth::u264::U264;
+
+    #[test]
+    fn witness_proves_one_time_account() {
+        let accounts = random_accounts(8, 32);
+        let mut state = StateCache::new(accounts.clone(), 32);
+        let pre_root = state.root();
+
+        let (tx, witness) = generate(
+            &mut state,
+            accounts[0].0,
+            PublicKey::one(),
+            3.into(),
+            &[7u8; 32],
+        );
+        assert_eq!(state.root(), pre_root);
+
+        let mut proof = witness.compress();
+        let mut mem = Imp::<U264>::new(&mut proof, 32 + 3);
+        assert_eq!(imp_root(&mem), *pre_root.as_bytes());
+
+        let to = apply(&mut mem, &tx, &[7u8; 32], 32).unwrap();
+        apply(&mut state, &tx, &[7u8; 32], 32).unwrap();
+
+        assert_eq!(imp_root(&mem), *state.root().as_bytes());
+        assert_eq!(mem.value(to), Ok(3.into()));
+        assert_eq!(mem.pubkey(to), Ok(PublicKey::one()));
+    }
+}
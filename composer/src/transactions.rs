@@ -1,30 +1,38 @@
 use crate::accounts::AddressedAccount;
-use rand::{rngs::StdRng, Rng, SeedableRng};
-use sheth::transaction::{Transaction, Transfer};
+use crate::rng::DeterministicRng;
+use sheth::address::Address;
+use sheth::amount::Amount;
+use sheth::error::Error;
+use sheth::nonce::Nonce;
+use sheth::transaction::{encode_transactions, Transaction, Transfer};
+use sheth::{deserialize_proposer, deserialize_slot, deserialize_transactions, BLOB_HEADER_LENGTH};
 
 /// Generate `n` number of transactions between `accounts`.
 pub fn generate(n: usize, mut accounts: Vec<AddressedAccount>) -> Vec<Transaction> {
-    let mut rng = StdRng::seed_from_u64(42);
+    let mut rng = DeterministicRng::new(42);
 
     let mut transactions: Vec<Transaction> = vec![];
 
     for _ in 0..n {
-        let to = rng.gen_range(0, accounts.len());
-        let from = rng.gen_range(0, accounts.len());
+        let to = rng.index(accounts.len());
+        let from = rng.index(accounts.len());
 
         let tx = Transaction::Transfer(Transfer {
             to: accounts[to].0.into(),
             from: accounts[from].0.into(),
-            nonce: accounts[from].1.nonce,
-            amount: rng.gen_range(0, accounts[from].1.value),
+            nonce: accounts[from].1.nonce.into(),
+            amount: Amount::new(rng.range(0, accounts[from].1.value))
+                .expect("amount to be below the limit"),
+            fee: Amount::default(),
+            valid_until: None,
             signature: [0u8; 96],
         });
 
         match &tx {
             Transaction::Transfer(t) => {
                 accounts[from].1.nonce += 1;
-                accounts[from].1.value -= t.amount;
-                accounts[to].1.value += t.amount;
+                accounts[from].1.value -= t.amount.get();
+                accounts[to].1.value += t.amount.get();
             }
             _ => unreachable!(),
         }
@@ -35,22 +43,96 @@ pub fn generate(n: usize, mut accounts: Vec<AddressedAccount>) -> Vec<Transactio
     transactions
 }
 
-/// Convert an array of `Transaction` to an unaligned array of `u8`.
-pub fn serialize(transactions: &[Transaction]) -> Vec<u8> {
-    let mut bytes = transactions.len().to_le_bytes()[0..4].to_vec();
+/// Apply `transactions` directly to the full set of `accounts`, following the same rules `sheth`
+/// uses to process a block at `slot` against a multi-proof, and pay their fees to `proposer`.
+///
+/// As in `sheth` without a deposit root or foreign receipts root, deposits and claims are
+/// skipped, and so is a transaction which has expired by `slot` or isn't at its sender's nonce.
+/// The amount of a withdrawal or cross transfer leaves the EE, so it is debited from the sender
+/// and credited to no account.
+pub fn apply(
+    transactions: &[Transaction],
+    proposer: Address,
+    slot: u64,
+    accounts: &mut [AddressedAccount],
+) -> Result<(), Error> {
+    fn find(accounts: &[AddressedAccount], address: Address) -> Result<usize, Error> {
+        accounts
+            .iter()
+            .position(|a| Address::from(a.0) == address)
+            .ok_or_else(|| Error::StateIncomplete(address.into()))
+    }
 
     for tx in transactions {
-        match tx {
-            Transaction::Transfer(tx) => {
-                bytes.extend_from_slice(&<[u8; 32]>::from(tx.to));
-                bytes.extend_from_slice(&<[u8; 32]>::from(tx.from));
-                bytes.extend_from_slice(&tx.nonce.to_le_bytes());
-                bytes.extend_from_slice(&tx.amount.to_le_bytes());
-                bytes.extend_from_slice(&tx.signature);
+        let mut payments: Vec<(Option<Address>, Amount)> = match tx {
+            Transaction::Transfer(t) => vec![(Some(t.to), t.amount)],
+            Transaction::Batch(b) => b.payments.iter().map(|p| (Some(p.to), p.amount)).collect(),
+            Transaction::Withdrawal(w) => vec![(None, w.amount)],
+            Transaction::CrossTransfer(c) => vec![(None, c.amount)],
+            Transaction::ChangeKey(_) => vec![],
+            Transaction::Deposit(_) | Transaction::Claim(_) => continue,
+        };
+
+        if tx.verify_slot(slot).is_err() {
+            continue;
+        }
+
+        let from = find(accounts, tx.from())?;
+        if Nonce::from(accounts[from].1.nonce) != tx.nonce() {
+            continue;
+        }
+
+        accounts[from].1.nonce = tx.nonce().next()?.get();
+        if tx.fee() != Amount::default() {
+            payments.push((Some(proposer), tx.fee()));
+        }
+
+        for (to, amount) in payments {
+            accounts[from].1.value = Amount::new(accounts[from].1.value)?
+                .checked_sub(amount)
+                .map_err(|_| Error::BalanceInsufficient)?
+                .get();
+
+            if let Some(to) = to {
+                let to = find(accounts, to)?;
+                accounts[to].1.value = Amount::new(accounts[to].1.value)?
+                    .checked_add(amount)?
+                    .get();
             }
-            _ => unimplemented!(),
+        }
+
+        if let Transaction::ChangeKey(k) = tx {
+            accounts[from].1.pubkey = k.pubkey.clone();
         }
     }
 
+    Ok(())
+}
+
+/// Convert an array of `Transaction` to the header of a blob proposed by `proposer` at `slot`
+/// followed by their SSZ encoding, as `sheth` expects at the start of a blob.
+pub fn serialize(proposer: Address, slot: u64, transactions: &[Transaction]) -> Vec<u8> {
+    let mut bytes = transactions.len().to_le_bytes()[0..4].to_vec();
+    bytes.extend_from_slice(&<[u8; 32]>::from(proposer));
+    bytes.extend_from_slice(&slot.to_le_bytes());
+    bytes.extend(encode_transactions(transactions));
     bytes
 }
+
+/// Parse the header and transactions at the start of a blob, the inverse of `serialize`,
+/// returning the proposer, slot and transactions.
+pub fn deserialize(blob: &[u8]) -> Result<(Address, u64, Vec<Transaction>), Error> {
+    if blob.len() < BLOB_HEADER_LENGTH {
+        return Err(Error::TransactionsLengthInvalid);
+    }
+
+    let mut tx_count = [0u8; 4];
+    tx_count.copy_from_slice(&blob[0..4]);
+    let (transactions, _) = deserialize_transactions(blob, u32::from_le_bytes(tx_count) as usize)?;
+
+    Ok((
+        deserialize_proposer(blob),
+        deserialize_slot(blob),
+        transactions,
+    ))
+}
@@ -0,0 +1,217 @@
+use crate::accounts::{address_from_pubkey, AddressedAccount};
+use crate::rng::DeterministicRng;
+use bigint::U256;
+use sheth::account::Account;
+use sheth::bls::PublicKey;
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::thread;
+
+/// The `bits` most significant bits of an address within a tree of a given height.
+///
+/// Since an account's address is its position amongst the leaves of the state tree, the prefix
+/// also describes the path from the root of the tree to the subtree containing the account.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Prefix {
+    pub value: U256,
+    pub bits: usize,
+}
+
+impl Prefix {
+    /// Returns `true` if the path to `address` in a tree of `height` begins with the prefix.
+    pub fn matches(&self, address: U256, height: usize) -> bool {
+        assert!(self.bits <= height, "prefix is longer than the tree height");
+
+        if self.bits == 0 {
+            return true;
+        }
+
+        (address >> (height - self.bits)) == self.value
+    }
+}
+
+/// Parses a string of `0`s and `1`s (e.g. `"0110"`) into a `Prefix`.
+impl FromStr for Prefix {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Prefix, String> {
+        if s.len() > 256 {
+            return Err(s.to_string());
+        }
+
+        s.chars().try_fold(
+            Prefix {
+                value: U256::zero(),
+                bits: 0,
+            },
+            |acc, c| {
+                let bit = match c {
+                    '0' => U256::zero(),
+                    '1' => U256::one(),
+                    _ => return Err(s.to_string()),
+                };
+
+                Ok(Prefix {
+                    value: (acc.value << 1) | bit,
+                    bits: acc.bits + 1,
+                })
+            },
+        )
+    }
+}
+
+/// Search for `n` accounts whose addresses begin with `prefix` in a tree of `height`.
+///
+/// The search is split across `threads` workers which each draw public keys from their own
+/// `DeterministicRng`, derived from `seed`. Results are merged in worker order, so the same
+/// arguments will always produce the same accounts regardless of how the workers are scheduled,
+/// or which version of `rand` is built.
+pub fn search(
+    prefix: Prefix,
+    n: usize,
+    height: usize,
+    seed: u64,
+    threads: usize,
+) -> Vec<AddressedAccount> {
+    assert!(threads > 0, "at least one worker is required");
+    assert!(
+        prefix.bits <= height,
+        "prefix is longer than the tree height"
+    );
+
+    // Guard against searching forever for accounts that can't exist.
+    let free_bits = height - prefix.bits;
+    if free_bits < 64 {
+        assert!(
+            n as u64 <= 1u64 << free_bits,
+            "not enough addresses share the prefix"
+        );
+    }
+
+    let mut seen: HashSet<U256> = HashSet::new();
+    let mut found: Vec<AddressedAccount> = vec![];
+    let mut round = 0;
+
+    while found.len() < n {
+        let per_worker = (n - found.len()).div_ceil(threads);
+
+        let workers: Vec<_> = (0..threads)
+            .map(|worker| {
+                let seed = seed.wrapping_add((round * threads + worker) as u64);
+                thread::spawn(move || search_worker(prefix, per_worker, height, seed))
+            })
+            .collect();
+
+        for worker in workers {
+            for account in worker.join().expect("worker to complete") {
+                if found.len() < n && seen.insert(account.0) {
+                    found.push(account);
+                }
+            }
+        }
+
+        round += 1;
+    }
+
+    found
+}
+
+fn search_worker(prefix: Prefix, n: usize, height: usize, seed: u64) -> Vec<AddressedAccount> {
+    let mut rng = DeterministicRng::new(seed);
+    let mut ret = vec![];
+    let mut pubkey = [0u8; 48];
+
+    while ret.len() < n {
+        rng.fill(&mut pubkey);
+
+        let address = address_from_pubkey(&pubkey, height);
+        if !prefix.matches(address, height) {
+            continue;
+        }
+
+        ret.push(AddressedAccount(
+            address,
+            Account {
+                pubkey: PublicKey::new(pubkey),
+                nonce: rng.next_u64(),
+                value: rng.range(1, 1000),
+            },
+        ));
+    }
+
+    ret
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_prefix() {
+        assert_eq!(
+            "".parse::<Prefix>(),
+            Ok(Prefix {
+                value: 0.into(),
+                bits: 0
+            })
+        );
+
+        assert_eq!(
+            "0110".parse::<Prefix>(),
+            Ok(Prefix {
+                value: 6.into(),
+                bits: 4
+            })
+        );
+
+        assert_eq!("012".parse::<Prefix>(), Err("012".to_string()));
+    }
+
+    #[test]
+    fn prefix_matches() {
+        let prefix: Prefix = "10".parse().unwrap();
+
+        assert!(prefix.matches(8.into(), 4));
+        assert!(prefix.matches(11.into(), 4));
+        assert!(!prefix.matches(4.into(), 4));
+        assert!(!prefix.matches(12.into(), 4));
+    }
+
+    #[test]
+    fn search_finds_prefixed_accounts() {
+        let prefix: Prefix = "1011".parse().unwrap();
+        let accounts = search(prefix, 5, 256, 42, 3);
+
+        assert_eq!(accounts.len(), 5);
+
+        let mut seen = HashSet::new();
+        for AddressedAccount(address, account) in accounts {
+            assert!(prefix.matches(address, 256));
+            assert_eq!(
+                address,
+                address_from_pubkey(&account.pubkey.as_bytes(), 256)
+            );
+            assert!(seen.insert(address));
+        }
+    }
+
+    #[test]
+    fn search_is_deterministic() {
+        let prefix: Prefix = "110".parse().unwrap();
+
+        let a: Vec<U256> = search(prefix, 4, 32, 7, 4).iter().map(|a| a.0).collect();
+        let b: Vec<U256> = search(prefix, 4, 32, 7, 4).iter().map(|a| a.0).collect();
+
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn search_exhausts_small_subtree() {
+        // Only 4 addresses begin with `11` in a tree of height 4.
+        let prefix: Prefix = "11".parse().unwrap();
+        let mut addresses: Vec<U256> = search(prefix, 4, 4, 0, 2).iter().map(|a| a.0).collect();
+        addresses.sort();
+
+        assert_eq!(addresses, vec![12.into(), 13.into(), 14.into(), 15.into()]);
+    }
+}
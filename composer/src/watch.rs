@@ -0,0 +1,179 @@
+use bigint::U256;
+use sheth::address::Address;
+use sheth::amount::Amount;
+use sheth::error::Error;
+use sheth::process::process_transactions;
+use sheth::state::State;
+use sheth::transaction::Transaction;
+use std::collections::BTreeSet;
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+/// The change in value of a watched account caused by a block.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Delta {
+    pub address: U256,
+    pub before: Amount,
+    pub after: Amount,
+}
+
+/// Notification sent to subscribers once a block has been processed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Update {
+    pub block: u64,
+    pub root: [u8; 32],
+
+    /// Deltas of the subscriber's watched accounts which the block changed.
+    pub deltas: Vec<Delta>,
+}
+
+struct Subscriber {
+    sender: Sender<Update>,
+    addresses: BTreeSet<U256>,
+}
+
+/// Notifies subscribers of each new post-state root, so light wallets don't need to poll for
+/// blocks.
+///
+/// Each subscription is a channel. Waiting on it with `Receiver::recv_timeout` gives long-poll
+/// semantics, and a server can forward the updates over whatever transport it offers.
+/// Subscribers which have dropped their receiver are removed on the next block.
+#[derive(Default)]
+pub struct Watcher {
+    block: u64,
+    subscribers: Vec<Subscriber>,
+}
+
+impl Watcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Subscribe to new roots, along with the deltas of any of `addresses` changed by a block.
+    pub fn subscribe(&mut self, addresses: &[U256]) -> Receiver<Update> {
+        let (sender, receiver) = channel();
+
+        self.subscribers.push(Subscriber {
+            sender,
+            addresses: addresses.iter().cloned().collect(),
+        });
+
+        receiver
+    }
+
+    /// Process the block of `transactions` against `db` and notify every subscriber of the new
+    /// root.
+    pub fn process<T: State>(
+        &mut self,
+        db: &mut T,
+        transactions: &[Transaction],
+    ) -> Result<[u8; 32], Error> {
+        // Only the accounts the block touches can change, and they are the only ones a witness
+        // is guaranteed to contain.
+        let touched: BTreeSet<U256> = transactions
+            .iter()
+            .flat_map(|tx| tx.accounts())
+            .map(|address| U256::from(<[u8; 32]>::from(address)))
+            .filter(|address| {
+                self.subscribers
+                    .iter()
+                    .any(|s| s.addresses.contains(address))
+            })
+            .collect();
+
+        let before = touched
+            .iter()
+            .map(|address| Ok((*address, db.value(Address::from(*address))?)))
+            .collect::<Result<Vec<(U256, Amount)>, Error>>()?;
+
+        process_transactions(db, transactions)?;
+        let root = db.root()?;
+
+        let mut deltas = vec![];
+        for (address, before) in before {
+            let after = db.value(Address::from(address))?;
+            if after != before {
+                deltas.push(Delta {
+                    address,
+                    before,
+                    after,
+                });
+            }
+        }
+
+        self.block += 1;
+        let block = self.block;
+
+        self.subscribers.retain(|subscriber| {
+            let update = Update {
+                block,
+                root,
+                deltas: deltas
+                    .iter()
+                    .filter(|d| subscriber.addresses.contains(&d.address))
+                    .cloned()
+                    .collect(),
+            };
+
+            subscriber.sender.send(update).is_ok()
+        });
+
+        Ok(root)
+    }
+
+    /// Returns the number of blocks which have been processed.
+    pub fn block(&self) -> u64 {
+        self.block
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::accounts::random_accounts;
+    use crate::proof::cache::StateCache;
+    use crate::transactions;
+    use std::time::Duration;
+
+    #[test]
+    fn notify_subscribers() {
+        let accounts = random_accounts(4, 16);
+        let txs = transactions::generate(3, accounts.clone());
+        let mut db = StateCache::new(accounts.clone(), 16);
+
+        let mut watcher = Watcher::new();
+        let all = watcher.subscribe(&accounts.iter().map(|a| a.0).collect::<Vec<U256>>());
+        let none = watcher.subscribe(&[]);
+
+        let root = watcher.process(&mut db, &txs).unwrap();
+
+        let update = all.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert_eq!(update.block, 1);
+        assert_eq!(update.root, root);
+        assert!(!update.deltas.is_empty());
+
+        for delta in update.deltas.iter() {
+            assert_eq!(db.value(delta.address.into()), Ok(delta.after));
+            let before = accounts.iter().find(|a| a.0 == delta.address).unwrap();
+            assert_eq!(delta.before, Amount::new(before.1.value).unwrap());
+        }
+
+        let update = none.try_recv().unwrap();
+        assert_eq!(update.root, root);
+        assert!(update.deltas.is_empty());
+    }
+
+    #[test]
+    fn drop_disconnected_subscribers() {
+        let accounts = random_accounts(2, 16);
+        let mut db = StateCache::new(accounts, 16);
+
+        let mut watcher = Watcher::new();
+        let subscription = watcher.subscribe(&[]);
+        drop(watcher.subscribe(&[]));
+
+        watcher.process(&mut db, &[]).unwrap();
+        assert_eq!(watcher.subscribers.len(), 1);
+        assert_eq!(subscription.try_recv().unwrap().block, 1);
+        assert_eq!(watcher.block(), 1);
+    }
+}
@@ -0,0 +1,37 @@
+//! Build a block of transfers with a multi-proof for every account it touches, then run it
+//! through the same entry point the execution environment uses.
+
+use composer::blob;
+use imp::Imp;
+use sheth::config::Config;
+use sheth::process::execute;
+use sheth::process_data_blob;
+use sheth::state::root::imp_root;
+use sheth::u264::U264;
+
+fn main() {
+    let config = Config::new(32);
+    let blob = blob::generate(16, 32, config.height);
+
+    // The block builder computes the roots it expects the block to transition between.
+    let mut proof = blob.proof.clone();
+    let mut mem = Imp::<U264>::new(&mut proof, config.proof_height());
+    let pre_root = imp_root(&mem);
+    let result = execute(&mut mem, &blob.transactions, &config.limits).expect("block to be valid");
+    let post_root = imp_root(&mem);
+
+    // Executing the serialized block must arrive at the same post-state root.
+    let mut data = blob.to_bytes();
+    assert_eq!(process_data_blob(&mut data, &pre_root, &config), post_root);
+
+    println!("pre_state_root  => {}", hex::encode(pre_root));
+    println!("post_state_root => {}", hex::encode(post_root));
+    println!("block size      => {} bytes", data.len());
+    println!(
+        "transactions    => {} of {} applied",
+        result.transactions_applied,
+        blob.transactions.len()
+    );
+    println!("accounts        => {}", result.accounts_touched);
+    println!("steps           => {}", result.steps);
+}
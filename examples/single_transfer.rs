@@ -0,0 +1,63 @@
+//! Create two accounts in a full state, build a witness for them, and transfer value between
+//! them statelessly. The post-state root computed from the witness must match the root of the
+//! full state after the same transfer.
+
+use composer::accounts::{address_from_pubkey, AddressedAccount};
+use composer::proof::cache::StateCache;
+use imp::Imp;
+use sheth::account::Account;
+use sheth::bls::PublicKey;
+use sheth::process::process_transactions;
+use sheth::state::root::imp_root;
+use sheth::state::State;
+use sheth::transaction::{Transaction, Transfer};
+use sheth::u264::U264;
+
+const HEIGHT: usize = 32;
+
+fn account(seed: u8, value: u64) -> AddressedAccount {
+    let pubkey = [seed; 48];
+
+    AddressedAccount(
+        address_from_pubkey(&pubkey, HEIGHT),
+        Account {
+            pubkey: PublicKey::new(pubkey),
+            nonce: 0,
+            value,
+        },
+    )
+}
+
+fn main() {
+    let alice = account(1, 100);
+    let bob = account(2, 0);
+
+    // The full state is kept by a state provider, who builds witnesses for the accounts a
+    // transaction touches.
+    let mut full = StateCache::new(vec![alice.clone(), bob.clone()], HEIGHT);
+    let pre_root = full.root();
+    let mut witness = full.witness(&[alice.0, bob.0]).compress();
+
+    let txs = vec![Transaction::Transfer(Transfer {
+        to: bob.0.into(),
+        from: alice.0.into(),
+        nonce: 0.into(),
+        amount: 30.into(),
+        fee: 0.into(),
+        valid_until: None,
+        signature: [0u8; 96],
+    })];
+
+    // The witness is all that's needed to authenticate and apply the transfer.
+    let mut mem = Imp::<U264>::new(&mut witness, HEIGHT + 3);
+    assert_eq!(imp_root(&mem), *pre_root.as_bytes());
+    process_transactions(&mut mem, &txs).expect("transfer to be valid");
+
+    process_transactions(&mut full, &txs).expect("transfer to be valid");
+    assert_eq!(imp_root(&mem), *full.root().as_bytes());
+
+    println!("pre_state_root  => {}", hex::encode(pre_root.as_bytes()));
+    println!("post_state_root => {}", hex::encode(imp_root(&mem)));
+    println!("alice           => {}", mem.value(alice.0.into()).unwrap());
+    println!("bob             => {}", mem.value(bob.0.into()).unwrap());
+}
@@ -0,0 +1,49 @@
+//! Verify witnesses for individual accounts against a state root, as a light client would before
+//! trusting the balances they contain.
+
+use composer::accounts::random_accounts;
+use composer::proof::cache::StateCache;
+use composer::proof::verify::{verify_account, BatchVerifier};
+
+const HEIGHT: usize = 32;
+
+fn main() {
+    let accounts = random_accounts(64, HEIGHT);
+    let mut full = StateCache::new(accounts.clone(), HEIGHT);
+    let root = *full.root().as_bytes();
+
+    let witnesses: Vec<Vec<u8>> = accounts
+        .iter()
+        .map(|account| full.witness(&[account.0]).compress())
+        .collect();
+
+    for (account, witness) in accounts.iter().zip(witnesses.iter()).take(4) {
+        assert!(verify_account(witness, &root, account, HEIGHT));
+
+        let mut address = [0u8; 32];
+        account.0.to_big_endian(&mut address);
+        println!(
+            "0x{} => {} ({} bytes)",
+            hex::encode(address),
+            account.1.value,
+            witness.len()
+        );
+    }
+
+    // Changing any chunk of the witness changes the root it proves.
+    let mut tampered = witnesses[0].clone();
+    let last = tampered.len() - 1;
+    tampered[last] ^= 1;
+    assert!(!verify_account(&tampered, &root, &accounts[0], HEIGHT));
+
+    // Many witnesses can be checked at once against one or more roots.
+    let pairs: Vec<([u8; 32], &[u8])> = witnesses.iter().map(|w| (root, &w[..])).collect();
+    let valid = BatchVerifier::new(HEIGHT)
+        .verify_all(&pairs)
+        .into_iter()
+        .filter(|v| *v)
+        .count();
+
+    assert_eq!(valid, witnesses.len());
+    println!("verified {} witnesses against {}", valid, hex::encode(root));
+}
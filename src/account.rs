@@ -1,5 +1,7 @@
 use crate::address::Address;
 use crate::bls::PublicKey;
+use crate::error::Error;
+use crate::schema::{field_index, Field, LeafSchema};
 use crate::u264::U264;
 
 ///  Account merkle tree schema:
@@ -11,8 +13,9 @@ use crate::u264::U264;
 ///           /      \
 ///         nonce   value
 /// ```
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "std", derive(Hash))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Account {
     pub pubkey: PublicKey,
     pub nonce: u64,
@@ -20,6 +23,15 @@ pub struct Account {
 }
 
 impl Account {
+    /// The two chunks of the account's public key, beneath the left child of its root.
+    pub const PUBKEY: Field = Field::new(0, 2);
+
+    /// The account's nonce, as the left child of `other_root`'s left child.
+    pub const NONCE: Field = Field::new(2, 1);
+
+    /// The account's value, as the left child of `other_root`'s right child.
+    pub const VALUE: Field = Field::new(4, 1);
+
     pub fn zero() -> Self {
         Account {
             pubkey: PublicKey::zero(),
@@ -29,6 +41,66 @@ impl Account {
     }
 }
 
+impl LeafSchema for Account {
+    const DEPTH: usize = 3;
+}
+
+/// The greatest supported height of the state tree, so every 256-bit address has a leaf.
+pub const MAX_HEIGHT: usize = 256;
+
+/// Height of a state tree whose addresses are 20 byte values, which keeps witnesses much smaller
+/// than a full 256-bit address space when that many accounts aren't needed.
+pub const SHORT_ADDRESS_HEIGHT: usize = 160;
+
+/// Given an address and tree height, calculate the general index of the account's leaf.
+///
+/// ```text
+/// leaf_index = first_leaf + account
+/// ```
+///
+/// States over a multi-proof pass the height of the proof, which includes the three levels of each
+/// account's subtree, so heights up to `MAX_HEIGHT + 3` are accepted. The indexes of the chunks
+/// below such a leaf still fit in `U264`. Panics on anything greater, since shifting past the
+/// width of `U264` would silently drop the marker bit.
+///
+/// `address` must fit in the tree, which `check_address` checks for addresses from a block.
+#[inline]
+pub fn calc_leaf_index(address: Address, height: usize) -> U264 {
+    assert!(height <= MAX_HEIGHT + 3, "height exceeds MAX_HEIGHT");
+
+    let address = U264::from(address);
+    debug_assert!(
+        (address >> height) == U264::zero(),
+        "address doesn't fit in the tree"
+    );
+
+    (U264::one() << height) + address
+}
+
+/// Returns `Error::AddressInvalid` unless `address` has a leaf in a tree of `height`, not
+/// including the account subtrees. An address beyond the last leaf would otherwise trip the
+/// assertion in `calc_leaf_index`, or in a release build alias the account at the address modulo
+/// `2**height`.
+#[inline]
+pub fn check_address(address: Address, height: usize) -> Result<(), Error> {
+    if (U264::from(address) >> height) != U264::zero() {
+        return Err(Error::AddressInvalid);
+    }
+
+    Ok(())
+}
+
+/// Given an address and tree height, calculate the general index of the first chunk of the
+/// `pubkey`. The second chunk is at the following index.
+///
+/// ```text
+/// pubkey_index = (first_leaf + account) * 8
+/// ```
+#[inline]
+pub fn calc_pubkey_index(address: Address, height: usize) -> U264 {
+    field_index::<Account>(address, height, Account::PUBKEY)
+}
+
 /// Given an address and tree height, calculate the `value`'s general index.
 ///
 /// ```text
@@ -36,7 +108,7 @@ impl Account {
 /// ```
 #[inline]
 pub fn calc_value_index(address: Address, height: usize) -> U264 {
-    ((((U264::one() << height) + address.into()) << 2) + 2.into()) << 1
+    field_index::<Account>(address, height, Account::VALUE)
 }
 
 /// Given an address and tree height, calculate the `nonce`'s general index.
@@ -46,5 +118,55 @@ pub fn calc_value_index(address: Address, height: usize) -> U264 {
 /// ```
 #[inline]
 pub fn calc_nonce_index(address: Address, height: usize) -> U264 {
-    ((((U264::one() << height) + address.into()) << 2) + 1.into()) << 1
+    field_index::<Account>(address, height, Account::NONCE)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use bigint::U256;
+
+    #[test]
+    fn indexes_at_max_height() {
+        let last = Address::from(U256::max_value());
+        let end = U264::one() << (MAX_HEIGHT + 4);
+
+        // The chunks of the last account are the last in the tree, so they must not wrap around.
+        assert_eq!(calc_pubkey_index(last, MAX_HEIGHT), end - 8.into());
+        assert_eq!(calc_nonce_index(last, MAX_HEIGHT), end - 6.into());
+        assert_eq!(calc_value_index(last, MAX_HEIGHT), end - 4.into());
+
+        let first = Address::from(0);
+        assert_eq!(
+            calc_pubkey_index(first, MAX_HEIGHT),
+            U264::one() << (MAX_HEIGHT + 3)
+        );
+
+        // The marker bit is kept when given the height of a full height multi-proof.
+        let proof_height = MAX_HEIGHT + 3;
+        assert_eq!(
+            calc_value_index(last, proof_height) >> proof_height,
+            0b1000.into()
+        );
+    }
+
+    #[test]
+    fn addresses_beyond_the_tree() {
+        let last = Address::from((U256::one() << SHORT_ADDRESS_HEIGHT) - U256::one());
+        assert_eq!(check_address(last, SHORT_ADDRESS_HEIGHT), Ok(()));
+        assert_eq!(
+            check_address(Address::from(U256::one() << 200), SHORT_ADDRESS_HEIGHT),
+            Err(Error::AddressInvalid)
+        );
+        assert_eq!(
+            check_address(Address::from(U256::max_value()), MAX_HEIGHT),
+            Ok(())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "height exceeds MAX_HEIGHT")]
+    fn height_above_max() {
+        calc_leaf_index(0.into(), MAX_HEIGHT + 4);
+    }
 }
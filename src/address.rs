@@ -1,13 +1,147 @@
+use crate::bls::PublicKey;
+use crate::error::Error;
+#[cfg(feature = "eth1")]
+use crate::hash::keccak::keccak256;
+use crate::hash::{hash, Incremental};
 use crate::u264::U264;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
 use bigint::{U256, U512};
+use core::convert::TryFrom;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Address(U256);
 
+const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// How the address of an account is derived from its public key, chosen when the tree is
+/// constructed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AddressScheme {
+    /// `sha256(pubkey) mod 2**height`, see `Address::from_pubkey`.
+    #[default]
+    Sha256,
+
+    /// The last 20 bytes of `keccak256(pubkey)` zero extended, like an eth1 address, so eth1
+    /// accounts can be mirrored into the EE. Trees shorter than 160 levels keep only the low
+    /// `height` bits.
+    #[cfg(feature = "eth1")]
+    Eth1,
+}
+
 impl Address {
     pub const fn new(n: U256) -> Address {
         Address(n)
     }
+
+    /// Derive the address of the account owned by `pubkey` in a tree of `height`, so a verifier
+    /// can check that a sender's address matches the public key in the witness.
+    ///
+    /// ```text
+    /// address = sha256(pubkey) mod 2**height
+    /// ```
+    pub fn from_pubkey(pubkey: &PublicKey, height: usize) -> Address {
+        let mut hasher = Incremental::new();
+        hasher.update(&pubkey.as_bytes());
+
+        let address = U256::from(hasher.finalize());
+        if height < 256 {
+            Address(address % (U256::one() << height))
+        } else {
+            Address(address)
+        }
+    }
+
+    /// Derive the address of the account owned by `pubkey` in a tree of `height` with `scheme`.
+    pub fn derive(pubkey: &PublicKey, height: usize, scheme: AddressScheme) -> Address {
+        match scheme {
+            AddressScheme::Sha256 => Address::from_pubkey(pubkey, height),
+            #[cfg(feature = "eth1")]
+            AddressScheme::Eth1 => {
+                let mut buf = keccak256(&pubkey.as_bytes());
+                buf[0..12].copy_from_slice(&[0u8; 12]);
+
+                let address = U256::from(buf);
+                if height < crate::account::SHORT_ADDRESS_HEIGHT {
+                    Address(address % (U256::one() << height))
+                } else {
+                    Address(address)
+                }
+            }
+        }
+    }
+
+    /// Returns the address as `0x` prefixed hex, with the case of each letter encoding a
+    /// checksum.
+    ///
+    /// Like EIP-55, a letter is upper case if the corresponding nibble of the hash of the lower
+    /// case hex is 8 or more, but the hash is SHA-256 rather than Keccak-256.
+    #[cfg(feature = "alloc")]
+    pub fn to_checksum(&self) -> String {
+        let digits = lower_hex(*self);
+
+        let mut buf = digits;
+        hash(&mut buf);
+
+        let mut ret = String::with_capacity(66);
+        ret.push_str("0x");
+
+        for (i, c) in digits.iter().enumerate() {
+            let nibble = if i % 2 == 0 {
+                buf[i / 2] >> 4
+            } else {
+                buf[i / 2] & 0xf
+            };
+
+            if nibble >= 8 {
+                ret.push(c.to_ascii_uppercase() as char);
+            } else {
+                ret.push(*c as char);
+            }
+        }
+
+        ret
+    }
+}
+
+/// Returns the 64 lower case hex digits of `address`.
+fn lower_hex(address: Address) -> [u8; 64] {
+    let mut ret = [0u8; 64];
+    for (i, byte) in <[u8; 32]>::from(address).iter().enumerate() {
+        ret[i * 2] = DIGITS[(byte >> 4) as usize];
+        ret[i * 2 + 1] = DIGITS[(byte & 0xf) as usize];
+    }
+
+    ret
+}
+
+/// Parses 64 hex digits, with or without a `0x` prefix. Digits which are all lower case or all
+/// upper case are accepted as is, and mixed case must match `Address::to_checksum`.
+impl TryFrom<&str> for Address {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let digits = s.strip_prefix("0x").unwrap_or(s).as_bytes();
+        if digits.len() != 64 {
+            return Err(Error::AddressInvalid);
+        }
+
+        let mut buf = [0u8; 32];
+        for (i, c) in digits.iter().enumerate() {
+            let digit = (*c as char).to_digit(16).ok_or(Error::AddressInvalid)? as u8;
+            buf[i / 2] |= digit << (4 * (1 - i % 2));
+        }
+
+        let address = Address::from(buf);
+
+        let lower = digits.iter().any(|c| c.is_ascii_lowercase());
+        let upper = digits.iter().any(|c| c.is_ascii_uppercase());
+        if lower && upper && address.to_checksum().as_bytes()[2..] != *digits {
+            return Err(Error::AddressInvalid);
+        }
+
+        Ok(address)
+    }
 }
 
 impl From<usize> for Address {
@@ -34,6 +168,51 @@ impl From<[u8; 32]> for Address {
     }
 }
 
+/// Fails if the most significant byte is set, since an address is only 32 bytes.
+impl TryFrom<U264> for Address {
+    type Error = Error;
+
+    fn try_from(n: U264) -> Result<Self, Self::Error> {
+        let bytes = n.to_be_bytes();
+        if bytes[0] != 0 {
+            return Err(Error::AddressInvalid);
+        }
+
+        Ok(Address(U256::from(&bytes[1..])))
+    }
+}
+
+impl From<Address> for U256 {
+    fn from(address: Address) -> U256 {
+        address.0
+    }
+}
+
+/// Zero extends a 20 byte address, e.g. for a tree of `SHORT_ADDRESS_HEIGHT`.
+impl From<[u8; 20]> for Address {
+    fn from(arr: [u8; 20]) -> Address {
+        let mut buf = [0u8; 32];
+        buf[12..32].copy_from_slice(&arr);
+        Address::from(buf)
+    }
+}
+
+/// Fails if any of the 12 most significant bytes are set.
+impl TryFrom<Address> for [u8; 20] {
+    type Error = Error;
+
+    fn try_from(address: Address) -> Result<Self, Self::Error> {
+        let bytes = <[u8; 32]>::from(address);
+        if bytes[0..12] != [0u8; 12] {
+            return Err(Error::AddressInvalid);
+        }
+
+        let mut ret = [0u8; 20];
+        ret.copy_from_slice(&bytes[12..32]);
+        Ok(ret)
+    }
+}
+
 impl From<Address> for U264 {
     fn from(address: Address) -> U264 {
         U264::from(address.0)
@@ -45,3 +224,155 @@ impl From<Address> for [u8; 32] {
         a.0.into()
     }
 }
+
+impl core::fmt::LowerHex for Address {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::LowerHex::fmt(&U264::from(*self), f)
+    }
+}
+
+impl core::fmt::Display for Address {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(&self.to_checksum())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::account::SHORT_ADDRESS_HEIGHT;
+    use alloc::string::ToString;
+
+    const ADDRESS: &str = "00000000000000000000000000000000000000000000000000000000000abcde";
+
+    #[test]
+    fn parse_hex() {
+        let address = Address::from(0xabcde);
+
+        assert_eq!(Address::try_from(ADDRESS), Ok(address));
+        assert_eq!(
+            Address::try_from(&*alloc::format!("0x{}", ADDRESS)),
+            Ok(address)
+        );
+        assert_eq!(
+            Address::try_from(&*ADDRESS.to_ascii_uppercase()),
+            Ok(address)
+        );
+    }
+
+    #[test]
+    fn parse_invalid_hex() {
+        assert_eq!(Address::try_from(&ADDRESS[1..]), Err(Error::AddressInvalid));
+        assert_eq!(Address::try_from("0x"), Err(Error::AddressInvalid));
+        assert_eq!(
+            Address::try_from(&*ADDRESS.replace('a', "g")),
+            Err(Error::AddressInvalid)
+        );
+    }
+
+    #[test]
+    fn checksum() {
+        let address = Address::from(0xabcde);
+        let checksum = address.to_checksum();
+
+        assert_eq!(
+            checksum.to_ascii_lowercase(),
+            alloc::format!("0x{}", ADDRESS)
+        );
+        assert_eq!(address.to_string(), checksum);
+        assert_eq!(Address::try_from(&*checksum), Ok(address));
+
+        // Flipping the case of one letter breaks the checksum, as long as the result is still
+        // mixed case.
+        let flipped: String = checksum
+            .chars()
+            .enumerate()
+            .map(|(i, c)| if i == 61 { flip(c) } else { c })
+            .collect();
+        assert_eq!(Address::try_from(&*flipped), Err(Error::AddressInvalid));
+    }
+
+    #[test]
+    fn from_pubkey() {
+        let pubkey = PublicKey::new([7u8; 48]);
+
+        let mut hasher = Incremental::new();
+        hasher.update(&[7u8; 48]);
+        let digest = hasher.finalize();
+
+        assert_eq!(Address::from_pubkey(&pubkey, 256), Address::from(digest));
+        assert_eq!(
+            Address::from_pubkey(&pubkey, 8),
+            Address::from(digest[31] as usize)
+        );
+        assert_eq!(Address::from_pubkey(&pubkey, 0), Address::from(0));
+    }
+
+    #[test]
+    fn derive() {
+        let pubkey = PublicKey::new([7u8; 48]);
+
+        assert_eq!(
+            Address::derive(&pubkey, 16, AddressScheme::default()),
+            Address::from_pubkey(&pubkey, 16)
+        );
+    }
+
+    #[cfg(feature = "eth1")]
+    #[test]
+    fn derive_eth1() {
+        let pubkey = PublicKey::new([7u8; 48]);
+
+        let mut digest = keccak256(&[7u8; 48]);
+        let address = Address::derive(&pubkey, 256, AddressScheme::Eth1);
+        let bytes = <[u8; 32]>::from(address);
+
+        assert_eq!(bytes[0..12], [0u8; 12]);
+        assert_eq!(bytes[12..32], digest[12..32]);
+
+        // A short tree keeps the low bits, as it does for the default scheme.
+        digest[0..31].copy_from_slice(&[0u8; 31]);
+        assert_eq!(
+            Address::derive(&pubkey, 8, AddressScheme::Eth1),
+            Address::from(digest)
+        );
+    }
+
+    #[test]
+    fn short_addresses() {
+        let mut bytes = [0u8; 20];
+        bytes[0] = 0xff;
+        bytes[19] = 1;
+
+        let address = Address::from(bytes);
+        let leaf = Address::from_pubkey(&PublicKey::new([7u8; 48]), SHORT_ADDRESS_HEIGHT);
+
+        assert_eq!(<[u8; 20]>::try_from(address), Ok(bytes));
+        assert_eq!(U264::from(address) >> SHORT_ADDRESS_HEIGHT, U264::zero());
+        assert!(<[u8; 20]>::try_from(leaf).is_ok());
+        assert_eq!(
+            <[u8; 20]>::try_from(Address::from([1u8; 32])),
+            Err(Error::AddressInvalid)
+        );
+    }
+
+    #[test]
+    fn u264_conversions() {
+        let address = Address::from(0xabcde);
+
+        assert_eq!(Address::try_from(U264::from(address)), Ok(address));
+        assert_eq!(
+            Address::try_from(U264::one() << 256usize),
+            Err(Error::AddressInvalid)
+        );
+        assert_eq!(Address::from(<[u8; 32]>::from(address)), address);
+    }
+
+    fn flip(c: char) -> char {
+        if c.is_ascii_uppercase() {
+            c.to_ascii_lowercase()
+        } else {
+            c.to_ascii_uppercase()
+        }
+    }
+}
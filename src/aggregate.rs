@@ -0,0 +1,286 @@
+//! Blocks signed with one BLS signature aggregated over all of their transactions, so verifying a
+//! block costs a single batched pairing check rather than one per transaction.
+//!
+//! An aggregate blob is laid out like the blob accepted by `process_data_blob`, except that each
+//! transaction omits its signature and the aggregate follows the transactions:
+//!
+//! ```text
+//! header (44) || tx_count * (to || from || nonce || amount || fee || valid_until) (96 each)
+//!     || signature (96) || proof
+//! ```
+//!
+//! Each transaction's message is its `Transfer::signing_root` in the config's `Limits::domain`,
+//! signed by its sender's public key in the pre-state. The block is rejected outright if the
+//! aggregate doesn't verify.
+
+use crate::bls::{AggregateVerifier, Unchecked};
+use crate::config::Config;
+use crate::domain::Domain;
+use crate::error::Error;
+use crate::process::{execute_with, settle_fees, Limits};
+use crate::state::imp::new_checked;
+use crate::state::State;
+use crate::transaction::{Transaction, Transfer, TRANSFER_LENGTH};
+use crate::{deserialize_proposer, deserialize_slot, BLOB_HEADER_LENGTH};
+use alloc::vec::Vec;
+use arrayref::array_ref;
+
+/// Length of a transaction without its signature.
+pub const UNSIGNED_TRANSACTION_LENGTH: usize = TRANSFER_LENGTH - SIGNATURE_LENGTH;
+
+/// Length of the aggregate signature.
+pub const SIGNATURE_LENGTH: usize = 96;
+
+/// Check `signature` against the signing roots of `transactions` in `domain` and their senders'
+/// public keys in `db`.
+pub fn verify_block<T: State, V: AggregateVerifier>(
+    db: &T,
+    transactions: &[Transaction],
+    signature: &[u8; 96],
+    verifier: &V,
+    domain: &Domain,
+) -> Result<(), Error> {
+    let mut pubkeys = Vec::with_capacity(transactions.len());
+    let mut messages = Vec::with_capacity(transactions.len());
+
+    for tx in transactions {
+        // Deposits and claims are proven against a root rather than signed.
+        if let Some(signing_root) = tx.signing_root() {
+            messages.push(domain.message(&signing_root));
+            pubkeys.push(db.pubkey(tx.from())?);
+        }
+    }
+
+    if verifier.verify_aggregate(&pubkeys, &messages, signature) {
+        Ok(())
+    } else {
+        Err(Error::SignatureInvalid)
+    }
+}
+
+/// Process an aggregate blob, returning the post-state root, or `Error::SignatureInvalid` if the
+/// aggregate signature doesn't verify against the pre-state.
+///
+/// A blob too short for its header or transactions is rejected with
+/// `Error::TransactionsLengthInvalid`, and one which ends before its signature with
+/// `Error::ProofTruncated`.
+pub fn process_aggregate_data_blob<V: AggregateVerifier>(
+    blob: &mut [u8],
+    pre_state_root: &[u8; 32],
+    config: &Config,
+    verifier: &V,
+) -> Result<[u8; 32], Error> {
+    config.validate()?;
+
+    if blob.len() < BLOB_HEADER_LENGTH {
+        return Err(Error::TransactionsLengthInvalid);
+    }
+
+    let tx_count = u32::from_le_bytes(*array_ref!(blob, 0, 4)) as usize;
+    let proposer = deserialize_proposer(blob);
+    let limits = Limits {
+        slot: Some(deserialize_slot(blob)),
+        ..config.limits
+    };
+    let transactions = deserialize_unsigned_transactions(blob, tx_count)?;
+
+    let offset = BLOB_HEADER_LENGTH + tx_count * UNSIGNED_TRANSACTION_LENGTH;
+    if blob.len() - offset < SIGNATURE_LENGTH {
+        return Err(Error::ProofTruncated);
+    }
+    let signature = *array_ref![blob, offset, SIGNATURE_LENGTH];
+
+    let proof = &mut blob[(offset + SIGNATURE_LENGTH)..];
+    let mut mem = new_checked(proof, config.proof_height())?;
+
+    if &config.root(&mem) != pre_state_root {
+        return Err(Error::PreStateRootInvalid);
+    }
+
+    verify_block(&mem, &transactions, &signature, verifier, &limits.domain)?;
+
+    // The aggregate already covers every transaction, so they aren't checked individually.
+    let result = execute_with(&mut mem, &transactions, &limits, &Unchecked)?;
+    settle_fees(&mut mem, proposer, result.fees, config.fees)?;
+
+    Ok(config.root(&mem))
+}
+
+/// Deserialize `tx_count` transactions without signatures, leaving each `signature` zeroed.
+/// Returns `Error::TransactionsLengthInvalid` if `data` is too short to hold them after the
+/// header.
+pub fn deserialize_unsigned_transactions(
+    data: &[u8],
+    tx_count: usize,
+) -> Result<Vec<Transaction>, Error> {
+    let available = data
+        .len()
+        .checked_sub(BLOB_HEADER_LENGTH)
+        .ok_or(Error::TransactionsLengthInvalid)?;
+    if tx_count > available / UNSIGNED_TRANSACTION_LENGTH {
+        return Err(Error::TransactionsLengthInvalid);
+    }
+
+    let mut ret = Vec::<Transaction>::with_capacity(tx_count);
+
+    // An unsigned transaction is a transfer's SSZ encoding without its trailing signature.
+    for i in (0..tx_count).map(|i| BLOB_HEADER_LENGTH + i * UNSIGNED_TRANSACTION_LENGTH) {
+        let mut buf = [0u8; TRANSFER_LENGTH];
+        buf[..UNSIGNED_TRANSACTION_LENGTH]
+            .copy_from_slice(&data[i..(i + UNSIGNED_TRANSACTION_LENGTH)]);
+
+        ret.push(Transaction::Transfer(Transfer::from_ssz(&buf)?));
+    }
+
+    Ok(ret)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bls::PublicKey;
+    use crate::hash::H256;
+    use crate::process_data_blob;
+    use crate::state::root::imp_root;
+
+    /// Aggregates by XORing the first 32 bytes of each public key with its message, which is
+    /// enough to tell whether the right keys and messages were collected.
+    struct Xor;
+
+    impl Xor {
+        fn sign(pubkeys: &[PublicKey], messages: &[H256]) -> [u8; 96] {
+            let mut ret = [0u8; 96];
+            for (pubkey, message) in pubkeys.iter().zip(messages.iter()) {
+                for i in 0..32 {
+                    ret[i] ^= pubkey.as_bytes()[i] ^ message[i];
+                }
+            }
+
+            ret
+        }
+    }
+
+    impl AggregateVerifier for Xor {
+        fn verify_aggregate(
+            &self,
+            pubkeys: &[PublicKey],
+            messages: &[H256],
+            signature: &[u8; 96],
+        ) -> bool {
+            pubkeys.len() == messages.len() && Xor::sign(pubkeys, messages)[..] == signature[..]
+        }
+    }
+
+    /// Builds a signed blob and its aggregate equivalent, signed by `Xor`.
+    fn build_blobs() -> (Vec<u8>, Vec<u8>, [u8; 32]) {
+        let blob = composer::blob::generate(4, 6, 256).to_bytes();
+        let tx_count = u32::from_le_bytes(*array_ref!(blob, 0, 4)) as usize;
+        let (transactions, proof_offset) =
+            crate::deserialize_transactions(&blob, tx_count).unwrap();
+
+        let mut proof = blob[proof_offset..].to_vec();
+        let mem = new_checked(&mut proof, Config::default().proof_height()).unwrap();
+        let pre_root = imp_root(&mem);
+
+        let mut aggregate = blob[0..BLOB_HEADER_LENGTH].to_vec();
+        let mut pubkeys = Vec::new();
+        let mut messages = Vec::new();
+        for tx in transactions.iter() {
+            if let Transaction::Transfer(t) = tx {
+                aggregate.extend(&t.to_ssz()[..UNSIGNED_TRANSACTION_LENGTH]);
+                pubkeys.push(mem.pubkey(t.from).unwrap());
+                messages.push(Domain::default().message(&t.signing_root()));
+            }
+        }
+        aggregate.extend(&Xor::sign(&pubkeys, &messages)[..]);
+        aggregate.extend(&blob[proof_offset..]);
+
+        (blob, aggregate, pre_root)
+    }
+
+    #[test]
+    fn matches_signed_blob() {
+        let (mut blob, mut aggregate, pre_root) = build_blobs();
+        let config = Config::default();
+
+        let expected = process_data_blob(&mut blob, &pre_root, &config);
+        assert_ne!(expected, pre_root);
+        assert_eq!(
+            process_aggregate_data_blob(&mut aggregate, &pre_root, &config, &Xor),
+            Ok(expected)
+        );
+    }
+
+    #[test]
+    fn invalid_aggregate() {
+        let (_, mut aggregate, pre_root) = build_blobs();
+
+        // Flip a bit of the first transaction's amount, so its signing root changes.
+        aggregate[BLOB_HEADER_LENGTH + 72] ^= 1;
+
+        assert_eq!(
+            process_aggregate_data_blob(&mut aggregate, &pre_root, &Config::default(), &Xor),
+            Err(Error::SignatureInvalid)
+        );
+    }
+
+    #[test]
+    fn unsigned_transactions() {
+        let (blob, aggregate, _) = build_blobs();
+        let tx_count = u32::from_le_bytes(*array_ref!(blob, 0, 4)) as usize;
+
+        let (signed, _) = crate::deserialize_transactions(&blob, tx_count).unwrap();
+        let unsigned = deserialize_unsigned_transactions(&aggregate, tx_count).unwrap();
+
+        for (a, b) in signed.iter().zip(unsigned.iter()) {
+            match (a, b) {
+                (Transaction::Transfer(a), Transaction::Transfer(b)) => {
+                    assert_eq!(a.signing_root(), b.signing_root());
+                    assert_eq!(b.signature, [0u8; 96]);
+                }
+                _ => panic!("expected transfers"),
+            }
+        }
+    }
+
+    #[test]
+    fn malformed_blobs() {
+        let (_, aggregate, pre_root) = build_blobs();
+        let config = Config::default();
+        let process = |blob: &[u8], root: &[u8; 32]| {
+            process_aggregate_data_blob(&mut blob.to_vec(), root, &config, &Xor)
+        };
+
+        let tx_count = u32::from_le_bytes(*array_ref!(aggregate, 0, 4)) as usize;
+        let signature = BLOB_HEADER_LENGTH + tx_count * UNSIGNED_TRANSACTION_LENGTH;
+
+        assert_eq!(
+            process(&[], &pre_root),
+            Err(Error::TransactionsLengthInvalid)
+        );
+        assert_eq!(
+            process(&aggregate[..signature - 1], &pre_root),
+            Err(Error::TransactionsLengthInvalid)
+        );
+        assert_eq!(
+            process(&aggregate[..signature + 1], &pre_root),
+            Err(Error::ProofTruncated)
+        );
+        assert_eq!(
+            process(&aggregate, &[0u8; 32]),
+            Err(Error::PreStateRootInvalid)
+        );
+
+        let mut huge = aggregate.clone();
+        huge[0..4].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(
+            process(&huge, &pre_root),
+            Err(Error::TransactionsLengthInvalid)
+        );
+
+        // However the blob is cut short, it is rejected rather than panicking.
+        for len in 0..aggregate.len() {
+            assert!(process(&aggregate[..len], &pre_root).is_err());
+        }
+    }
+}
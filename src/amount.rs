@@ -0,0 +1,81 @@
+use crate::error::Error;
+
+/// A quantity of value which can be held by an account or moved by a transaction.
+///
+/// Amounts can never exceed `Amount::MAX`, so the difference between any two amounts always fits
+/// in an `i64`. All arithmetic is checked against the limit.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Amount(u64);
+
+impl Amount {
+    /// The largest amount an account may hold or a transaction may move.
+    pub const MAX: Amount = Amount(i64::MAX as u64);
+
+    /// Returns `n` as an amount, or `Error::AmountInvalid` if it exceeds `Amount::MAX`.
+    pub fn new(n: u64) -> Result<Amount, Error> {
+        if n > Self::MAX.0 {
+            return Err(Error::AmountInvalid);
+        }
+
+        Ok(Amount(n))
+    }
+
+    pub const fn zero() -> Amount {
+        Amount(0)
+    }
+
+    pub const fn get(self) -> u64 {
+        self.0
+    }
+
+    pub fn checked_add(self, other: Amount) -> Result<Amount, Error> {
+        match self.0.checked_add(other.0) {
+            Some(n) if n <= Self::MAX.0 => Ok(Amount(n)),
+            _ => Err(Error::Overflow),
+        }
+    }
+
+    pub fn checked_sub(self, other: Amount) -> Result<Amount, Error> {
+        self.0
+            .checked_sub(other.0)
+            .map(Amount)
+            .ok_or(Error::Underflow)
+    }
+}
+
+/// Every `u32` is below `Amount::MAX`, so the conversion can't fail.
+impl From<u32> for Amount {
+    fn from(n: u32) -> Amount {
+        Amount(n.into())
+    }
+}
+
+impl From<Amount> for u64 {
+    fn from(amount: Amount) -> u64 {
+        amount.0
+    }
+}
+
+impl core::fmt::Display for Amount {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn enforce_max() {
+        assert_eq!(Amount::new(Amount::MAX.get()), Ok(Amount::MAX));
+        assert_eq!(
+            Amount::new(Amount::MAX.get() + 1),
+            Err(Error::AmountInvalid)
+        );
+
+        assert_eq!(Amount::MAX.checked_add(1.into()), Err(Error::Overflow));
+        assert_eq!(Amount::zero().checked_sub(1.into()), Err(Error::Underflow));
+        assert_eq!(Amount::from(2).checked_add(3.into()), Ok(5.into()));
+    }
+}
@@ -0,0 +1,217 @@
+//! Bech32 encoding of addresses (see BIP-173), so wallets can present and accept human readable
+//! addresses which detect typos, rather than raw hex.
+//!
+//! An address is written as `hrp || "1" || data || checksum`, where the 32 bytes of the address
+//! are regrouped into 52 characters of 5 bits and the checksum is a BCH code over the human
+//! readable part and data. Decoding accepts all lower or all upper case strings.
+
+use crate::address::Address;
+use crate::error::Error;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const GENERATOR: [u32; 5] = [
+    0x3b6a_57b2,
+    0x2650_8e6d,
+    0x1ea1_19fa,
+    0x3d42_33dd,
+    0x2a14_62b3,
+];
+const CHECKSUM_LENGTH: usize = 6;
+const MAX_LENGTH: usize = 90;
+
+impl Address {
+    /// Returns the address as bech32 with the human readable part `hrp`, e.g. `eth1q...`.
+    pub fn to_bech32(&self, hrp: &str) -> String {
+        encode(hrp, &regroup(&<[u8; 32]>::from(*self), 8, 5))
+    }
+
+    /// Parses a bech32 address, returning `Error::AddressInvalid` if it is malformed, fails its
+    /// checksum, or has a human readable part other than `hrp`.
+    pub fn from_bech32(hrp: &str, s: &str) -> Result<Address, Error> {
+        let (prefix, data) = decode(s)?;
+        if prefix != hrp.to_ascii_lowercase() {
+            return Err(Error::AddressInvalid);
+        }
+
+        // 52 groups of 5 bits leave 4 bits of padding, which must be zero.
+        let bytes = regroup(&data, 5, 8);
+        if data.len() != 52 || data[51] & 0xf != 0 || bytes.len() != 32 {
+            return Err(Error::AddressInvalid);
+        }
+
+        let mut ret = [0u8; 32];
+        ret.copy_from_slice(&bytes);
+        Ok(Address::from(ret))
+    }
+}
+
+/// Encodes 5 bit `data` with the human readable part `hrp`.
+pub fn encode(hrp: &str, data: &[u8]) -> String {
+    let hrp = hrp.to_ascii_lowercase();
+
+    let mut values = expand(&hrp);
+    values.extend(data);
+    values.extend(&[0u8; CHECKSUM_LENGTH]);
+    let checksum = polymod(&values) ^ 1;
+
+    let mut ret = String::with_capacity(hrp.len() + 1 + data.len() + CHECKSUM_LENGTH);
+    ret.push_str(&hrp);
+    ret.push('1');
+    for d in data {
+        ret.push(CHARSET[*d as usize] as char);
+    }
+    for i in 0..CHECKSUM_LENGTH {
+        let d = (checksum >> (5 * (CHECKSUM_LENGTH - 1 - i))) & 0x1f;
+        ret.push(CHARSET[d as usize] as char);
+    }
+
+    ret
+}
+
+/// Decodes a bech32 string into its lower case human readable part and 5 bit data, without the
+/// checksum.
+pub fn decode(s: &str) -> Result<(String, Vec<u8>), Error> {
+    let lower = s.bytes().any(|c| c.is_ascii_lowercase());
+    let upper = s.bytes().any(|c| c.is_ascii_uppercase());
+    if (lower && upper) || s.len() > MAX_LENGTH {
+        return Err(Error::AddressInvalid);
+    }
+
+    let s = s.to_ascii_lowercase();
+    let separator = s.rfind('1').ok_or(Error::AddressInvalid)?;
+    let (hrp, data) = (&s[..separator], &s.as_bytes()[separator + 1..]);
+
+    if hrp.is_empty()
+        || data.len() < CHECKSUM_LENGTH
+        || hrp.bytes().any(|c| !(33..=126).contains(&c))
+    {
+        return Err(Error::AddressInvalid);
+    }
+
+    let data = data
+        .iter()
+        .map(|c| CHARSET.iter().position(|d| d == c).map(|d| d as u8))
+        .collect::<Option<Vec<u8>>>()
+        .ok_or(Error::AddressInvalid)?;
+
+    let mut values = expand(hrp);
+    values.extend(&data);
+    if polymod(&values) != 1 {
+        return Err(Error::AddressInvalid);
+    }
+
+    Ok((hrp.into(), data[..data.len() - CHECKSUM_LENGTH].to_vec()))
+}
+
+/// Expands the human readable part into the values it contributes to the checksum.
+fn expand(hrp: &str) -> Vec<u8> {
+    let mut ret: Vec<u8> = hrp.bytes().map(|c| c >> 5).collect();
+    ret.push(0);
+    ret.extend(hrp.bytes().map(|c| c & 0x1f));
+    ret
+}
+
+fn polymod(values: &[u8]) -> u32 {
+    let mut chk = 1u32;
+    for v in values {
+        let top = chk >> 25;
+        chk = (chk & 0x01ff_ffff) << 5 ^ *v as u32;
+        for (i, g) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= g;
+            }
+        }
+    }
+
+    chk
+}
+
+/// Regroups `data` from groups of `from` bits into groups of `to` bits. Leftover bits are padded
+/// with zeros when widening the groups and dropped when narrowing them.
+fn regroup(data: &[u8], from: u32, to: u32) -> Vec<u8> {
+    let mut ret = Vec::with_capacity(data.len() * from as usize / to as usize + 1);
+    let (mut acc, mut bits) = (0u32, 0u32);
+
+    for d in data {
+        acc = acc << from | *d as u32;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            ret.push(((acc >> bits) & ((1 << to) - 1)) as u8);
+        }
+    }
+
+    if from > to && bits > 0 {
+        ret.push(((acc << (to - bits)) & ((1 << to) - 1)) as u8);
+    }
+
+    ret
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn known_strings() {
+        for s in [
+            "A12UEL5L",
+            "a12uel5l",
+            "an83characterlonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1tt5tgs",
+            "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw",
+            "split1checkupstagehandshakeupstreamerranterredcaperred2y9e3w",
+        ]
+        .iter()
+        {
+            let (hrp, data) = decode(s).unwrap();
+            assert_eq!(encode(&hrp, &data), s.to_ascii_lowercase());
+        }
+    }
+
+    #[test]
+    fn invalid_strings() {
+        for s in [
+            "a12uel5m",
+            "A12uEL5L",
+            "1nwldj5",
+            "pzry9x0s0muk",
+            "x1b4n0q5v",
+            "li1dgmt3",
+            "an84characterslonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1569pvx",
+        ]
+        .iter()
+        {
+            assert_eq!(decode(s), Err(Error::AddressInvalid), "{}", s);
+        }
+    }
+
+    #[test]
+    fn address_round_trip() {
+        let address = Address::from([0xab; 32]);
+        let s = address.to_bech32("eth");
+
+        assert!(s.starts_with("eth1"));
+        assert_eq!(Address::from_bech32("eth", &s), Ok(address));
+        assert_eq!(
+            Address::from_bech32("eth", &s.to_ascii_uppercase()),
+            Ok(address)
+        );
+        assert_eq!(Address::from_bech32("btc", &s), Err(Error::AddressInvalid));
+
+        // A single mistyped character is caught by the checksum.
+        let mut typo = s.into_bytes();
+        typo[10] = if typo[10] == b'q' { b'p' } else { b'q' };
+        assert_eq!(
+            Address::from_bech32("eth", core::str::from_utf8(&typo).unwrap()),
+            Err(Error::AddressInvalid)
+        );
+    }
+
+    #[test]
+    fn wrong_length_data() {
+        let s = encode("eth", &[0u8; 51]);
+        assert_eq!(Address::from_bech32("eth", &s), Err(Error::AddressInvalid));
+    }
+}
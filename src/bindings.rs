@@ -0,0 +1,187 @@
+//! JavaScript bindings for browser light clients, built with the `wasm-bindgen` feature, so a
+//! wallet can check blocks and balances itself rather than trusting a node:
+//!
+//! ```text
+//! wasm-pack build --target web --release -- --features wasm-bindgen
+//! ```
+//!
+//! Roots, addresses, blobs and proofs are passed as `Uint8Array`s, and an invalid block or proof
+//! throws with the `Debug` name and `Error::code` of its error. Proofs are multi-proofs in the
+//! same format as a blob's, and the tree height defaults to that of `Config::default`.
+
+use crate::account::Account;
+use crate::address::Address;
+use crate::bls::PublicKey;
+use crate::config::Config;
+use crate::error::Error;
+use crate::hash::H256;
+use crate::state::imp::new_checked;
+use crate::state::root::imp_root;
+use crate::state::State;
+use crate::u264::U264;
+use alloc::format;
+use alloc::vec::Vec;
+use arrayref::array_ref;
+use imp::Imp;
+use wasm_bindgen::prelude::*;
+
+/// An account as seen by JavaScript, with its nonce and value as `BigInt`s.
+#[wasm_bindgen(js_name = Account)]
+pub struct JsAccount {
+    pubkey: PublicKey,
+    nonce: u64,
+    value: u64,
+}
+
+#[wasm_bindgen(js_class = Account)]
+impl JsAccount {
+    #[wasm_bindgen(getter)]
+    pub fn pubkey(&self) -> Vec<u8> {
+        self.pubkey.as_bytes().to_vec()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+}
+
+impl From<Account> for JsAccount {
+    fn from(account: Account) -> Self {
+        JsAccount {
+            pubkey: account.pubkey,
+            nonce: account.nonce,
+            value: account.value,
+        }
+    }
+}
+
+/// Processes `blob` against `preRoot` under the default config and returns the post-state root,
+/// see `block::verify_block`.
+#[wasm_bindgen(js_name = verifyBlock)]
+pub fn verify_block(pre_root: &[u8], blob: &[u8]) -> Result<Vec<u8>, JsValue> {
+    let pre_root = root(pre_root).map_err(to_js)?;
+    crate::block::verify_block(&pre_root, blob)
+        .map(|post_root| post_root.to_vec())
+        .map_err(to_js)
+}
+
+/// Checks that `proof` is a proof of the account at `address` against the state root `root`,
+/// returning the account.
+#[wasm_bindgen(js_name = verifyAccountProof)]
+pub fn verify_account_proof(
+    root: &[u8],
+    address: &[u8],
+    proof: &[u8],
+    height: Option<usize>,
+) -> Result<JsAccount, JsValue> {
+    verify_account(root, address, proof, height)
+        .map(JsAccount::from)
+        .map_err(to_js)
+}
+
+/// Returns the account at `address` in `proof` without checking the proof against a root, e.g.
+/// to show what a witness holds. Its contents can't be trusted until `verifyAccountProof` has
+/// accepted it.
+#[wasm_bindgen(js_name = decodeAccount)]
+pub fn decode_account(
+    address: &[u8],
+    proof: &[u8],
+    height: Option<usize>,
+) -> Result<JsAccount, JsValue> {
+    let height = height.unwrap_or(Config::default().height);
+    let mut proof = proof.to_vec();
+    let mem = new_checked(&mut proof, height + 3).map_err(to_js)?;
+
+    read_account(&mem, address, height)
+        .map(JsAccount::from)
+        .map_err(to_js)
+}
+
+/// Checks `proof` against `root` and reads the account at `address` from it.
+fn verify_account(
+    root: &[u8],
+    address: &[u8],
+    proof: &[u8],
+    height: Option<usize>,
+) -> Result<Account, Error> {
+    let height = height.unwrap_or(Config::default().height);
+    let root = self::root(root)?;
+
+    let mut proof = proof.to_vec();
+    let mem = new_checked(&mut proof, height + 3)?;
+    if imp_root(&mem) != root {
+        return Err(Error::PreStateRootInvalid);
+    }
+
+    read_account(&mem, address, height)
+}
+
+/// Reads the account at `address`, which must be 32 bytes and fit a tree of `height`, from `mem`.
+fn read_account(mem: &Imp<U264>, address: &[u8], height: usize) -> Result<Account, Error> {
+    if address.len() != 32 {
+        return Err(Error::AddressInvalid);
+    }
+
+    let address = Address::from(*array_ref![address, 0, 32]);
+    if height < 256 && U264::from(address) >> height != U264::zero() {
+        return Err(Error::AddressInvalid);
+    }
+
+    Ok(Account {
+        pubkey: mem.pubkey(address)?,
+        nonce: mem.nonce(address)?.into(),
+        value: mem.value(address)?.into(),
+    })
+}
+
+fn root(bytes: &[u8]) -> Result<H256, Error> {
+    if bytes.len() != 32 {
+        return Err(Error::PreStateRootInvalid);
+    }
+
+    Ok(*array_ref![bytes, 0, 32])
+}
+
+fn to_js(e: Error) -> JsValue {
+    JsValue::from_str(&format!("{:?} (code {})", e, e.code()))
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::block::BlockData;
+
+    #[test]
+    fn verify_and_read_accounts() {
+        let blob = composer::blob::generate(2, 1, 256);
+        let mut address = [0u8; 32];
+        blob.accounts[0].0.to_big_endian(&mut address);
+
+        let mut proof = BlockData::from_bytes(&blob.to_bytes()).unwrap().proof;
+        let root = imp_root(&new_checked(&mut proof, 259).unwrap());
+
+        let account = verify_account(&root, &address, &proof, None).unwrap();
+        assert_eq!(account.nonce, blob.accounts[0].1.nonce);
+        assert_eq!(account.value, blob.accounts[0].1.value);
+
+        assert_eq!(
+            verify_account(&[0u8; 32], &address, &proof, None),
+            Err(Error::PreStateRootInvalid)
+        );
+        assert_eq!(
+            verify_account(&root, &address[..31], &proof, None),
+            Err(Error::AddressInvalid)
+        );
+        assert_eq!(
+            verify_account(&root, &address, &proof[..16], None),
+            Err(Error::ProofTruncated)
+        );
+    }
+}
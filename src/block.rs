@@ -0,0 +1,985 @@
+//! The state transition as a library function, for native tooling (e.g. packagers, fuzzers and
+//! RPC servers) which holds a block as typed data rather than a blob, and the header which
+//! commits to a processed block.
+
+use crate::address::Address;
+use crate::bloom::{block_bloom, Bloom, BLOOM_LENGTH};
+use crate::config::Config;
+use crate::diff::{snapshot, update, StateDiff};
+use crate::error::Error;
+use crate::hash::{branch_root, hash_pair, list_proof, list_root, HashScheme, Sha256, H256};
+use crate::history::record;
+use crate::log::{logs, logs_root};
+use crate::process::{execute_with, execute_with_receipts, settle_fees, ExecutionResult, Limits};
+use crate::receipt::{tx_receipts_root, TxReceipt};
+use crate::state::imp::new_checked;
+use crate::state::witness::refresh;
+use crate::transaction::{encode_transactions, Transaction};
+use crate::{deserialize_proposer, deserialize_slot, deserialize_transactions, BLOB_HEADER_LENGTH};
+use alloc::vec;
+use alloc::vec::Vec;
+use arrayref::array_ref;
+
+/// Depth of the tree of a block's transactions, which limits a block to `2**32` of them like the
+/// count in a blob's header.
+pub const TRANSACTIONS_TREE_DEPTH: usize = 32;
+
+/// Length of a block header's SSZ encoding.
+pub const BLOCK_HEADER_LENGTH: usize = 456;
+
+/// A block of transactions and the multi-proof of the pre-state they are applied to.
+#[cfg_attr(feature = "std", derive(Clone, Debug))]
+pub struct BlockData {
+    /// The block's proposer, who is paid the transactions' fees.
+    pub proposer: Address,
+
+    /// The block's slot, which transactions must be valid until.
+    pub slot: u64,
+
+    pub transactions: Vec<Transaction>,
+
+    /// The multi-proof of every chunk the transactions read or write.
+    pub proof: Vec<u8>,
+}
+
+impl BlockData {
+    /// Decode a blob laid out as `header || transactions || proof`, see `process_data_blob`.
+    pub fn from_bytes(blob: &[u8]) -> Result<Self, Error> {
+        if blob.len() < BLOB_HEADER_LENGTH {
+            return Err(Error::TransactionsLengthInvalid);
+        }
+
+        let tx_count = u32::from_le_bytes(*array_ref!(blob, 0, 4)) as usize;
+        let (transactions, offset) = deserialize_transactions(blob, tx_count)?;
+
+        Ok(BlockData {
+            proposer: deserialize_proposer(blob),
+            slot: deserialize_slot(blob),
+            transactions,
+            proof: blob[offset..].to_vec(),
+        })
+    }
+
+    /// Returns the block as a blob which `process_data_blob` accepts.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut ret = Vec::with_capacity(BLOB_HEADER_LENGTH + self.proof.len());
+        ret.extend(&(self.transactions.len() as u32).to_le_bytes());
+        ret.extend(&<[u8; 32]>::from(self.proposer));
+        ret.extend(&self.slot.to_le_bytes());
+        ret.extend(encode_transactions(&self.transactions));
+        ret.extend(&self.proof);
+        ret
+    }
+}
+
+/// Commits to a processed block, so a chain of blocks can be linked by their state roots and
+/// committed to by the shard or beacon chain.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BlockHeader {
+    pub slot: u64,
+    pub pre_state_root: H256,
+    pub post_state_root: H256,
+
+    /// The `transactions_root` of the block's transactions.
+    pub transactions_root: H256,
+
+    /// The root of the receipts of the block's cross transfers, see
+    /// `ExecutionResult::receipts_root`.
+    pub receipts_root: H256,
+
+    /// The `tx_receipts_root` of the receipts of the block's transactions.
+    pub tx_receipts_root: H256,
+
+    /// The `logs_root` of the payments made by the block's transactions.
+    pub logs_root: H256,
+
+    /// The `block_bloom` of the accounts the block touched.
+    pub bloom: Bloom,
+}
+
+impl BlockHeader {
+    /// Returns the `hash_tree_root` of the header as an SSZ container of eight fields:
+    ///
+    /// ```text
+    /// root = hash(hash(hash(slot, pre_state_root), hash(post_state_root, transactions_root)),
+    ///     hash(hash(receipts_root, tx_receipts_root), hash(logs_root, bloom_root)))
+    /// ```
+    pub fn root(&self) -> H256 {
+        let mut slot = [0u8; 32];
+        slot[0..8].copy_from_slice(&self.slot.to_le_bytes());
+
+        hash_pair(
+            &hash_pair(
+                &hash_pair(&slot, &self.pre_state_root),
+                &hash_pair(&self.post_state_root, &self.transactions_root),
+            ),
+            &hash_pair(
+                &hash_pair(&self.receipts_root, &self.tx_receipts_root),
+                &hash_pair(&self.logs_root, &self.bloom.root()),
+            ),
+        )
+    }
+
+    /// Returns the SSZ encoding of the header:
+    ///
+    /// ```text
+    /// slot (8, LE) || pre_state_root (32) || post_state_root (32) || transactions_root (32)
+    ///     || receipts_root (32) || tx_receipts_root (32) || logs_root (32) || bloom (256)
+    /// ```
+    pub fn to_ssz(&self) -> [u8; BLOCK_HEADER_LENGTH] {
+        let mut ret = [0u8; BLOCK_HEADER_LENGTH];
+        ret[0..8].copy_from_slice(&self.slot.to_le_bytes());
+        ret[8..40].copy_from_slice(&self.pre_state_root);
+        ret[40..72].copy_from_slice(&self.post_state_root);
+        ret[72..104].copy_from_slice(&self.transactions_root);
+        ret[104..136].copy_from_slice(&self.receipts_root);
+        ret[136..168].copy_from_slice(&self.tx_receipts_root);
+        ret[168..200].copy_from_slice(&self.logs_root);
+        ret[200..456].copy_from_slice(&self.bloom.0);
+        ret
+    }
+
+    /// Decodes a header encoded by `to_ssz`.
+    pub fn from_ssz(bytes: &[u8; BLOCK_HEADER_LENGTH]) -> Self {
+        BlockHeader {
+            slot: u64::from_le_bytes(*array_ref![bytes, 0, 8]),
+            pre_state_root: *array_ref![bytes, 8, 32],
+            post_state_root: *array_ref![bytes, 40, 32],
+            transactions_root: *array_ref![bytes, 72, 32],
+            receipts_root: *array_ref![bytes, 104, 32],
+            tx_receipts_root: *array_ref![bytes, 136, 32],
+            logs_root: *array_ref![bytes, 168, 32],
+            bloom: Bloom(*array_ref![bytes, 200, BLOOM_LENGTH]),
+        }
+    }
+}
+
+/// Returns the root of `transactions` as an SSZ list of their `Transaction::hash`es.
+pub fn transactions_root(transactions: &[Transaction]) -> H256 {
+    let leaves: Vec<H256> = transactions.iter().map(|tx| tx.hash()).collect();
+    list_root(&leaves, TRANSACTIONS_TREE_DEPTH)
+}
+
+/// A proof that a transaction is in a block's `transactions_root`, which lets a light client check
+/// that a transaction was included without downloading the whole block.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InclusionProof {
+    /// The transaction's index in the block.
+    pub index: u64,
+
+    /// The transaction's sibling at each level of the tree, then the number of transactions.
+    pub branch: Vec<H256>,
+}
+
+/// Returns the proof that `block.transactions[index]` is in the block's `transactions_root`, or
+/// `None` if the block doesn't have that many transactions.
+pub fn prove_tx_inclusion(block: &BlockData, index: usize) -> Option<InclusionProof> {
+    if index >= block.transactions.len() {
+        return None;
+    }
+
+    let leaves: Vec<H256> = block.transactions.iter().map(|tx| tx.hash()).collect();
+
+    Some(InclusionProof {
+        index: index as u64,
+        branch: list_proof(&leaves, TRANSACTIONS_TREE_DEPTH, index),
+    })
+}
+
+/// Returns whether `proof` shows that `tx` is in the block whose transactions root is `root`.
+pub fn verify_tx_inclusion(root: &H256, tx: &Transaction, proof: &InclusionProof) -> bool {
+    proof.index >> TRANSACTIONS_TREE_DEPTH == 0
+        && proof.branch.len() == TRANSACTIONS_TREE_DEPTH + 1
+        && branch_root(tx.hash(), proof.index, &proof.branch) == *root
+}
+
+/// Apply `block` to the state at `pre_state_root`, returning the post-state root. The block's
+/// proof is copied, so the block can be processed again (e.g. against another config).
+pub fn process_block(
+    pre_state_root: &H256,
+    block: &BlockData,
+    config: &Config,
+) -> Result<H256, Error> {
+    let mut proof = block.proof.clone();
+
+    transition(
+        &mut proof,
+        pre_state_root,
+        &block.transactions,
+        block.proposer,
+        block.slot,
+        config,
+        Outputs::default(),
+    )
+    .map(|(post_state_root, _)| post_state_root)
+}
+
+/// Like `process_block`, but returns the header of the processed block.
+pub fn process_block_header(
+    pre_state_root: &H256,
+    block: &BlockData,
+    config: &Config,
+) -> Result<BlockHeader, Error> {
+    process_block_receipts(pre_state_root, block, config).map(|(header, _)| header)
+}
+
+/// Like `process_block_header`, but also returns the receipts of the block's transactions, which
+/// a node keeps to prove them to light clients with `prove_receipt_inclusion`.
+pub fn process_block_receipts(
+    pre_state_root: &H256,
+    block: &BlockData,
+    config: &Config,
+) -> Result<(BlockHeader, Vec<TxReceipt>), Error> {
+    let mut proof = block.proof.clone();
+    let mut receipts = Vec::new();
+
+    let (post_state_root, result) = transition(
+        &mut proof,
+        pre_state_root,
+        &block.transactions,
+        block.proposer,
+        block.slot,
+        config,
+        Outputs {
+            receipts: Some(&mut receipts),
+            ..Outputs::default()
+        },
+    )?;
+
+    let header = BlockHeader {
+        slot: block.slot,
+        pre_state_root: *pre_state_root,
+        post_state_root,
+        transactions_root: transactions_root(&block.transactions),
+        receipts_root: result.receipts_root,
+        tx_receipts_root: tx_receipts_root(&receipts),
+        logs_root: logs_root(&logs(&block.transactions, &receipts)),
+        bloom: block_bloom(&block.transactions, block.proposer),
+    };
+
+    Ok((header, receipts))
+}
+
+/// Like `process_block`, but also returns the diff of every account the block touched, for
+/// indexers which track balances. The value and nonce of each of them must be in the block's proof.
+pub fn process_block_diff(
+    pre_state_root: &H256,
+    block: &BlockData,
+    config: &Config,
+) -> Result<(H256, StateDiff), Error> {
+    let mut proof = block.proof.clone();
+    let mut diff = StateDiff::new();
+
+    let (post_state_root, _) = transition(
+        &mut proof,
+        pre_state_root,
+        &block.transactions,
+        block.proposer,
+        block.slot,
+        config,
+        Outputs {
+            diff: Some(&mut diff),
+            ..Outputs::default()
+        },
+    )?;
+
+    Ok((post_state_root, diff))
+}
+
+/// Verify the blob `blob` against `pre_state_root` under the default `Config`, returning the
+/// post-state root. This is the whole of what a shard validator does with a block: the blob is
+/// decoded, its proof is checked against `pre_state_root`, its transactions are executed and
+/// their fees paid to its proposer.
+///
+/// Unlike `process_data_blob`, which traps, any malformed or invalid blob is rejected with an
+/// error, so it is safe to call on untrusted input.
+pub fn verify_block(pre_state_root: &H256, blob: &[u8]) -> Result<H256, Error> {
+    verify_block_with(pre_state_root, blob, &Config::default())
+}
+
+/// Like `verify_block`, but under `config`.
+pub fn verify_block_with(
+    pre_state_root: &H256,
+    blob: &[u8],
+    config: &Config,
+) -> Result<H256, Error> {
+    let mut block = BlockData::from_bytes(blob)?;
+
+    transition(
+        &mut block.proof,
+        pre_state_root,
+        &block.transactions,
+        block.proposer,
+        block.slot,
+        config,
+        Outputs::default(),
+    )
+    .map(|(post_state_root, _)| post_state_root)
+}
+
+/// Apply each of `blocks` in turn to the state at `pre_state_root`, returning the state root after
+/// the last.
+///
+/// A block's proof should be of the state left by the block before it. A proof made instead for
+/// an earlier state of the chain, e.g. when a tool builds several blocks from the same snapshot,
+/// is accepted only if every chunk changed since that snapshot is also in the proof, or is
+/// refreshed from a later block's post-state proof. Any other proof is rejected with
+/// `Error::PreStateRootInvalid`.
+pub fn process_chain(
+    pre_state_root: &H256,
+    blocks: &[BlockData],
+    config: &Config,
+) -> Result<H256, Error> {
+    config.validate()?;
+    let height = config.proof_height();
+
+    // The root before each block and the post-state proof of each block applied so far.
+    let mut roots = vec![*pre_state_root];
+    let mut posts: Vec<Vec<u8>> = Vec::with_capacity(blocks.len());
+
+    for block in blocks {
+        let root = roots[roots.len() - 1];
+        let mut proof = block.proof.clone();
+
+        {
+            let mut mem = new_checked(&mut proof, height)?;
+            let base = config.root(&mem);
+
+            if base != root {
+                if let Some(first) = roots.iter().position(|r| *r == base) {
+                    for post in posts[first..].iter_mut() {
+                        let fresh = new_checked(post, height)?;
+                        match config.hash {
+                            HashScheme::Sha256 => refresh::<Sha256>(&mut mem, &fresh),
+                            #[cfg(feature = "eth1")]
+                            HashScheme::Keccak256 => {
+                                refresh::<crate::hash::keccak::Keccak256>(&mut mem, &fresh)
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let (post_state_root, _) = transition(
+            &mut proof,
+            &root,
+            &block.transactions,
+            block.proposer,
+            block.slot,
+            config,
+            Outputs::default(),
+        )?;
+
+        roots.push(post_state_root);
+        posts.push(proof);
+    }
+
+    Ok(roots[roots.len() - 1])
+}
+
+/// The optional outputs of `transition`, which are only collected if they are given.
+#[derive(Default)]
+pub(crate) struct Outputs<'a> {
+    /// The receipts of the block's transactions.
+    pub receipts: Option<&'a mut Vec<TxReceipt>>,
+
+    /// The diff of the accounts the block touched.
+    pub diff: Option<&'a mut StateDiff>,
+}
+
+/// Apply `transactions` to the state proven by `proof`, which is updated in place, and settle their
+/// fees by `config.fees`, returning the post-state root and the block's metering. Roots are hashed
+/// with `config.hash`. Returns `Error::PreStateRootInvalid` if the proof isn't of the state at
+/// `pre_state_root`.
+pub(crate) fn transition(
+    proof: &mut [u8],
+    pre_state_root: &H256,
+    transactions: &[Transaction],
+    proposer: Address,
+    slot: u64,
+    config: &Config,
+    outputs: Outputs,
+) -> Result<(H256, ExecutionResult), Error> {
+    config.validate()?;
+
+    let limits = Limits {
+        slot: Some(slot),
+        ..config.limits
+    };
+
+    // Load multi-merkle proof, rejecting it up front if its structure is malformed.
+    let mut mem = new_checked(proof, config.proof_height())?;
+
+    // Verify pre_state_root. The root is calculated iteratively, since `Imp::root` recurses
+    // once per level of the proof.
+    if &config.root(&mem) != pre_state_root {
+        return Err(Error::PreStateRootInvalid);
+    }
+
+    if let Some(length) = config.history.filter(|length| *length > 0) {
+        record(&mut mem, slot, pre_state_root, length, config.height)?;
+    }
+
+    let mut diff = outputs.diff;
+    if let Some(diff) = diff.as_mut() {
+        **diff = snapshot(&mem, transactions, proposer)?;
+    }
+
+    // Process all transactions and settle their fees
+    let result = match outputs.receipts {
+        Some(receipts) => {
+            let (result, tx_receipts) =
+                execute_with_receipts(&mut mem, transactions, &limits, &config.signatures)?;
+            *receipts = tx_receipts;
+            result
+        }
+        None => execute_with(&mut mem, transactions, &limits, &config.signatures)?,
+    };
+    settle_fees(&mut mem, proposer, result.fees, config.fees)?;
+
+    if let Some(diff) = diff {
+        update(&mem, diff)?;
+    }
+
+    Ok((config.root(&mem), result))
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::amount::Amount;
+    use crate::bls::{PublicKey, SignatureScheme};
+    use crate::cross::CrossTransfer;
+    use crate::history::{recent_root, ring_address};
+    use crate::nonce::Nonce;
+    use crate::process::FeePolicy;
+    use crate::process_data_blob;
+    use crate::state::root::imp_root;
+    use crate::transaction::{ChangeKey, Transfer};
+    use crate::withdrawal::Withdrawal;
+    use bigint::U256;
+    use composer::accounts::AddressedAccount;
+
+    fn build_block() -> (BlockData, H256) {
+        let blob = composer::blob::generate(4, 6, 256).to_bytes();
+        let block = BlockData::from_bytes(&blob).unwrap();
+
+        let mut proof = block.proof.clone();
+        let mem = new_checked(&mut proof, Config::default().proof_height()).unwrap();
+
+        (block, imp_root(&mem))
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let blob = composer::blob::generate(4, 6, 256).to_bytes();
+        let block = BlockData::from_bytes(&blob).unwrap();
+
+        assert_eq!(block.transactions.len(), 6);
+        assert_eq!(block.to_bytes(), blob);
+        assert_eq!(
+            BlockData::from_bytes(&blob[..BLOB_HEADER_LENGTH - 1]).err(),
+            Some(Error::TransactionsLengthInvalid)
+        );
+    }
+
+    #[test]
+    fn matches_data_blob() {
+        let (block, pre_root) = build_block();
+        let config = Config::default();
+
+        let post_root = process_block(&pre_root, &block, &config).unwrap();
+        assert_ne!(post_root, pre_root);
+        assert_eq!(
+            process_data_blob(&mut block.to_bytes(), &pre_root, &config),
+            post_root
+        );
+
+        // The block is unchanged, so processing it again gives the same root.
+        assert_eq!(process_block(&pre_root, &block, &config), Ok(post_root));
+    }
+
+    #[test]
+    fn header() {
+        let (block, pre_root) = build_block();
+        let config = Config::default();
+
+        let header = process_block_header(&pre_root, &block, &config).unwrap();
+        assert_eq!(header.slot, block.slot);
+        assert_eq!(header.pre_state_root, pre_root);
+        assert_eq!(
+            Ok(header.post_state_root),
+            process_block(&pre_root, &block, &config)
+        );
+        assert_eq!(
+            header.transactions_root,
+            transactions_root(&block.transactions)
+        );
+        assert_ne!(header.transactions_root, transactions_root(&[]));
+        assert_eq!(BlockHeader::from_ssz(&header.to_ssz()), header);
+
+        let (with_receipts, receipts) = process_block_receipts(&pre_root, &block, &config).unwrap();
+        assert_eq!(with_receipts, header);
+        assert_eq!(receipts.len(), block.transactions.len());
+        assert_eq!(header.tx_receipts_root, tx_receipts_root(&receipts));
+        assert_eq!(
+            header.logs_root,
+            logs_root(&logs(&block.transactions, &receipts))
+        );
+        assert_ne!(header.logs_root, logs_root(&[]));
+        assert!(header.bloom.contains(block.proposer));
+        for tx in block.transactions.iter() {
+            assert!(tx.accounts().iter().all(|a| header.bloom.contains(*a)));
+        }
+    }
+
+    #[test]
+    fn diff() {
+        let (block, pre_root) = build_block();
+        let config = Config::default();
+
+        let (post_root, diff) = process_block_diff(&pre_root, &block, &config).unwrap();
+        assert_eq!(process_block(&pre_root, &block, &config), Ok(post_root));
+        assert!(diff.contains_key(&block.proposer));
+
+        for tx in block.transactions.iter() {
+            assert!(tx.accounts().iter().all(|a| diff.contains_key(a)));
+        }
+
+        // Value only moves between the accounts in the diff.
+        let old: u64 = diff.values().map(|a| a.old_value.get()).sum();
+        let new: u64 = diff.values().map(|a| a.new_value.get()).sum();
+        assert_eq!(old, new);
+        assert!(diff.values().any(|a| a.new_nonce != a.old_nonce));
+    }
+
+    #[test]
+    fn tx_inclusion() {
+        let (block, _) = build_block();
+        let root = transactions_root(&block.transactions);
+
+        for (i, tx) in block.transactions.iter().enumerate() {
+            let proof = prove_tx_inclusion(&block, i).unwrap();
+            assert!(verify_tx_inclusion(&root, tx, &proof));
+
+            // The proof is only valid at its own index.
+            let other = &block.transactions[(i + 1) % block.transactions.len()];
+            assert!(!verify_tx_inclusion(&root, other, &proof));
+        }
+
+        let mut proof = prove_tx_inclusion(&block, 0).unwrap();
+        proof.index += 1 << TRANSACTIONS_TREE_DEPTH;
+        assert!(!verify_tx_inclusion(&root, &block.transactions[0], &proof));
+
+        proof.index = 0;
+        proof.branch.pop();
+        assert!(!verify_tx_inclusion(&root, &block.transactions[0], &proof));
+
+        assert_eq!(prove_tx_inclusion(&block, block.transactions.len()), None);
+    }
+
+    #[test]
+    fn header_root() {
+        let header = BlockHeader {
+            slot: 3,
+            pre_state_root: [1u8; 32],
+            post_state_root: [2u8; 32],
+            transactions_root: [3u8; 32],
+            receipts_root: [4u8; 32],
+            tx_receipts_root: [5u8; 32],
+            logs_root: [6u8; 32],
+            bloom: Bloom([7u8; BLOOM_LENGTH]),
+        };
+
+        // hash_tree_root(BlockHeader) as computed by the SSZ spec.
+        assert_eq!(
+            hex::encode(header.root()),
+            "d1b9d3c76a8ac2dbda42f4ed6ceb723549fadc6d2a668a6247fdbc38b11ed85b"
+        );
+    }
+
+    #[test]
+    fn chain() {
+        let (block, pre_root) = build_block();
+        let config = Config::default();
+
+        assert_eq!(process_chain(&pre_root, &[], &config), Ok(pre_root));
+
+        // The second block replays the first against its post-state, given both as a proof of
+        // the post-state and as a stale proof of the pre-state.
+        let mut post = block.proof.clone();
+        let (post_root, _) = transition(
+            &mut post,
+            &pre_root,
+            &block.transactions,
+            block.proposer,
+            block.slot,
+            &config,
+            Outputs::default(),
+        )
+        .unwrap();
+        let fresh = BlockData {
+            proof: post,
+            ..block.clone()
+        };
+        let expected = process_block(&post_root, &fresh, &config).unwrap();
+
+        assert_eq!(
+            process_chain(&pre_root, &[block.clone(), fresh], &config),
+            Ok(expected)
+        );
+        assert_eq!(
+            process_chain(&pre_root, &[block.clone(), block.clone()], &config),
+            Ok(expected)
+        );
+
+        // A proof of an unrelated state can't be brought up to date.
+        let blob = composer::blob::generate(2, 1, 256).to_bytes();
+        let other = BlockData::from_bytes(&blob).unwrap();
+        assert_eq!(
+            process_chain(&pre_root, &[block, other], &config),
+            Err(Error::PreStateRootInvalid)
+        );
+    }
+
+    #[test]
+    fn verify() {
+        let (block, pre_root) = build_block();
+        let blob = block.to_bytes();
+
+        assert_eq!(
+            verify_block(&pre_root, &blob),
+            process_block(&pre_root, &block, &Config::default())
+        );
+        assert_eq!(
+            verify_block(&[0u8; 32], &blob),
+            Err(Error::PreStateRootInvalid)
+        );
+    }
+
+    /// Truncate, corrupt and replace `blob`, which must each be rejected or processed under
+    /// `config`, but never panic.
+    fn fuzz(pre_root: &H256, blob: &[u8], config: &Config) {
+        let mut rng = composer::rng::DeterministicRng::new(42);
+
+        for len in 0..blob.len() {
+            assert!(verify_block_with(pre_root, &blob[..len], config).is_err());
+        }
+
+        for _ in 0..1_000 {
+            let mut mutated = blob.to_vec();
+            for _ in 0..rng.range(1, 4) {
+                let i = rng.index(mutated.len());
+                mutated[i] = rng.next_u64() as u8;
+            }
+
+            let _ = verify_block_with(pre_root, &mutated, config);
+        }
+
+        for _ in 0..100 {
+            let mut random = vec![0u8; rng.index(blob.len())];
+            rng.fill(&mut random);
+
+            let _ = verify_block_with(pre_root, &random, config);
+        }
+    }
+
+    #[test]
+    fn verify_mutated_blobs() {
+        let (block, pre_root) = build_block();
+        fuzz(&pre_root, &block.to_bytes(), &Config::default());
+    }
+
+    #[test]
+    fn verify_mutated_short_address_blobs() {
+        let config = Config::short_addresses();
+        let mut block =
+            BlockData::from_bytes(&composer::blob::generate(4, 6, config.height).to_bytes())
+                .unwrap();
+
+        let mut proof = block.proof.clone();
+        let pre_root = imp_root(&new_checked(&mut proof, config.proof_height()).unwrap());
+        let post_root = verify_block_with(&pre_root, &block.to_bytes(), &config).unwrap();
+
+        // Most of the bytes of an address are beyond a tree of 160 levels, so corrupting them
+        // mostly gives addresses which don't fit it.
+        fuzz(&pre_root, &block.to_bytes(), &config);
+
+        // An address beyond the tree doesn't alias the account at the address modulo `2**160`.
+        if let Transaction::Transfer(tx) = &mut block.transactions[0] {
+            tx.to = Address::from(U256::from(tx.to) + (U256::one() << 200));
+        }
+        assert_ne!(
+            verify_block_with(&pre_root, &block.to_bytes(), &config),
+            Ok(post_root)
+        );
+    }
+
+    #[test]
+    fn recent_roots() {
+        let blob = composer::blob::generate(4, 6, 256);
+        let config = Config {
+            history: Some(2),
+            ..Config::default()
+        };
+
+        // The block's witness also proves the accounts of the ring, whose contents don't matter
+        // since recording a root overwrites the key. The composer's `Account` is its own build of
+        // this crate, so one of the block's accounts is copied.
+        let mut accounts = blob.accounts.clone();
+        for index in 0..2 {
+            let mut account = blob.accounts[0].1.clone();
+            account.value = 0;
+            account.nonce = 0;
+            accounts.push(AddressedAccount(
+                ring_address(index, config.height).into(),
+                account,
+            ));
+        }
+
+        let mut block = BlockData::from_bytes(&blob.to_bytes()).unwrap();
+        block.slot = 3;
+        block.proof = composer::proof::uncompressed::generate(accounts, config.height).compress();
+
+        let mut proof = block.proof.clone();
+        let pre_root = imp_root(&new_checked(&mut proof, config.proof_height()).unwrap());
+
+        let mut post = block.proof.clone();
+        let (post_root, _) = transition(
+            &mut post,
+            &pre_root,
+            &block.transactions,
+            block.proposer,
+            block.slot,
+            &config,
+            Outputs::default(),
+        )
+        .unwrap();
+        let mem = new_checked(&mut post, config.proof_height()).unwrap();
+
+        assert_eq!(recent_root(&mem, 3, 2, config.height), Ok(Some(pre_root)));
+        assert_eq!(recent_root(&mem, 1, 2, config.height), Ok(None));
+        assert_ne!(
+            Ok(post_root),
+            process_block(&pre_root, &block, &Config::default())
+        );
+    }
+
+    #[test]
+    fn signatures_checked_by_config() {
+        let (block, pre_root) = build_block();
+        let unchecked = process_block(&pre_root, &block, &Config::default());
+
+        let config = Config {
+            signatures: SignatureScheme::Bls(|_, _, _| true),
+            ..Config::default()
+        };
+        assert_eq!(process_block(&pre_root, &block, &config), unchecked);
+
+        // Every transaction is skipped, so the state is unchanged.
+        let config = Config {
+            signatures: SignatureScheme::Bls(|_, _, _| false),
+            ..Config::default()
+        };
+        assert_eq!(process_block(&pre_root, &block, &config), Ok(pre_root));
+        assert_eq!(
+            verify_block_with(&pre_root, &block.to_bytes(), &config),
+            Ok(pre_root)
+        );
+    }
+
+    #[test]
+    fn arity_checked_by_config() {
+        let (block, pre_root) = build_block();
+        let config = Config {
+            arity: 4,
+            ..Config::default()
+        };
+        assert_eq!(
+            process_block(&pre_root, &block, &config),
+            Err(Error::ArityInvalid)
+        );
+        assert_eq!(
+            process_chain(&pre_root, &[block], &config),
+            Err(Error::ArityInvalid)
+        );
+    }
+
+    #[cfg(feature = "secp256k1")]
+    #[test]
+    fn secp256k1_signatures() {
+        // The composer doesn't sign with secp256k1, so every transaction is skipped.
+        let (block, pre_root) = build_block();
+        let config = Config {
+            signatures: SignatureScheme::Secp256k1,
+            ..Config::default()
+        };
+        assert_eq!(process_block(&pre_root, &block, &config), Ok(pre_root));
+    }
+
+    #[test]
+    fn fees_settled_by_config() {
+        let (mut block, pre_root) = build_block();
+        for tx in block.transactions.iter_mut() {
+            if let Transaction::Transfer(t) = tx {
+                t.fee = Amount::from(1);
+            }
+        }
+
+        let total = |diff: &StateDiff, new: bool| -> u64 {
+            diff.values()
+                .map(|a| if new { a.new_value } else { a.old_value }.get())
+                .sum()
+        };
+
+        // Paying the proposer moves the fees between accounts of the block.
+        let (paid, diff) = process_block_diff(&pre_root, &block, &Config::default()).unwrap();
+        assert_eq!(total(&diff, true), total(&diff, false));
+
+        let config = Config {
+            fees: FeePolicy::Burn,
+            ..Config::default()
+        };
+        let (burned, diff) = process_block_diff(&pre_root, &block, &config).unwrap();
+        assert!(total(&diff, true) < total(&diff, false));
+        assert_ne!(burned, paid);
+    }
+
+    #[cfg(feature = "eth1")]
+    #[test]
+    fn keccak_state_roots() {
+        let (block, sha_root) = build_block();
+        let config = Config {
+            hash: HashScheme::Keccak256,
+            ..Config::default()
+        };
+
+        let mut proof = block.proof.clone();
+        let pre_root = config.root(&new_checked(&mut proof, config.proof_height()).unwrap());
+        assert_ne!(pre_root, sha_root);
+
+        assert_eq!(
+            process_block(&sha_root, &block, &config),
+            Err(Error::PreStateRootInvalid)
+        );
+
+        let post_root = process_block(&pre_root, &block, &config).unwrap();
+        assert_ne!(
+            Ok(post_root),
+            process_block(&sha_root, &block, &Config::default())
+        );
+
+        // The second copy of the block is refreshed from the first's post-state with Keccak-256,
+        // and its transactions are all skipped as replays.
+        assert_eq!(
+            process_chain(&pre_root, &[block.clone(), block], &config),
+            Ok(post_root)
+        );
+    }
+
+    /// Builds a witness from the full state, executes a block against it, then applies the same
+    /// block to the full state with `composer::transactions::apply` and rebuilds the witness, which
+    /// must match the executed one chunk for chunk.
+    #[test]
+    fn witness_round_trip() {
+        for &height in &[4, 160, 256] {
+            let config = Config::new(height);
+            let mut accounts = composer::accounts::random_accounts(4, height);
+            let address = |i: usize| Address::from(accounts[i].0);
+            let nonce = |i: usize| Nonce::new(accounts[i].1.nonce);
+            let half = |i: usize| Amount::new(accounts[i].1.value / 2).unwrap();
+
+            // A block of every kind of transaction the composer applies, followed by transfers
+            // from the state they leave.
+            let kinds = BlockData {
+                proposer: address(0),
+                slot: 2,
+                transactions: vec![
+                    Transaction::Withdrawal(Withdrawal {
+                        from: address(1),
+                        nonce: nonce(1),
+                        amount: half(1),
+                        fee: Amount::from(1),
+                        valid_until: None,
+                        signature: [0u8; 96],
+                    }),
+                    Transaction::ChangeKey(ChangeKey {
+                        from: address(2),
+                        nonce: nonce(2),
+                        pubkey: PublicKey::new([9u8; 48]),
+                        fee: Amount::zero(),
+                        valid_until: Some(2),
+                        signature: [0u8; 96],
+                    }),
+                    Transaction::CrossTransfer(CrossTransfer {
+                        to: address(0),
+                        from: address(3),
+                        destination: 1,
+                        nonce: nonce(3),
+                        amount: half(3),
+                        fee: Amount::zero(),
+                        valid_until: None,
+                        signature: [0u8; 96],
+                    }),
+                    Transaction::Transfer(Transfer {
+                        to: address(1),
+                        from: address(0),
+                        nonce: nonce(0),
+                        amount: Amount::from(1),
+                        fee: Amount::zero(),
+                        valid_until: Some(1),
+                        signature: [0u8; 96],
+                    }),
+                ],
+                proof: vec![],
+            };
+
+            let (proposer, slot, mut transactions) =
+                composer::transactions::deserialize(&kinds.to_bytes()).unwrap();
+            let mut after = accounts.clone();
+            composer::transactions::apply(&transactions, proposer, slot, &mut after).unwrap();
+            transactions.extend(composer::transactions::generate(6, after));
+
+            let mut blob = composer::transactions::serialize(proposer, slot, &transactions);
+            blob.extend(
+                composer::proof::uncompressed::generate(accounts.clone(), height).compress(),
+            );
+            let block = BlockData::from_bytes(&blob).unwrap();
+
+            let mut executed = block.proof.clone();
+            let pre_root = config.root(&new_checked(&mut executed, config.proof_height()).unwrap());
+            let (post_root, result) = transition(
+                &mut executed,
+                &pre_root,
+                &block.transactions,
+                block.proposer,
+                block.slot,
+                &config,
+                Outputs::default(),
+            )
+            .unwrap();
+
+            // Only the expired transfer is skipped.
+            assert_eq!(result.transactions_applied, transactions.len() - 1);
+
+            composer::transactions::apply(&transactions, proposer, slot, &mut accounts).unwrap();
+            let mut rebuilt = composer::proof::uncompressed::generate(accounts, height).compress();
+            let mem = new_checked(&mut rebuilt, config.proof_height()).unwrap();
+            assert_eq!(config.root(&mem), post_root);
+
+            assert_eq!(rebuilt, executed);
+        }
+    }
+
+    #[test]
+    fn wrong_pre_state_root() {
+        let (block, _) = build_block();
+
+        assert_eq!(
+            process_block(&[0u8; 32], &block, &Config::default()),
+            Err(Error::PreStateRootInvalid)
+        );
+    }
+}
@@ -0,0 +1,122 @@
+//! A bloom filter of the addresses a block touched, so wallets and indexers can skip blocks which
+//! can't have affected the accounts they track without downloading them.
+//!
+//! Like Ethereum's logs bloom, the filter is 2048 bits and each address sets 3 of them, taken from
+//! the first three pairs of bytes of `SHA-256(address)` modulo 2048. Bit `i` is bit `i % 8` of
+//! byte `i / 8`.
+
+use crate::address::Address;
+use crate::hash::{hash_pair, Incremental, H256};
+use crate::transaction::Transaction;
+use arrayref::array_ref;
+
+/// Length of a bloom filter in bytes.
+pub const BLOOM_LENGTH: usize = 256;
+
+/// A bloom filter of addresses.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bloom(pub [u8; BLOOM_LENGTH]);
+
+impl Bloom {
+    /// Add `address` to the filter.
+    pub fn insert(&mut self, address: Address) {
+        for bit in bits(address).iter() {
+            self.0[bit / 8] |= 1 << (bit % 8);
+        }
+    }
+
+    /// Returns whether `address` may be in the filter. An address which was inserted is always
+    /// found, but an address which wasn't may be too.
+    pub fn contains(&self, address: Address) -> bool {
+        bits(address)
+            .iter()
+            .all(|bit| self.0[bit / 8] & (1 << (bit % 8)) != 0)
+    }
+
+    /// Returns the `hash_tree_root` of the filter as an SSZ vector of bytes, the root of its eight
+    /// chunks.
+    pub fn root(&self) -> H256 {
+        let mut nodes = [[0u8; 32]; BLOOM_LENGTH / 32];
+        for (i, node) in nodes.iter_mut().enumerate() {
+            *node = *array_ref![self.0, i * 32, 32];
+        }
+
+        let mut len = nodes.len();
+        while len > 1 {
+            for i in 0..len / 2 {
+                nodes[i] = hash_pair(&nodes[2 * i], &nodes[2 * i + 1]);
+            }
+            len /= 2;
+        }
+
+        nodes[0]
+    }
+}
+
+impl Default for Bloom {
+    fn default() -> Self {
+        Bloom([0u8; BLOOM_LENGTH])
+    }
+}
+
+/// Returns the filter of every account `transactions` touch, whether they were applied or not,
+/// and the block's `proposer`.
+pub fn block_bloom(transactions: &[Transaction], proposer: Address) -> Bloom {
+    let mut ret = Bloom::default();
+    ret.insert(proposer);
+
+    for tx in transactions {
+        for address in tx.accounts() {
+            ret.insert(address);
+        }
+    }
+
+    ret
+}
+
+/// Returns the three bits of the filter which `address` sets.
+fn bits(address: Address) -> [usize; 3] {
+    let mut hasher = Incremental::new();
+    hasher.update(&<[u8; 32]>::from(address));
+    let hash = hasher.finalize();
+
+    let bit = |i: usize| (u16::from_be_bytes([hash[i], hash[i + 1]]) as usize) % (BLOOM_LENGTH * 8);
+    [bit(0), bit(2), bit(4)]
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn insert_and_contains() {
+        let mut bloom = Bloom::default();
+        assert!(!bloom.contains(1.into()));
+
+        bloom.insert(1.into());
+        bloom.insert(2.into());
+        assert!(bloom.contains(1.into()));
+        assert!(bloom.contains(2.into()));
+        assert_eq!(bloom.0.iter().map(|b| b.count_ones()).sum::<u32>(), 6);
+
+        // With six of 2048 bits set, a false positive among a few addresses is vanishingly rare.
+        assert!((3..100).all(|n| !bloom.contains(n.into())));
+    }
+
+    #[test]
+    fn root() {
+        let mut bloom = Bloom::default();
+        bloom.insert(1.into());
+
+        // hash_tree_root(Vector[byte, 256]) as computed by the SSZ spec.
+        assert_eq!(
+            hex::encode(bloom.root()),
+            "f354a5d4c3dc0ad4f9f9ca78f09a707a96216cc8e5eb32e2814502e955d95cf1"
+        );
+        assert_eq!(
+            hex::encode(Bloom::default().root()),
+            "c78009fdf07fc56a11f122370658a353aaa542ed63e44c4bc15ff4cd105ab33c"
+        );
+    }
+}
@@ -1,4 +1,6 @@
-#[derive(Clone)]
+use crate::hash::H256;
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct PublicKey([u8; 48]);
 
 impl PublicKey {
@@ -19,6 +21,103 @@ impl PublicKey {
     }
 }
 
+/// Checks BLS12-381 signatures of transactions against the sender's public key.
+///
+/// `sheth` doesn't bundle a pairing implementation, so a host which has one (e.g. as a host
+/// function, or a native crate in a builder) plugs it in here, and the wasm build can stub it
+/// with `Unchecked`.
+pub trait SignatureVerifier {
+    /// Returns whether `signature` is `pubkey`'s signature of `message`.
+    fn verify(&self, pubkey: &PublicKey, message: &H256, signature: &[u8; 96]) -> bool;
+
+    /// Whether signatures are checked at all. When this is false the sender's public key isn't
+    /// read from the state, so blocks cost the same as they did before signatures were checked.
+    fn enabled(&self) -> bool {
+        true
+    }
+}
+
+/// Accepts every signature, which is how blocks are processed unless a verifier is given.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Unchecked;
+
+impl SignatureVerifier for Unchecked {
+    fn verify(&self, _: &PublicKey, _: &H256, _: &[u8; 96]) -> bool {
+        true
+    }
+
+    fn enabled(&self) -> bool {
+        false
+    }
+}
+
+/// How the signatures of a block's transactions are checked, chosen with the rest of the
+/// `Config` so every entry point which processes blocks checks them the same way.
+#[derive(Clone, Copy, Debug, Default)]
+pub enum SignatureScheme {
+    /// Signatures aren't checked, see `Unchecked`.
+    #[default]
+    Unchecked,
+
+    /// BLS12-381 signatures, checked by a function the host supplies, since `sheth` doesn't
+    /// bundle a pairing implementation. It is given the sender's public key, the transaction's
+    /// signing root and its signature, as `SignatureVerifier::verify` is.
+    Bls(fn(&PublicKey, &H256, &[u8; 96]) -> bool),
+
+    /// secp256k1 ECDSA signatures with key recovery, see `secp256k1::Secp256k1`.
+    #[cfg(feature = "secp256k1")]
+    Secp256k1,
+}
+
+/// Verifying functions are compared by address, which may differ for the same function in
+/// different codegen units, so a scheme is only known to equal a copy of itself.
+impl PartialEq for SignatureScheme {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (SignatureScheme::Unchecked, SignatureScheme::Unchecked) => true,
+            (SignatureScheme::Bls(a), SignatureScheme::Bls(b)) => core::ptr::fn_addr_eq(*a, *b),
+            #[cfg(feature = "secp256k1")]
+            (SignatureScheme::Secp256k1, SignatureScheme::Secp256k1) => true,
+            _ => false,
+        }
+    }
+}
+
+impl SignatureVerifier for SignatureScheme {
+    fn verify(&self, pubkey: &PublicKey, message: &H256, signature: &[u8; 96]) -> bool {
+        match self {
+            SignatureScheme::Unchecked => Unchecked.verify(pubkey, message, signature),
+            SignatureScheme::Bls(verify) => verify(pubkey, message, signature),
+            #[cfg(feature = "secp256k1")]
+            SignatureScheme::Secp256k1 => {
+                crate::secp256k1::Secp256k1.verify(pubkey, message, signature)
+            }
+        }
+    }
+
+    fn enabled(&self) -> bool {
+        match self {
+            SignatureScheme::Unchecked => Unchecked.enabled(),
+            SignatureScheme::Bls(_) => true,
+            #[cfg(feature = "secp256k1")]
+            SignatureScheme::Secp256k1 => crate::secp256k1::Secp256k1.enabled(),
+        }
+    }
+}
+
+/// Checks a single BLS12-381 signature aggregated over every transaction in a block, so a block
+/// costs one batched pairing check rather than one per transaction.
+pub trait AggregateVerifier {
+    /// Returns whether `signature` is the aggregate of each `pubkeys[i]`'s signature of
+    /// `messages[i]`.
+    fn verify_aggregate(
+        &self,
+        pubkeys: &[PublicKey],
+        messages: &[H256],
+        signature: &[u8; 96],
+    ) -> bool;
+}
+
 #[cfg(feature = "std")]
 impl std::hash::Hash for PublicKey {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
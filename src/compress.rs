@@ -0,0 +1,202 @@
+use crate::config::Config;
+use crate::error::Error;
+use crate::process_data_blob;
+use alloc::vec;
+use alloc::vec::Vec;
+use arrayref::array_ref;
+
+/// Length of the header which prefixes compressed data with the length of the raw data.
+pub const HEADER_LENGTH: usize = 4;
+
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = MIN_MATCH + 0x7f;
+const MAX_LITERALS: usize = 0x80;
+const MAX_DISTANCE: usize = u16::MAX as usize;
+const HASH_BITS: usize = 12;
+
+/// Compresses serialized data (e.g. a block or witness) with a small LZ77 codec, so it uses less
+/// bandwidth when sent to stateless clients.
+///
+/// The output is laid out as `len(data) || tokens`, with the length as a little endian `u32`.
+/// A token with a control byte `c` below `0x80` is followed by `c + 1` literal bytes. Otherwise
+/// it is followed by a little endian `u16` distance, and copies `(c & 0x7f) + 3` bytes starting
+/// that far back in the output. Witnesses are mostly zero padding and repeated hashes of empty
+/// subtrees, which compress well.
+pub fn compress(data: &[u8]) -> Vec<u8> {
+    let mut ret = (data.len() as u32).to_le_bytes().to_vec();
+
+    // The most recent position of each hashed 3 byte sequence.
+    let mut table = vec![usize::MAX; 1 << HASH_BITS];
+    let mut literals = 0;
+    let mut i = 0;
+
+    while i < data.len() {
+        let mut length = 0;
+
+        if i + MIN_MATCH <= data.len() {
+            let h = hash(array_ref![data, i, 3]);
+            let candidate = table[h];
+            table[h] = i;
+
+            if candidate != usize::MAX && i - candidate <= MAX_DISTANCE {
+                while i + length < data.len()
+                    && length < MAX_MATCH
+                    && data[candidate + length] == data[i + length]
+                {
+                    length += 1;
+                }
+            }
+
+            if length >= MIN_MATCH {
+                flush(&mut ret, &data[i - literals..i]);
+                literals = 0;
+
+                ret.push(0x80 | (length - MIN_MATCH) as u8);
+                ret.extend(&((i - candidate) as u16).to_le_bytes());
+                i += length;
+                continue;
+            }
+        }
+
+        literals += 1;
+        i += 1;
+
+        if literals == MAX_LITERALS {
+            flush(&mut ret, &data[i - literals..i]);
+            literals = 0;
+        }
+    }
+
+    flush(&mut ret, &data[i - literals..i]);
+
+    ret
+}
+
+/// Decompresses data created by `compress`, returning `Error::CompressionInvalid` if it is
+/// malformed.
+pub fn decompress(data: &[u8]) -> Result<Vec<u8>, Error> {
+    if data.len() < HEADER_LENGTH {
+        return Err(Error::CompressionInvalid);
+    }
+
+    let len = u32::from_le_bytes(*array_ref![data, 0, 4]) as usize;
+
+    // Each byte of input produces at most `MAX_MATCH` bytes of output, so a header claiming more
+    // than that can't be valid and isn't allocated for.
+    if len > (data.len() - HEADER_LENGTH).saturating_mul(MAX_MATCH) {
+        return Err(Error::CompressionInvalid);
+    }
+
+    let mut ret = Vec::with_capacity(len);
+    let mut i = HEADER_LENGTH;
+
+    while i < data.len() {
+        let control = data[i] as usize;
+        i += 1;
+
+        if control < 0x80 {
+            let end = i + control + 1;
+            if end > data.len() {
+                return Err(Error::CompressionInvalid);
+            }
+
+            ret.extend(&data[i..end]);
+            i = end;
+        } else {
+            if i + 2 > data.len() {
+                return Err(Error::CompressionInvalid);
+            }
+
+            let distance = u16::from_le_bytes(*array_ref![data, i, 2]) as usize;
+            i += 2;
+
+            if distance == 0 || distance > ret.len() {
+                return Err(Error::CompressionInvalid);
+            }
+
+            // The match may overlap the bytes it is copying, so they're copied one at a time.
+            let start = ret.len() - distance;
+            for j in 0..((control & 0x7f) + MIN_MATCH) {
+                ret.push(ret[start + j]);
+            }
+        }
+
+        if ret.len() > len {
+            return Err(Error::CompressionInvalid);
+        }
+    }
+
+    if ret.len() != len {
+        return Err(Error::CompressionInvalid);
+    }
+
+    Ok(ret)
+}
+
+/// Decompresses a data blob created by `compress` and processes it.
+pub fn process_compressed_data_blob(
+    data: &[u8],
+    pre_state_root: &[u8; 32],
+    config: &Config,
+) -> Result<[u8; 32], Error> {
+    let mut blob = decompress(data)?;
+    Ok(process_data_blob(&mut blob, pre_state_root, config))
+}
+
+fn hash(bytes: &[u8; 3]) -> usize {
+    let n = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0]);
+    (n.wrapping_mul(2_654_435_761) >> (32 - HASH_BITS)) as usize
+}
+
+/// Writes `literals` as a run of literal tokens.
+fn flush(ret: &mut Vec<u8>, literals: &[u8]) {
+    for run in literals.chunks(MAX_LITERALS) {
+        ret.push((run.len() - 1) as u8);
+        ret.extend(run);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn compress_and_decompress() {
+        let cases: Vec<Vec<u8>> = vec![
+            vec![],
+            b"a".to_vec(),
+            b"abcabcabcabcabcabc".to_vec(),
+            vec![0u8; 1000],
+            (0..=255u8).cycle().take(3000).collect(),
+        ];
+
+        for data in cases {
+            assert_eq!(decompress(&compress(&data)), Ok(data));
+        }
+    }
+
+    #[test]
+    fn compress_zero_chunks() {
+        let data = vec![0u8; 32 * 64];
+        assert!(compress(&data).len() < data.len() / 16);
+    }
+
+    #[test]
+    fn decompress_malformed_data() {
+        let mut compressed = compress(b"abcabcabcabcabcabc");
+
+        // Truncated header, copy before the start of the output, and a length which doesn't
+        // match the header.
+        assert_eq!(
+            decompress(&compressed[0..3]),
+            Err(Error::CompressionInvalid)
+        );
+        assert_eq!(
+            decompress(&[3, 0, 0, 0, 0x80, 1, 0]),
+            Err(Error::CompressionInvalid)
+        );
+
+        compressed[0] += 1;
+        assert_eq!(decompress(&compressed), Err(Error::CompressionInvalid));
+    }
+}
@@ -0,0 +1,95 @@
+use crate::account::SHORT_ADDRESS_HEIGHT;
+use crate::address::{Address, AddressScheme};
+use crate::bls::{PublicKey, SignatureScheme};
+use crate::error::Error;
+use crate::hash::{HashScheme, Sha256, H256};
+use crate::process::{FeePolicy, Limits};
+use crate::state::root::imp_root_with;
+use crate::u264::U264;
+use imp::Imp;
+
+/// Parameters of the execution environment, constructed once and passed to everything which
+/// needs to agree on them (e.g. block processing and the packager).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Config {
+    /// Height of the state tree, not including the account subtrees.
+    pub height: usize,
+
+    /// Number of children of each interior node of the state tree. Only binary trees are
+    /// supported, so any other arity is rejected with `Error::ArityInvalid`.
+    pub arity: usize,
+
+    /// Limits enforced while processing a block.
+    pub limits: Limits,
+
+    /// How account addresses are derived from public keys.
+    pub addresses: AddressScheme,
+
+    /// How the signatures of transactions are checked.
+    pub signatures: SignatureScheme,
+
+    /// The hash function the state tree is merkleized with.
+    pub hash: HashScheme,
+
+    /// What happens to the fees each block collects.
+    pub fees: FeePolicy,
+
+    /// The number of recent state roots the state keeps, see `history`. When it is `None` or
+    /// zero no roots are kept, and blocks needn't prove the accounts of the ring.
+    pub history: Option<usize>,
+}
+
+impl Config {
+    pub fn new(height: usize) -> Self {
+        Config {
+            height,
+            arity: 2,
+            limits: Limits::default(),
+            addresses: AddressScheme::default(),
+            signatures: SignatureScheme::default(),
+            hash: HashScheme::default(),
+            fees: FeePolicy::default(),
+            history: None,
+        }
+    }
+
+    /// A tree with 20 byte addresses, see `SHORT_ADDRESS_HEIGHT`.
+    pub fn short_addresses() -> Self {
+        Config::new(SHORT_ADDRESS_HEIGHT)
+    }
+
+    /// Returns the address of the account owned by `pubkey` in this tree.
+    pub fn address(&self, pubkey: &PublicKey) -> Address {
+        Address::derive(pubkey, self.height, self.addresses)
+    }
+
+    /// Returns `Error::ArityInvalid` unless the tree is binary, the only shape the state is
+    /// merkleized as.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.arity != 2 {
+            return Err(Error::ArityInvalid);
+        }
+
+        Ok(())
+    }
+
+    /// Height of the multi-proof, which includes the three levels of each account's subtree.
+    pub fn proof_height(&self) -> usize {
+        self.height + 3
+    }
+
+    /// Returns the root of the state proven by `imp`, hashed with `hash`.
+    pub fn root(&self, imp: &Imp<U264>) -> H256 {
+        match self.hash {
+            HashScheme::Sha256 => imp_root_with::<Sha256>(imp),
+            #[cfg(feature = "eth1")]
+            HashScheme::Keccak256 => imp_root_with::<crate::hash::keccak::Keccak256>(imp),
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config::new(256)
+    }
+}
@@ -0,0 +1,363 @@
+//! Transfers between EEs. A `CrossTransfer` debits its sender and emits a `TransferReceipt`
+//! naming the destination EE and recipient, and the block commits to its receipts with
+//! `ExecutionResult::receipts_root`. Once the host of the destination accepts that root, the
+//! recipient claims the transfer with a `Claim` proving the receipt against it.
+//!
+//! Each claim is recorded in the state at `Claim::claimed_address`, an account derived from the
+//! receipt's position which nobody owns, whose nonce is incremented once the claim is credited.
+//! Its nonce is zero until then, so a receipt is only ever credited once. The witness must prove
+//! the marker's account down to its nonce: one which collapses it into the hash of its subtree
+//! can't be read, so it can't pass the marker off as unclaimed.
+
+use crate::address::Address;
+use crate::amount::Amount;
+use crate::error::Error;
+use crate::hash::{branch_root, hash_pair, list_proof, list_root, Incremental, H256};
+use crate::nonce::Nonce;
+use crate::state::State;
+use crate::transaction::{decode_valid_until, encode_valid_until, TransactionKind};
+use alloc::vec::Vec;
+use arrayref::array_ref;
+
+/// Length of a cross transfer's SSZ encoding.
+pub const CROSS_TRANSFER_LENGTH: usize = 200;
+
+/// Length of a receipt's SSZ encoding.
+pub const RECEIPT_LENGTH: usize = 88;
+
+/// Length of a claim's SSZ encoding.
+pub const CLAIM_LENGTH: usize = RECEIPT_LENGTH + 40 + RECEIPT_PROOF_LENGTH * 32;
+
+/// Depth of the tree of a block's receipts, which limits a block to `2**32` of them.
+pub const RECEIPTS_TREE_DEPTH: usize = 32;
+
+/// Number of hashes in a receipt's proof: a sibling at each level of the tree, then the number of
+/// receipts which is mixed into the root.
+pub const RECEIPT_PROOF_LENGTH: usize = RECEIPTS_TREE_DEPTH + 1;
+
+/// The receipts a block may claim, which the host supplies once it has accepted another EE's
+/// receipts root.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Receipts {
+    /// The foreign receipts root claims are proven against.
+    pub root: H256,
+
+    /// The id of this EE, which claimed receipts must be sent to.
+    pub destination: u64,
+}
+
+/// A transfer of `amount` from `from` to `to` in the EE `destination`.
+#[cfg_attr(feature = "std", derive(Clone, Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CrossTransfer {
+    pub to: Address,
+    pub from: Address,
+    pub destination: u64,
+    pub nonce: Nonce,
+    pub amount: Amount,
+    pub fee: Amount,
+    pub valid_until: Option<u64>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::signature"))]
+    pub signature: [u8; 96],
+}
+
+impl CrossTransfer {
+    /// Returns the message the sender signs. It starts with the cross transfer's type byte, so it
+    /// can never be the signing root of another kind of transaction.
+    pub fn signing_root(&self) -> H256 {
+        let mut hasher = Incremental::new();
+        hasher.update(&[TransactionKind::CrossTransfer as u8]);
+        hasher.update(&<[u8; 32]>::from(self.to));
+        hasher.update(&<[u8; 32]>::from(self.from));
+        hasher.update(&self.destination.to_le_bytes());
+        hasher.update(&self.nonce.get().to_le_bytes());
+        hasher.update(&self.amount.get().to_le_bytes());
+        hasher.update(&self.fee.get().to_le_bytes());
+        hasher.update(&encode_valid_until(self.valid_until).to_le_bytes());
+        hasher.finalize()
+    }
+
+    /// Returns the receipt the transfer emits once it is applied.
+    pub fn receipt(&self) -> TransferReceipt {
+        TransferReceipt {
+            destination: self.destination,
+            from: self.from,
+            to: self.to,
+            amount: self.amount,
+            nonce: self.nonce,
+        }
+    }
+
+    /// Returns the SSZ encoding of the transfer, a fixed size container of its fields:
+    ///
+    /// ```text
+    /// to (32) || from (32) || destination (8, LE) || nonce (8, LE) || amount (8, LE)
+    ///     || fee (8, LE) || valid_until (8, LE) || signature (96)
+    /// ```
+    pub fn to_ssz(&self) -> [u8; CROSS_TRANSFER_LENGTH] {
+        let mut ret = [0u8; CROSS_TRANSFER_LENGTH];
+        ret[0..32].copy_from_slice(&<[u8; 32]>::from(self.to));
+        ret[32..64].copy_from_slice(&<[u8; 32]>::from(self.from));
+        ret[64..72].copy_from_slice(&self.destination.to_le_bytes());
+        ret[72..80].copy_from_slice(&self.nonce.get().to_le_bytes());
+        ret[80..88].copy_from_slice(&self.amount.get().to_le_bytes());
+        ret[88..96].copy_from_slice(&self.fee.get().to_le_bytes());
+        ret[96..104].copy_from_slice(&encode_valid_until(self.valid_until).to_le_bytes());
+        ret[104..200].copy_from_slice(&self.signature);
+        ret
+    }
+
+    /// Decodes a cross transfer encoded by `to_ssz`.
+    pub fn from_ssz(bytes: &[u8; CROSS_TRANSFER_LENGTH]) -> Result<Self, Error> {
+        Ok(CrossTransfer {
+            to: (*array_ref![bytes, 0, 32]).into(),
+            from: (*array_ref![bytes, 32, 32]).into(),
+            destination: u64::from_le_bytes(*array_ref![bytes, 64, 8]),
+            nonce: u64::from_le_bytes(*array_ref![bytes, 72, 8]).into(),
+            amount: Amount::new(u64::from_le_bytes(*array_ref![bytes, 80, 8]))?,
+            fee: Amount::new(u64::from_le_bytes(*array_ref![bytes, 88, 8]))?,
+            valid_until: decode_valid_until(u64::from_le_bytes(*array_ref![bytes, 96, 8])),
+            signature: *array_ref![bytes, 104, 96],
+        })
+    }
+}
+
+/// The record of a cross transfer, which `to` claims in the EE `destination`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransferReceipt {
+    pub destination: u64,
+    pub from: Address,
+    pub to: Address,
+    pub amount: Amount,
+    pub nonce: Nonce,
+}
+
+impl TransferReceipt {
+    /// Returns the `hash_tree_root` of the receipt as an SSZ container, whose five fields are
+    /// padded to eight chunks.
+    pub fn root(&self) -> H256 {
+        let chunk = |n: u64| {
+            let mut ret = [0u8; 32];
+            ret[0..8].copy_from_slice(&n.to_le_bytes());
+            ret
+        };
+
+        hash_pair(
+            &hash_pair(
+                &hash_pair(&chunk(self.destination), &<[u8; 32]>::from(self.from)),
+                &hash_pair(&<[u8; 32]>::from(self.to), &chunk(self.amount.get())),
+            ),
+            &hash_pair(
+                &hash_pair(&chunk(self.nonce.get()), &[0u8; 32]),
+                &hash_pair(&[0u8; 32], &[0u8; 32]),
+            ),
+        )
+    }
+
+    /// Returns the SSZ encoding of the receipt:
+    ///
+    /// ```text
+    /// destination (8, LE) || from (32) || to (32) || amount (8, LE) || nonce (8, LE)
+    /// ```
+    pub fn to_ssz(&self) -> [u8; RECEIPT_LENGTH] {
+        let mut ret = [0u8; RECEIPT_LENGTH];
+        ret[0..8].copy_from_slice(&self.destination.to_le_bytes());
+        ret[8..40].copy_from_slice(&<[u8; 32]>::from(self.from));
+        ret[40..72].copy_from_slice(&<[u8; 32]>::from(self.to));
+        ret[72..80].copy_from_slice(&self.amount.get().to_le_bytes());
+        ret[80..88].copy_from_slice(&self.nonce.get().to_le_bytes());
+        ret
+    }
+
+    /// Decodes a receipt encoded by `to_ssz`.
+    pub fn from_ssz(bytes: &[u8; RECEIPT_LENGTH]) -> Result<Self, Error> {
+        Ok(TransferReceipt {
+            destination: u64::from_le_bytes(*array_ref![bytes, 0, 8]),
+            from: (*array_ref![bytes, 8, 32]).into(),
+            to: (*array_ref![bytes, 40, 32]).into(),
+            amount: Amount::new(u64::from_le_bytes(*array_ref![bytes, 72, 8]))?,
+            nonce: u64::from_le_bytes(*array_ref![bytes, 80, 8]).into(),
+        })
+    }
+}
+
+/// Returns the receipts root of a block's `receipts`, the root of their SSZ list.
+pub fn receipts_root(receipts: &[TransferReceipt]) -> H256 {
+    let leaves: Vec<H256> = receipts.iter().map(|r| r.root()).collect();
+    list_root(&leaves, RECEIPTS_TREE_DEPTH)
+}
+
+/// Returns a claim of `receipts[index]` against their `receipts_root`.
+///
+/// Panics if `index` is out of bounds.
+pub fn build_claim(receipts: &[TransferReceipt], index: usize) -> Claim {
+    let leaves: Vec<H256> = receipts.iter().map(|r| r.root()).collect();
+
+    Claim {
+        receipt: receipts[index],
+        root: list_root(&leaves, RECEIPTS_TREE_DEPTH),
+        index: index as u64,
+        proof: list_proof(&leaves, RECEIPTS_TREE_DEPTH, index),
+    }
+}
+
+/// A claim of the receipt at `index` in a foreign `root`, which credits its recipient.
+#[cfg_attr(feature = "std", derive(Clone, Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Claim {
+    pub receipt: TransferReceipt,
+    pub root: H256,
+    pub index: u64,
+    pub proof: Vec<H256>,
+}
+
+impl Claim {
+    /// Returns the address of the account which records whether the receipt has been claimed.
+    pub fn claimed_address(&self) -> Address {
+        let mut index = [0u8; 32];
+        index[0..8].copy_from_slice(&self.index.to_le_bytes());
+        hash_pair(&self.root, &index).into()
+    }
+
+    /// Check the claim against the `receipts` the host accepted and `db`, returning
+    /// `Error::ReceiptInvalid` if it isn't for this EE or isn't in the root, and
+    /// `Error::ReceiptClaimed` if it has been claimed before.
+    pub fn verify<T: State>(&self, db: &T, receipts: &Receipts) -> Result<(), Error> {
+        if self.root != receipts.root
+            || self.receipt.destination != receipts.destination
+            || self.proof.len() != RECEIPT_PROOF_LENGTH
+            || branch_root(self.receipt.root(), self.index, &self.proof) != self.root
+        {
+            return Err(Error::ReceiptInvalid);
+        }
+
+        if db.nonce(self.claimed_address())? != Nonce::new(0) {
+            return Err(Error::ReceiptClaimed);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the SSZ encoding of the claim:
+    ///
+    /// ```text
+    /// receipt (88) || root (32) || index (8, LE) || proof (33 * 32)
+    /// ```
+    pub fn to_ssz(&self) -> [u8; CLAIM_LENGTH] {
+        let mut ret = [0u8; CLAIM_LENGTH];
+        ret[0..88].copy_from_slice(&self.receipt.to_ssz());
+        ret[88..120].copy_from_slice(&self.root);
+        ret[120..128].copy_from_slice(&self.index.to_le_bytes());
+        for (i, hash) in self.proof.iter().take(RECEIPT_PROOF_LENGTH).enumerate() {
+            ret[128 + i * 32..160 + i * 32].copy_from_slice(hash);
+        }
+        ret
+    }
+
+    /// Decodes a claim encoded by `to_ssz`.
+    pub fn from_ssz(bytes: &[u8; CLAIM_LENGTH]) -> Result<Self, Error> {
+        Ok(Claim {
+            receipt: TransferReceipt::from_ssz(array_ref![bytes, 0, RECEIPT_LENGTH])?,
+            root: *array_ref![bytes, 88, 32],
+            index: u64::from_le_bytes(*array_ref![bytes, 120, 8]),
+            proof: bytes[128..]
+                .chunks_exact(32)
+                .map(|hash| *array_ref![hash, 0, 32])
+                .collect(),
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::account::Account;
+    use crate::state::MockState;
+    use std::collections::BTreeMap;
+
+    fn build_receipts() -> Vec<TransferReceipt> {
+        (0..3)
+            .map(|i| TransferReceipt {
+                destination: 7,
+                from: 1.into(),
+                to: 2.into(),
+                amount: 5.into(),
+                nonce: i.into(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn receipt_root() {
+        // hash_tree_root(TransferReceipt) as computed by the SSZ spec.
+        assert_eq!(
+            hex::encode(build_receipts()[1].root()),
+            "eec29feff2bea48a7d9de5ddb5455c2706f7d1d0b4a54bb6fbb03b9227dcc5e4"
+        );
+    }
+
+    #[test]
+    fn verify() {
+        let receipts = build_receipts();
+        let claim = build_claim(&receipts, 2);
+        let accepted = Receipts {
+            root: receipts_root(&receipts),
+            destination: 7,
+        };
+
+        let mut accounts = BTreeMap::new();
+        accounts.insert(claim.claimed_address(), Account::zero());
+        let mut mem = MockState::new(accounts);
+
+        assert_eq!(claim.verify(&mem, &accepted), Ok(()));
+
+        // Receipts sent to another EE can't be claimed here.
+        let elsewhere = Receipts {
+            destination: 8,
+            ..accepted
+        };
+        assert_eq!(claim.verify(&mem, &elsewhere), Err(Error::ReceiptInvalid));
+
+        let mut other = claim.clone();
+        other.receipt.amount = 6.into();
+        assert_eq!(other.verify(&mem, &accepted), Err(Error::ReceiptInvalid));
+
+        let mut other = claim.clone();
+        other.index = 1;
+        assert_eq!(other.verify(&mem, &accepted), Err(Error::ReceiptInvalid));
+
+        mem.inc_nonce(claim.claimed_address()).unwrap();
+        assert_eq!(claim.verify(&mem, &accepted), Err(Error::ReceiptClaimed));
+
+        // Each receipt is recorded at its own address.
+        assert_ne!(
+            build_claim(&receipts, 1).claimed_address(),
+            claim.claimed_address()
+        );
+    }
+
+    #[test]
+    fn ssz_round_trip() {
+        let claim = build_claim(&build_receipts(), 1);
+        let bytes = claim.to_ssz();
+        let decoded = Claim::from_ssz(&bytes).unwrap();
+        assert_eq!(decoded.to_ssz()[..], bytes[..]);
+        assert_eq!(decoded.receipt, claim.receipt);
+
+        let transfer = CrossTransfer {
+            to: 2.into(),
+            from: 1.into(),
+            destination: 7,
+            nonce: 4.into(),
+            amount: 5.into(),
+            fee: 1.into(),
+            valid_until: Some(3),
+            signature: [9u8; 96],
+        };
+        let decoded = CrossTransfer::from_ssz(&transfer.to_ssz()).unwrap();
+        assert_eq!(decoded.signing_root(), transfer.signing_root());
+        assert_eq!(decoded.receipt(), transfer.receipt());
+    }
+}
@@ -0,0 +1,221 @@
+//! Deposits move value from the beacon chain into the EE. Each is a leaf of the beacon chain's
+//! deposit tree, and is applied once it is proven against the tree's root, which the host passes
+//! in with `Limits::deposits`.
+//!
+//! The EE reads its own data from the deposit's `withdrawal_credentials`, which hold the address
+//! to credit. If the account there is empty it is created for the deposit's `pubkey`.
+
+use crate::address::Address;
+use crate::amount::Amount;
+use crate::bls::PublicKey;
+use crate::error::Error;
+use crate::hash::{branch_root, hash_pair, hash_pubkey, H256};
+use alloc::vec::Vec;
+use arrayref::array_ref;
+
+/// Depth of the beacon chain's deposit tree.
+pub const DEPOSIT_CONTRACT_TREE_DEPTH: usize = 32;
+
+/// Number of hashes in a deposit's proof: a sibling at each level of the tree, then the number of
+/// deposits which is mixed into the root.
+pub const DEPOSIT_PROOF_LENGTH: usize = DEPOSIT_CONTRACT_TREE_DEPTH + 1;
+
+/// Length of a deposit's SSZ encoding.
+pub const DEPOSIT_LENGTH: usize = 192 + DEPOSIT_PROOF_LENGTH * 32;
+
+/// The deposits a block may apply, which the host supplies from the beacon chain.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Deposits {
+    /// The root of the deposit tree, including the mixed in number of deposits.
+    pub root: H256,
+
+    /// The index of the first deposit the EE hasn't applied yet. The block's deposits must
+    /// continue from it in order, so none are skipped or applied twice.
+    pub index: u64,
+}
+
+/// A deposit of `amount` to the account at the address in `withdrawal_credentials`.
+#[cfg_attr(feature = "std", derive(Clone, Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Deposit {
+    pub pubkey: PublicKey,
+    pub withdrawal_credentials: H256,
+    pub amount: Amount,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::signature"))]
+    pub signature: [u8; 96],
+    pub index: u64,
+    pub proof: Vec<H256>,
+}
+
+impl Deposit {
+    /// Returns the address the deposit credits.
+    pub fn to(&self) -> Address {
+        self.withdrawal_credentials.into()
+    }
+
+    /// Returns the `hash_tree_root` of the deposit's `DepositData`, which is its leaf in the
+    /// deposit tree.
+    ///
+    /// ```text
+    /// root = hash(hash(pubkey, withdrawal_credentials), hash(amount, signature))
+    /// ```
+    pub fn data_root(&self) -> H256 {
+        let mut amount = [0u8; 32];
+        amount[0..8].copy_from_slice(&self.amount.get().to_le_bytes());
+
+        // The 96 byte signature is three chunks, padded to four.
+        let chunk = |i: usize| *array_ref![self.signature, i * 32, 32];
+        let signature = hash_pair(
+            &hash_pair(&chunk(0), &chunk(1)),
+            &hash_pair(&chunk(2), &[0u8; 32]),
+        );
+
+        hash_pair(
+            &hash_pair(&hash_pubkey(&self.pubkey), &self.withdrawal_credentials),
+            &hash_pair(&amount, &signature),
+        )
+    }
+
+    /// Returns the root of the deposit tree that the deposit's proof leads to from its leaf.
+    pub fn root(&self) -> H256 {
+        branch_root(self.data_root(), self.index, &self.proof)
+    }
+
+    /// Check the deposit's proof against `deposits`, returning `Error::DepositInvalid` if the
+    /// deposit isn't the next one or isn't in the tree.
+    pub fn verify(&self, deposits: &Deposits) -> Result<(), Error> {
+        if self.index != deposits.index
+            || self.proof.len() != DEPOSIT_PROOF_LENGTH
+            || self.root() != deposits.root
+        {
+            return Err(Error::DepositInvalid);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the SSZ encoding of the deposit, its `DepositData` followed by its proof:
+    ///
+    /// ```text
+    /// pubkey (48) || withdrawal_credentials (32) || amount (8, LE) || signature (96)
+    ///     || index (8, LE) || proof (33 * 32)
+    /// ```
+    pub fn to_ssz(&self) -> [u8; DEPOSIT_LENGTH] {
+        let mut ret = [0u8; DEPOSIT_LENGTH];
+        ret[0..48].copy_from_slice(&self.pubkey.as_bytes());
+        ret[48..80].copy_from_slice(&self.withdrawal_credentials);
+        ret[80..88].copy_from_slice(&self.amount.get().to_le_bytes());
+        ret[88..184].copy_from_slice(&self.signature);
+        ret[184..192].copy_from_slice(&self.index.to_le_bytes());
+        for (i, hash) in self.proof.iter().take(DEPOSIT_PROOF_LENGTH).enumerate() {
+            ret[192 + i * 32..224 + i * 32].copy_from_slice(hash);
+        }
+        ret
+    }
+
+    /// Decodes a deposit encoded by `to_ssz`.
+    pub fn from_ssz(bytes: &[u8; DEPOSIT_LENGTH]) -> Result<Self, Error> {
+        Ok(Deposit {
+            pubkey: PublicKey::new(*array_ref![bytes, 0, 48]),
+            withdrawal_credentials: *array_ref![bytes, 48, 32],
+            amount: Amount::new(u64::from_le_bytes(*array_ref![bytes, 80, 8]))?,
+            signature: *array_ref![bytes, 88, 96],
+            index: u64::from_le_bytes(*array_ref![bytes, 184, 8]),
+            proof: bytes[192..]
+                .chunks_exact(32)
+                .map(|hash| *array_ref![hash, 0, 32])
+                .collect(),
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn build_deposit() -> Deposit {
+        let mut withdrawal_credentials = [0u8; 32];
+        withdrawal_credentials[31] = 5;
+
+        Deposit {
+            pubkey: PublicKey::one(),
+            withdrawal_credentials,
+            amount: 32.into(),
+            signature: [2u8; 96],
+            index: 0,
+            proof: Vec::new(),
+        }
+    }
+
+    /// Returns the root of a deposit tree of `index + 1` deposits ending with `leaf`, and the
+    /// leaf's proof. The other nodes are arbitrary, since only the path to the root is hashed.
+    fn build_tree(leaf: H256, index: u64) -> (H256, Vec<H256>) {
+        let mut proof = Vec::new();
+        let mut value = leaf;
+
+        for i in 0..DEPOSIT_CONTRACT_TREE_DEPTH {
+            let sibling = [i as u8 + 1; 32];
+
+            value = if (index >> i) & 1 == 1 {
+                hash_pair(&sibling, &value)
+            } else {
+                hash_pair(&value, &sibling)
+            };
+            proof.push(sibling);
+        }
+
+        let mut count = [0u8; 32];
+        count[0..8].copy_from_slice(&(index + 1).to_le_bytes());
+        proof.push(count);
+
+        (hash_pair(&value, &count), proof)
+    }
+
+    #[test]
+    fn data_root() {
+        // hash_tree_root(DepositData) as computed by the beacon chain spec.
+        assert_eq!(
+            hex::encode(build_deposit().data_root()),
+            "6a1fb1f08a8df20f57128e92ed91f6c27264ca4f2a352831fe4ebcd5a2210428"
+        );
+    }
+
+    #[test]
+    fn verify() {
+        let mut deposit = build_deposit();
+        deposit.index = 3;
+        let (root, proof) = build_tree(deposit.data_root(), 3);
+        deposit.proof = proof;
+
+        let deposits = Deposits { root, index: 3 };
+        assert_eq!(deposit.to(), 5.into());
+        assert_eq!(deposit.verify(&deposits), Ok(()));
+
+        // Deposits must be applied in order.
+        let later = Deposits { root, index: 2 };
+        assert_eq!(deposit.verify(&later), Err(Error::DepositInvalid));
+
+        // A different amount isn't in the tree.
+        let mut other = deposit.clone();
+        other.amount = 33.into();
+        assert_eq!(other.verify(&deposits), Err(Error::DepositInvalid));
+
+        let mut other = deposit.clone();
+        other.proof.pop();
+        assert_eq!(other.verify(&deposits), Err(Error::DepositInvalid));
+    }
+
+    #[test]
+    fn ssz_round_trip() {
+        let mut deposit = build_deposit();
+        let (_, proof) = build_tree(deposit.data_root(), 0);
+        deposit.proof = proof;
+
+        let bytes = deposit.to_ssz();
+        let decoded = Deposit::from_ssz(&bytes).unwrap();
+        assert_eq!(decoded.to_ssz()[..], bytes[..]);
+        assert_eq!(decoded.proof, deposit.proof);
+        assert_eq!(decoded.data_root(), deposit.data_root());
+    }
+}
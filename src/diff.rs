@@ -0,0 +1,133 @@
+//! The changes a block makes to the accounts it touches, so block explorers and indexers can
+//! track balances and nonces without re-deriving them from the chunks written to the proof.
+
+use crate::address::Address;
+use crate::amount::Amount;
+use crate::error::Error;
+use crate::nonce::Nonce;
+use crate::state::State;
+use crate::transaction::Transaction;
+use alloc::collections::btree_map::Entry;
+use alloc::collections::BTreeMap;
+use alloc::vec;
+
+/// The value and nonce of an account before and after a block.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AccountDiff {
+    pub old_value: Amount,
+    pub new_value: Amount,
+    pub old_nonce: Nonce,
+    pub new_nonce: Nonce,
+}
+
+impl AccountDiff {
+    /// Returns whether the block left the account's value and nonce as they were, e.g. because
+    /// its only transaction was skipped.
+    pub fn is_unchanged(&self) -> bool {
+        self.old_value == self.new_value && self.old_nonce == self.new_nonce
+    }
+}
+
+/// The diff of every account a block touched, by address.
+pub type StateDiff = BTreeMap<Address, AccountDiff>;
+
+/// Returns a diff of the accounts of `transactions` and the block's `proposer` against `db`, whose
+/// old and new values are both their current ones. A claim's marker account, which holds no
+/// value, is left out.
+pub fn snapshot<T: State>(
+    db: &T,
+    transactions: &[Transaction],
+    proposer: Address,
+) -> Result<StateDiff, Error> {
+    let mut ret = StateDiff::new();
+
+    for tx in transactions {
+        let accounts = match tx {
+            Transaction::Claim(c) => vec![c.receipt.to],
+            _ => tx.accounts(),
+        };
+
+        for address in accounts.into_iter().chain(core::iter::once(proposer)) {
+            if let Entry::Vacant(entry) = ret.entry(address) {
+                let value = db.value(address)?;
+                let nonce = db.nonce(address)?;
+
+                entry.insert(AccountDiff {
+                    old_value: value,
+                    new_value: value,
+                    old_nonce: nonce,
+                    new_nonce: nonce,
+                });
+            }
+        }
+    }
+
+    Ok(ret)
+}
+
+/// Update the new values and nonces of the accounts in `diff` from `db`.
+pub fn update<T: State>(db: &T, diff: &mut StateDiff) -> Result<(), Error> {
+    for (address, account) in diff.iter_mut() {
+        account.new_value = db.value(*address)?;
+        account.new_nonce = db.nonce(*address)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::account::Account;
+    use crate::bls::PublicKey;
+    use crate::state::MockState;
+    use crate::transaction::Transfer;
+
+    #[test]
+    fn snapshot_and_update() {
+        let mut accounts: BTreeMap<Address, Account> = BTreeMap::new();
+        accounts.insert(
+            0.into(),
+            Account {
+                pubkey: PublicKey::one(),
+                nonce: 3,
+                value: 10,
+            },
+        );
+        accounts.insert(1.into(), Account::zero());
+        accounts.insert(2.into(), Account::zero());
+        let mut db = MockState::new(accounts);
+
+        let transfer = Transaction::Transfer(Transfer {
+            to: 1.into(),
+            from: 0.into(),
+            nonce: 3.into(),
+            amount: 4.into(),
+            fee: 0.into(),
+            valid_until: None,
+            signature: [0u8; 96],
+        });
+
+        let mut diff = snapshot(&db, &[transfer.clone(), transfer], 2.into()).unwrap();
+        assert_eq!(diff.len(), 3);
+        assert!(diff.values().all(|account| account.is_unchanged()));
+
+        db.sub_value(0.into(), 4.into()).unwrap();
+        db.inc_nonce(0.into()).unwrap();
+        db.add_value(1.into(), 4.into()).unwrap();
+        update(&db, &mut diff).unwrap();
+
+        assert_eq!(
+            diff[&0.into()],
+            AccountDiff {
+                old_value: 10.into(),
+                new_value: 6.into(),
+                old_nonce: 3.into(),
+                new_nonce: 4.into(),
+            }
+        );
+        assert_eq!(diff[&1.into()].new_value, 4.into());
+        assert!(diff[&2.into()].is_unchanged());
+    }
+}
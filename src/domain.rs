@@ -0,0 +1,78 @@
+//! Signatures are bound to the chain they were made for, so a transaction signed on one
+//! deployment of the EE (e.g. a testnet) can't be replayed on another.
+//!
+//! Like the beacon chain's `compute_signing_root`, the message a sender signs is a transaction's
+//! signing root mixed with the domain it is valid in.
+
+use crate::hash::{hash_pair, H256};
+
+/// The chain a transaction is signed for.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Domain {
+    /// Identifies the deployment of the EE.
+    pub chain_id: u64,
+
+    /// The version of the deployment's current fork, so signatures made before a fork which
+    /// changes the rules aren't valid after it.
+    pub fork_version: [u8; 4],
+}
+
+impl Domain {
+    /// Returns the domain's single chunk:
+    ///
+    /// ```text
+    /// chain_id (8, LE) || fork_version (4) || zero padding (20)
+    /// ```
+    pub fn root(&self) -> H256 {
+        let mut ret = [0u8; 32];
+        ret[0..8].copy_from_slice(&self.chain_id.to_le_bytes());
+        ret[8..12].copy_from_slice(&self.fork_version);
+        ret
+    }
+
+    /// Returns the message signed for `signing_root` in this domain.
+    ///
+    /// ```text
+    /// message = hash(signing_root, root)
+    /// ```
+    pub fn message(&self, signing_root: &H256) -> H256 {
+        hash_pair(signing_root, &self.root())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn messages_differ_per_chain() {
+        let signing_root = [7u8; 32];
+        let mainnet = Domain::default();
+        let testnet = Domain {
+            chain_id: 5,
+            ..mainnet
+        };
+        let forked = Domain {
+            fork_version: [0, 0, 0, 1],
+            ..mainnet
+        };
+
+        assert_eq!(
+            hex::encode(testnet.message(&signing_root)),
+            "490638e99faf8e51a62147a1a0ba760aa255ae6f28f5c607f231cbf6d0489560"
+        );
+        assert_ne!(
+            mainnet.message(&signing_root),
+            testnet.message(&signing_root)
+        );
+        assert_ne!(
+            mainnet.message(&signing_root),
+            forked.message(&signing_root)
+        );
+        assert_ne!(
+            testnet.message(&signing_root),
+            forked.message(&signing_root)
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use crate::config::Config;
+use crate::error::Error;
+use crate::process_data_blob;
+use alloc::vec::Vec;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+
+/// Length of the nonce which prefixes every envelope.
+pub const NONCE_LENGTH: usize = 12;
+
+/// Wraps serialized data (e.g. a block or witness) in an authenticated encryption envelope so it
+/// can be passed over private channels between relayers and builders.
+///
+/// The envelope is laid out as `nonce || ChaCha20Poly1305(key, nonce, data)`. A nonce must never
+/// be reused with the same key.
+pub fn seal(key: &[u8; 32], nonce: &[u8; NONCE_LENGTH], data: &[u8]) -> Vec<u8> {
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(key));
+
+    let mut ret = nonce.to_vec();
+    ret.extend(
+        cipher
+            .encrypt(Nonce::from_slice(nonce), data)
+            .expect("encryption to succeed"),
+    );
+
+    ret
+}
+
+/// Authenticates and decrypts an envelope created by `seal`.
+pub fn open(key: &[u8; 32], envelope: &[u8]) -> Result<Vec<u8>, Error> {
+    if envelope.len() < NONCE_LENGTH {
+        return Err(Error::EnvelopeInvalid);
+    }
+
+    let (nonce, ciphertext) = envelope.split_at(NONCE_LENGTH);
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(key));
+
+    cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| Error::EnvelopeInvalid)
+}
+
+/// Opens a sealed data blob using the `key` supplied by the host and processes it.
+pub fn process_sealed_data_blob(
+    key: &[u8; 32],
+    envelope: &[u8],
+    pre_state_root: &[u8; 32],
+    config: &Config,
+) -> Result<[u8; 32], Error> {
+    let mut blob = open(key, envelope)?;
+    Ok(process_data_blob(&mut blob, pre_state_root, config))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const KEY: [u8; 32] = [7u8; 32];
+    const NONCE: [u8; NONCE_LENGTH] = [3u8; NONCE_LENGTH];
+
+    #[test]
+    fn seal_and_open() {
+        let data = b"some block data".to_vec();
+        let envelope = seal(&KEY, &NONCE, &data);
+
+        assert_eq!(&envelope[0..NONCE_LENGTH], &NONCE[..]);
+        assert_ne!(
+            &envelope[NONCE_LENGTH..NONCE_LENGTH + data.len()],
+            &data[..]
+        );
+        assert_eq!(open(&KEY, &envelope), Ok(data));
+    }
+
+    #[test]
+    fn open_tampered_envelope() {
+        let mut envelope = seal(&KEY, &NONCE, b"some block data");
+        envelope[NONCE_LENGTH] ^= 1;
+
+        assert_eq!(open(&KEY, &envelope), Err(Error::EnvelopeInvalid));
+    }
+
+    #[test]
+    fn open_with_wrong_key() {
+        let envelope = seal(&KEY, &NONCE, b"some block data");
+        assert_eq!(open(&[8u8; 32], &envelope), Err(Error::EnvelopeInvalid));
+    }
+
+    #[test]
+    fn open_truncated_envelope() {
+        assert_eq!(open(&KEY, &NONCE[1..]), Err(Error::EnvelopeInvalid));
+    }
+}
@@ -7,4 +7,132 @@ pub enum Error {
     BalanceInsufficient,
     StateIncomplete(U264),
     Overflow,
+    Underflow,
+    AccountLimitExceeded,
+    EnvelopeInvalid,
+    AccountExists,
+    Timeout,
+    Uncommitted,
+    AmountInvalid,
+    ProofTruncated,
+    CompressionInvalid,
+    OffsetsLengthInvalid,
+    OffsetsInvalid,
+    ChunksLengthInvalid,
+    AddressInvalid,
+    TransactionsLengthInvalid,
+    TransactionExpired,
+    TransactionKindInvalid,
+    DepositInvalid,
+    ReceiptInvalid,
+    ReceiptClaimed,
+    GasLimitExceeded,
+    PreStateRootInvalid,
+    TransactionDuplicate,
+    ScratchInsufficient,
+    ArityInvalid,
+}
+
+impl Error {
+    /// Returns the error's numeric code, which is how a block's rejection is reported to hosts
+    /// that can't inspect a Rust value, e.g. by the Scout entry point. Codes start at 1, so 0 can
+    /// stand for success, and never change: a new error takes the next unused code, and the code
+    /// of a removed one isn't reused. `StateIncomplete` has a single code whatever its index.
+    ///
+    /// | Code | Error                  | Code | Error                       |
+    /// |------|------------------------|------|-----------------------------|
+    /// | 1    | `SignatureInvalid`     | 16   | `OffsetsInvalid`            |
+    /// | 2    | `NonceInvalid`         | 17   | `ChunksLengthInvalid`       |
+    /// | 3    | `BalanceInsufficient`  | 18   | `AddressInvalid`            |
+    /// | 4    | `StateIncomplete`      | 19   | `TransactionsLengthInvalid` |
+    /// | 5    | `Overflow`             | 20   | `TransactionExpired`        |
+    /// | 6    | `Underflow`            | 21   | `TransactionKindInvalid`    |
+    /// | 7    | `AccountLimitExceeded` | 22   | `DepositInvalid`            |
+    /// | 8    | `EnvelopeInvalid`      | 23   | `ReceiptInvalid`            |
+    /// | 9    | `AccountExists`        | 24   | `ReceiptClaimed`            |
+    /// | 10   | `Timeout`              | 25   | `GasLimitExceeded`          |
+    /// | 11   | `Uncommitted`          | 26   | `PreStateRootInvalid`       |
+    /// | 12   | `AmountInvalid`        | 27   | `TransactionDuplicate`      |
+    /// | 13   | `ProofTruncated`       | 28   | `ScratchInsufficient`       |
+    /// | 14   | `CompressionInvalid`   | 29   | `ArityInvalid`              |
+    /// | 15   | `OffsetsLengthInvalid` |      |                             |
+    pub fn code(&self) -> u32 {
+        match self {
+            Error::SignatureInvalid => 1,
+            Error::NonceInvalid => 2,
+            Error::BalanceInsufficient => 3,
+            Error::StateIncomplete(_) => 4,
+            Error::Overflow => 5,
+            Error::Underflow => 6,
+            Error::AccountLimitExceeded => 7,
+            Error::EnvelopeInvalid => 8,
+            Error::AccountExists => 9,
+            Error::Timeout => 10,
+            Error::Uncommitted => 11,
+            Error::AmountInvalid => 12,
+            Error::ProofTruncated => 13,
+            Error::CompressionInvalid => 14,
+            Error::OffsetsLengthInvalid => 15,
+            Error::OffsetsInvalid => 16,
+            Error::ChunksLengthInvalid => 17,
+            Error::AddressInvalid => 18,
+            Error::TransactionsLengthInvalid => 19,
+            Error::TransactionExpired => 20,
+            Error::TransactionKindInvalid => 21,
+            Error::DepositInvalid => 22,
+            Error::ReceiptInvalid => 23,
+            Error::ReceiptClaimed => 24,
+            Error::GasLimitExceeded => 25,
+            Error::PreStateRootInvalid => 26,
+            Error::TransactionDuplicate => 27,
+            Error::ScratchInsufficient => 28,
+            Error::ArityInvalid => 29,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn codes() {
+        let errors = [
+            Error::SignatureInvalid,
+            Error::NonceInvalid,
+            Error::BalanceInsufficient,
+            Error::StateIncomplete(U264::zero()),
+            Error::Overflow,
+            Error::Underflow,
+            Error::AccountLimitExceeded,
+            Error::EnvelopeInvalid,
+            Error::AccountExists,
+            Error::Timeout,
+            Error::Uncommitted,
+            Error::AmountInvalid,
+            Error::ProofTruncated,
+            Error::CompressionInvalid,
+            Error::OffsetsLengthInvalid,
+            Error::OffsetsInvalid,
+            Error::ChunksLengthInvalid,
+            Error::AddressInvalid,
+            Error::TransactionsLengthInvalid,
+            Error::TransactionExpired,
+            Error::TransactionKindInvalid,
+            Error::DepositInvalid,
+            Error::ReceiptInvalid,
+            Error::ReceiptClaimed,
+            Error::GasLimitExceeded,
+            Error::PreStateRootInvalid,
+            Error::TransactionDuplicate,
+            Error::ScratchInsufficient,
+            Error::ArityInvalid,
+        ];
+
+        // The codes are part of the host interface, so they are pinned rather than derived.
+        for (i, error) in errors.iter().enumerate() {
+            assert_eq!(error.code(), i as u32 + 1);
+        }
+        assert_eq!(Error::StateIncomplete(U264::one()).code(), 4);
+    }
 }
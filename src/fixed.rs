@@ -0,0 +1,275 @@
+//! Execution of a blob entirely on buffers the caller provides, for hosts which can't supply an
+//! allocator. The blob's proof is updated in place and the hashes of its transactions are kept
+//! in a scratch region, so nothing here allocates and the crate can be built without the
+//! `alloc` feature.
+//!
+//! Only blocks of transfers are processed this way, since they have a fixed length and touch a
+//! fixed number of accounts. Under the default `Config`, a block of transfers has the same
+//! post-state root as under `process_data_blob`: transactions which can't be applied or are
+//! copies of earlier ones are skipped, signatures aren't checked and fees are paid to the
+//! proposer.
+
+use crate::amount::Amount;
+use crate::error::Error;
+use crate::hash::{hash_pair, Incremental, H256};
+use crate::state::State;
+use crate::transaction::{TransactionKind, Transfer, TRANSFER_LENGTH};
+use crate::u264::U264;
+use crate::{deserialize_proposer, deserialize_slot, BLOB_HEADER_LENGTH};
+use arrayref::array_ref;
+use imp::Imp;
+
+/// Length of a transfer in a blob, including its type byte.
+const ENCODED_TRANSFER_LENGTH: usize = 1 + TRANSFER_LENGTH;
+
+/// Length of the scratch region `process_fixed` needs for each transaction of a blob, which holds
+/// its hash.
+pub const SCRATCH_PER_TRANSACTION: usize = 32;
+
+/// Returns the length of the scratch region `process_fixed` needs for `blob`.
+pub fn scratch_length(blob: &[u8]) -> usize {
+    if blob.len() < BLOB_HEADER_LENGTH {
+        return 0;
+    }
+
+    u32::from_le_bytes(*array_ref![blob, 0, 4]) as usize * SCRATCH_PER_TRANSACTION
+}
+
+/// Process a blob of transfers laid out as `header || transactions || proof` against
+/// `pre_state_root` in a tree of `height`, returning the post-state root. The proof is updated in
+/// place and `scratch` must be at least `scratch_length(blob)` bytes, or the blob is rejected
+/// with `Error::ScratchInsufficient`.
+///
+/// Unlike `process_data_blob`, a malformed blob is rejected with an error rather than panicking,
+/// as is a blob with any other kind of transaction with `Error::TransactionKindInvalid`.
+pub fn process_fixed(
+    blob: &mut [u8],
+    scratch: &mut [u8],
+    pre_state_root: &H256,
+    height: usize,
+) -> Result<H256, Error> {
+    if blob.len() < BLOB_HEADER_LENGTH {
+        return Err(Error::TransactionsLengthInvalid);
+    }
+
+    let tx_count = u32::from_le_bytes(*array_ref![blob, 0, 4]) as usize;
+    let proposer = deserialize_proposer(blob);
+    let slot = deserialize_slot(blob);
+
+    if scratch.len() < scratch_length(blob) {
+        return Err(Error::ScratchInsufficient);
+    }
+
+    let end = tx_count
+        .checked_mul(ENCODED_TRANSFER_LENGTH)
+        .and_then(|len| len.checked_add(BLOB_HEADER_LENGTH))
+        .ok_or(Error::TransactionsLengthInvalid)?;
+    if blob.len() < end {
+        return Err(Error::TransactionsLengthInvalid);
+    }
+
+    let (transactions, proof) = blob.split_at_mut(end);
+    let transactions = &transactions[BLOB_HEADER_LENGTH..];
+
+    // Decode every transfer before touching the state, so a malformed one rejects the block
+    // whatever comes before it.
+    for tx in transactions.chunks(ENCODED_TRANSFER_LENGTH) {
+        if tx[0] != TransactionKind::Transfer as u8 {
+            return Err(Error::TransactionKindInvalid);
+        }
+
+        Transfer::from_ssz(array_ref![tx, 1, TRANSFER_LENGTH])?;
+    }
+
+    let mut mem = new_fixed(proof, height + 3)?;
+    if proof_root(&mem)? != *pre_state_root {
+        return Err(Error::PreStateRootInvalid);
+    }
+
+    let mut fees = Amount::default();
+
+    for (i, tx) in transactions.chunks(ENCODED_TRANSFER_LENGTH).enumerate() {
+        let mut hasher = Incremental::new();
+        hasher.update(tx);
+        let hash = hasher.finalize();
+
+        let (seen, rest) = scratch.split_at_mut(i * SCRATCH_PER_TRANSACTION);
+        rest[0..32].copy_from_slice(&hash);
+        if seen.chunks(SCRATCH_PER_TRANSACTION).any(|h| h == hash) {
+            continue;
+        }
+
+        let transfer = Transfer::from_ssz(array_ref![tx, 1, TRANSFER_LENGTH])?;
+        if transfer_checked(&mut mem, &transfer, slot).is_ok() {
+            fees = fees.checked_add(transfer.fee)?;
+        }
+    }
+
+    if fees != Amount::default() {
+        mem.add_value(proposer, fees)?;
+    }
+
+    proof_root(&mem)
+}
+
+/// Check that `tx` can be applied in the block at `slot` and apply it, leaving `db` unchanged
+/// if it can't be.
+fn transfer_checked<T: State>(db: &mut T, tx: &Transfer, slot: u64) -> Result<(), Error> {
+    if let Some(valid_until) = tx.valid_until {
+        if valid_until < slot {
+            return Err(Error::TransactionExpired);
+        }
+    }
+
+    if db.nonce(tx.from)? != tx.nonce {
+        return Err(Error::NonceInvalid);
+    }
+
+    let total = tx.amount.checked_add(tx.fee)?;
+    if db.value(tx.from)? < total {
+        return Err(Error::BalanceInsufficient);
+    }
+
+    db.value(tx.to)?.checked_add(tx.amount)?;
+    db.nonce(tx.from)?.next()?;
+
+    db.sub_value(tx.from, total)?;
+    db.add_value(tx.to, tx.amount)?;
+    db.inc_nonce(tx.from)?;
+
+    Ok(())
+}
+
+/// Returns an `Imp` over `data` once the lengths of its offsets and chunks have been checked.
+/// Its structure is checked by `proof_root`.
+fn new_fixed(data: &mut [u8], height: usize) -> Result<Imp<'_, U264>, Error> {
+    if data.len() < 8 {
+        return Err(Error::ProofTruncated);
+    }
+
+    let len = u64::from_le_bytes(*array_ref![data, 0, 8]) as usize;
+    let end = len.checked_mul(40).ok_or(Error::ProofTruncated)?;
+    if len == 0 || data.len() < end {
+        return Err(Error::ProofTruncated);
+    }
+
+    Ok(Imp::new(data, height))
+}
+
+/// Returns the root of `imp`, rejecting a malformed multi-proof with the same errors as
+/// `imp::validate`, or with `Error::OffsetsInvalid` if it is deeper than the tree.
+///
+/// Every subtree of a multi-proof is itself one, with its offsets and chunks contiguous, so the
+/// root is found by recursing into the two subtrees of each node. Unlike `Imp::root` the recursion
+/// is bounded by the height of the tree rather than the proof.
+pub fn proof_root(imp: &Imp<U264>) -> Result<H256, Error> {
+    subtree_root(imp.offsets, imp.db, imp.height)
+}
+
+fn subtree_root(offsets: &[u8], chunks: &[u8], depth: usize) -> Result<H256, Error> {
+    let len = chunks.len() / 32;
+    if offsets.len() != (len - 1) * 8 {
+        return Err(Error::OffsetsLengthInvalid);
+    }
+    if len == 1 {
+        return Ok(*array_ref![chunks, 0, 32]);
+    }
+
+    let left = u64::from_le_bytes(*array_ref![offsets, 0, 8]) as usize;
+    if depth == 0 || left == 0 || left >= len {
+        return Err(Error::OffsetsInvalid);
+    }
+
+    Ok(hash_pair(
+        &subtree_root(&offsets[8..left * 8], &chunks[..left * 32], depth - 1)?,
+        &subtree_root(&offsets[left * 8..], &chunks[left * 32..], depth - 1)?,
+    ))
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::block::BlockData;
+    use crate::config::Config;
+    use crate::process_data_blob;
+    use crate::state::imp::new_checked;
+    use crate::state::root::imp_root;
+
+    fn pre_root(blob: &[u8]) -> H256 {
+        let mut proof = BlockData::from_bytes(blob).unwrap().proof;
+        imp_root(&new_checked(&mut proof, Config::default().proof_height()).unwrap())
+    }
+
+    #[test]
+    fn matches_process_data_blob() {
+        let mut blob = composer::blob::generate(4, 6, 256).to_bytes();
+        let pre_root = pre_root(&blob);
+
+        let mut expected = blob.clone();
+        let expected = process_data_blob(&mut expected, &pre_root, &Config::default());
+
+        let mut scratch = vec![0u8; scratch_length(&blob)];
+        assert_eq!(
+            process_fixed(&mut blob, &mut scratch, &pre_root, 256),
+            Ok(expected)
+        );
+    }
+
+    #[test]
+    fn skips_duplicates() {
+        let blob = composer::blob::generate(2, 1, 256).to_bytes();
+        let pre_root = pre_root(&blob);
+
+        // Repeat the only transfer, which must leave the post-state root as it was.
+        let end = BLOB_HEADER_LENGTH + ENCODED_TRANSFER_LENGTH;
+        let mut repeated = blob[..end].to_vec();
+        repeated.extend_from_slice(&blob[BLOB_HEADER_LENGTH..end]);
+        repeated.extend_from_slice(&blob[end..]);
+        repeated[0..4].copy_from_slice(&2u32.to_le_bytes());
+
+        let mut once = blob.clone();
+        let mut scratch = [0u8; 64];
+        let expected = process_fixed(&mut once, &mut scratch, &pre_root, 256).unwrap();
+        assert_eq!(
+            process_fixed(&mut repeated, &mut scratch, &pre_root, 256),
+            Ok(expected)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_blobs() {
+        let blob = composer::blob::generate(2, 1, 256).to_bytes();
+        let pre_root = pre_root(&blob);
+        let mut scratch = [0u8; 32];
+
+        assert_eq!(
+            process_fixed(&mut blob.clone(), &mut [], &pre_root, 256),
+            Err(Error::ScratchInsufficient)
+        );
+        assert_eq!(
+            process_fixed(&mut blob.clone(), &mut scratch, &[0u8; 32], 256),
+            Err(Error::PreStateRootInvalid)
+        );
+        assert_eq!(
+            process_fixed(&mut blob[..100].to_vec(), &mut scratch, &pre_root, 256),
+            Err(Error::TransactionsLengthInvalid)
+        );
+
+        let mut batch = blob.clone();
+        batch[BLOB_HEADER_LENGTH] = TransactionKind::Batch as u8;
+        assert_eq!(
+            process_fixed(&mut batch, &mut scratch, &pre_root, 256),
+            Err(Error::TransactionKindInvalid)
+        );
+
+        // The first offset of the proof must split its tree into two non-empty subtrees.
+        let mut offsets = blob.clone();
+        let first = BLOB_HEADER_LENGTH + ENCODED_TRANSFER_LENGTH + 8;
+        offsets[first..first + 8].copy_from_slice(&0u64.to_le_bytes());
+        assert_eq!(
+            process_fixed(&mut offsets, &mut scratch, &pre_root, 256),
+            Err(Error::OffsetsInvalid)
+        );
+    }
+}
@@ -0,0 +1,52 @@
+//! Gas prices the work a transaction does, so EE designs can be compared by the resources their
+//! blocks consume rather than only by how many transactions they fit.
+//!
+//! A transaction costs a flat `base` plus `per_chunk` for every witness chunk its state
+//! operations read or write. Reading or writing a value or nonce is one chunk, a public key is two
+//! and creating or deleting an account is all four of its chunks.
+
+/// The prices that gas is charged at.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Schedule {
+    /// The gas every applied transaction costs.
+    pub base: u64,
+
+    /// The gas each witness chunk read or written by a transaction costs.
+    pub per_chunk: u64,
+}
+
+impl Schedule {
+    /// Returns the gas used by a transaction which accessed `chunks` witness chunks.
+    pub fn cost(&self, chunks: usize) -> u64 {
+        self.per_chunk
+            .saturating_mul(chunks as u64)
+            .saturating_add(self.base)
+    }
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Self {
+            base: 1_000,
+            per_chunk: 200,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cost() {
+        let schedule = Schedule::default();
+        assert_eq!(schedule.cost(0), 1_000);
+        assert_eq!(schedule.cost(6), 2_200);
+
+        let schedule = Schedule {
+            base: 1,
+            per_chunk: u64::MAX,
+        };
+        assert_eq!(schedule.cost(2), u64::MAX);
+    }
+}
@@ -0,0 +1,160 @@
+use crate::account::{calc_leaf_index, calc_nonce_index, calc_pubkey_index, calc_value_index};
+use crate::address::Address;
+use crate::u264::U264;
+
+/// The general index of a node in the state tree: the root is 1, and the children of a node at
+/// `i` are at `2i` and `2i + 1`.
+///
+/// Wraps a `U264` so walking the tree reads as what it does, rather than as shifts of the raw
+/// index. States and partials still store chunks by `U264`, so it converts both ways.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GeneralizedIndex(U264);
+
+impl GeneralizedIndex {
+    pub const fn new(index: U264) -> Self {
+        GeneralizedIndex(index)
+    }
+
+    pub fn root() -> Self {
+        GeneralizedIndex(U264::one())
+    }
+
+    pub fn get(self) -> U264 {
+        self.0
+    }
+
+    pub fn is_root(self) -> bool {
+        self.0 == U264::one()
+    }
+
+    /// Returns the node above this one, which the root doesn't have.
+    pub fn parent(self) -> Self {
+        debug_assert!(!self.is_root(), "root has no parent");
+        GeneralizedIndex(self.0 >> 1usize)
+    }
+
+    /// Returns the node `levels` above this one.
+    pub fn ancestor(self, levels: usize) -> Self {
+        debug_assert!(levels <= self.depth(), "ancestor is above the root");
+        GeneralizedIndex(self.0 >> levels)
+    }
+
+    /// Returns the other child of this node's parent.
+    pub fn sibling(self) -> Self {
+        debug_assert!(!self.is_root(), "root has no sibling");
+        GeneralizedIndex(self.0 ^ U264::one())
+    }
+
+    pub fn left_child(self) -> Self {
+        GeneralizedIndex(self.0 << 1usize)
+    }
+
+    pub fn right_child(self) -> Self {
+        GeneralizedIndex((self.0 << 1usize) + U264::one())
+    }
+
+    /// Returns the `n`th node of the `2**levels` nodes `levels` below this one.
+    pub fn descendant(self, levels: usize, n: u8) -> Self {
+        debug_assert!(
+            levels >= 8 || (n as usize) < 1 << levels,
+            "not a descendant"
+        );
+        GeneralizedIndex((self.0 << levels) + n.into())
+    }
+
+    pub fn is_left(self) -> bool {
+        !self.0.bit(0)
+    }
+
+    /// Returns the number of levels between this node and the root, which is at depth 0.
+    pub fn depth(self) -> usize {
+        debug_assert!(self.0 != U264::zero(), "0 is not a general index");
+        self.0.bits() - 1
+    }
+
+    /// Returns the root of the subtree of the account at `address`.
+    pub fn account(address: Address, height: usize) -> Self {
+        GeneralizedIndex(calc_leaf_index(address, height))
+    }
+
+    /// Returns the first chunk of the account's `pubkey`, see `calc_pubkey_index`.
+    pub fn pubkey_index(address: Address, height: usize) -> Self {
+        GeneralizedIndex(calc_pubkey_index(address, height))
+    }
+
+    /// Returns the account's `nonce`, padded to the depth of the pubkey chunks as `Imp`
+    /// expects, see `calc_nonce_index`.
+    pub fn nonce_index(address: Address, height: usize) -> Self {
+        GeneralizedIndex(calc_nonce_index(address, height))
+    }
+
+    /// Returns the account's `value`, padded to the depth of the pubkey chunks as `Imp`
+    /// expects, see `calc_value_index`.
+    pub fn value_index(address: Address, height: usize) -> Self {
+        GeneralizedIndex(calc_value_index(address, height))
+    }
+}
+
+impl From<U264> for GeneralizedIndex {
+    fn from(index: U264) -> Self {
+        GeneralizedIndex(index)
+    }
+}
+
+impl From<GeneralizedIndex> for U264 {
+    fn from(index: GeneralizedIndex) -> U264 {
+        index.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn g(n: u8) -> GeneralizedIndex {
+        GeneralizedIndex::new(n.into())
+    }
+
+    #[test]
+    fn navigation() {
+        assert_eq!(GeneralizedIndex::root(), g(1));
+        assert_eq!(g(1).left_child(), g(2));
+        assert_eq!(g(1).right_child(), g(3));
+        assert_eq!(g(11).parent(), g(5));
+        assert_eq!(g(11).ancestor(3), g(1));
+        assert_eq!(g(10).sibling(), g(11));
+        assert_eq!(g(11).sibling(), g(10));
+        assert_eq!(g(3).descendant(2, 1), g(13));
+        assert!(g(10).is_left() && !g(11).is_left());
+    }
+
+    #[test]
+    fn depth() {
+        assert_eq!(g(1).depth(), 0);
+        assert_eq!(g(3).depth(), 1);
+        assert_eq!(g(16).depth(), 4);
+        assert_eq!(g(31).depth(), 4);
+    }
+
+    #[test]
+    fn account_fields() {
+        // (first_leaf + account) = 16 + 5 = 21
+        let address = Address::from(5);
+        let account = GeneralizedIndex::account(address, 4);
+
+        assert_eq!(account, g(21));
+        assert_eq!(
+            GeneralizedIndex::pubkey_index(address, 4),
+            account.descendant(3, 0)
+        );
+        assert_eq!(
+            GeneralizedIndex::nonce_index(address, 4),
+            account.descendant(2, 1).left_child()
+        );
+        assert_eq!(
+            GeneralizedIndex::value_index(address, 4),
+            account.descendant(2, 2).left_child()
+        );
+        assert_eq!(GeneralizedIndex::value_index(address, 4).depth(), 4 + 3);
+    }
+}
@@ -0,0 +1,30 @@
+//! SHA-256 of a 64 byte buffer using the assembly compression function of `sha2-asm`.
+//!
+//! As with `ni`, the message is always one block followed by the same padding block. The assembly
+//! only builds for native targets, so it is used directly rather than through `sha2`'s `asm`
+//! feature, which would also be enabled for the wasm build.
+
+use sha2_asm::compress256;
+
+const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// The padding of a 64 byte message: a single set bit followed by the message length in bits.
+const PADDING: [u8; 64] = {
+    let mut ret = [0u8; 64];
+    ret[0] = 0x80;
+    ret[62] = 0x02;
+    ret
+};
+
+/// Hashes `buf` in the same way as `super::hash`, writing the digest to its first 32 bytes.
+pub fn hash(buf: &mut [u8; 64]) {
+    let mut state = IV;
+    compress256(&mut state, buf);
+    compress256(&mut state, &PADDING);
+
+    for (i, word) in state.iter().enumerate() {
+        buf[i * 4..i * 4 + 4].copy_from_slice(&word.to_be_bytes());
+    }
+}
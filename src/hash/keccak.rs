@@ -0,0 +1,187 @@
+//! A portable Keccak-256, as used by eth1 for addresses. It is the original Keccak submission
+//! rather than the NIST standardized SHA3-256, so its padding starts with `0x01` rather than
+//! `0x06`.
+
+use super::{Hasher, H256};
+
+/// Bytes absorbed into the state per permutation, i.e. `(1600 - 2 * 256) / 8`.
+const RATE: usize = 136;
+
+const ROUND_CONSTANTS: [u64; 24] = [
+    0x0000_0000_0000_0001,
+    0x0000_0000_0000_8082,
+    0x8000_0000_0000_808a,
+    0x8000_0000_8000_8000,
+    0x0000_0000_0000_808b,
+    0x0000_0000_8000_0001,
+    0x8000_0000_8000_8081,
+    0x8000_0000_0000_8009,
+    0x0000_0000_0000_008a,
+    0x0000_0000_0000_0088,
+    0x0000_0000_8000_8009,
+    0x0000_0000_8000_000a,
+    0x0000_0000_8000_808b,
+    0x8000_0000_0000_008b,
+    0x8000_0000_0000_8089,
+    0x8000_0000_0000_8003,
+    0x8000_0000_0000_8002,
+    0x8000_0000_0000_0080,
+    0x0000_0000_0000_800a,
+    0x8000_0000_8000_000a,
+    0x8000_0000_8000_8081,
+    0x8000_0000_0000_8080,
+    0x0000_0000_8000_0001,
+    0x8000_0000_8000_8008,
+];
+
+/// Rotation of each lane visited by the combined rho and pi steps, in the order of `PI`.
+const RHO: [u32; 24] = [
+    1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44,
+];
+
+const PI: [usize; 24] = [
+    10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1,
+];
+
+/// Returns the Keccak-256 hash of `data`.
+pub fn keccak256(data: &[u8]) -> H256 {
+    sponge(data, 0x01)
+}
+
+/// Keccak-256, for a state tree merkleized like eth1's rather than the beacon chain's. An empty
+/// account is the root of its subtree of zero chunks, so the zero hashes are calculated rather
+/// than taken from `ZERO_HASHES`.
+pub struct Keccak256;
+
+impl Hasher for Keccak256 {
+    fn hash(buf: &mut [u8; 64]) {
+        let digest = keccak256(&buf[..]);
+        buf[0..32].copy_from_slice(&digest);
+    }
+}
+
+/// Absorbs `data` padded with `delimiter` and squeezes 32 bytes. SHA3-256 only differs from
+/// Keccak-256 by its delimiter, which lets the permutation be checked against it.
+fn sponge(data: &[u8], delimiter: u8) -> H256 {
+    let mut state = [0u64; 25];
+
+    let mut blocks = data.chunks_exact(RATE);
+    for block in &mut blocks {
+        absorb(&mut state, block);
+    }
+
+    let rest = blocks.remainder();
+    let mut last = [0u8; RATE];
+    last[..rest.len()].copy_from_slice(rest);
+    last[rest.len()] ^= delimiter;
+    last[RATE - 1] ^= 0x80;
+    absorb(&mut state, &last);
+
+    let mut ret = [0u8; 32];
+    for (i, lane) in state[0..4].iter().enumerate() {
+        ret[i * 8..(i + 1) * 8].copy_from_slice(&lane.to_le_bytes());
+    }
+
+    ret
+}
+
+fn absorb(state: &mut [u64; 25], block: &[u8]) {
+    for (lane, bytes) in state.iter_mut().zip(block.chunks_exact(8)) {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(bytes);
+        *lane ^= u64::from_le_bytes(buf);
+    }
+
+    permute(state);
+}
+
+/// The Keccak-f[1600] permutation.
+fn permute(a: &mut [u64; 25]) {
+    for rc in ROUND_CONSTANTS.iter() {
+        // Theta
+        let mut c = [0u64; 5];
+        for x in 0..5 {
+            c[x] = a[x] ^ a[x + 5] ^ a[x + 10] ^ a[x + 15] ^ a[x + 20];
+        }
+        for x in 0..5 {
+            let d = c[(x + 4) % 5] ^ c[(x + 1) % 5].rotate_left(1);
+            for y in 0..5 {
+                a[y * 5 + x] ^= d;
+            }
+        }
+
+        // Rho and pi
+        let mut last = a[1];
+        for (pi, rho) in PI.iter().zip(RHO.iter()) {
+            let tmp = a[*pi];
+            a[*pi] = last.rotate_left(*rho);
+            last = tmp;
+        }
+
+        // Chi
+        for y in 0..5 {
+            let row = [
+                a[y * 5],
+                a[y * 5 + 1],
+                a[y * 5 + 2],
+                a[y * 5 + 3],
+                a[y * 5 + 4],
+            ];
+            for x in 0..5 {
+                a[y * 5 + x] = row[x] ^ (!row[(x + 1) % 5] & row[(x + 2) % 5]);
+            }
+        }
+
+        // Iota
+        a[0] ^= rc;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn hex(hash: H256) -> alloc::string::String {
+        hash.iter().map(|b| alloc::format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn known_hashes() {
+        assert_eq!(
+            hex(keccak256(b"")),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+        assert_eq!(
+            hex(keccak256(b"abc")),
+            "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"
+        );
+    }
+
+    #[test]
+    fn multiple_blocks() {
+        // Exactly one block, which needs a second block for the padding, and more than one.
+        let sha3 = |data: &[u8]| hex(sponge(data, 0x06));
+
+        assert_eq!(
+            sha3(&[0u8; RATE]),
+            "e772c9cf9eb9c991cdfcf125001b454fdbc0a95f188d1b4c844aa032ad6e075e"
+        );
+        assert_eq!(
+            sha3(&[0u8; RATE + 1]),
+            "9ed57188470a83b758cd71c00c6cc3beb984b36a6c35864b4e53017b24cf5699"
+        );
+        assert_eq!(
+            sha3(&b"abc".repeat(100)),
+            "26fb4fd7881f77c4c67694021204c5fcb0eb222e62aa6fe6e21e096ad58b6dc4"
+        );
+    }
+
+    #[test]
+    fn hasher() {
+        let mut buf = [7u8; 64];
+        Keccak256::hash(&mut buf);
+
+        assert_eq!(buf[0..32], keccak256(&[7u8; 64])[..]);
+        assert_eq!(buf[32..64], [7u8; 32]);
+    }
+}
@@ -0,0 +1,377 @@
+#[cfg(all(feature = "asm", not(target_arch = "wasm32")))]
+mod asm;
+#[cfg(feature = "eth1")]
+pub mod keccak;
+#[cfg(all(feature = "sha-ni", target_arch = "x86_64"))]
+mod ni;
+mod zero;
+
+pub use zero::ZERO_HASHES;
+
+use crate::bls::PublicKey;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use sha2::Digest;
+
+pub type H256 = [u8; 32];
+
+/// The hash function used to merkleize the state tree.
+///
+/// The tree, multi-proofs and roots only ever hash two concatenated nodes, so an implementation
+/// only needs to compress 64 bytes into 32. Code which calculates roots is generic over this
+/// trait and defaults to `Sha256`, so a protocol built on another hash function (e.g. blake2b or
+/// keccak256) can reuse it by implementing `Hasher`.
+pub trait Hasher {
+    /// Hashes the 64 bytes in `buf` and writes the digest to its first 32 bytes.
+    fn hash(buf: &mut [u8; 64]);
+
+    /// Hashes each buffer in `bufs` in the same way as `hash`.
+    ///
+    /// The buffers are independent, so an implementation can hash several at once (e.g. with
+    /// SIMD) or only pay its per-call overhead once per batch. By default each is hashed in turn.
+    fn hash_many(bufs: &mut [[u8; 64]]) {
+        for buf in bufs.iter_mut() {
+            Self::hash(buf);
+        }
+    }
+
+    /// Writes the root of an empty subtree whose accounts are `depth` levels below it to the first
+    /// 32 bytes of `buf`.
+    ///
+    /// By default, an empty account is the root of its subtree of zero chunks. `Sha256` overrides
+    /// this with the fixed hash of an empty account the state has always used.
+    fn zh(depth: usize, buf: &mut [u8; 64]) {
+        *buf = [0u8; 64];
+
+        for _ in 0..(depth + ACCOUNT_DEPTH) {
+            Self::hash(buf);
+            let (left, right) = buf.split_at_mut(32);
+            right.copy_from_slice(left);
+        }
+    }
+}
+
+/// The hash function the state tree is merkleized with, chosen when the tree is constructed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HashScheme {
+    /// SHA-256, see `Sha256`.
+    #[default]
+    Sha256,
+
+    /// Keccak-256, see `keccak::Keccak256`.
+    #[cfg(feature = "eth1")]
+    Keccak256,
+}
+
+/// Number of levels below the root of an account, matching `Account`'s `LeafSchema::DEPTH`.
+const ACCOUNT_DEPTH: usize = 3;
+
+/// SHA-256, which the state tree is merkleized with unless another `Hasher` is given.
+pub struct Sha256;
+
+impl Hasher for Sha256 {
+    fn hash(buf: &mut [u8; 64]) {
+        hash(buf);
+    }
+
+    fn hash_many(bufs: &mut [[u8; 64]]) {
+        hash_many(bufs);
+    }
+
+    fn zh(depth: usize, buf: &mut [u8; 64]) {
+        zh(depth, buf);
+    }
+}
+
+/// Hashes the 64 bytes in `buf` with SHA-256 and writes the digest to its first 32 bytes.
+///
+/// With the `sha-ni` feature, the x86 SHA extensions are used when the CPU supports them.
+pub fn hash(buf: &mut [u8; 64]) {
+    #[cfg(all(feature = "sha-ni", target_arch = "x86_64"))]
+    {
+        if ni::available() {
+            // Safe, since the CPU has been checked to support the instructions.
+            unsafe { ni::hash(buf) };
+            return;
+        }
+    }
+
+    portable(buf);
+}
+
+/// Hashes each buffer in `bufs` in the same way as `hash`, checking which implementation to use
+/// once for the whole batch.
+pub fn hash_many(bufs: &mut [[u8; 64]]) {
+    #[cfg(all(feature = "sha-ni", target_arch = "x86_64"))]
+    {
+        if ni::available() {
+            for buf in bufs.iter_mut() {
+                // Safe, since the CPU has been checked to support the instructions.
+                unsafe { ni::hash(buf) };
+            }
+            return;
+        }
+    }
+
+    for buf in bufs.iter_mut() {
+        portable(buf);
+    }
+}
+
+fn portable(buf: &mut [u8; 64]) {
+    #[cfg(all(feature = "asm", not(target_arch = "wasm32")))]
+    {
+        asm::hash(buf);
+    }
+
+    #[cfg(not(all(feature = "asm", not(target_arch = "wasm32"))))]
+    {
+        let mut tmp = [0u8; 32];
+        tmp.copy_from_slice(sha2::Sha256::digest(buf).as_ref());
+        buf[0..32].copy_from_slice(&tmp);
+    }
+}
+
+/// SHA-256 of input which doesn't fit in a single 64 byte buffer, fed in as any number of
+/// pieces.
+#[derive(Clone, Default)]
+pub struct Incremental(sha2::Sha256);
+
+impl Incremental {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `data` to the input.
+    pub fn update(&mut self, data: &[u8]) {
+        self.0.input(data);
+    }
+
+    /// Returns the digest of everything passed to `update`.
+    pub fn finalize(self) -> H256 {
+        let mut ret = [0u8; 32];
+        ret.copy_from_slice(self.0.result().as_ref());
+        ret
+    }
+}
+
+/// Returns the root of the subtree holding `pubkey` in an account, as it is laid out by
+/// `Account::PUBKEY`: the 48 byte key is split into two chunks with the second padded with zeros.
+pub fn hash_pubkey(pubkey: &PublicKey) -> H256 {
+    let mut hasher = Incremental::new();
+    hasher.update(&pubkey.as_bytes());
+    hasher.update(&[0u8; 16]);
+    hasher.finalize()
+}
+
+/// Returns the hash of `left` followed by `right`, their parent in a merkle tree.
+pub fn hash_pair(left: &H256, right: &H256) -> H256 {
+    let mut buf = [0u8; 64];
+    buf[0..32].copy_from_slice(left);
+    buf[32..64].copy_from_slice(right);
+    hash(&mut buf);
+
+    let mut ret = [0u8; 32];
+    ret.copy_from_slice(&buf[0..32]);
+    ret
+}
+
+/// Returns the root `branch` leads to from `leaf` at `index`, where `branch[i]` is the sibling
+/// `i` levels above the leaf, like the beacon chain's `is_valid_merkle_branch`.
+pub fn branch_root(leaf: H256, index: u64, branch: &[H256]) -> H256 {
+    branch.iter().enumerate().fold(leaf, |value, (i, sibling)| {
+        if (index >> i) & 1 == 1 {
+            hash_pair(sibling, &value)
+        } else {
+            hash_pair(&value, sibling)
+        }
+    })
+}
+
+/// Returns the root of an SSZ list of `leaves` with a limit of `2**depth`, which is the root of
+/// the tree of `depth` levels holding them with their number mixed in.
+///
+/// Panics if there are more than `2**depth` leaves.
+#[cfg(feature = "alloc")]
+pub fn list_root(leaves: &[H256], depth: usize) -> H256 {
+    assert!(depth >= 64 || (leaves.len() as u64) <= 1 << depth);
+
+    let mut layer: Vec<H256> = leaves.to_vec();
+    let mut zero = [0u8; 32];
+
+    for _ in 0..depth {
+        if layer.len() % 2 == 1 {
+            layer.push(zero);
+        }
+
+        layer = layer
+            .chunks_exact(2)
+            .map(|pair| hash_pair(&pair[0], &pair[1]))
+            .collect();
+        zero = hash_pair(&zero, &zero);
+    }
+
+    let mut length = [0u8; 32];
+    length[0..8].copy_from_slice(&(leaves.len() as u64).to_le_bytes());
+    hash_pair(layer.first().unwrap_or(&zero), &length)
+}
+
+/// Returns the proof of `leaves[index]` against their `list_root`: its sibling at each of the
+/// `depth` levels of the tree, then the mixed in length. `branch_root` of the leaf and proof is
+/// the list's root.
+///
+/// Panics if `index` is out of bounds.
+#[cfg(feature = "alloc")]
+pub fn list_proof(leaves: &[H256], depth: usize, index: usize) -> Vec<H256> {
+    assert!(index < leaves.len());
+
+    let mut proof = Vec::with_capacity(depth + 1);
+    let mut layer: Vec<H256> = leaves.to_vec();
+    let mut zero = [0u8; 32];
+    let mut position = index;
+
+    for _ in 0..depth {
+        if layer.len() % 2 == 1 {
+            layer.push(zero);
+        }
+
+        proof.push(layer[position ^ 1]);
+        position >>= 1;
+
+        layer = layer
+            .chunks_exact(2)
+            .map(|pair| hash_pair(&pair[0], &pair[1]))
+            .collect();
+        zero = hash_pair(&zero, &zero);
+    }
+
+    let mut length = [0u8; 32];
+    length[0..8].copy_from_slice(&(leaves.len() as u64).to_le_bytes());
+    proof.push(length);
+    proof
+}
+
+/// Writes `ZERO_HASHES[depth]` to the first 32 bytes of `buf`, followed by the hash one level
+/// below it.
+///
+/// Panics if `depth` is greater than `MAX_HEIGHT`.
+pub fn zh(depth: usize, buf: &mut [u8; 64]) {
+    buf[0..32].copy_from_slice(&ZERO_HASHES[depth]);
+    buf[32..64].copy_from_slice(&ZERO_HASHES[depth.saturating_sub(1)]);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::account::MAX_HEIGHT;
+
+    /// Hash of an account with a balance of zero, which every entry of `ZERO_HASHES` is built
+    /// from.
+    const EMPTY_ACCOUNT: H256 = [
+        218, 109, 128, 123, 247, 149, 16, 97, 70, 229, 130, 39, 117, 217, 20, 176, 39, 122, 101,
+        36, 15, 101, 14, 212, 200, 167, 202, 119, 130, 78, 90, 223,
+    ];
+
+    /// A hasher which only XORs the two nodes together.
+    struct Xor;
+
+    impl Hasher for Xor {
+        fn hash(buf: &mut [u8; 64]) {
+            for i in 0..32 {
+                buf[i] ^= buf[i + 32];
+            }
+        }
+    }
+
+    #[test]
+    fn zero_hashes() {
+        assert_eq!(ZERO_HASHES[0], EMPTY_ACCOUNT);
+
+        let mut buf = [0u8; 64];
+        buf[0..32].copy_from_slice(&EMPTY_ACCOUNT);
+        buf[32..64].copy_from_slice(&EMPTY_ACCOUNT);
+
+        for expected in ZERO_HASHES.iter().skip(1) {
+            let child = buf[0..32].to_vec();
+            buf[32..64].copy_from_slice(&child);
+            hash(&mut buf);
+
+            assert_eq!(buf[0..32], expected[..]);
+        }
+        assert_eq!(ZERO_HASHES.len(), MAX_HEIGHT + 1);
+    }
+
+    #[test]
+    fn hash_many_matches_hash() {
+        let mut bufs: Vec<[u8; 64]> = (0..16u8).map(|n| [n; 64]).collect();
+        let expected: Vec<[u8; 64]> = bufs
+            .iter()
+            .map(|buf| {
+                let mut buf = *buf;
+                hash(&mut buf);
+                buf
+            })
+            .collect();
+
+        hash_many(&mut bufs);
+        assert_eq!(bufs, expected);
+
+        let mut bufs = [[1u8; 64], [2u8; 64]];
+        Xor::hash_many(&mut bufs);
+        assert_eq!(bufs[0][0..32], [0u8; 32]);
+        assert_eq!(bufs[1][0..32], [0u8; 32]);
+    }
+
+    #[test]
+    fn incremental() {
+        let data: Vec<u8> = (0..200u8).collect();
+
+        for split in [0, 1, 63, 64, 65, 200].iter() {
+            let mut hasher = Incremental::new();
+            hasher.update(&data[..*split]);
+            hasher.update(&data[*split..]);
+
+            assert_eq!(
+                hasher.finalize()[..],
+                sha2::Sha256::digest(&data).as_ref()[..]
+            );
+        }
+    }
+
+    #[test]
+    fn pubkey_root() {
+        let pubkey = PublicKey::new([7u8; 48]);
+
+        let mut buf = [0u8; 64];
+        buf[0..48].copy_from_slice(&pubkey.as_bytes());
+        hash(&mut buf);
+
+        assert_eq!(hash_pubkey(&pubkey)[..], buf[0..32]);
+    }
+
+    #[test]
+    fn custom_zero_hashes() {
+        let mut buf = [1u8; 64];
+        Xor::zh(2, &mut buf);
+        assert_eq!(buf, [0u8; 64]);
+    }
+
+    #[test]
+    fn list_proofs() {
+        let leaves: Vec<H256> = (1..=5u8).map(|i| [i; 32]).collect();
+        let root = list_root(&leaves, 4);
+
+        for (i, leaf) in leaves.iter().enumerate() {
+            let proof = list_proof(&leaves, 4, i);
+            assert_eq!(proof.len(), 5);
+            assert_eq!(branch_root(*leaf, i as u64, &proof), root);
+        }
+
+        // An empty list is the zero tree with a length of zero.
+        let mut zero = [0u8; 32];
+        for _ in 0..4 {
+            zero = hash_pair(&zero, &zero);
+        }
+        assert_eq!(list_root(&[], 4), hash_pair(&zero, &[0u8; 32]));
+    }
+}
@@ -0,0 +1,164 @@
+//! SHA-256 of a 64 byte buffer using the x86 SHA extensions (SHA-NI).
+//!
+//! Merkleization only ever hashes two concatenated nodes, so the message is always exactly one
+//! block followed by the same padding block and the schedule of the padding block never changes.
+//! This skips the buffering and length bookkeeping of `sha2`, which doesn't use the extensions.
+
+use core::arch::x86_64::*;
+
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// The padding of a 64 byte message: a single set bit followed by the message length in bits.
+const PADDING: [u8; 64] = {
+    let mut ret = [0u8; 64];
+    ret[0] = 0x80;
+    ret[62] = 0x02;
+    ret
+};
+
+/// Returns whether the CPU supports the instructions used by `hash`.
+pub fn available() -> bool {
+    is_x86_feature_detected!("sha")
+        && is_x86_feature_detected!("sse2")
+        && is_x86_feature_detected!("ssse3")
+        && is_x86_feature_detected!("sse4.1")
+}
+
+/// Hashes `buf` in the same way as `super::hash`, writing the digest to its first 32 bytes.
+///
+/// # Safety
+///
+/// The CPU must support the instructions checked by `available`.
+#[target_feature(enable = "sha,sse2,ssse3,sse4.1")]
+pub unsafe fn hash(buf: &mut [u8; 64]) {
+    let mut state = IV;
+    compress(&mut state, buf);
+    compress(&mut state, &PADDING);
+
+    for (i, word) in state.iter().enumerate() {
+        buf[i * 4..i * 4 + 4].copy_from_slice(&word.to_be_bytes());
+    }
+}
+
+#[target_feature(enable = "sha,sse2,ssse3,sse4.1")]
+unsafe fn schedule(w0: __m128i, w1: __m128i, w2: __m128i, w3: __m128i) -> __m128i {
+    let t1 = _mm_sha256msg1_epu32(w0, w1);
+    let t2 = _mm_alignr_epi8(w3, w2, 4);
+    _mm_sha256msg2_epu32(_mm_add_epi32(t1, t2), w3)
+}
+
+/// Runs four rounds, two at a time, with the message words `w` scheduled for rounds `4 * i..`.
+macro_rules! rounds4 {
+    ($abef:ident, $cdgh:ident, $w:expr, $i:expr) => {{
+        let k = _mm_set_epi32(
+            K[4 * $i + 3] as i32,
+            K[4 * $i + 2] as i32,
+            K[4 * $i + 1] as i32,
+            K[4 * $i] as i32,
+        );
+        let t = _mm_add_epi32($w, k);
+        $cdgh = _mm_sha256rnds2_epu32($cdgh, $abef, t);
+        $abef = _mm_sha256rnds2_epu32($abef, $cdgh, _mm_shuffle_epi32(t, 0x0e));
+    }};
+}
+
+/// Schedules the next four message words into `w4` and runs the rounds which use them.
+macro_rules! schedule_rounds4 {
+    ($abef:ident, $cdgh:ident, $w0:expr, $w1:expr, $w2:expr, $w3:expr, $w4:expr, $i:expr) => {{
+        $w4 = schedule($w0, $w1, $w2, $w3);
+        rounds4!($abef, $cdgh, $w4, $i);
+    }};
+}
+
+/// Applies the SHA-256 compression function for `block` to `state`.
+#[target_feature(enable = "sha,sse2,ssse3,sse4.1")]
+unsafe fn compress(state: &mut [u32; 8], block: &[u8; 64]) {
+    // Reverses the bytes of each word, since the message is big endian.
+    let mask = _mm_set_epi64x(
+        0x0c0d_0e0f_0809_0a0bu64 as i64,
+        0x0405_0607_0001_0203u64 as i64,
+    );
+
+    // The instructions expect the state as `abef` and `cdgh` rather than `abcd` and `efgh`.
+    let dcba = _mm_loadu_si128(state.as_ptr() as *const __m128i);
+    let efgh = _mm_loadu_si128(state.as_ptr().add(4) as *const __m128i);
+    let cdab = _mm_shuffle_epi32(dcba, 0xb1);
+    let efgh = _mm_shuffle_epi32(efgh, 0x1b);
+    let mut abef = _mm_alignr_epi8(cdab, efgh, 8);
+    let mut cdgh = _mm_blend_epi16(efgh, cdab, 0xf0);
+
+    let (abef_save, cdgh_save) = (abef, cdgh);
+
+    let data = block.as_ptr() as *const __m128i;
+    let mut w0 = _mm_shuffle_epi8(_mm_loadu_si128(data), mask);
+    let mut w1 = _mm_shuffle_epi8(_mm_loadu_si128(data.add(1)), mask);
+    let mut w2 = _mm_shuffle_epi8(_mm_loadu_si128(data.add(2)), mask);
+    let mut w3 = _mm_shuffle_epi8(_mm_loadu_si128(data.add(3)), mask);
+    let mut w4;
+
+    rounds4!(abef, cdgh, w0, 0);
+    rounds4!(abef, cdgh, w1, 1);
+    rounds4!(abef, cdgh, w2, 2);
+    rounds4!(abef, cdgh, w3, 3);
+    schedule_rounds4!(abef, cdgh, w0, w1, w2, w3, w4, 4);
+    schedule_rounds4!(abef, cdgh, w1, w2, w3, w4, w0, 5);
+    schedule_rounds4!(abef, cdgh, w2, w3, w4, w0, w1, 6);
+    schedule_rounds4!(abef, cdgh, w3, w4, w0, w1, w2, 7);
+    schedule_rounds4!(abef, cdgh, w4, w0, w1, w2, w3, 8);
+    schedule_rounds4!(abef, cdgh, w0, w1, w2, w3, w4, 9);
+    schedule_rounds4!(abef, cdgh, w1, w2, w3, w4, w0, 10);
+    schedule_rounds4!(abef, cdgh, w2, w3, w4, w0, w1, 11);
+    schedule_rounds4!(abef, cdgh, w3, w4, w0, w1, w2, 12);
+    schedule_rounds4!(abef, cdgh, w4, w0, w1, w2, w3, 13);
+    schedule_rounds4!(abef, cdgh, w0, w1, w2, w3, w4, 14);
+    schedule_rounds4!(abef, cdgh, w1, w2, w3, w4, w0, 15);
+
+    abef = _mm_add_epi32(abef, abef_save);
+    cdgh = _mm_add_epi32(cdgh, cdgh_save);
+
+    let feba = _mm_shuffle_epi32(abef, 0x1b);
+    let dchg = _mm_shuffle_epi32(cdgh, 0xb1);
+    let dcba = _mm_blend_epi16(feba, dchg, 0xf0);
+    let hgef = _mm_alignr_epi8(dchg, feba, 8);
+
+    _mm_storeu_si128(state.as_mut_ptr() as *mut __m128i, dcba);
+    _mm_storeu_si128(state.as_mut_ptr().add(4) as *mut __m128i, hgef);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn matches_portable_hash() {
+        if !available() {
+            return;
+        }
+
+        let mut buf = [0u8; 64];
+        for n in 0..=255u8 {
+            for (i, byte) in buf.iter_mut().enumerate() {
+                *byte = n.wrapping_mul(31).wrapping_add(i as u8);
+            }
+
+            let mut expected = buf;
+            super::super::portable(&mut expected);
+
+            unsafe { hash(&mut buf) };
+            assert_eq!(&buf[0..32], &expected[0..32]);
+        }
+    }
+}
@@ -0,0 +1,269 @@
+//! Generated by hashing up from the hash of an empty account. `hash::test::zero_hashes` checks
+//! every entry.
+
+use super::H256;
+use crate::account::MAX_HEIGHT;
+
+/// The root of an empty subtree whose accounts are `depth` levels below it, for every depth of
+/// the state tree, so proof builders and verifiers can index into it instead of hashing up from
+/// an empty account each time they need a zero hash.
+#[rustfmt::skip]
+pub const ZERO_HASHES: [H256; MAX_HEIGHT + 1] = [
+    [0xda, 0x6d, 0x80, 0x7b, 0xf7, 0x95, 0x10, 0x61, 0x46, 0xe5, 0x82, 0x27, 0x75, 0xd9, 0x14, 0xb0, 0x27, 0x7a, 0x65, 0x24, 0x0f, 0x65, 0x0e, 0xd4, 0xc8, 0xa7, 0xca, 0x77, 0x82, 0x4e, 0x5a, 0xdf],
+    [0xc3, 0x56, 0xa9, 0x6e, 0xb1, 0x79, 0x0f, 0x3a, 0x85, 0x62, 0xc7, 0x0d, 0x6f, 0x90, 0xae, 0x54, 0x84, 0xec, 0x19, 0x55, 0xfb, 0xdc, 0xe4, 0x65, 0x4b, 0x6f, 0x2a, 0xb1, 0x92, 0xba, 0xcb, 0x99],
+    [0x7b, 0x7a, 0x40, 0xea, 0x65, 0xe1, 0x5b, 0x5d, 0x67, 0x22, 0xd8, 0x8b, 0x7d, 0x5e, 0xbc, 0x81, 0x98, 0x74, 0x7b, 0xaf, 0xe7, 0x98, 0x0e, 0x6c, 0x1a, 0xee, 0xf8, 0xff, 0xdf, 0x0d, 0x41, 0xcd],
+    [0x91, 0xf3, 0x99, 0xa4, 0x0f, 0xd6, 0x75, 0x17, 0x60, 0x39, 0x88, 0x39, 0x47, 0x51, 0xe9, 0xc0, 0x39, 0xae, 0x8d, 0x4f, 0x7f, 0x89, 0x80, 0x06, 0x26, 0x97, 0x3a, 0xa1, 0xb8, 0xa6, 0xf5, 0x6e],
+    [0x73, 0xb0, 0x7b, 0xc8, 0x9d, 0xa2, 0xd6, 0x69, 0xa8, 0xff, 0xa1, 0x85, 0x8c, 0x77, 0xa9, 0xaa, 0x9d, 0x33, 0xe7, 0xa5, 0x15, 0xdd, 0xc1, 0x34, 0xff, 0x67, 0xb3, 0x77, 0x3c, 0x63, 0x1a, 0xbb],
+    [0x18, 0x97, 0x96, 0x73, 0x56, 0x54, 0xf4, 0x82, 0x42, 0x3c, 0xb7, 0xe2, 0xd3, 0x47, 0x64, 0xdf, 0x05, 0x8b, 0xff, 0xcb, 0xe2, 0x66, 0x23, 0x99, 0x45, 0xff, 0x6a, 0xfc, 0x9d, 0xfc, 0x4b, 0x34],
+    [0x57, 0xdb, 0x4c, 0x8b, 0xc9, 0xf7, 0x1a, 0xd0, 0x83, 0xa7, 0x39, 0xb2, 0xb5, 0x93, 0x15, 0x68, 0x4e, 0xe2, 0x71, 0x13, 0xdf, 0x1a, 0xa9, 0xa5, 0xd0, 0xec, 0xa4, 0xbf, 0x4e, 0xa6, 0x09, 0x77],
+    [0x9b, 0x3e, 0xe7, 0x97, 0x3b, 0x4a, 0x47, 0x97, 0xc1, 0x0d, 0x9c, 0x53, 0xab, 0xa4, 0x2e, 0x6e, 0x00, 0x65, 0xaa, 0xa4, 0xb2, 0xe2, 0x40, 0xb8, 0x10, 0xa3, 0x77, 0xd2, 0xf8, 0xd6, 0xdd, 0x0c],
+    [0x5a, 0x54, 0x97, 0x37, 0x5e, 0xd2, 0x07, 0xcd, 0xb9, 0x63, 0x72, 0x45, 0x9c, 0x2f, 0x35, 0xdd, 0x09, 0x49, 0xe8, 0x91, 0xca, 0xaf, 0xc2, 0x14, 0x84, 0x02, 0xc9, 0x64, 0x56, 0xb9, 0xbc, 0x87],
+    [0x51, 0x85, 0x71, 0x0f, 0xe4, 0xc2, 0x64, 0x33, 0x5f, 0x0f, 0x20, 0x46, 0x15, 0xc4, 0xde, 0x45, 0xeb, 0x75, 0xc4, 0xe0, 0xbd, 0xcb, 0xe8, 0xcc, 0x9a, 0xee, 0x3a, 0x1b, 0x0a, 0x1f, 0xf6, 0x45],
+    [0x2e, 0x5b, 0xd3, 0xf6, 0x61, 0x62, 0x85, 0x7c, 0x77, 0x99, 0xab, 0x47, 0xa9, 0xb1, 0x56, 0x7b, 0x04, 0x1f, 0x35, 0x6c, 0xcf, 0xe8, 0x81, 0x3a, 0x2f, 0x9e, 0x6b, 0xb7, 0x84, 0xca, 0xfc, 0x56],
+    [0x4a, 0xe8, 0x29, 0xaa, 0x3b, 0x14, 0x7f, 0x64, 0x9c, 0x6f, 0xa7, 0xdf, 0x6d, 0x0f, 0x7e, 0x09, 0x9d, 0x88, 0x21, 0x3f, 0x62, 0x4a, 0xc5, 0x7e, 0x78, 0xbc, 0x6b, 0xbd, 0x66, 0x22, 0xcb, 0x99],
+    [0x39, 0x96, 0xf8, 0x52, 0xc6, 0x4c, 0x43, 0x7b, 0xf9, 0xd4, 0xb1, 0xc5, 0x5a, 0x98, 0x6b, 0xc3, 0x24, 0x4c, 0x6e, 0xd9, 0x23, 0x70, 0xdb, 0xd0, 0x4b, 0xba, 0xef, 0x76, 0xbd, 0xd9, 0x26, 0x3d],
+    [0x58, 0xd2, 0xed, 0x2d, 0x0a, 0x6f, 0xab, 0xf1, 0x15, 0x7e, 0x90, 0x3b, 0x02, 0x82, 0x97, 0x9c, 0xb7, 0x47, 0x71, 0x2f, 0x1e, 0x31, 0xc3, 0x10, 0xd7, 0x7d, 0xa8, 0xfb, 0xf6, 0x55, 0x86, 0x22],
+    [0x18, 0x2c, 0x19, 0xf8, 0x5e, 0x6c, 0x50, 0xc5, 0xdd, 0x6e, 0xcd, 0x56, 0x81, 0x75, 0x4d, 0x38, 0x38, 0xf5, 0x47, 0x64, 0x88, 0x87, 0x3c, 0x99, 0x46, 0x9c, 0x2c, 0x82, 0xb2, 0xb2, 0x51, 0xa1],
+    [0x67, 0x10, 0x4b, 0x98, 0x4a, 0x14, 0xe9, 0x6c, 0x9d, 0x01, 0x6d, 0xf0, 0x9f, 0x7c, 0x09, 0x6a, 0x28, 0x0b, 0x11, 0x95, 0x3d, 0xba, 0xfe, 0x88, 0xbc, 0x29, 0x05, 0x0e, 0xba, 0xe1, 0xbf, 0xf5],
+    [0xca, 0x40, 0xc0, 0xaf, 0x7a, 0x15, 0xaa, 0xbc, 0x30, 0x76, 0x5c, 0xa3, 0x50, 0xdc, 0x85, 0xd6, 0xe1, 0xe9, 0x17, 0xb9, 0xf5, 0xac, 0xec, 0xf9, 0x0f, 0x2f, 0x4b, 0x4f, 0xf9, 0x0e, 0x9e, 0x1e],
+    [0xa9, 0x98, 0x96, 0xcb, 0x8c, 0xb1, 0x04, 0x2c, 0xea, 0x3b, 0x59, 0x55, 0x3d, 0xf6, 0x28, 0x03, 0x25, 0x1b, 0xea, 0x25, 0x58, 0xcf, 0xe2, 0x85, 0x8d, 0xcc, 0xd9, 0x8d, 0x0d, 0x34, 0x1c, 0x92],
+    [0xa7, 0xaa, 0xa1, 0xa6, 0xaa, 0xc7, 0x4c, 0x48, 0x7f, 0xd3, 0xb7, 0x52, 0xf1, 0xe8, 0x1d, 0xb2, 0xb0, 0xe7, 0x6c, 0xe9, 0x4b, 0x54, 0xa1, 0xc1, 0x9d, 0xec, 0x16, 0xf6, 0xde, 0x11, 0x14, 0xe5],
+    [0x9f, 0xaa, 0x85, 0x58, 0x73, 0xaf, 0x01, 0x59, 0x11, 0x3b, 0x9f, 0x71, 0xdd, 0x07, 0x5a, 0x8a, 0xa2, 0x22, 0x64, 0x7c, 0x92, 0x85, 0x8f, 0x34, 0x73, 0xaa, 0x25, 0xee, 0x3f, 0x1f, 0xfa, 0x8d],
+    [0xfa, 0xa4, 0xa2, 0x36, 0x5d, 0x28, 0x22, 0xf5, 0x4a, 0xad, 0xb3, 0x13, 0x26, 0x87, 0xd9, 0xbd, 0x3d, 0x88, 0x8e, 0xbf, 0xc5, 0x8b, 0x46, 0xcf, 0x6f, 0xcf, 0x85, 0xe7, 0xa3, 0x4f, 0xbe, 0x28],
+    [0xe7, 0x4c, 0x9a, 0xc7, 0x35, 0x60, 0xde, 0xfd, 0x87, 0xd1, 0xab, 0xaf, 0x6b, 0x3f, 0x0e, 0x48, 0xa9, 0x7f, 0xbf, 0xb7, 0x54, 0xaf, 0xec, 0x57, 0xdf, 0xe3, 0xda, 0x99, 0xd1, 0x71, 0xdd, 0xee],
+    [0x82, 0x16, 0x77, 0x71, 0x9b, 0xfb, 0x62, 0x37, 0x76, 0xf4, 0xd6, 0x6b, 0x75, 0x18, 0xdc, 0xb8, 0xb6, 0xea, 0x68, 0x82, 0x36, 0x0a, 0x3c, 0x3a, 0xf8, 0x35, 0x7f, 0xe9, 0x0f, 0x8e, 0xad, 0x69],
+    [0xf1, 0x24, 0x13, 0x5b, 0xf2, 0xb9, 0xfa, 0x3c, 0xcf, 0x6a, 0x5e, 0xb4, 0xcd, 0x12, 0xe7, 0xed, 0x64, 0xcc, 0xc3, 0x14, 0xf9, 0x7c, 0x96, 0x4c, 0x2d, 0xa0, 0x84, 0xe7, 0x79, 0x46, 0xde, 0x9b],
+    [0xe7, 0x22, 0x9c, 0x96, 0x5b, 0x09, 0x2f, 0x63, 0x26, 0x6c, 0xe1, 0xc7, 0xc2, 0x79, 0x54, 0xb9, 0x11, 0xf2, 0x5e, 0x03, 0x8f, 0x55, 0xda, 0xc5, 0xe2, 0x8e, 0x49, 0x80, 0x67, 0x16, 0x35, 0xf2],
+    [0x72, 0x5c, 0xe5, 0xe9, 0x8e, 0x67, 0x3a, 0xc7, 0xdf, 0x20, 0xc0, 0x17, 0x96, 0x86, 0xa0, 0xf7, 0x72, 0x9e, 0xec, 0xf5, 0x8e, 0xe3, 0x63, 0x9f, 0x58, 0x63, 0x5c, 0xb3, 0xf0, 0xcd, 0x3e, 0x58],
+    [0x8c, 0x6d, 0x18, 0xb3, 0x6a, 0xef, 0xfd, 0x46, 0x51, 0xfe, 0x88, 0x5e, 0x08, 0x7d, 0x25, 0x16, 0xe8, 0x30, 0x1d, 0x57, 0xd1, 0x93, 0xcb, 0x39, 0xf7, 0xee, 0xec, 0x6a, 0x31, 0x86, 0xd7, 0x90],
+    [0x36, 0xfa, 0x72, 0xc5, 0xea, 0xa3, 0xd7, 0x35, 0xe5, 0x5a, 0x44, 0x78, 0x51, 0x91, 0xe0, 0x29, 0x4f, 0xa5, 0xb3, 0x2b, 0x6e, 0x4b, 0xcb, 0x94, 0xc5, 0xef, 0x53, 0x94, 0xf7, 0x38, 0x1d, 0x91],
+    [0xfd, 0x9c, 0x02, 0x51, 0xdf, 0xa6, 0xc7, 0xd4, 0x30, 0x9e, 0xa6, 0xc4, 0x32, 0x78, 0xff, 0x87, 0x43, 0x84, 0xd2, 0x8f, 0x32, 0xac, 0xa8, 0xa1, 0x46, 0x40, 0xd4, 0xa3, 0x7a, 0x7c, 0x4b, 0x2a],
+    [0xb1, 0x50, 0x12, 0x9f, 0xf8, 0xdc, 0x2d, 0x55, 0xad, 0x9f, 0x83, 0x17, 0x37, 0x7b, 0x88, 0x2e, 0x0a, 0xb3, 0x5b, 0x5c, 0x2a, 0x78, 0x28, 0xad, 0x2d, 0xd7, 0xa9, 0x34, 0x45, 0x63, 0x1a, 0xfb],
+    [0xb2, 0xa5, 0xe6, 0x9f, 0x2f, 0xc3, 0xec, 0x31, 0x43, 0x42, 0xba, 0x37, 0xb1, 0x44, 0xf7, 0xaf, 0x86, 0xf7, 0x9d, 0xb1, 0x20, 0x04, 0xda, 0x8e, 0x4f, 0xf7, 0x4f, 0xe2, 0x09, 0xce, 0xc1, 0x01],
+    [0xda, 0x27, 0x9b, 0x09, 0x3d, 0x88, 0x8f, 0x08, 0xca, 0x49, 0x2b, 0xec, 0x09, 0x24, 0xbc, 0x1b, 0x71, 0x9c, 0xc5, 0xdc, 0x03, 0x18, 0x7b, 0x84, 0xe0, 0x52, 0xf7, 0xea, 0x25, 0x75, 0x77, 0x20],
+    [0xdc, 0x4b, 0x72, 0x53, 0xa1, 0xec, 0xf6, 0x55, 0xee, 0x5e, 0xf8, 0x43, 0x47, 0x3e, 0x11, 0x7d, 0xe5, 0xbb, 0xf8, 0xfd, 0x31, 0xad, 0x87, 0xc9, 0xe1, 0x7f, 0x90, 0xf7, 0x31, 0x9c, 0x95, 0xd3],
+    [0x26, 0xd4, 0x0f, 0x6d, 0xf9, 0x77, 0xe5, 0x94, 0x90, 0xe8, 0x08, 0x35, 0x52, 0x12, 0x4f, 0xac, 0x9d, 0x3a, 0x80, 0xe8, 0x93, 0xf6, 0x64, 0x31, 0xbe, 0x7c, 0xa9, 0x05, 0x2e, 0x05, 0xca, 0x44],
+    [0x96, 0xa2, 0xdc, 0x0d, 0x4d, 0x64, 0xd7, 0x20, 0x26, 0xe5, 0xc4, 0x75, 0x24, 0x81, 0x81, 0x63, 0x5e, 0xb2, 0x30, 0x48, 0x10, 0xd4, 0xca, 0x3f, 0xf9, 0x05, 0xa7, 0x5b, 0xdb, 0xa7, 0x4e, 0xb3],
+    [0x0e, 0x69, 0x90, 0x9a, 0x9a, 0xf8, 0x49, 0x20, 0x96, 0x0e, 0x79, 0x79, 0x64, 0x5d, 0xee, 0x91, 0xca, 0x8b, 0x9e, 0x13, 0xb5, 0x46, 0xd0, 0x82, 0xee, 0x11, 0x3e, 0x06, 0xa3, 0xf6, 0x6e, 0x84],
+    [0xb2, 0x94, 0xc2, 0xdb, 0x16, 0xb3, 0xa3, 0xea, 0x86, 0xad, 0xcd, 0xdf, 0xa7, 0xa4, 0xf2, 0xbc, 0x35, 0x21, 0x17, 0x7c, 0x4a, 0x4e, 0xa8, 0x0d, 0x63, 0x25, 0x79, 0x53, 0xac, 0xf1, 0x51, 0x34],
+    [0x79, 0xea, 0x0e, 0xed, 0xa3, 0x68, 0xec, 0xe8, 0x93, 0xf4, 0xf9, 0xcc, 0xb9, 0x61, 0x46, 0xb4, 0x0e, 0x54, 0x46, 0x23, 0x19, 0xaa, 0xa3, 0x7d, 0x17, 0x4b, 0xe7, 0x3b, 0xbb, 0x9e, 0xc9, 0x8f],
+    [0xe5, 0x83, 0x40, 0x9b, 0x18, 0xca, 0x4a, 0x44, 0xb6, 0x68, 0xd0, 0xe3, 0x20, 0x1a, 0x29, 0x23, 0x11, 0xc1, 0xba, 0xa2, 0x00, 0x7b, 0xa8, 0xa1, 0x8a, 0x90, 0xa6, 0x16, 0xe7, 0xb0, 0x4c, 0x4e],
+    [0x17, 0x62, 0xa7, 0x3f, 0x5e, 0x7d, 0x0e, 0x92, 0x30, 0x8f, 0x35, 0xb3, 0xc1, 0xe9, 0xa0, 0xd7, 0x5f, 0xab, 0x7e, 0xe0, 0x37, 0x1e, 0xdd, 0xe5, 0x7b, 0xb3, 0xc6, 0xd4, 0x91, 0xef, 0x78, 0x9f],
+    [0xb0, 0x4f, 0xc9, 0x90, 0xcd, 0x68, 0x04, 0xc5, 0xb4, 0x7d, 0x28, 0xd6, 0x81, 0xab, 0xd8, 0xa3, 0x4e, 0x5d, 0xe9, 0x73, 0x32, 0x7b, 0xa5, 0x4d, 0x22, 0xc3, 0x4d, 0x5a, 0x2c, 0xcb, 0xc8, 0x9e],
+    [0x2b, 0x0f, 0x81, 0x2b, 0x78, 0x20, 0x6d, 0xc0, 0xe8, 0x73, 0x8c, 0xd3, 0xd2, 0x70, 0x8a, 0xca, 0x29, 0x19, 0x49, 0x5c, 0x6d, 0x16, 0xdf, 0xb0, 0xf6, 0xf4, 0x22, 0x3b, 0x68, 0x94, 0x9b, 0x82],
+    [0xb6, 0x63, 0x83, 0xdf, 0x28, 0x69, 0x38, 0x1c, 0x79, 0x3a, 0x5c, 0x82, 0x56, 0x4b, 0x95, 0x0b, 0x99, 0x30, 0x38, 0x44, 0x42, 0x98, 0xe8, 0x5b, 0x7b, 0xdc, 0x9a, 0xdd, 0x52, 0xfb, 0x9a, 0x79],
+    [0xa4, 0xfe, 0x54, 0xca, 0x51, 0x19, 0x94, 0xef, 0x58, 0xc5, 0x67, 0x80, 0x2e, 0x27, 0x33, 0x7e, 0x6b, 0xef, 0x4f, 0xee, 0x71, 0x2e, 0xdb, 0x52, 0x6d, 0x31, 0xe9, 0x24, 0x1e, 0x11, 0x53, 0xc7],
+    [0xf7, 0x2b, 0x86, 0x41, 0x3b, 0xfe, 0xec, 0x8b, 0xb9, 0xcf, 0x67, 0xbd, 0x8d, 0x89, 0x20, 0xe7, 0x5f, 0xf7, 0xf5, 0x76, 0xab, 0xe7, 0x82, 0x7c, 0x99, 0xe8, 0xa6, 0xe8, 0x11, 0xa4, 0xf2, 0x9f],
+    [0x27, 0x8e, 0xd1, 0x92, 0x72, 0xc0, 0x1c, 0xe1, 0xc6, 0xbb, 0x22, 0xf3, 0x28, 0xef, 0xee, 0x17, 0x62, 0x05, 0x68, 0xd8, 0x0e, 0x2c, 0x2f, 0xa8, 0xef, 0x33, 0x5b, 0x8a, 0xcd, 0x21, 0x8a, 0x1f],
+    [0x5e, 0xc4, 0x01, 0x00, 0x2f, 0x5b, 0x13, 0x9a, 0x3f, 0xda, 0x79, 0x3c, 0x9a, 0x4c, 0xa7, 0x93, 0x84, 0xec, 0x49, 0x5f, 0x79, 0xbd, 0xb8, 0xa8, 0x44, 0x4d, 0x44, 0x71, 0x1d, 0x51, 0x37, 0xe7],
+    [0xf7, 0x17, 0x37, 0x76, 0xf1, 0xf0, 0x69, 0x45, 0xb4, 0x8e, 0xaa, 0xd5, 0x98, 0x69, 0xdf, 0x3b, 0x77, 0xa9, 0x56, 0xda, 0x14, 0xe9, 0xe5, 0x48, 0xb8, 0xab, 0x82, 0xdf, 0x7a, 0xf7, 0xe4, 0xd7],
+    [0xe5, 0x62, 0x2b, 0x2f, 0xdb, 0xa1, 0xe2, 0x16, 0x59, 0xf0, 0x8c, 0x6f, 0x89, 0x69, 0x0c, 0xbf, 0x29, 0x09, 0xa4, 0x83, 0xb3, 0x3e, 0x82, 0x59, 0x49, 0x8d, 0xa5, 0xea, 0xe8, 0x6c, 0x7d, 0x62],
+    [0xf6, 0x4f, 0xaa, 0x25, 0xe3, 0x68, 0x3a, 0x53, 0x74, 0x22, 0x6a, 0x60, 0xc3, 0x03, 0x86, 0xed, 0x07, 0x7a, 0xce, 0x58, 0x5a, 0xd4, 0x62, 0x92, 0x0f, 0x7a, 0xd6, 0xda, 0xdc, 0xf0, 0x33, 0xd2],
+    [0x36, 0x87, 0x32, 0x0e, 0x9a, 0x41, 0x34, 0x74, 0xd8, 0x5f, 0xac, 0x95, 0xb4, 0xe1, 0x64, 0x3d, 0x73, 0x0a, 0x89, 0x36, 0x72, 0xdf, 0x63, 0x81, 0x14, 0x87, 0x56, 0xae, 0xb7, 0xd7, 0xcb, 0x3e],
+    [0xde, 0x1f, 0x57, 0xb5, 0x00, 0xe5, 0x48, 0x33, 0x0e, 0xb2, 0xc5, 0x4a, 0x66, 0x8f, 0x0a, 0x8f, 0x98, 0xde, 0x76, 0xee, 0x0f, 0xe0, 0x04, 0xe2, 0x75, 0xea, 0xd1, 0xfa, 0x5d, 0x63, 0x80, 0x2e],
+    [0x41, 0xa2, 0xa7, 0xca, 0xfa, 0xc0, 0x25, 0xdc, 0x48, 0x48, 0xdf, 0x30, 0x64, 0xdc, 0x72, 0xb8, 0x55, 0x66, 0xd9, 0xe4, 0x45, 0x38, 0xf6, 0x9f, 0x7e, 0xf2, 0x61, 0xa7, 0xb0, 0xcc, 0x31, 0x4d],
+    [0x92, 0x18, 0x97, 0x82, 0xfe, 0x5a, 0x90, 0x20, 0xbb, 0xf0, 0x4f, 0xf8, 0xcd, 0x3e, 0xad, 0xec, 0x80, 0x3d, 0x51, 0x55, 0xbe, 0xd7, 0x5d, 0xd3, 0xc6, 0x49, 0x13, 0xef, 0x3a, 0xe9, 0xe3, 0x6a],
+    [0xba, 0x26, 0x64, 0xac, 0x4b, 0x12, 0xb0, 0xc4, 0x09, 0x85, 0x2c, 0xd7, 0x15, 0x57, 0x58, 0xe1, 0x9d, 0xef, 0xfa, 0xdc, 0x19, 0x11, 0x4b, 0xad, 0x6e, 0x56, 0xbb, 0xdf, 0xf6, 0x6f, 0xf8, 0x3f],
+    [0xb1, 0xce, 0x43, 0x6b, 0x20, 0x5b, 0xf7, 0xc6, 0xd7, 0x8b, 0x7c, 0xa0, 0x98, 0xeb, 0x89, 0x1d, 0x77, 0x3c, 0xc1, 0xdf, 0x17, 0x2e, 0xd4, 0x75, 0x6b, 0x38, 0x95, 0xd6, 0xdf, 0xfa, 0xcc, 0xcd],
+    [0xb5, 0xed, 0xbb, 0x0b, 0x37, 0x84, 0xf2, 0x3f, 0x17, 0x2c, 0x42, 0x6a, 0x8e, 0x6e, 0x23, 0x61, 0x36, 0xa4, 0xf3, 0x51, 0x43, 0xc2, 0xe1, 0x65, 0x68, 0x97, 0xa1, 0x65, 0x0f, 0x69, 0xb5, 0x97],
+    [0xb0, 0x40, 0x8c, 0x7b, 0x6e, 0x99, 0x38, 0xbb, 0x26, 0x02, 0xec, 0x24, 0xfd, 0x23, 0x5f, 0x39, 0x2f, 0x1c, 0x4c, 0xaa, 0xc1, 0x81, 0x89, 0xad, 0x60, 0x16, 0x5b, 0xbf, 0x28, 0x3a, 0x65, 0xcc],
+    [0xc3, 0xd8, 0x42, 0x7b, 0x60, 0x50, 0x49, 0xd8, 0x9e, 0x00, 0x08, 0x35, 0x2a, 0xa5, 0x38, 0xcd, 0x81, 0xf4, 0xba, 0x05, 0x55, 0x26, 0xd1, 0xbd, 0x35, 0x7e, 0x45, 0xca, 0x96, 0xee, 0x65, 0x4f],
+    [0x94, 0x0e, 0x24, 0xdc, 0x57, 0x48, 0x17, 0x14, 0xd7, 0x8b, 0x9e, 0xfe, 0xc2, 0x3f, 0xd5, 0x3e, 0xc2, 0x99, 0xab, 0x66, 0x15, 0xdd, 0xd4, 0xcf, 0x9b, 0xd3, 0xa9, 0xee, 0xff, 0x7b, 0x8e, 0xbb],
+    [0x59, 0x2a, 0x21, 0x24, 0x1d, 0x53, 0x72, 0xe5, 0x33, 0xc6, 0x95, 0xba, 0xdc, 0x4c, 0xd5, 0xf9, 0xc2, 0xcb, 0xe8, 0x8e, 0xef, 0x07, 0xe0, 0xf8, 0x83, 0x58, 0x9b, 0xd2, 0xb9, 0x85, 0x9a, 0xc4],
+    [0xe5, 0x6f, 0xa7, 0xaa, 0x35, 0x72, 0x20, 0x53, 0x19, 0x7c, 0x0a, 0x46, 0x0c, 0x8c, 0xc6, 0x86, 0x8c, 0xaf, 0xaf, 0xd0, 0xcd, 0x42, 0x87, 0xde, 0x46, 0x76, 0x1e, 0x5c, 0x47, 0xec, 0xaa, 0xab],
+    [0xad, 0xbf, 0xa9, 0x92, 0xdb, 0x2a, 0x4e, 0x96, 0xa6, 0x74, 0x06, 0x22, 0xc9, 0xdb, 0x8a, 0x07, 0xa7, 0xb0, 0xd8, 0xab, 0x14, 0x6c, 0x58, 0xa1, 0x71, 0xa2, 0x91, 0x45, 0xcb, 0x51, 0xc4, 0xcc],
+    [0xe5, 0x51, 0x43, 0xb7, 0x21, 0xeb, 0x9f, 0x53, 0xfc, 0xdd, 0x44, 0x20, 0xea, 0x34, 0x8d, 0xb4, 0xa2, 0xef, 0x42, 0x86, 0x89, 0x57, 0x89, 0xdc, 0x90, 0xf0, 0xe6, 0x68, 0xb6, 0x3a, 0x74, 0x73],
+    [0x49, 0xe1, 0xef, 0x23, 0x86, 0x6a, 0xc4, 0xbb, 0xbe, 0x41, 0x2c, 0x53, 0xfa, 0xf5, 0x8b, 0xfa, 0xa0, 0x3a, 0x1d, 0xa6, 0xc0, 0xcb, 0x61, 0x48, 0x70, 0x38, 0xa4, 0x88, 0xcf, 0xf4, 0x8f, 0xc9],
+    [0x30, 0x63, 0xec, 0xb2, 0xa8, 0xc7, 0x43, 0x80, 0x01, 0xeb, 0xb4, 0x24, 0xe4, 0x30, 0xbf, 0x9d, 0x3b, 0x7e, 0xba, 0x65, 0x77, 0x60, 0x7e, 0xed, 0xd4, 0x8e, 0xcf, 0xbb, 0x6b, 0x77, 0x59, 0x45],
+    [0x0d, 0xcf, 0x3f, 0x47, 0x00, 0xfb, 0xdd, 0xa8, 0x69, 0x63, 0xa1, 0xa0, 0x94, 0xa6, 0x90, 0x55, 0x04, 0x0f, 0x38, 0x66, 0x97, 0x08, 0x9b, 0x9e, 0xd5, 0x17, 0x29, 0x93, 0x8b, 0xfa, 0x56, 0x5e],
+    [0x07, 0xbc, 0x5c, 0xb6, 0x49, 0xc2, 0x86, 0xd2, 0x72, 0x94, 0x83, 0xdd, 0x01, 0x85, 0x3b, 0x38, 0xb6, 0x56, 0xfb, 0xeb, 0x60, 0xfa, 0x7c, 0x47, 0x33, 0x81, 0xfc, 0xe2, 0xae, 0xe7, 0x9e, 0xa6],
+    [0x49, 0x84, 0x94, 0x60, 0x6f, 0x61, 0x3e, 0x49, 0x56, 0xd2, 0x1b, 0x1c, 0x39, 0x84, 0x50, 0x18, 0x6d, 0x43, 0x8d, 0x62, 0xf2, 0x9c, 0xb3, 0x2a, 0x4a, 0xf4, 0xfa, 0x8e, 0xc8, 0xea, 0x97, 0x63],
+    [0xf6, 0x10, 0x3c, 0x74, 0xae, 0x43, 0x92, 0x6a, 0x90, 0xe8, 0x33, 0xb6, 0xf6, 0x1e, 0xe6, 0x10, 0x73, 0xc5, 0x26, 0x98, 0x4a, 0xc8, 0x33, 0xa1, 0x50, 0x6e, 0xa5, 0x30, 0x7c, 0x2a, 0x15, 0x0f],
+    [0xb8, 0xfe, 0x42, 0x7c, 0xa5, 0xfb, 0xdb, 0xe0, 0x35, 0xfb, 0x60, 0x06, 0x7a, 0x92, 0xec, 0x52, 0x97, 0x35, 0xba, 0x2b, 0x44, 0xd0, 0xcd, 0xde, 0x09, 0xb2, 0x48, 0x31, 0xc2, 0xf4, 0x63, 0x22],
+    [0x35, 0x2b, 0xbc, 0xf0, 0x0e, 0xe2, 0x13, 0x1b, 0xf1, 0xf4, 0xa4, 0xef, 0x1e, 0xc9, 0xcb, 0x52, 0xa0, 0x57, 0x5c, 0x79, 0x51, 0x8b, 0x91, 0x7d, 0xc6, 0x6e, 0xc0, 0xad, 0x40, 0x10, 0x42, 0xe9],
+    [0x36, 0x5c, 0xbf, 0xc0, 0x53, 0x1e, 0xc4, 0x87, 0x3a, 0x54, 0xcf, 0xa5, 0x70, 0x83, 0x82, 0x1f, 0x59, 0xa7, 0xb7, 0xeb, 0x0d, 0x15, 0xa4, 0x58, 0x10, 0xbc, 0x50, 0x53, 0x5e, 0x56, 0xb1, 0x25],
+    [0x97, 0xba, 0x0b, 0x58, 0x8b, 0x33, 0x4e, 0x4e, 0x07, 0xba, 0xf5, 0xa0, 0x29, 0xc3, 0xc1, 0x8c, 0x3b, 0xac, 0x48, 0x5c, 0x86, 0x8c, 0x9c, 0x9a, 0x92, 0x8f, 0x8a, 0xa9, 0x93, 0x73, 0x31, 0x3c],
+    [0x9e, 0xe5, 0xa6, 0x79, 0x59, 0xf3, 0x0f, 0x12, 0x83, 0x3a, 0xb4, 0xf9, 0x97, 0xaf, 0xb4, 0xd8, 0x32, 0x22, 0x4f, 0xbc, 0xb3, 0x8e, 0x18, 0x9a, 0x05, 0x16, 0x13, 0x30, 0x84, 0x73, 0x8d, 0xeb],
+    [0xd5, 0x61, 0x75, 0xed, 0xc7, 0x73, 0x7a, 0x68, 0xdb, 0x07, 0xe3, 0x56, 0xfb, 0x2a, 0x26, 0xf4, 0x86, 0x81, 0x40, 0x39, 0x60, 0x9b, 0xd3, 0xb2, 0x8b, 0x48, 0x83, 0xcb, 0x19, 0x18, 0xaf, 0x7e],
+    [0xf2, 0xd2, 0x60, 0xe7, 0xbe, 0x7d, 0x88, 0x09, 0x7a, 0x74, 0x20, 0x9a, 0x73, 0x7c, 0xf3, 0x54, 0x42, 0xb1, 0x7a, 0xd9, 0xdb, 0xbf, 0x56, 0x8e, 0xac, 0xe9, 0x9e, 0x46, 0xaf, 0xd9, 0xf4, 0x9b],
+    [0xc0, 0x01, 0x91, 0xd5, 0x04, 0x8b, 0xfe, 0xdf, 0x1d, 0xea, 0x9f, 0xdc, 0x1b, 0xf2, 0xb4, 0x2b, 0x06, 0x7d, 0xa5, 0x4b, 0x35, 0x74, 0xd9, 0x1a, 0x07, 0x6f, 0xe3, 0x13, 0x14, 0xed, 0x50, 0xf0],
+    [0x41, 0xef, 0x15, 0x76, 0x79, 0x4a, 0x75, 0x5c, 0x7b, 0xd9, 0x96, 0xf5, 0xcf, 0x65, 0x94, 0xfa, 0xd4, 0x71, 0x9a, 0x40, 0x72, 0x2a, 0xfc, 0xda, 0xd9, 0x29, 0xec, 0xa8, 0x67, 0x16, 0x96, 0xe6],
+    [0xb3, 0xcd, 0x1a, 0x94, 0x98, 0x16, 0xf7, 0x8a, 0x03, 0xdb, 0xc0, 0x17, 0xaf, 0x69, 0x98, 0x79, 0x7b, 0xa7, 0x03, 0x98, 0x2a, 0xb4, 0x00, 0x40, 0xef, 0xc2, 0x1a, 0xcc, 0xf1, 0x95, 0x62, 0x22],
+    [0x8e, 0x70, 0x12, 0xf3, 0xf6, 0x63, 0x11, 0xb8, 0x91, 0x3a, 0x5a, 0x92, 0x1f, 0x10, 0x11, 0xa7, 0x63, 0x49, 0x1a, 0x38, 0x89, 0x1e, 0x0c, 0xbd, 0xa2, 0x95, 0x1e, 0xd8, 0x04, 0x1b, 0xbe, 0xe4],
+    [0x63, 0x0f, 0xfc, 0x83, 0x52, 0x9b, 0x38, 0xbb, 0xf1, 0x7c, 0x6c, 0x5e, 0xe8, 0x8c, 0xa1, 0x26, 0xb3, 0x10, 0xdb, 0x12, 0x86, 0x94, 0x0d, 0x66, 0x14, 0x1f, 0xb9, 0x3d, 0x8d, 0x46, 0x6d, 0xb2],
+    [0xc7, 0xd3, 0xa9, 0xa8, 0x89, 0x9d, 0x55, 0x85, 0x97, 0xc6, 0x48, 0x6f, 0x53, 0x44, 0x35, 0xb5, 0xb7, 0xb1, 0xd6, 0x5a, 0x5e, 0xde, 0x1a, 0x97, 0x1a, 0xb2, 0x9f, 0x18, 0x69, 0xa2, 0xe9, 0xaa],
+    [0x90, 0x4b, 0x85, 0x88, 0xdc, 0xc7, 0x78, 0x37, 0x7e, 0xcf, 0xfb, 0x4c, 0x6b, 0x5e, 0xc3, 0xd2, 0x9d, 0xfa, 0xb0, 0xae, 0xcb, 0xfe, 0xab, 0x52, 0x28, 0xa7, 0xc7, 0x08, 0xe7, 0x3a, 0x5c, 0x06],
+    [0xa4, 0xc0, 0xa2, 0x56, 0xc5, 0x08, 0xbf, 0xab, 0xd8, 0x60, 0x77, 0xc2, 0x44, 0x7c, 0x0a, 0xe7, 0x63, 0xc2, 0x7b, 0x82, 0x2f, 0x71, 0xd3, 0x73, 0x1b, 0x02, 0x20, 0xcf, 0x5e, 0x7f, 0x11, 0x83],
+    [0xdb, 0xdb, 0xf9, 0xd8, 0x67, 0x46, 0x0c, 0x45, 0x53, 0x45, 0x42, 0x33, 0x4e, 0x6e, 0x00, 0x41, 0x93, 0x51, 0x09, 0x13, 0x35, 0xbe, 0x61, 0x88, 0x07, 0x37, 0x80, 0x7d, 0xeb, 0x7d, 0x99, 0x14],
+    [0x55, 0x4b, 0xac, 0xa1, 0x99, 0x3e, 0x16, 0xbc, 0xec, 0xa8, 0x97, 0x4b, 0x59, 0x96, 0x5e, 0x07, 0xd6, 0xee, 0x09, 0x76, 0x75, 0xba, 0xf3, 0x84, 0x19, 0x8d, 0xb4, 0x6c, 0xfb, 0x4e, 0x16, 0xa5],
+    [0x6a, 0x08, 0x3e, 0xa9, 0x47, 0x0f, 0xde, 0xf9, 0x02, 0xda, 0x72, 0xd6, 0x64, 0xef, 0xd6, 0x93, 0x62, 0x18, 0xf2, 0xb5, 0x34, 0xb2, 0x4c, 0xe0, 0xbd, 0xee, 0x11, 0x1e, 0xb9, 0xce, 0xdf, 0xd2],
+    [0xb8, 0x1a, 0xa6, 0x64, 0x99, 0xaf, 0xa8, 0x46, 0xce, 0xfb, 0x4e, 0x83, 0xd4, 0x46, 0x39, 0x87, 0x8f, 0x3b, 0xc0, 0x9d, 0x64, 0x32, 0xf9, 0x71, 0x48, 0xc2, 0x07, 0xbe, 0xf9, 0x12, 0xcf, 0x98],
+    [0x92, 0x09, 0x39, 0x15, 0x2f, 0x62, 0x09, 0xac, 0x71, 0x3a, 0x13, 0xe7, 0x16, 0x9e, 0x57, 0xb1, 0x0e, 0x0d, 0x45, 0x5d, 0x4d, 0xf2, 0x61, 0x60, 0x59, 0xe8, 0xf9, 0x58, 0xfa, 0x23, 0x5e, 0xb7],
+    [0xde, 0xd0, 0x4d, 0x85, 0xd5, 0x2d, 0x9c, 0x68, 0x02, 0x11, 0x2d, 0x8e, 0x32, 0x6d, 0xf9, 0x17, 0x40, 0xb8, 0xac, 0xae, 0xb3, 0x10, 0x89, 0x03, 0x30, 0x7c, 0x38, 0x08, 0x92, 0x92, 0x9c, 0x2a],
+    [0x47, 0x79, 0x68, 0x59, 0xc1, 0x35, 0x2b, 0x5d, 0x7c, 0xee, 0x8f, 0x48, 0xee, 0xa3, 0x95, 0xa7, 0x8a, 0x19, 0xba, 0xba, 0xc9, 0x36, 0x09, 0x77, 0x0c, 0x00, 0xe7, 0xfd, 0x12, 0x96, 0x11, 0x52],
+    [0x82, 0x1a, 0x0f, 0x8f, 0x6f, 0x03, 0x09, 0xea, 0xf1, 0x48, 0x3b, 0xa0, 0x6f, 0xf4, 0xf9, 0x0a, 0x54, 0xe1, 0xd3, 0x73, 0x17, 0x8a, 0x33, 0xfd, 0x91, 0x31, 0xa3, 0xd6, 0x24, 0xd6, 0x3f, 0x77],
+    [0x91, 0xad, 0x54, 0x21, 0x09, 0x46, 0x5a, 0x90, 0x43, 0x19, 0x85, 0xd4, 0x73, 0x81, 0x17, 0x3a, 0x67, 0x1b, 0x52, 0x1c, 0x4b, 0x5a, 0xb5, 0xca, 0xa5, 0x4e, 0x85, 0x99, 0xec, 0x52, 0x54, 0x47],
+    [0x7e, 0x4e, 0x4d, 0x50, 0x2b, 0x98, 0x8d, 0xe3, 0xe7, 0x2a, 0x40, 0xd9, 0xef, 0x24, 0xc5, 0x4d, 0xb7, 0x7d, 0x43, 0x4d, 0xea, 0xf9, 0x1e, 0x94, 0xaf, 0x53, 0xe2, 0x4c, 0xcd, 0x4a, 0x01, 0x5a],
+    [0x6d, 0xd0, 0xf5, 0xee, 0xdc, 0x7c, 0xd6, 0x20, 0xa7, 0xab, 0x29, 0xf7, 0x54, 0x91, 0x64, 0xf5, 0x46, 0xc8, 0x59, 0xb2, 0x57, 0xdc, 0xb2, 0xfb, 0x0e, 0x8e, 0x8d, 0x02, 0x99, 0x41, 0x79, 0x6a],
+    [0xbb, 0x43, 0xa8, 0x8c, 0xef, 0x92, 0x40, 0x9d, 0xd1, 0x4a, 0x8c, 0x55, 0x47, 0x0c, 0xd5, 0x7f, 0x29, 0xa3, 0xec, 0xe7, 0x2b, 0x15, 0xf7, 0x2a, 0x2b, 0x8a, 0x0f, 0x30, 0x02, 0xf3, 0xdb, 0x0e],
+    [0x15, 0xf7, 0xe7, 0x79, 0xe4, 0xb8, 0x1e, 0xb2, 0x3e, 0xfc, 0x5c, 0xf2, 0x8c, 0x1c, 0x3d, 0x4a, 0xed, 0x46, 0x55, 0xab, 0x93, 0xf5, 0x80, 0x41, 0x0f, 0xe7, 0x0d, 0xaa, 0x8c, 0xa8, 0x20, 0x2d],
+    [0xfe, 0x3a, 0x75, 0x8e, 0xae, 0xcf, 0xf2, 0xf4, 0xe6, 0x06, 0xe6, 0x54, 0xb8, 0x2a, 0x81, 0xfc, 0xae, 0x8b, 0xfd, 0xb4, 0x7e, 0xa4, 0x38, 0x36, 0xfe, 0xa1, 0x3e, 0x66, 0x4d, 0xf7, 0x0d, 0x59],
+    [0xef, 0xa5, 0xce, 0x29, 0xea, 0x73, 0x8a, 0x8d, 0x9e, 0x0d, 0x70, 0xd8, 0x8b, 0xf2, 0x51, 0x7f, 0x52, 0x50, 0x0f, 0x5d, 0xc6, 0xf0, 0x38, 0x44, 0xc6, 0xa1, 0x25, 0x0a, 0xb0, 0xf8, 0xa2, 0xfa],
+    [0xe5, 0x03, 0x52, 0x89, 0xaa, 0xc8, 0x21, 0xe9, 0xb8, 0x31, 0x4b, 0x41, 0x44, 0x50, 0xcd, 0x9c, 0xa3, 0x6c, 0x9e, 0x9c, 0x75, 0x2b, 0x7b, 0xfa, 0x15, 0xed, 0xa6, 0x1d, 0x44, 0x71, 0xbe, 0x0d],
+    [0xf1, 0x3c, 0xc1, 0xea, 0xef, 0xcc, 0xc0, 0xf4, 0x31, 0xb8, 0x47, 0xa1, 0x42, 0x0c, 0x7a, 0x58, 0x06, 0x1e, 0x31, 0xcf, 0xd4, 0x11, 0x64, 0xd2, 0xd8, 0x67, 0xe4, 0x6b, 0xd6, 0x65, 0x12, 0x60],
+    [0xcf, 0x3f, 0xd0, 0x87, 0x06, 0xcd, 0xa5, 0x50, 0x25, 0x1d, 0xce, 0xc0, 0xb0, 0xa3, 0x66, 0xc2, 0x2a, 0xe3, 0x64, 0xca, 0x39, 0xb9, 0xda, 0xdf, 0x28, 0x1d, 0x4b, 0x9b, 0xbe, 0x45, 0xdc, 0xe3],
+    [0xee, 0x38, 0xf9, 0xf7, 0xfc, 0xdb, 0x34, 0x8e, 0x55, 0x5d, 0xf4, 0x9c, 0xc9, 0x01, 0xfa, 0xa6, 0xa9, 0x04, 0xf8, 0x10, 0xe5, 0x26, 0xed, 0x58, 0x72, 0x9b, 0xd5, 0x7f, 0xf2, 0x4c, 0x70, 0x53],
+    [0x32, 0xe5, 0x80, 0xb6, 0x8d, 0xc0, 0xcc, 0x7a, 0xa2, 0x63, 0xe8, 0x85, 0xac, 0xcc, 0x6c, 0x58, 0xc7, 0xe3, 0x80, 0x88, 0x5e, 0xd4, 0xa2, 0x67, 0x2e, 0xc6, 0xcf, 0x5d, 0x5b, 0x53, 0x52, 0x3f],
+    [0x5f, 0xef, 0xef, 0x59, 0x13, 0x15, 0x87, 0x18, 0xea, 0xea, 0x70, 0xb6, 0x06, 0x01, 0x81, 0x13, 0xca, 0x31, 0xcf, 0x6b, 0x0a, 0xa0, 0xcb, 0xa3, 0x79, 0xdc, 0x53, 0x8a, 0x0d, 0xb1, 0x20, 0x09],
+    [0x3c, 0x45, 0x7b, 0x11, 0xbd, 0xc4, 0xd2, 0xd1, 0x12, 0x52, 0x18, 0xb9, 0x4e, 0xee, 0xd3, 0x10, 0xf7, 0xa9, 0x50, 0x89, 0x05, 0x97, 0x68, 0xe8, 0x94, 0x37, 0x93, 0xce, 0x5c, 0x21, 0x3a, 0x59],
+    [0x11, 0x93, 0x30, 0x50, 0xd4, 0xe6, 0x1d, 0xde, 0x7f, 0xc7, 0x23, 0xae, 0x26, 0x9a, 0x84, 0x66, 0x2a, 0x15, 0xff, 0x3a, 0x18, 0xab, 0xd9, 0x89, 0xa9, 0xd7, 0xd5, 0xec, 0xb3, 0xc2, 0x91, 0xc0],
+    [0x13, 0xf3, 0xe2, 0xc2, 0x0a, 0x6c, 0x08, 0xbb, 0xa2, 0x1d, 0x0a, 0x61, 0x3d, 0x56, 0xe9, 0x11, 0xa2, 0xbf, 0x1d, 0x93, 0xdb, 0xa0, 0xa6, 0x32, 0x6a, 0xd6, 0x09, 0xdd, 0xa0, 0x90, 0xe9, 0x0b],
+    [0xcd, 0x83, 0x4a, 0x6f, 0x11, 0xdb, 0x3c, 0x28, 0x52, 0x8d, 0x8b, 0xfc, 0x63, 0x2e, 0x06, 0xf0, 0xa8, 0xcb, 0x53, 0x70, 0x51, 0x2e, 0x5e, 0xdf, 0x4d, 0xd4, 0x15, 0xf8, 0x8a, 0x21, 0x40, 0x17],
+    [0x5d, 0xea, 0x61, 0x7c, 0x5b, 0x83, 0xd4, 0x37, 0xd4, 0xd2, 0x71, 0x56, 0x3b, 0x3e, 0xb4, 0xee, 0xa6, 0xad, 0xbe, 0x4d, 0xd8, 0x4e, 0x56, 0xbd, 0xc8, 0x98, 0x1a, 0x3c, 0x5a, 0x4a, 0xcd, 0xbe],
+    [0x0e, 0xb8, 0x3f, 0x3d, 0x42, 0xce, 0x8e, 0xb1, 0xa4, 0xf2, 0xf2, 0x37, 0x24, 0x0a, 0xc6, 0x5d, 0x88, 0x80, 0xe5, 0xe7, 0x1b, 0xe1, 0x45, 0x68, 0x12, 0x52, 0x52, 0xe1, 0xea, 0x34, 0xe7, 0x80],
+    [0x7f, 0x8d, 0x9e, 0x5c, 0xb0, 0x2b, 0x4e, 0x0e, 0xc1, 0xac, 0x0f, 0xcc, 0x9c, 0x97, 0x42, 0x85, 0x76, 0xe1, 0x7d, 0x5b, 0x7d, 0x0a, 0x0b, 0x0f, 0x37, 0x1d, 0xb0, 0xe4, 0x14, 0xcb, 0x45, 0xfd],
+    [0xe1, 0x6a, 0x60, 0xbd, 0xd0, 0xb1, 0x3f, 0x8e, 0xa8, 0x51, 0x3a, 0x15, 0x96, 0x2a, 0xd8, 0x5b, 0x49, 0x80, 0x51, 0x1f, 0xcb, 0x53, 0xfd, 0xf1, 0x05, 0x1a, 0xe7, 0x16, 0x92, 0x31, 0x64, 0xd1],
+    [0xa4, 0x9d, 0x5d, 0xe7, 0x53, 0xc1, 0x60, 0xf2, 0x66, 0xf2, 0x09, 0x98, 0x76, 0x57, 0x71, 0x0b, 0xa4, 0xf1, 0x93, 0xae, 0x97, 0x33, 0xb4, 0xa5, 0x1e, 0x08, 0x00, 0x56, 0x17, 0xf0, 0x0b, 0x11],
+    [0xa0, 0xe2, 0xbc, 0xaa, 0x05, 0x38, 0x9a, 0x20, 0x46, 0x75, 0xc1, 0x35, 0x9f, 0x6c, 0x7d, 0x3e, 0x8f, 0x2d, 0x68, 0x38, 0x02, 0x52, 0x71, 0xc2, 0x93, 0xe3, 0x98, 0xb0, 0xf8, 0xd9, 0x6b, 0x6f],
+    [0x5d, 0x3f, 0x2a, 0x75, 0xdf, 0x10, 0x38, 0xed, 0xbb, 0xb2, 0x78, 0x5d, 0xa7, 0x69, 0x74, 0xa6, 0x28, 0x3f, 0x26, 0x5a, 0xb2, 0xd6, 0x70, 0x00, 0x05, 0xde, 0xd9, 0x14, 0xe7, 0x86, 0x5c, 0x39],
+    [0xd3, 0x0e, 0xc8, 0xda, 0xc0, 0x0c, 0xed, 0x3b, 0x76, 0x32, 0xc3, 0x32, 0xfa, 0x1e, 0xa1, 0x78, 0x39, 0xa0, 0x71, 0xc2, 0x28, 0x68, 0x80, 0x74, 0xc8, 0x4c, 0xb1, 0xdd, 0x53, 0x84, 0xad, 0xd2],
+    [0xd1, 0x8b, 0x46, 0x01, 0xbb, 0x57, 0xaf, 0xb4, 0x83, 0x55, 0xbb, 0xad, 0x87, 0xa3, 0x56, 0xef, 0xcb, 0x76, 0x0d, 0xc5, 0xd4, 0x5b, 0xe4, 0x2a, 0x4f, 0xfc, 0xd6, 0xda, 0xa2, 0x62, 0xc5, 0x06],
+    [0x07, 0x8f, 0x01, 0x27, 0xc3, 0x5a, 0xa5, 0x2e, 0xfa, 0x19, 0xa9, 0x7c, 0xea, 0x70, 0x23, 0x2f, 0x94, 0x9b, 0x62, 0xe8, 0x28, 0x07, 0xb8, 0x74, 0x0d, 0x83, 0x29, 0x40, 0xb4, 0xdf, 0x03, 0x9a],
+    [0x6e, 0xf9, 0x89, 0x7b, 0xcc, 0xe3, 0xe2, 0x6b, 0x1c, 0x64, 0x47, 0x75, 0xec, 0x9b, 0x77, 0x63, 0x6b, 0xc4, 0xa6, 0xae, 0x05, 0xa8, 0x38, 0x79, 0xd3, 0xcf, 0x50, 0xe8, 0x64, 0xde, 0x58, 0x38],
+    [0xd9, 0xe5, 0xd4, 0x7c, 0xef, 0x41, 0xe0, 0x26, 0x83, 0x17, 0x60, 0xde, 0xca, 0xc6, 0x7b, 0x5c, 0x14, 0x6e, 0x4a, 0xea, 0x91, 0xbf, 0xb9, 0x0e, 0x3e, 0x15, 0x8b, 0x91, 0x31, 0x1a, 0x90, 0x96],
+    [0x53, 0xfc, 0xd7, 0xbc, 0x84, 0x5b, 0xe8, 0x06, 0xb3, 0x72, 0x0a, 0x68, 0x75, 0xb2, 0x81, 0x4a, 0x54, 0x06, 0xc0, 0x04, 0x34, 0xb6, 0xf4, 0x09, 0x75, 0x91, 0x23, 0x06, 0x99, 0x43, 0x56, 0x22],
+    [0xce, 0x72, 0xb7, 0x9e, 0xd1, 0xea, 0x23, 0x53, 0xa8, 0x29, 0x80, 0x08, 0xc7, 0x0a, 0x81, 0x6c, 0xc4, 0x99, 0x33, 0x2f, 0xa0, 0x09, 0xd6, 0x14, 0x2e, 0x83, 0x0b, 0x3c, 0x00, 0x09, 0x51, 0x7a],
+    [0x1a, 0x89, 0xd2, 0x8b, 0x03, 0xb9, 0x9c, 0x9f, 0x42, 0x93, 0x58, 0x80, 0xd6, 0x59, 0x80, 0x93, 0x68, 0x38, 0x3a, 0x3d, 0xa4, 0xc6, 0x55, 0x6e, 0x08, 0x9b, 0xe8, 0x2d, 0x94, 0x2e, 0xd7, 0xfc],
+    [0x4f, 0x75, 0x88, 0x6e, 0xe6, 0xa1, 0x92, 0x8f, 0x80, 0x1c, 0xa9, 0x72, 0xf5, 0x60, 0x9e, 0x04, 0xfb, 0x47, 0x2f, 0x64, 0x7b, 0x7e, 0x42, 0x47, 0x7c, 0x45, 0x70, 0x82, 0x61, 0x4a, 0xda, 0x3d],
+    [0x8e, 0x9c, 0x12, 0xb4, 0x51, 0xc5, 0xb3, 0x13, 0x66, 0x4c, 0x45, 0x2e, 0x81, 0xe7, 0x30, 0xe4, 0xfb, 0x3f, 0xcb, 0x0b, 0xc1, 0x5b, 0x3e, 0x56, 0x66, 0xc6, 0x6d, 0x50, 0xce, 0x6b, 0x0d, 0x07],
+    [0x93, 0xa9, 0x55, 0x99, 0xbf, 0x52, 0x9b, 0xf1, 0xbc, 0x7a, 0x4e, 0xe5, 0xd9, 0x69, 0x08, 0x6b, 0xad, 0xbe, 0xaa, 0x14, 0x4b, 0xac, 0xf3, 0x51, 0xfd, 0x6e, 0x41, 0x62, 0xcf, 0xb5, 0x38, 0x50],
+    [0x10, 0xe4, 0x58, 0x19, 0x09, 0xd5, 0x97, 0x83, 0xfd, 0x7d, 0x08, 0x3b, 0x53, 0x03, 0x10, 0x56, 0xad, 0xda, 0xd1, 0x51, 0xed, 0xff, 0x6d, 0x8d, 0x01, 0xc4, 0x93, 0x0b, 0x71, 0x0d, 0x89, 0x78],
+    [0x76, 0xaf, 0x41, 0xb1, 0xc8, 0x95, 0x19, 0x0d, 0xc3, 0xa8, 0x37, 0x9b, 0x55, 0x1f, 0xb3, 0xb9, 0xf0, 0x56, 0xa4, 0x63, 0x00, 0x0e, 0x86, 0xb6, 0x31, 0x5d, 0x1a, 0xce, 0x0c, 0x66, 0x6a, 0xff],
+    [0x56, 0xe0, 0xf0, 0x37, 0x65, 0xcb, 0x09, 0x9a, 0xc6, 0xff, 0x4c, 0xe8, 0x30, 0xed, 0x75, 0xc3, 0x95, 0x8e, 0x57, 0xdf, 0xff, 0x9e, 0x57, 0x73, 0x95, 0xa7, 0x93, 0xd2, 0xe4, 0x85, 0x3d, 0xe9],
+    [0x7e, 0x31, 0xab, 0xde, 0x1f, 0xf7, 0xbe, 0x0d, 0xaf, 0xde, 0x91, 0x6e, 0x3a, 0xd7, 0xd6, 0x95, 0xcb, 0x18, 0xf5, 0x70, 0x85, 0x8d, 0xcd, 0x39, 0x73, 0xec, 0xd4, 0xd8, 0x4e, 0x5e, 0xe1, 0x30],
+    [0x0b, 0x84, 0x8d, 0xbb, 0x2a, 0xee, 0xe4, 0xe2, 0x6e, 0x48, 0xc4, 0xc4, 0x79, 0xde, 0xc2, 0x32, 0x24, 0x08, 0x4b, 0x3d, 0x6e, 0xea, 0xa7, 0xbf, 0x9b, 0x7d, 0x0d, 0x99, 0xd9, 0x79, 0x9f, 0x03],
+    [0x5f, 0xe5, 0xc1, 0x8f, 0xd3, 0xc0, 0x00, 0xca, 0xd0, 0x47, 0xfd, 0xeb, 0xa6, 0x60, 0x93, 0xcc, 0xd0, 0xee, 0xd6, 0x9d, 0x25, 0x92, 0xe9, 0x25, 0xcc, 0xa3, 0x9a, 0xaa, 0x45, 0xc1, 0x21, 0x14],
+    [0x64, 0x17, 0x38, 0xbe, 0x4c, 0xe4, 0xee, 0x83, 0x99, 0x95, 0x0b, 0xca, 0x9f, 0x2b, 0x34, 0x5a, 0xd9, 0xec, 0x35, 0xb8, 0xc9, 0x76, 0x39, 0xb4, 0x96, 0x2f, 0xb5, 0x53, 0x0f, 0x16, 0xf5, 0xf3],
+    [0xe7, 0x31, 0x1e, 0x38, 0x75, 0x0c, 0xba, 0x01, 0x80, 0x2f, 0x2b, 0x9a, 0xaf, 0xc1, 0xd9, 0x73, 0x65, 0x6d, 0x76, 0xba, 0x5a, 0x47, 0x3d, 0x65, 0x58, 0x1e, 0x32, 0xa1, 0x18, 0xd8, 0xda, 0x67],
+    [0x4b, 0x13, 0xaa, 0x3e, 0xfb, 0x0f, 0xcd, 0x73, 0x7c, 0x21, 0xb2, 0x01, 0xa4, 0x49, 0xf3, 0xe1, 0x42, 0xe1, 0x20, 0x5c, 0x0b, 0x77, 0x0f, 0x9c, 0x25, 0x8d, 0xae, 0xfc, 0x12, 0x7f, 0x23, 0x12],
+    [0x27, 0x86, 0x95, 0x55, 0xb4, 0xdd, 0x28, 0x51, 0x6a, 0x1d, 0x16, 0xce, 0x0b, 0x74, 0x2c, 0x28, 0x96, 0xb8, 0x7c, 0x98, 0x58, 0xc3, 0x86, 0xee, 0x2d, 0x06, 0x12, 0xd6, 0xd5, 0xf8, 0x9f, 0xbb],
+    [0xce, 0x0e, 0x11, 0x58, 0x3c, 0xb7, 0x7d, 0xdc, 0xdd, 0x01, 0x44, 0xc1, 0x64, 0xd1, 0x03, 0x9f, 0x7c, 0xc4, 0x9e, 0xbd, 0xf8, 0x80, 0x5f, 0x52, 0xeb, 0xbc, 0xba, 0xce, 0xc1, 0x98, 0x11, 0xca],
+    [0x47, 0xf0, 0x8d, 0x65, 0x1b, 0x0a, 0x03, 0xe9, 0x9f, 0xd7, 0xe3, 0x66, 0x51, 0x71, 0x7e, 0xfc, 0x6a, 0x8d, 0x56, 0x4c, 0xe9, 0xf6, 0x21, 0x69, 0x45, 0x68, 0x0a, 0xb1, 0x42, 0x73, 0xb9, 0x93],
+    [0x41, 0x84, 0x7f, 0xf0, 0x4d, 0x87, 0xdb, 0x43, 0x7f, 0xfc, 0xd2, 0x7b, 0xde, 0x2c, 0x1a, 0x5d, 0x59, 0xa5, 0x15, 0x5c, 0x75, 0x83, 0x9c, 0x08, 0x3f, 0x86, 0xd1, 0x24, 0xa1, 0x82, 0xb2, 0x35],
+    [0x30, 0xea, 0x29, 0x4d, 0x78, 0x0f, 0xb8, 0x98, 0x4e, 0x9a, 0x5b, 0xcd, 0xeb, 0xd3, 0x4d, 0xaf, 0x5d, 0xf0, 0x28, 0x42, 0x74, 0x2c, 0x28, 0xb7, 0x09, 0x4b, 0x5f, 0x6c, 0xe0, 0x81, 0x2a, 0xfa],
+    [0x2b, 0x27, 0xd5, 0x9c, 0x69, 0x2d, 0xbc, 0xc7, 0xca, 0x7f, 0xb4, 0x00, 0x47, 0x2f, 0x0d, 0x12, 0x22, 0x48, 0xe9, 0x8b, 0x26, 0x9f, 0xa9, 0x77, 0x05, 0x73, 0xf7, 0x68, 0x44, 0xb0, 0xaa, 0x1c],
+    [0xdc, 0xc1, 0xcf, 0x07, 0xbe, 0xd0, 0x34, 0x07, 0x4a, 0xb4, 0x98, 0x28, 0xd5, 0x43, 0xf9, 0xc3, 0x3e, 0x76, 0x82, 0xee, 0x2b, 0x48, 0xad, 0x52, 0xfb, 0xaa, 0x5e, 0xbb, 0x4f, 0x61, 0x00, 0x58],
+    [0xe1, 0x83, 0x71, 0x4b, 0xd4, 0xb8, 0x8e, 0xcd, 0x9d, 0x92, 0xfb, 0x79, 0xbd, 0xae, 0x32, 0x17, 0x23, 0x7d, 0x32, 0x5a, 0xb5, 0xc8, 0x9a, 0xaa, 0x2d, 0x2c, 0x4c, 0xf6, 0x00, 0x79, 0x61, 0x4f],
+    [0xde, 0x34, 0x56, 0xa8, 0x28, 0x87, 0xfe, 0x8b, 0x15, 0x84, 0x1a, 0x40, 0x07, 0xaa, 0x6d, 0xd2, 0x02, 0x3f, 0xec, 0x73, 0xe1, 0x6c, 0x32, 0x5d, 0x67, 0x4f, 0x96, 0x80, 0x82, 0xb1, 0x5f, 0x77],
+    [0x38, 0x4e, 0xfc, 0x53, 0xb6, 0xf2, 0x2a, 0x80, 0x54, 0x7b, 0x2e, 0xb4, 0x07, 0x29, 0x0a, 0x96, 0x22, 0xab, 0x22, 0x5d, 0x7d, 0x0a, 0xcc, 0x35, 0xe2, 0x3f, 0x48, 0x55, 0x72, 0x5e, 0x6a, 0x62],
+    [0x04, 0x3d, 0xb6, 0x1a, 0x98, 0xc1, 0xe6, 0x37, 0xd2, 0x84, 0x76, 0x3b, 0xb2, 0xff, 0xce, 0x19, 0x27, 0x31, 0xdc, 0x93, 0x59, 0xbc, 0xa0, 0x1a, 0x64, 0x35, 0xb2, 0xb9, 0x4f, 0xed, 0x71, 0xaf],
+    [0x04, 0x43, 0x27, 0xce, 0x36, 0x16, 0x55, 0xa4, 0xe2, 0xc2, 0xc2, 0xe8, 0xa1, 0x6d, 0x02, 0x9e, 0xd8, 0xcc, 0x8e, 0x2f, 0x2d, 0x90, 0xb6, 0xc2, 0x44, 0x96, 0xc0, 0x54, 0xfe, 0x75, 0x72, 0xa3],
+    [0x4d, 0x46, 0xb6, 0xdb, 0xe6, 0x77, 0x11, 0x8d, 0x71, 0xaf, 0xe9, 0xaf, 0xf7, 0x5e, 0xf1, 0xb2, 0x7a, 0x92, 0x6c, 0xbf, 0x49, 0xac, 0xfd, 0x88, 0x52, 0xed, 0xfa, 0x32, 0x84, 0x85, 0x6b, 0xd7],
+    [0x98, 0x07, 0xc9, 0x67, 0x3c, 0xff, 0x6a, 0x91, 0xe5, 0x41, 0x82, 0xbc, 0x79, 0xfb, 0x18, 0x31, 0xf6, 0xab, 0x42, 0xc2, 0xf2, 0x52, 0x3f, 0xda, 0x3c, 0x3f, 0xd9, 0xfb, 0x5f, 0x3c, 0xce, 0xc4],
+    [0xf8, 0xc8, 0x90, 0xad, 0xc1, 0xb3, 0xc7, 0x7c, 0x6c, 0x87, 0xe6, 0xc7, 0x9f, 0x77, 0x17, 0xe2, 0xb6, 0xe8, 0x5f, 0x24, 0xdc, 0x0c, 0xc6, 0x91, 0x99, 0xf8, 0x6c, 0x35, 0xb6, 0x17, 0x45, 0xb6],
+    [0x67, 0xe1, 0x69, 0x51, 0xd4, 0xd1, 0xf8, 0x51, 0x00, 0x2c, 0x47, 0x6b, 0x35, 0x82, 0xf3, 0xd0, 0x2f, 0xb3, 0xf3, 0x6b, 0x1d, 0x61, 0xba, 0x90, 0x98, 0x6f, 0x93, 0x01, 0x05, 0xd4, 0x16, 0x93],
+    [0x1e, 0xe9, 0xa8, 0x50, 0x8b, 0x7a, 0x7d, 0xd1, 0xa6, 0xeb, 0xca, 0x50, 0x13, 0x42, 0xb3, 0xf8, 0xba, 0xf5, 0x5d, 0x3e, 0xb4, 0x7c, 0x5e, 0x32, 0xad, 0x0d, 0x66, 0x7c, 0xa5, 0x5d, 0xb0, 0x0c],
+    [0x8e, 0xa3, 0x9d, 0x25, 0x0a, 0x1b, 0x90, 0xc2, 0xda, 0xf4, 0x21, 0xf4, 0xe7, 0xd1, 0x9f, 0xc2, 0xc6, 0x20, 0x7b, 0x3a, 0xfc, 0x6d, 0x2d, 0x7d, 0x00, 0xf4, 0xf8, 0x16, 0x31, 0xbb, 0x1c, 0xb4],
+    [0xfe, 0xd0, 0xb7, 0x57, 0xbb, 0x1e, 0x35, 0xa4, 0x08, 0x9d, 0xde, 0xf0, 0xd6, 0x40, 0xeb, 0x81, 0x1c, 0xbd, 0x9d, 0xa7, 0xa2, 0x64, 0xaf, 0xc2, 0x17, 0x73, 0xb9, 0x5a, 0xa2, 0x63, 0xa8, 0xda],
+    [0xc6, 0x19, 0xdd, 0xf6, 0x62, 0x36, 0x34, 0x24, 0x52, 0xec, 0xd1, 0x2c, 0x0f, 0xf3, 0xeb, 0x88, 0xad, 0x6c, 0xef, 0x57, 0x7e, 0x3e, 0x35, 0x80, 0x69, 0x6c, 0x92, 0x70, 0x7e, 0xa3, 0x5d, 0x28],
+    [0x64, 0x3c, 0x11, 0x17, 0x01, 0x4e, 0x24, 0x2e, 0x9d, 0x44, 0x8c, 0x26, 0x37, 0x0a, 0x37, 0x1a, 0xf1, 0x01, 0xe8, 0x6f, 0x03, 0x69, 0x25, 0x98, 0x85, 0x7d, 0x7d, 0x00, 0xbc, 0x05, 0xd5, 0x9f],
+    [0x02, 0x1a, 0x4d, 0x19, 0xc2, 0x52, 0x0b, 0x6b, 0x03, 0xea, 0x8e, 0xc0, 0x87, 0xee, 0x2d, 0x08, 0x57, 0x6b, 0x65, 0x2e, 0xcd, 0x77, 0x95, 0x24, 0xec, 0xa1, 0x53, 0x95, 0x47, 0xf0, 0x6d, 0x1d],
+    [0x3f, 0xb3, 0xb0, 0xc1, 0xc8, 0x8c, 0xe6, 0x8a, 0xe1, 0x4a, 0xa1, 0x3b, 0xd4, 0xa4, 0x87, 0xb1, 0x66, 0x94, 0xb9, 0x67, 0xaf, 0x49, 0x22, 0xd5, 0xf5, 0xdc, 0x75, 0x19, 0xb5, 0x00, 0xac, 0x64],
+    [0x9e, 0xf7, 0xb8, 0x04, 0xc7, 0xcd, 0x78, 0xff, 0x90, 0x24, 0xcb, 0xae, 0xdc, 0x88, 0x57, 0xb3, 0x33, 0xae, 0x73, 0x8c, 0x56, 0xbb, 0xc6, 0x47, 0x89, 0x48, 0x95, 0xfc, 0x05, 0xfc, 0x6f, 0xc0],
+    [0x9e, 0x75, 0x83, 0xe7, 0xa3, 0xa9, 0x23, 0x10, 0xf6, 0x72, 0x4c, 0xff, 0x57, 0xd9, 0x67, 0xfc, 0x70, 0xdb, 0xee, 0x71, 0xa4, 0x87, 0xfc, 0x75, 0x6b, 0x07, 0x99, 0x26, 0x5d, 0x7c, 0xfc, 0xd0],
+    [0x9b, 0x6a, 0x25, 0xc8, 0xfe, 0xae, 0x97, 0x8d, 0x84, 0x0a, 0xe4, 0xe7, 0x35, 0x1f, 0xfc, 0xb6, 0xd9, 0xaa, 0x6e, 0x08, 0x12, 0x11, 0xe1, 0xc9, 0xce, 0x5b, 0xdf, 0x9c, 0xbe, 0xfb, 0x0c, 0x89],
+    [0x65, 0x07, 0x56, 0x15, 0xb0, 0xe1, 0xbb, 0x65, 0xcc, 0x9c, 0x82, 0x4a, 0xcb, 0x9a, 0x3a, 0x11, 0x47, 0xde, 0xbc, 0xd7, 0x84, 0xf7, 0xb0, 0x58, 0xce, 0x8d, 0xe5, 0xeb, 0x85, 0x02, 0x81, 0xd3],
+    [0xfb, 0x2f, 0xd9, 0xed, 0xc6, 0xca, 0xd9, 0xa1, 0x09, 0x74, 0xdc, 0xaa, 0x5b, 0x8b, 0xf6, 0xf6, 0xad, 0x87, 0xcf, 0x11, 0xe0, 0xa9, 0x9e, 0x56, 0xcd, 0x4d, 0xe0, 0xda, 0xd0, 0xb8, 0xc8, 0x49],
+    [0xe2, 0x11, 0x3e, 0xe5, 0x63, 0x68, 0xfc, 0x14, 0x9e, 0xce, 0x9c, 0x78, 0xde, 0x25, 0xde, 0x63, 0x9f, 0x66, 0x7e, 0xdd, 0x55, 0x38, 0xc0, 0x2a, 0xdf, 0x8c, 0x04, 0xe5, 0x02, 0x65, 0xe2, 0x40],
+    [0x8a, 0x3b, 0xf7, 0xf7, 0x13, 0x6e, 0xac, 0x99, 0xb1, 0x17, 0x69, 0x27, 0x99, 0x38, 0xdb, 0xdf, 0xda, 0x68, 0xa0, 0x9d, 0x6c, 0x0b, 0x8f, 0x05, 0x17, 0x03, 0x27, 0x6c, 0x2f, 0xd3, 0x4b, 0xba],
+    [0xbb, 0xde, 0xf2, 0xfe, 0xff, 0x9e, 0x4e, 0x6b, 0xd7, 0x16, 0x67, 0x69, 0x89, 0x17, 0x67, 0x05, 0x27, 0x3f, 0xc7, 0x3d, 0xe4, 0x62, 0x68, 0xe8, 0x47, 0xda, 0x86, 0xce, 0x34, 0x1b, 0xd9, 0x9a],
+    [0xd3, 0xca, 0x08, 0xb8, 0x82, 0x6f, 0xb6, 0x69, 0x9d, 0xee, 0x70, 0xe8, 0xca, 0x16, 0x0a, 0x72, 0xc6, 0xa2, 0x07, 0x61, 0x68, 0xe2, 0xff, 0xb7, 0x3f, 0x5b, 0xfd, 0x7e, 0x31, 0x40, 0x10, 0x25],
+    [0xba, 0xaf, 0x7f, 0x25, 0x26, 0x95, 0x2f, 0xcf, 0xe7, 0x6a, 0x5e, 0x40, 0x6f, 0xf7, 0x16, 0x07, 0xb5, 0x06, 0xfa, 0x83, 0xe9, 0xcc, 0x6d, 0xc0, 0xcf, 0x88, 0x7b, 0x94, 0x0a, 0x7a, 0x15, 0xa0],
+    [0x46, 0x3e, 0x89, 0xbf, 0x39, 0xe9, 0x67, 0xd5, 0x9c, 0x71, 0xbf, 0xd9, 0xb1, 0x0e, 0x47, 0x24, 0x72, 0x7a, 0xc7, 0x3e, 0x03, 0xf1, 0x6d, 0xbb, 0x3c, 0x18, 0xcd, 0x3e, 0xc3, 0x46, 0xbb, 0xf2],
+    [0x83, 0x2e, 0x01, 0x98, 0x1f, 0x97, 0x6e, 0x0c, 0x36, 0x2d, 0x58, 0xbf, 0xa7, 0xb1, 0xed, 0x6e, 0xd3, 0x4d, 0xc5, 0xb6, 0x24, 0xb1, 0x3b, 0x8b, 0x36, 0x52, 0x22, 0x07, 0xe5, 0x61, 0x34, 0xf5],
+    [0x10, 0xe6, 0x85, 0x0e, 0xfd, 0x02, 0xb8, 0x98, 0xa2, 0x30, 0x20, 0x73, 0x60, 0xdd, 0xbe, 0xa2, 0x55, 0x19, 0x2c, 0x85, 0xa0, 0xc0, 0xbb, 0x15, 0xba, 0xc4, 0xb9, 0x75, 0xf7, 0xee, 0x88, 0x48],
+    [0x40, 0x01, 0xf3, 0x49, 0xd2, 0x1a, 0x55, 0x54, 0x83, 0x07, 0x35, 0x0a, 0xed, 0x09, 0x0f, 0x82, 0x92, 0xe8, 0xae, 0xae, 0x6b, 0xac, 0xe3, 0xf9, 0x28, 0xf7, 0xff, 0x91, 0x21, 0x44, 0xeb, 0x68],
+    [0x34, 0x47, 0x42, 0x11, 0xcd, 0x06, 0x79, 0x6d, 0x42, 0x62, 0x5c, 0x93, 0xbb, 0x52, 0xb4, 0xe2, 0xd8, 0x45, 0xe8, 0x5a, 0x20, 0xd4, 0xd2, 0x9d, 0x34, 0xd7, 0xac, 0x59, 0xbd, 0x9a, 0x2e, 0x9e],
+    [0xa6, 0x75, 0x84, 0x8e, 0x73, 0x34, 0x09, 0x34, 0x68, 0x4a, 0xda, 0xa3, 0x76, 0xb7, 0x2d, 0xcf, 0x3f, 0x50, 0x35, 0xa3, 0xaf, 0xe5, 0xd4, 0x09, 0x03, 0xfa, 0xa1, 0xd7, 0xff, 0xff, 0xbb, 0x14],
+    [0xa2, 0x30, 0xb9, 0xd7, 0x71, 0x92, 0x54, 0x58, 0x9e, 0xdd, 0x14, 0x7a, 0x9c, 0x9a, 0x68, 0x37, 0x01, 0x28, 0xba, 0x41, 0x96, 0x80, 0x06, 0x85, 0x7c, 0x23, 0x42, 0x04, 0xd1, 0x1f, 0xc2, 0xff],
+    [0x8d, 0x65, 0x15, 0x7b, 0xa2, 0xb2, 0x42, 0xb7, 0xe8, 0x68, 0x00, 0xc1, 0xd9, 0xa5, 0xf2, 0xb9, 0xc1, 0xef, 0xdc, 0xa2, 0x61, 0x83, 0xec, 0xf7, 0xa7, 0x9e, 0xb1, 0x8b, 0x50, 0xe0, 0x7e, 0x09],
+    [0x2f, 0xca, 0x57, 0x0b, 0x23, 0xb6, 0x94, 0x88, 0x40, 0x02, 0x66, 0xf5, 0xf6, 0x2f, 0x3a, 0xc7, 0xf8, 0x08, 0x9e, 0xf7, 0x09, 0x68, 0xde, 0xaf, 0x92, 0x82, 0xc3, 0xca, 0xfa, 0xd6, 0xd9, 0x5b],
+    [0x34, 0xe5, 0x7f, 0xdb, 0xdb, 0x3b, 0x5a, 0x03, 0x12, 0x1a, 0xb6, 0x4b, 0x0f, 0x3f, 0x13, 0x23, 0x39, 0x8b, 0x76, 0x22, 0x16, 0xf5, 0x32, 0x9d, 0x4d, 0xe0, 0xc7, 0x5e, 0x87, 0x3e, 0xa2, 0x63],
+    [0xd4, 0x6c, 0x61, 0x7b, 0xf4, 0xef, 0x3c, 0x72, 0x8e, 0x9c, 0x82, 0x1a, 0x22, 0x74, 0xba, 0x20, 0x84, 0xb7, 0x47, 0x4d, 0xe9, 0x2d, 0x56, 0x02, 0x06, 0x6b, 0x59, 0x2b, 0x09, 0xc7, 0x48, 0xa7],
+    [0x8c, 0x4b, 0x5c, 0x50, 0x7a, 0x2c, 0xbb, 0xe8, 0xa9, 0xb1, 0xaa, 0xf3, 0x51, 0x8b, 0x4b, 0xc9, 0x38, 0x31, 0x7d, 0xc0, 0x9d, 0xdb, 0x1a, 0x6c, 0x95, 0xa9, 0x8e, 0x5b, 0x4d, 0x3e, 0x0c, 0xb8],
+    [0x63, 0xb4, 0x3c, 0x13, 0x1e, 0x80, 0xfc, 0xca, 0x8a, 0x32, 0xfd, 0x0b, 0x36, 0x72, 0x96, 0xc3, 0x6a, 0x9a, 0xe1, 0xf5, 0x7b, 0xe8, 0x6b, 0xa3, 0x93, 0x87, 0xd8, 0x43, 0xa1, 0x00, 0xf7, 0xd9],
+    [0xd7, 0x53, 0x81, 0x65, 0x48, 0x12, 0xf4, 0x72, 0x6b, 0x72, 0x57, 0x43, 0xd9, 0x6d, 0x2f, 0x22, 0xeb, 0xa3, 0xfe, 0x8f, 0xab, 0xd5, 0x86, 0x40, 0xe7, 0x09, 0x44, 0xef, 0xb5, 0xe3, 0x77, 0x31],
+    [0xdb, 0x23, 0x26, 0x31, 0xe1, 0xbe, 0xaf, 0xcc, 0x40, 0xd9, 0x89, 0xca, 0xb6, 0x0c, 0xf1, 0x93, 0x34, 0x96, 0x37, 0xf2, 0x72, 0x55, 0xa6, 0x1d, 0xb9, 0x5f, 0x84, 0x3d, 0x1f, 0xea, 0xde, 0x70],
+    [0xe4, 0x82, 0x85, 0xa9, 0x42, 0xac, 0x07, 0x54, 0x41, 0x36, 0xd2, 0x13, 0xed, 0x4e, 0x2e, 0xf6, 0xa5, 0x74, 0x5c, 0xb2, 0xd3, 0x0e, 0x96, 0xdc, 0x8e, 0x2d, 0xaf, 0xfc, 0x2e, 0xe7, 0x88, 0xb4],
+    [0x81, 0xf2, 0xae, 0x69, 0xe3, 0x19, 0x37, 0xc0, 0xd2, 0x06, 0x73, 0xe0, 0x14, 0x28, 0x53, 0x8e, 0x9d, 0xa0, 0x7f, 0xb8, 0xbb, 0x9d, 0xfb, 0xac, 0xa3, 0x65, 0x3a, 0x3e, 0x4a, 0xc5, 0x0e, 0x53],
+    [0x57, 0xb9, 0x32, 0xc1, 0x3a, 0x60, 0xa7, 0x4d, 0x0c, 0x8a, 0x28, 0x64, 0x3e, 0x3e, 0x4b, 0x44, 0x06, 0x81, 0x88, 0x16, 0x0e, 0x28, 0x47, 0x81, 0x2d, 0x8d, 0x59, 0xf2, 0xde, 0x1c, 0x0e, 0xc9],
+    [0x6e, 0xdc, 0x5b, 0xc2, 0x76, 0x35, 0x51, 0x55, 0x22, 0x19, 0x1d, 0x33, 0x13, 0x20, 0x96, 0x45, 0xca, 0x2a, 0xba, 0xb8, 0x34, 0x32, 0xcc, 0x6d, 0x5b, 0xdb, 0x3c, 0xd9, 0x7f, 0x38, 0xbc, 0x3e],
+    [0x9e, 0x78, 0xde, 0x17, 0xee, 0x1c, 0x29, 0x76, 0x2e, 0x43, 0x54, 0x94, 0x98, 0x23, 0x8c, 0x6d, 0x11, 0xef, 0xd2, 0xb5, 0x81, 0x36, 0x94, 0x14, 0x80, 0x6d, 0x93, 0x79, 0x7e, 0xc7, 0x70, 0xf2],
+    [0xfa, 0xf8, 0xff, 0xca, 0x63, 0xd8, 0xb9, 0x3d, 0x2c, 0x9e, 0x0b, 0x20, 0xc1, 0xb9, 0x1f, 0x3e, 0x2e, 0x63, 0xd0, 0xd1, 0x52, 0xb6, 0x18, 0x49, 0x6c, 0xd0, 0xac, 0x09, 0xa7, 0xb3, 0x82, 0x60],
+    [0x5e, 0xf2, 0xdb, 0x5d, 0xba, 0xc2, 0xb6, 0x8f, 0x49, 0x27, 0xb8, 0x88, 0x4e, 0x95, 0x31, 0x54, 0x10, 0x58, 0x70, 0xe7, 0x56, 0xc7, 0xc3, 0xb1, 0xeb, 0x14, 0xb2, 0x61, 0x0a, 0x64, 0x1a, 0xa0],
+    [0x82, 0xd8, 0x57, 0x31, 0x8d, 0x8f, 0x0a, 0x35, 0xd3, 0xff, 0x14, 0x25, 0xc1, 0xc5, 0x31, 0xca, 0x08, 0xe1, 0xfb, 0x98, 0x08, 0x18, 0x16, 0x42, 0xa9, 0x71, 0x38, 0x5a, 0x3a, 0x9c, 0x17, 0x61],
+    [0x89, 0xf1, 0x09, 0x33, 0xfd, 0x1b, 0xf7, 0x37, 0x8f, 0x00, 0x04, 0xd3, 0x78, 0xeb, 0xfa, 0x21, 0xbe, 0x6f, 0x77, 0x7f, 0x84, 0xd9, 0xbb, 0xa5, 0x9e, 0xd2, 0xdd, 0x66, 0x70, 0xfb, 0x09, 0xee],
+    [0xa8, 0x8a, 0xe2, 0xe9, 0xdf, 0x00, 0x92, 0xeb, 0xd2, 0x95, 0x8f, 0xa7, 0x97, 0x0b, 0x1b, 0x51, 0x8a, 0xfb, 0x28, 0x56, 0x80, 0x05, 0x06, 0x54, 0x5f, 0xa4, 0x12, 0xfe, 0xac, 0x3c, 0x9a, 0xdd],
+    [0xfb, 0xf2, 0xe1, 0xba, 0xfc, 0xea, 0xe3, 0xc5, 0x20, 0xa8, 0xeb, 0x71, 0x25, 0x92, 0x8c, 0xc9, 0x34, 0x29, 0xe3, 0x90, 0xe7, 0x27, 0xec, 0x3e, 0xd9, 0x99, 0xc4, 0x74, 0x4e, 0xf5, 0xed, 0x82],
+    [0x93, 0xc9, 0x46, 0x84, 0x2e, 0xae, 0x8c, 0x23, 0x3a, 0xa3, 0x8e, 0x04, 0xd5, 0x16, 0x75, 0x5f, 0xcb, 0xa8, 0x17, 0x39, 0x72, 0x72, 0xd6, 0x52, 0x8b, 0x41, 0xdc, 0x22, 0xb6, 0x35, 0x57, 0xa1],
+    [0xf9, 0x7a, 0x6b, 0x3c, 0x2b, 0x7c, 0x2f, 0x11, 0x1f, 0x90, 0xe0, 0x4e, 0xa8, 0x01, 0xec, 0x91, 0xbe, 0xb2, 0x50, 0x88, 0xe7, 0x50, 0x1d, 0x79, 0x18, 0xc5, 0x27, 0x57, 0x5c, 0x89, 0x1e, 0x55],
+    [0x39, 0x50, 0x08, 0xc6, 0x56, 0xf7, 0x49, 0x25, 0x93, 0xfd, 0x0a, 0x5c, 0x95, 0x60, 0x63, 0x48, 0xb6, 0x7b, 0xf1, 0x33, 0xb4, 0x7c, 0x99, 0xb8, 0x21, 0xe3, 0x9a, 0xb4, 0x9f, 0xe2, 0x1b, 0x1a],
+    [0x8f, 0xdf, 0xcf, 0xa0, 0xa8, 0x49, 0x79, 0xfa, 0x9d, 0xfd, 0x36, 0x9e, 0x77, 0xc3, 0x4e, 0xcc, 0x0d, 0x82, 0x4d, 0xad, 0xac, 0x19, 0x4e, 0xf9, 0x07, 0xcf, 0xbc, 0x70, 0x12, 0x9f, 0x53, 0x4a],
+    [0xf6, 0x1d, 0x1f, 0xe0, 0xd1, 0xb6, 0x9f, 0x1c, 0x13, 0x6a, 0x8d, 0xaf, 0x76, 0xce, 0x16, 0x72, 0x35, 0xfc, 0xeb, 0x74, 0xaa, 0xf3, 0x9d, 0xf7, 0x2a, 0x19, 0x26, 0xeb, 0x52, 0xb6, 0x2e, 0x6a],
+    [0x66, 0xd9, 0x48, 0x20, 0x94, 0xb7, 0xb2, 0x53, 0x7d, 0x8f, 0x4c, 0x02, 0x27, 0x2a, 0x17, 0xbd, 0x14, 0x15, 0xa1, 0x45, 0x14, 0x38, 0x5a, 0xba, 0x2e, 0x83, 0xb7, 0x48, 0x02, 0x07, 0x62, 0x5d],
+    [0x27, 0x5f, 0x61, 0x9d, 0x10, 0x66, 0x23, 0x29, 0xd2, 0x94, 0x4a, 0xf3, 0x5d, 0xc6, 0xab, 0x0f, 0xdf, 0x40, 0x19, 0x48, 0x83, 0x6f, 0xc3, 0xe3, 0xaf, 0x28, 0x66, 0xf3, 0xd0, 0x69, 0x47, 0xb7],
+    [0x50, 0x2b, 0xa7, 0x28, 0x72, 0xbb, 0x1b, 0x3c, 0xf9, 0x15, 0xa2, 0x74, 0x56, 0x2b, 0x55, 0x53, 0x82, 0xf1, 0xac, 0xba, 0x93, 0x16, 0x6e, 0x60, 0x79, 0x16, 0x21, 0x0b, 0xe8, 0x65, 0x1a, 0x52],
+    [0x87, 0x3f, 0xf0, 0x9e, 0x81, 0xa6, 0xd7, 0xbb, 0xdf, 0xce, 0x33, 0xf2, 0x50, 0xb8, 0xd3, 0xbf, 0xad, 0xd2, 0x42, 0x73, 0xbd, 0x2c, 0xb9, 0xc6, 0xbb, 0xec, 0xc8, 0x9b, 0xec, 0xa5, 0x8d, 0x35],
+    [0x79, 0xbe, 0x3f, 0xbe, 0x43, 0xee, 0x5a, 0xeb, 0xc3, 0x85, 0x26, 0x6f, 0x6c, 0x44, 0x03, 0x1b, 0xae, 0xcf, 0xad, 0x3a, 0x36, 0x21, 0x67, 0xd4, 0x53, 0x2b, 0x4b, 0x88, 0xab, 0xe4, 0xdd, 0x6f],
+    [0x42, 0x38, 0x1a, 0xce, 0xda, 0xf1, 0x9e, 0x2d, 0x35, 0x54, 0x1b, 0x04, 0xd0, 0x6d, 0x74, 0x81, 0x49, 0x09, 0xa8, 0xc3, 0xe1, 0xb1, 0x2a, 0xfd, 0xa6, 0x1c, 0x41, 0xd3, 0x88, 0x79, 0xef, 0x2c],
+    [0xc6, 0x41, 0x13, 0xf5, 0x72, 0x55, 0xe2, 0xd9, 0xdb, 0x1c, 0x8a, 0x2f, 0x4a, 0xf3, 0x6d, 0xf5, 0xb7, 0x46, 0x7f, 0x4a, 0x26, 0x48, 0xd6, 0xbb, 0xfe, 0xf3, 0xad, 0x03, 0x0c, 0xb1, 0x67, 0xf3],
+    [0x8d, 0xc4, 0xfe, 0xf3, 0xd8, 0xc1, 0x3c, 0x66, 0xfc, 0xab, 0x02, 0x78, 0x36, 0x60, 0x94, 0xb8, 0x42, 0x5d, 0xd7, 0x05, 0x47, 0xee, 0x46, 0x7b, 0x62, 0x11, 0x26, 0x11, 0x74, 0xc2, 0xdf, 0xba],
+    [0x55, 0x1b, 0xfa, 0x5c, 0xc8, 0x3d, 0x13, 0x9c, 0x19, 0xab, 0xc6, 0x7c, 0x2b, 0xaf, 0xc6, 0x71, 0xbc, 0x79, 0xa4, 0x31, 0x35, 0x07, 0xe2, 0x5f, 0xb0, 0x06, 0xca, 0x16, 0xe7, 0xf5, 0x29, 0x9f],
+    [0xa3, 0x54, 0x45, 0xfc, 0xf1, 0xfd, 0xf2, 0xe3, 0xae, 0x1d, 0x31, 0xd6, 0xd7, 0x74, 0x90, 0xb1, 0xd4, 0x54, 0x2e, 0x99, 0x60, 0x47, 0x3d, 0x04, 0x66, 0xa4, 0xf8, 0x1f, 0xad, 0x18, 0x2d, 0x65],
+    [0xae, 0xeb, 0x6f, 0x16, 0x65, 0x70, 0x5c, 0x74, 0xfc, 0x22, 0xa4, 0x41, 0x07, 0x9a, 0x62, 0x6b, 0xf9, 0x58, 0x73, 0x8d, 0xec, 0xc7, 0x25, 0x55, 0x8a, 0xd4, 0xae, 0xc6, 0x76, 0x5a, 0x9a, 0x2d],
+    [0x45, 0xed, 0x0b, 0x6f, 0x0e, 0xcf, 0x84, 0x89, 0x5d, 0x9e, 0xcb, 0x7b, 0xee, 0xcd, 0x91, 0x12, 0x01, 0xd7, 0xf3, 0x06, 0x52, 0xbb, 0xd1, 0x04, 0xb2, 0x71, 0xbf, 0xbc, 0xd5, 0xce, 0xc2, 0x3c],
+    [0x80, 0xa0, 0x94, 0xb9, 0xb3, 0x41, 0x7b, 0xd1, 0xf9, 0x01, 0xc2, 0x5f, 0x93, 0x4d, 0xf8, 0x4c, 0x79, 0x6a, 0x7f, 0xbd, 0xc2, 0xda, 0xbb, 0x11, 0x82, 0xf5, 0xf0, 0xca, 0xfc, 0xbe, 0x9f, 0x36],
+    [0x88, 0xf8, 0xbb, 0x54, 0xd1, 0x45, 0x68, 0xd9, 0xeb, 0x18, 0xb0, 0x1c, 0x58, 0xd8, 0xab, 0x3a, 0x9c, 0x4b, 0x40, 0x50, 0xc5, 0x5c, 0x42, 0xfb, 0x7e, 0x6c, 0x0e, 0xd8, 0x58, 0xbf, 0x88, 0x70],
+    [0x88, 0x99, 0x81, 0xd1, 0x9d, 0x15, 0x7e, 0x7d, 0xe4, 0x6d, 0x8a, 0x9c, 0xe1, 0x1f, 0x45, 0x7b, 0xcc, 0xc3, 0x2b, 0xb2, 0x8c, 0x1a, 0xbd, 0x89, 0x14, 0xd7, 0x7c, 0x9a, 0xac, 0xcf, 0x88, 0x61],
+    [0xf9, 0x15, 0x0d, 0x57, 0x00, 0x91, 0x87, 0x9e, 0x3d, 0xbf, 0xb8, 0x31, 0x7a, 0xb9, 0xeb, 0xc8, 0x84, 0x12, 0x0a, 0x93, 0xec, 0xe9, 0x09, 0x4c, 0x36, 0x75, 0x29, 0x66, 0x92, 0x16, 0xe0, 0x3f],
+    [0x86, 0xfa, 0x7f, 0x6c, 0x0a, 0xb1, 0x62, 0x46, 0xdc, 0x3b, 0xa0, 0x20, 0xc9, 0x95, 0xf4, 0xe8, 0x10, 0xd1, 0x55, 0xbb, 0x5a, 0xba, 0x06, 0x70, 0x46, 0xa1, 0x6c, 0x56, 0x6e, 0x99, 0x21, 0xb5],
+    [0x2a, 0x5b, 0xc8, 0x8f, 0x88, 0x1b, 0x71, 0xbe, 0x0d, 0xe3, 0xb8, 0x15, 0xe1, 0xdf, 0xd6, 0xcd, 0x70, 0x91, 0x10, 0xf8, 0xe3, 0x2f, 0x12, 0x71, 0x02, 0x75, 0xc0, 0xf1, 0x9a, 0x88, 0xfb, 0xb8],
+    [0x47, 0xaf, 0x2d, 0x59, 0x55, 0xab, 0xe0, 0x2e, 0x30, 0x5c, 0x7a, 0x74, 0xd0, 0xfa, 0x3e, 0x1d, 0x82, 0x0c, 0xa8, 0x0f, 0x66, 0x9a, 0xdc, 0x44, 0xa1, 0xb5, 0x50, 0x83, 0xab, 0x37, 0x5d, 0xb9],
+    [0xd1, 0x51, 0x5f, 0xb7, 0x2b, 0x85, 0xa9, 0xce, 0x4a, 0x02, 0x5b, 0xfd, 0xaf, 0x21, 0xcb, 0x7a, 0x79, 0x95, 0x56, 0x13, 0x3f, 0x13, 0xa0, 0xad, 0x9c, 0x95, 0x94, 0x48, 0x08, 0x64, 0xe1, 0xf4],
+    [0x94, 0x12, 0xe6, 0x94, 0xc1, 0x4d, 0xf6, 0x47, 0x32, 0x69, 0x12, 0x66, 0x01, 0x4c, 0x6e, 0x0a, 0xe3, 0x96, 0x21, 0xb3, 0xf7, 0x7a, 0xfe, 0xf8, 0x48, 0x88, 0x83, 0x8f, 0xf5, 0xd7, 0x51, 0xc3],
+    [0x81, 0xb9, 0x93, 0x99, 0x85, 0x7f, 0x42, 0x34, 0xab, 0x32, 0xca, 0xf8, 0x1f, 0xc0, 0x67, 0x96, 0x63, 0x60, 0x65, 0xfe, 0xc0, 0xe9, 0x55, 0x22, 0xd9, 0xdd, 0x4c, 0xca, 0xdd, 0xbe, 0x22, 0x7b],
+    [0x60, 0x6a, 0x6b, 0xa2, 0x9b, 0xa2, 0x45, 0x19, 0xfc, 0x92, 0x17, 0xa5, 0x7a, 0x4b, 0xa9, 0x9e, 0x60, 0x2c, 0x45, 0x92, 0xfe, 0xad, 0xfd, 0xe5, 0x20, 0x93, 0x63, 0xfa, 0x2c, 0x0c, 0xda, 0x48],
+    [0xf9, 0xc4, 0x1a, 0x61, 0xe6, 0x64, 0x3f, 0x4c, 0xa3, 0x16, 0x18, 0x63, 0x0d, 0xe3, 0xfe, 0x62, 0x68, 0x6e, 0x71, 0x9b, 0x1c, 0xe9, 0x95, 0xda, 0x87, 0x2f, 0x7a, 0xee, 0x37, 0x5f, 0x6b, 0xcb],
+    [0x95, 0x0d, 0x9c, 0xa4, 0xca, 0x87, 0x6c, 0x17, 0x3c, 0xa6, 0xdd, 0xfd, 0x42, 0x9b, 0x90, 0x26, 0x1f, 0x42, 0x88, 0x6c, 0x6a, 0xd0, 0x12, 0xac, 0x82, 0x9a, 0xe6, 0x73, 0x92, 0x95, 0x10, 0xc0],
+    [0xc5, 0x9d, 0xbc, 0x27, 0x7a, 0x1c, 0xe0, 0x36, 0x20, 0xa0, 0xac, 0x7a, 0xeb, 0xf5, 0x7b, 0x59, 0xc7, 0x1f, 0x1e, 0xbc, 0xa8, 0xf5, 0xce, 0x59, 0x66, 0x39, 0x00, 0x16, 0xb2, 0x5d, 0xf6, 0xa5],
+    [0x07, 0x3c, 0xfe, 0x7a, 0xf3, 0x21, 0x46, 0xdc, 0x57, 0x55, 0x91, 0x8e, 0x39, 0x31, 0x03, 0xde, 0xe4, 0x46, 0x64, 0x86, 0x6a, 0x73, 0x79, 0x57, 0xc7, 0x06, 0xb0, 0x76, 0x1d, 0x7c, 0x65, 0xc4],
+    [0x4b, 0xf2, 0x62, 0x6e, 0x5c, 0xc1, 0x28, 0x9a, 0x23, 0xab, 0xee, 0x85, 0xec, 0x58, 0xa9, 0x29, 0xd2, 0xe5, 0x48, 0x9b, 0x52, 0x0b, 0x26, 0xbe, 0xb5, 0x6f, 0x76, 0x8c, 0x20, 0x26, 0x6f, 0x30],
+    [0xbe, 0x70, 0x42, 0xcd, 0x5e, 0x20, 0xa5, 0xaf, 0x69, 0x9b, 0x7a, 0xff, 0xcc, 0x1b, 0x32, 0x8e, 0xf7, 0xe2, 0x14, 0xc4, 0xd7, 0x1f, 0xf5, 0xbc, 0x0b, 0xde, 0xa1, 0x51, 0xd1, 0x5b, 0xf8, 0x10],
+    [0x4b, 0x51, 0xa5, 0xef, 0x8e, 0x81, 0x0e, 0xb8, 0x1a, 0xf2, 0x34, 0x5d, 0xc6, 0xb3, 0x6b, 0x91, 0xbd, 0xc9, 0x99, 0xca, 0x8c, 0x23, 0xf8, 0x5a, 0x49, 0xc8, 0x94, 0x41, 0x96, 0x73, 0x49, 0xad],
+    [0x7b, 0x01, 0x56, 0xcc, 0x75, 0xed, 0x1c, 0x7e, 0x44, 0x42, 0x9b, 0x9d, 0xa9, 0x8d, 0xd6, 0xc2, 0xdf, 0x6d, 0x5c, 0x3f, 0xd6, 0x52, 0xde, 0xa9, 0xae, 0xe3, 0x53, 0x48, 0x4f, 0x75, 0xea, 0x8e],
+    [0x47, 0x76, 0x32, 0xa3, 0x93, 0x1c, 0xc1, 0xe4, 0x06, 0x1b, 0x64, 0x64, 0x72, 0x1b, 0x72, 0xba, 0x0c, 0xf6, 0x1d, 0x89, 0x5b, 0xbf, 0x54, 0xc6, 0x7c, 0x00, 0xd6, 0x71, 0x6f, 0xe0, 0xc0, 0xfd],
+    [0x7a, 0x62, 0x62, 0xfb, 0x75, 0x5a, 0xb9, 0xd3, 0x73, 0x91, 0x19, 0xa7, 0x81, 0x96, 0xf1, 0x18, 0xd0, 0xf2, 0x4b, 0x35, 0x70, 0xab, 0xfd, 0xc8, 0x3e, 0xaa, 0xfb, 0x19, 0x94, 0x04, 0xca, 0xb8],
+    [0xa0, 0xa5, 0xbe, 0xf9, 0x05, 0x87, 0x26, 0xa8, 0x08, 0xa0, 0xff, 0x8c, 0x0b, 0xcf, 0x52, 0xfe, 0xc4, 0x57, 0x61, 0x8e, 0xff, 0x03, 0x18, 0x63, 0x32, 0x06, 0xcf, 0x66, 0xaf, 0x9c, 0x1b, 0x00],
+    [0x08, 0x3f, 0xfd, 0x7f, 0x9f, 0xab, 0xa6, 0x55, 0xfd, 0xa2, 0xaf, 0xac, 0xef, 0x47, 0x6f, 0x59, 0x2c, 0x56, 0x77, 0x89, 0x46, 0x5c, 0x5d, 0x6e, 0x32, 0xaf, 0xc0, 0x05, 0xfd, 0x7d, 0x5f, 0xd0],
+    [0x22, 0x82, 0x23, 0x4b, 0x0b, 0xea, 0xda, 0x14, 0xd6, 0x8d, 0x12, 0x83, 0x61, 0x63, 0x9a, 0x60, 0xbe, 0xf4, 0x63, 0x54, 0x48, 0x1b, 0x82, 0x9c, 0xed, 0xa5, 0xd5, 0x6e, 0xe1, 0x01, 0xba, 0xbc],
+    [0x5b, 0x3a, 0x3b, 0xca, 0x1b, 0x1d, 0xba, 0x89, 0xb0, 0x89, 0x2e, 0x9c, 0x5f, 0x13, 0x7e, 0xa4, 0x53, 0x57, 0xc1, 0xfe, 0xc4, 0x92, 0x03, 0x2b, 0xae, 0xe9, 0xfa, 0x0f, 0x1b, 0xfc, 0x33, 0x58],
+    [0x4a, 0xab, 0x8c, 0x24, 0x17, 0xc6, 0x34, 0xd5, 0xed, 0x93, 0x67, 0x03, 0x22, 0xd0, 0x36, 0xf5, 0x00, 0x9b, 0x4c, 0xad, 0x41, 0xeb, 0x04, 0xd3, 0x29, 0x48, 0x4c, 0xe6, 0x7e, 0x3b, 0x2d, 0x41],
+    [0xd1, 0x17, 0x00, 0x94, 0xd9, 0xad, 0x3d, 0x65, 0x0d, 0x41, 0x73, 0xa6, 0x2b, 0x13, 0x6d, 0x67, 0x50, 0x9c, 0x76, 0xd0, 0xa6, 0x07, 0x9f, 0xa1, 0x87, 0xc4, 0xf0, 0xb8, 0xe4, 0xa3, 0xab, 0x21],
+    [0x36, 0x67, 0xa0, 0x91, 0xb5, 0xe9, 0x3c, 0x4a, 0xb9, 0xcf, 0x13, 0x04, 0x83, 0x5e, 0x52, 0xb4, 0x3d, 0xf3, 0xf2, 0x50, 0xa0, 0xba, 0x9e, 0xba, 0xa2, 0x39, 0xd3, 0x46, 0x05, 0x3d, 0x5e, 0xbb],
+    [0x51, 0x1c, 0x91, 0xb5, 0xc7, 0x01, 0x19, 0x02, 0xbe, 0xe2, 0x20, 0xf2, 0x6d, 0x0c, 0xc0, 0x5d, 0x8d, 0x03, 0xbb, 0xd7, 0x84, 0x40, 0xbc, 0x43, 0xcd, 0x4d, 0xbe, 0xb9, 0xa8, 0x73, 0x50, 0xf7],
+    [0x72, 0x3f, 0xac, 0x53, 0xcc, 0x7a, 0x65, 0xd8, 0x2b, 0x77, 0xff, 0x09, 0x3d, 0x56, 0xed, 0x41, 0xb8, 0x94, 0xcf, 0xcf, 0x54, 0xe5, 0xe1, 0x49, 0xb2, 0xf2, 0x28, 0x2f, 0xd6, 0xcd, 0xe1, 0xe1],
+    [0xcf, 0xcb, 0x8b, 0xf1, 0x8f, 0xf3, 0xdd, 0x0c, 0x33, 0x0e, 0x48, 0x53, 0x1b, 0xab, 0x4e, 0x95, 0xb5, 0xc2, 0x44, 0xb5, 0x84, 0xa5, 0x14, 0x4f, 0xec, 0xc8, 0xe9, 0x06, 0x43, 0xe8, 0x49, 0xcb],
+    [0xe6, 0x6b, 0x18, 0x1f, 0xb9, 0x4a, 0x98, 0xba, 0x4f, 0xde, 0xf2, 0x7d, 0x95, 0x2d, 0x8a, 0x5c, 0x5a, 0xe5, 0x29, 0xe9, 0x9f, 0x0f, 0x5f, 0xf6, 0x91, 0xdd, 0x6c, 0xfa, 0xd0, 0xe5, 0xc5, 0xce],
+    [0xee, 0x19, 0xd0, 0x1d, 0x80, 0xc5, 0x48, 0xd4, 0xd3, 0xfc, 0xef, 0xd2, 0xf5, 0xc7, 0x91, 0xe0, 0xa9, 0x33, 0xc9, 0xf1, 0xac, 0x17, 0x3a, 0x94, 0x2d, 0x85, 0xce, 0x6b, 0x77, 0xd9, 0x65, 0x51],
+    [0xb8, 0xb8, 0x86, 0x14, 0x7d, 0x30, 0x4a, 0xb6, 0x58, 0x98, 0x27, 0x09, 0xe4, 0x10, 0x20, 0x62, 0x74, 0x3c, 0xe8, 0x63, 0xd0, 0x4e, 0xef, 0xef, 0x18, 0x41, 0x20, 0x0d, 0xac, 0xe4, 0x88, 0x97],
+    [0xf4, 0xd0, 0x3c, 0xef, 0x6c, 0x04, 0xcd, 0x30, 0x5d, 0x37, 0x81, 0x51, 0x75, 0x44, 0xd7, 0x41, 0x87, 0x85, 0xf8, 0x67, 0x41, 0x34, 0x51, 0x97, 0x59, 0x63, 0xa3, 0x24, 0x42, 0xef, 0x2d, 0x73],
+    [0xba, 0xf1, 0x68, 0x4f, 0x22, 0x18, 0x91, 0xdb, 0x42, 0x57, 0x5c, 0xb4, 0x54, 0xa6, 0xe5, 0xec, 0x8d, 0x8c, 0x5a, 0x11, 0x69, 0xb2, 0xc2, 0xa1, 0xb4, 0x3d, 0x5a, 0x60, 0xee, 0x0b, 0xcc, 0x94],
+    [0xe8, 0x68, 0x6a, 0x70, 0x71, 0xf0, 0x7f, 0x2f, 0xa2, 0x5d, 0x2d, 0x6d, 0x8e, 0xcf, 0x22, 0xc4, 0x1c, 0xee, 0x8d, 0x35, 0x70, 0xe3, 0x41, 0x67, 0x9d, 0x8e, 0x95, 0x55, 0xdb, 0xb6, 0xf3, 0x1e],
+    [0xb0, 0x4b, 0x98, 0x8b, 0x01, 0xb8, 0x03, 0xc1, 0x94, 0xb2, 0x43, 0xd2, 0xbd, 0x32, 0x5a, 0x5b, 0x0e, 0x97, 0xb2, 0xd2, 0xc7, 0x6d, 0xda, 0x83, 0x76, 0x20, 0x6c, 0xa0, 0x43, 0x15, 0xfe, 0xe3],
+    [0x39, 0x85, 0x1f, 0x44, 0x17, 0x55, 0xf6, 0x1d, 0xad, 0xa2, 0xdf, 0x3b, 0x9c, 0x1a, 0x32, 0x6f, 0xf8, 0xeb, 0x92, 0xea, 0xd8, 0xb1, 0x56, 0x96, 0xe7, 0x06, 0x9f, 0xfd, 0xdd, 0xe4, 0xbc, 0x6a],
+    [0x01, 0xdb, 0x8e, 0x01, 0x46, 0xdb, 0x86, 0x08, 0xb4, 0x16, 0xd1, 0x19, 0x73, 0xa5, 0xaf, 0x4e, 0x16, 0x75, 0x09, 0x8d, 0x0c, 0x86, 0x45, 0xef, 0x04, 0xc8, 0xc9, 0x31, 0x43, 0x3d, 0xc2, 0xda],
+    [0x8a, 0xb8, 0x4c, 0x98, 0xd4, 0x7f, 0x32, 0x67, 0x79, 0x8e, 0x7e, 0x5d, 0xbf, 0x13, 0x41, 0xdd, 0x82, 0xc1, 0x43, 0xe6, 0x26, 0xab, 0xa5, 0x9c, 0x05, 0xce, 0xeb, 0x68, 0xf8, 0xa5, 0x22, 0xf3],
+    [0x34, 0xe4, 0x3d, 0xc2, 0xa4, 0xb6, 0xa5, 0x16, 0x57, 0x90, 0x12, 0xac, 0x75, 0x17, 0x6c, 0x43, 0xda, 0xcd, 0x03, 0xe3, 0x08, 0x82, 0x31, 0x28, 0x6a, 0x67, 0xf9, 0x76, 0xcd, 0xa4, 0x2d, 0xb1],
+    [0xa9, 0xde, 0x57, 0xbc, 0x70, 0x8e, 0xd4, 0x7e, 0x49, 0x4b, 0x29, 0xa1, 0x3c, 0x93, 0x99, 0x44, 0x98, 0x12, 0xeb, 0x84, 0xe0, 0x0b, 0x47, 0xd9, 0xeb, 0x41, 0x15, 0x99, 0x06, 0xc3, 0xa4, 0xe5],
+    [0x36, 0x16, 0x9d, 0x2d, 0x28, 0xc3, 0xa8, 0x97, 0x0f, 0x6b, 0x8b, 0xf5, 0x9f, 0x54, 0xf4, 0x16, 0x43, 0x16, 0xe8, 0xa3, 0xb8, 0x08, 0x3e, 0x55, 0xc7, 0x29, 0xad, 0xb8, 0xd0, 0x14, 0xfd, 0x3b],
+];
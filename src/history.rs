@@ -0,0 +1,115 @@
+//! The state keeps the roots of its recent states, so transactions which are proven against a
+//! recent root (e.g. cross shard receipts) and hosts handling reorgs can check them from inside
+//! the EE.
+//!
+//! The roots are kept in a ring of `length` reserved accounts. Before its transactions are
+//! applied, the block at `slot` records its pre-state root, the post-state root of the block before
+//! it, in the account at `slot % length`, so the ring holds the roots of the last `length` slots
+//! with blocks. An entry is stored in its account's public key, which no one can sign for:
+//!
+//! ```text
+//! pubkey = root (32) || slot (8, LE) || zero padding (8)
+//! ```
+
+use crate::address::Address;
+use crate::bls::PublicKey;
+use crate::error::Error;
+use crate::hash::{hash_pair, H256};
+use crate::state::State;
+use arrayref::array_ref;
+use bigint::U256;
+
+/// Mixed into the addresses of the ring, so they don't collide with those of accounts or claims.
+const RING_DOMAIN: &[u8; 32] = b"sheth/recent-state-roots/ring/v1";
+
+/// Returns the address of the `index`th account of the ring in a tree of `height`.
+///
+/// ```text
+/// address = hash(RING_DOMAIN, index (8, LE) || zero padding (24)) mod 2**height
+/// ```
+pub fn ring_address(index: u64, height: usize) -> Address {
+    let mut chunk = [0u8; 32];
+    chunk[0..8].copy_from_slice(&index.to_le_bytes());
+
+    let address = U256::from(hash_pair(RING_DOMAIN, &chunk));
+    if height < 256 {
+        Address::new(address % (U256::one() << height))
+    } else {
+        Address::new(address)
+    }
+}
+
+/// Record `root` as the root of the state at the start of `slot` in the ring of `length` accounts
+/// of a tree of `height`, overwriting the entry of the slot `length` before it.
+pub fn record<T: State>(
+    db: &mut T,
+    slot: u64,
+    root: &H256,
+    length: usize,
+    height: usize,
+) -> Result<(), Error> {
+    let mut entry = [0u8; 48];
+    entry[0..32].copy_from_slice(root);
+    entry[32..40].copy_from_slice(&slot.to_le_bytes());
+
+    db.set_pubkey(
+        ring_address(slot % length as u64, height),
+        PublicKey::new(entry),
+    )
+}
+
+/// Returns the root of the state at the start of `slot` if it is still held by the ring of
+/// `length` accounts of a tree of `height`, or `None` if there was no block at `slot` or it has
+/// been overwritten.
+pub fn recent_root<T: State>(
+    db: &T,
+    slot: u64,
+    length: usize,
+    height: usize,
+) -> Result<Option<H256>, Error> {
+    let entry = db
+        .pubkey(ring_address(slot % length as u64, height))?
+        .as_bytes();
+    let root = *array_ref![entry, 0, 32];
+
+    if root == [0u8; 32] || u64::from_le_bytes(*array_ref![entry, 32, 8]) != slot {
+        return Ok(None);
+    }
+
+    Ok(Some(root))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::account::Account;
+    use crate::state::MockState;
+    use alloc::collections::BTreeMap;
+
+    #[test]
+    fn ring() {
+        let mut accounts = BTreeMap::new();
+        for index in 0..3 {
+            accounts.insert(ring_address(index, 256), Account::zero());
+        }
+        let mut db = MockState::new(accounts);
+
+        for slot in [0u64, 1, 2, 4].iter() {
+            record(&mut db, *slot, &[*slot as u8 + 1; 32], 3, 256).unwrap();
+        }
+
+        // Slot 4 overwrote slot 1, there was no block at slot 3 and slot 5 is in the future.
+        assert_eq!(recent_root(&db, 0, 3, 256), Ok(Some([1u8; 32])));
+        assert_eq!(recent_root(&db, 1, 3, 256), Ok(None));
+        assert_eq!(recent_root(&db, 2, 3, 256), Ok(Some([3u8; 32])));
+        assert_eq!(recent_root(&db, 3, 3, 256), Ok(None));
+        assert_eq!(recent_root(&db, 4, 3, 256), Ok(Some([5u8; 32])));
+        assert_eq!(recent_root(&db, 5, 3, 256), Ok(None));
+    }
+
+    #[test]
+    fn ring_addresses_fit_the_tree() {
+        assert_ne!(ring_address(0, 256), ring_address(1, 256));
+        assert!(U256::from(ring_address(0, 20)) < U256::one() << 20);
+    }
+}
@@ -0,0 +1,119 @@
+//! The functions a host provides to the EE, so the same block processing runs under unit tests,
+//! Scout and whatever host API comes next, with only the host swapped out.
+
+use crate::block::verify_block_with;
+use crate::config::Config;
+use crate::error::Error;
+use crate::hash::H256;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// The environment a block is executed in, modelled on the eth2 EE interface.
+pub trait ExecutionHost {
+    /// Returns the root of the state the block is applied to.
+    fn load_pre_state_root(&self) -> H256;
+
+    /// Returns the length of the block data in bytes.
+    fn block_data_size(&self) -> usize;
+
+    /// Copy the block data starting at `offset` into `buf`, which must fit within it.
+    fn read_block_data(&self, offset: usize, buf: &mut [u8]);
+
+    /// Save the root of the state after the block.
+    fn save_post_state_root(&mut self, root: &H256);
+
+    /// Print `message` for debugging. By default it is dropped.
+    fn debug_print(&mut self, _message: &str) {}
+}
+
+/// Process the block `host` supplies against its pre-state root under `config`, saving and
+/// returning the post-state root. An invalid block is rejected with an error, and no root is
+/// saved.
+pub fn run<H: ExecutionHost>(host: &mut H, config: &Config) -> Result<H256, Error> {
+    let pre_state_root = host.load_pre_state_root();
+
+    let mut blob = vec![0u8; host.block_data_size()];
+    host.read_block_data(0, &mut blob);
+
+    let post_state_root = verify_block_with(&pre_state_root, &blob, config)?;
+    host.save_post_state_root(&post_state_root);
+
+    Ok(post_state_root)
+}
+
+/// A host which keeps everything in memory, for native callers and tests.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MemoryHost {
+    pub pre_state_root: H256,
+    pub block_data: Vec<u8>,
+
+    /// The post-state root, once one has been saved.
+    pub post_state_root: Option<H256>,
+
+    /// Every message printed, in order.
+    pub messages: Vec<String>,
+}
+
+impl MemoryHost {
+    pub fn new(pre_state_root: H256, block_data: Vec<u8>) -> Self {
+        MemoryHost {
+            pre_state_root,
+            block_data,
+            ..MemoryHost::default()
+        }
+    }
+}
+
+impl ExecutionHost for MemoryHost {
+    fn load_pre_state_root(&self) -> H256 {
+        self.pre_state_root
+    }
+
+    fn block_data_size(&self) -> usize {
+        self.block_data.len()
+    }
+
+    fn read_block_data(&self, offset: usize, buf: &mut [u8]) {
+        buf.copy_from_slice(&self.block_data[offset..offset + buf.len()]);
+    }
+
+    fn save_post_state_root(&mut self, root: &H256) {
+        self.post_state_root = Some(*root);
+    }
+
+    fn debug_print(&mut self, message: &str) {
+        self.messages.push(message.into());
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::block::{process_block, BlockData};
+    use crate::state::imp::new_checked;
+    use crate::state::root::imp_root;
+
+    #[test]
+    fn run_in_memory() {
+        let blob = composer::blob::generate(4, 6, 256).to_bytes();
+        let block = BlockData::from_bytes(&blob).unwrap();
+        let config = Config::default();
+
+        let mut proof = block.proof.clone();
+        let pre_root = imp_root(&new_checked(&mut proof, config.proof_height()).unwrap());
+
+        let mut host = MemoryHost::new(pre_root, blob.clone());
+        let post_root = run(&mut host, &config).unwrap();
+        assert_eq!(Ok(post_root), process_block(&pre_root, &block, &config));
+        assert_eq!(host.post_state_root, Some(post_root));
+
+        let mut host = MemoryHost::new([0u8; 32], blob);
+        assert_eq!(run(&mut host, &config), Err(Error::PreStateRootInvalid));
+        assert_eq!(host.post_state_root, None);
+
+        host.debug_print("rejected");
+        assert_eq!(host.messages, vec![String::from("rejected")]);
+    }
+}
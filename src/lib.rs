@@ -1,106 +1,145 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "alloc")]
 extern crate alloc;
+#[cfg(feature = "alloc")]
 extern crate qimalloc;
 
 pub mod account;
 pub mod address;
+#[cfg(feature = "aggregate")]
+pub mod aggregate;
+pub mod amount;
+#[cfg(feature = "bech32")]
+pub mod bech32;
+#[cfg(feature = "wasm-bindgen")]
+pub mod bindings;
+#[cfg(feature = "alloc")]
+pub mod block;
+#[cfg(feature = "alloc")]
+pub mod bloom;
 pub mod bls;
+#[cfg(feature = "compress")]
+pub mod compress;
+#[cfg(feature = "alloc")]
+pub mod config;
+#[cfg(feature = "alloc")]
+pub mod cross;
+#[cfg(feature = "alloc")]
+pub mod deposit;
+#[cfg(feature = "alloc")]
+pub mod diff;
+pub mod domain;
+#[cfg(feature = "envelope")]
+pub mod envelope;
 pub mod error;
+pub mod fixed;
+pub mod gas;
+pub mod gindex;
 pub mod hash;
+#[cfg(feature = "alloc")]
+pub mod history;
+#[cfg(feature = "alloc")]
+pub mod host;
+#[cfg(feature = "alloc")]
+pub mod log;
+pub mod nonce;
+#[cfg(feature = "alloc")]
 pub mod process;
+#[cfg(feature = "alloc")]
+pub mod receipt;
+pub mod schema;
+#[cfg(feature = "scout")]
+pub mod scout;
+#[cfg(feature = "secp256k1")]
+pub mod secp256k1;
+#[cfg(feature = "serde")]
+pub mod serialize;
+#[cfg(feature = "alloc")]
+pub mod settlement;
 pub mod state;
+#[cfg(feature = "stealth")]
+pub mod stealth;
 pub mod transaction;
 pub mod u264;
-
-use crate::process::process_transactions;
-use crate::transaction::{Transaction, Transfer};
-
-#[cfg(feature = "scout")]
+#[cfg(feature = "alloc")]
+pub mod withdrawal;
+
+use crate::address::Address;
+#[cfg(feature = "alloc")]
+use crate::config::Config;
+#[cfg(feature = "alloc")]
+use crate::error::Error;
+#[cfg(feature = "alloc")]
+use crate::transaction::{decode_transactions_prefix, Transaction};
+
+#[cfg(feature = "alloc")]
+pub use crate::block::{
+    process_block, process_block_diff, process_block_header, process_block_receipts, process_chain,
+    verify_block, verify_block_with, BlockData, BlockHeader,
+};
+pub use crate::fixed::process_fixed;
+
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 use arrayref::array_ref;
-use imp::Imp;
 
 // A global memory allocator is provided as part of the Rust standard library. When a crate is
 // compiled using `no_std` and dynamically allocates memory, it must specify an allocator it wishes
 // to use. `QIMalloc` is a "quick incremental memory allocator" that doesn't bother with
 // deallocating memory, since these runtimes are short-lived.
-#[cfg(not(feature = "std"))]
+#[cfg(all(feature = "alloc", not(feature = "std")))]
 #[global_allocator]
 static ALLOC: qimalloc::QIMalloc = qimalloc::QIMalloc::INIT;
 
-// This is a list of functions that `ewasm` environments support. They provide additional data and
-// functionality to execution environments. Each function is implemented in the host environment.
-#[cfg(feature = "scout")]
-mod native {
-    extern "C" {
-        pub fn eth2_loadPreStateRoot(offset: *const u32);
-        pub fn eth2_blockDataSize() -> u32;
-        pub fn eth2_blockDataCopy(outputOfset: *const u32, offset: u32, length: u32);
-        pub fn eth2_savePostStateRoot(offset: *const u32);
-    }
-}
-
-#[cfg(feature = "scout")]
-#[no_mangle]
-pub extern "C" fn main() {
-    let input_size = unsafe { native::eth2_blockDataSize() as usize };
-
-    // Copy input into buffer (buffer fixed at 42kb for now)
-    let mut input = [0u8; 42000];
-    unsafe {
-        native::eth2_blockDataCopy(input.as_mut_ptr() as *const u32, 0, input_size as u32);
-    }
-
-    // Get pre-state-root
-    let mut pre_state_root = [0u8; 32];
-    unsafe { native::eth2_loadPreStateRoot(pre_state_root.as_mut_ptr() as *const u32) }
-
-    // Process input data
-    let post_root = process_data_blob(&mut input, &pre_state_root);
+/// Length of the header at the start of a blob, which is the number of transactions (4, LE),
+/// the address of the block's proposer (32), who is paid the transactions' fees, and the block's
+/// slot (8, LE), which transactions must be valid until.
+pub const BLOB_HEADER_LENGTH: usize = 44;
 
-    // Return post state
-    unsafe { native::eth2_savePostStateRoot(post_root.as_ptr() as *const u32) }
-}
-
-pub fn process_data_blob(blob: &mut [u8], pre_state_root: &[u8; 32]) -> [u8; 32] {
+/// Process a blob laid out as `header || transactions || proof`, returning the post-state root.
+/// The proof is updated in place rather than copied, unlike `process_block`, and the blob is
+/// expected to be valid.
+#[cfg(feature = "alloc")]
+pub fn process_data_blob(blob: &mut [u8], pre_state_root: &[u8; 32], config: &Config) -> [u8; 32] {
     // Deserialize transactions from byte array. Although this is essentially copying all the
     // transactions, it appears to not have a massive cost. We can optimize later.
     let tx_count = u32::from_le_bytes(*array_ref!(blob, 0, 4)) as usize;
-    let transactions = deserialize_transactions(&blob, tx_count);
-
-    // Load multi-merkle proof
-    let mut mem = Imp::new(&mut blob[(4 + tx_count * 176)..], 259);
-
-    // Verify pre_state_root
-    let pre_root = mem.root();
-    assert_eq!(pre_state_root, &pre_root);
-
-    // Proccess all transactions (only transfers for now)
-    assert_eq!(process_transactions(&mut mem, &transactions), Ok(()));
-
-    mem.root()
+    let proposer = deserialize_proposer(blob);
+    let slot = deserialize_slot(blob);
+    let (transactions, offset) =
+        deserialize_transactions(blob, tx_count).expect("Transactions to be valid");
+
+    block::transition(
+        &mut blob[offset..],
+        pre_state_root,
+        &transactions,
+        proposer,
+        slot,
+        config,
+        block::Outputs::default(),
+    )
+    .expect("Block to be valid")
+    .0
 }
 
-pub fn deserialize_transactions(data: &[u8], tx_count: usize) -> Vec<Transaction> {
-    unsafe {
-        let mut ret = Vec::<Transaction>::new();
-
-        for i in (0..4 + (tx_count * 176)).skip(4).step_by(176) {
-            let mut buf = [0u8; 176];
-            buf.copy_from_slice(&data[i..(i + 176)]);
-
-            let tx = Transaction::Transfer(Transfer {
-                to: (*array_ref![buf, 0, 32]).into(),
-                from: (*array_ref![buf, 32, 32]).into(),
-                nonce: u64::from_le_bytes(*array_ref![buf, 64, 8]),
-                amount: u64::from_le_bytes(*array_ref![buf, 72, 8]),
-                signature: *array_ref![buf, 80, 96],
-            });
+/// Returns the address of the block's proposer from the header of a blob.
+pub fn deserialize_proposer(data: &[u8]) -> Address {
+    (*array_ref![data, 4, 32]).into()
+}
 
-            ret.push(tx);
-        }
+/// Returns the slot of the block from the header of a blob.
+pub fn deserialize_slot(data: &[u8]) -> u64 {
+    u64::from_le_bytes(*array_ref![data, 36, 8])
+}
 
-        ret
-    }
+/// Deserialize the `tx_count` transactions which follow the header of a blob, see
+/// `transaction::encode_transactions`, returning them and the offset of the proof which follows.
+#[cfg(feature = "alloc")]
+pub fn deserialize_transactions(
+    data: &[u8],
+    tx_count: usize,
+) -> Result<(Vec<Transaction>, usize), Error> {
+    decode_transactions_prefix(&data[BLOB_HEADER_LENGTH..], tx_count)
+        .map(|(transactions, len)| (transactions, BLOB_HEADER_LENGTH + len))
 }
@@ -0,0 +1,206 @@
+//! Logs of the payments made by a processed block. A block's header commits to the root of its
+//! logs, so a recipient can prove they were paid with a log and a proof rather than the whole
+//! block.
+
+use crate::address::Address;
+use crate::amount::Amount;
+use crate::block::InclusionProof;
+use crate::hash::{branch_root, hash_pair, list_proof, list_root, H256};
+use crate::receipt::TxReceipt;
+use crate::transaction::Transaction;
+use alloc::vec::Vec;
+
+/// Depth of the tree of a block's logs, which limits a block to `2**32` of them.
+pub const LOGS_TREE_DEPTH: usize = 32;
+
+/// A payment made by an applied transfer, or by one of the payments of an applied batch.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Log {
+    pub from: Address,
+    pub to: Address,
+    pub amount: Amount,
+
+    /// The `Transaction::hash` of the transaction which made the payment.
+    pub tx_hash: H256,
+}
+
+impl Log {
+    /// Returns the `hash_tree_root` of the log as an SSZ container:
+    ///
+    /// ```text
+    /// root = hash(hash(from, to), hash(amount, tx_hash))
+    /// ```
+    pub fn root(&self) -> H256 {
+        let mut amount = [0u8; 32];
+        amount[0..8].copy_from_slice(&self.amount.get().to_le_bytes());
+
+        hash_pair(
+            &hash_pair(&<[u8; 32]>::from(self.from), &<[u8; 32]>::from(self.to)),
+            &hash_pair(&amount, &self.tx_hash),
+        )
+    }
+}
+
+/// Returns the logs of the payments made by `transactions`, in order, given their `receipts`. A
+/// skipped transaction makes no payments.
+pub fn logs(transactions: &[Transaction], receipts: &[TxReceipt]) -> Vec<Log> {
+    let mut ret = Vec::new();
+
+    for (tx, receipt) in transactions.iter().zip(receipts.iter()) {
+        if !receipt.applied {
+            continue;
+        }
+
+        match tx {
+            Transaction::Transfer(t) => ret.push(Log {
+                from: t.from,
+                to: t.to,
+                amount: t.amount,
+                tx_hash: tx.hash(),
+            }),
+            Transaction::Batch(b) => {
+                let tx_hash = tx.hash();
+                ret.extend(b.payments.iter().map(|p| Log {
+                    from: b.from,
+                    to: p.to,
+                    amount: p.amount,
+                    tx_hash,
+                }));
+            }
+            _ => (),
+        }
+    }
+
+    ret
+}
+
+/// Returns the root of a block's `logs`, the root of their SSZ list.
+pub fn logs_root(logs: &[Log]) -> H256 {
+    let leaves: Vec<H256> = logs.iter().map(|l| l.root()).collect();
+    list_root(&leaves, LOGS_TREE_DEPTH)
+}
+
+/// Returns the proof that `logs[index]` is in their `logs_root`, or `None` if there aren't that
+/// many logs.
+pub fn prove_log_inclusion(logs: &[Log], index: usize) -> Option<InclusionProof> {
+    if index >= logs.len() {
+        return None;
+    }
+
+    let leaves: Vec<H256> = logs.iter().map(|l| l.root()).collect();
+
+    Some(InclusionProof {
+        index: index as u64,
+        branch: list_proof(&leaves, LOGS_TREE_DEPTH, index),
+    })
+}
+
+/// Returns whether `proof` shows that `log` is in the block whose logs root is `root`.
+pub fn verify_log_inclusion(root: &H256, log: &Log, proof: &InclusionProof) -> bool {
+    proof.index >> LOGS_TREE_DEPTH == 0
+        && proof.branch.len() == LOGS_TREE_DEPTH + 1
+        && branch_root(log.root(), proof.index, &proof.branch) == *root
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::transaction::{Batch, Payment, Transfer};
+
+    fn build_transactions() -> Vec<Transaction> {
+        vec![
+            Transaction::Transfer(Transfer {
+                to: 1.into(),
+                from: 0.into(),
+                nonce: 0.into(),
+                amount: 4.into(),
+                fee: 1.into(),
+                valid_until: None,
+                signature: [0u8; 96],
+            }),
+            Transaction::Transfer(Transfer {
+                to: 2.into(),
+                from: 0.into(),
+                nonce: 1.into(),
+                amount: 5.into(),
+                fee: 1.into(),
+                valid_until: None,
+                signature: [0u8; 96],
+            }),
+            Transaction::Batch(Batch {
+                from: 0.into(),
+                nonce: 1.into(),
+                payments: vec![
+                    Payment {
+                        to: 1.into(),
+                        amount: 2.into(),
+                    },
+                    Payment {
+                        to: 2.into(),
+                        amount: 3.into(),
+                    },
+                ],
+                fee: 1.into(),
+                valid_until: None,
+                signature: [0u8; 96],
+            }),
+        ]
+    }
+
+    fn applied(applied: bool) -> TxReceipt {
+        TxReceipt {
+            applied,
+            ..TxReceipt::skipped()
+        }
+    }
+
+    #[test]
+    fn root() {
+        let log = Log {
+            from: 1.into(),
+            to: 2.into(),
+            amount: 3.into(),
+            tx_hash: [4u8; 32],
+        };
+
+        // hash_tree_root(Log) as computed by the SSZ spec.
+        assert_eq!(
+            hex::encode(log.root()),
+            "b41fe878bcbe8f0b785b1c4f93be863b80710eb91bc970acd723118811c2dbb1"
+        );
+    }
+
+    #[test]
+    fn logs_of_applied_payments() {
+        let transactions = build_transactions();
+        let receipts = vec![applied(true), applied(false), applied(true)];
+        let logs = logs(&transactions, &receipts);
+
+        assert_eq!(logs.len(), 3);
+        assert_eq!(logs[0].to, 1.into());
+        assert_eq!(logs[0].tx_hash, transactions[0].hash());
+        assert_eq!(logs[2].amount, 3.into());
+        assert_eq!(logs[1].tx_hash, transactions[2].hash());
+        assert_eq!(logs[2].tx_hash, transactions[2].hash());
+    }
+
+    #[test]
+    fn log_inclusion() {
+        let transactions = build_transactions();
+        let logs = logs(
+            &transactions,
+            &[applied(true), applied(true), applied(true)],
+        );
+        let root = logs_root(&logs);
+
+        for (i, log) in logs.iter().enumerate() {
+            let proof = prove_log_inclusion(&logs, i).unwrap();
+            assert!(verify_log_inclusion(&root, log, &proof));
+        }
+
+        let proof = prove_log_inclusion(&logs, 1).unwrap();
+        assert!(!verify_log_inclusion(&root, &logs[0], &proof));
+        assert_eq!(prove_log_inclusion(&logs, logs.len()), None);
+    }
+}
@@ -1,4 +1,5 @@
 use arrayref::array_ref;
+use sheth::config::Config;
 use sheth::process_data_blob;
 use std::fs;
 
@@ -15,7 +16,11 @@ pub fn main() {
     let mut input = hex::decode(args[2]).unwrap();
 
     // Process input data
-    let post_root = process_data_blob(&mut input, array_ref![pre_state_root, 0, 32]);
+    let post_root = process_data_blob(
+        &mut input,
+        array_ref![pre_state_root, 0, 32],
+        &Config::default(),
+    );
 
     assert_eq!(post_root, *array_ref![post_state_root, 0, 32]);
 
@@ -0,0 +1,42 @@
+use crate::error::Error;
+
+/// The number of transactions an account has sent.
+///
+/// A nonce can only ever be advanced by one with `next`, so it is impossible for an account's
+/// nonce to move backwards or skip ahead.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Nonce(u64);
+
+impl Nonce {
+    pub const fn new(n: u64) -> Nonce {
+        Nonce(n)
+    }
+
+    pub const fn get(self) -> u64 {
+        self.0
+    }
+
+    /// Returns the nonce which follows this one.
+    pub fn next(self) -> Result<Nonce, Error> {
+        self.0.checked_add(1).map(Nonce).ok_or(Error::Overflow)
+    }
+}
+
+impl From<u64> for Nonce {
+    fn from(n: u64) -> Nonce {
+        Nonce(n)
+    }
+}
+
+impl From<Nonce> for u64 {
+    fn from(nonce: Nonce) -> u64 {
+        nonce.0
+    }
+}
+
+impl core::fmt::Display for Nonce {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
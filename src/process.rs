@@ -1,35 +1,707 @@
+use crate::address::Address;
+use crate::amount::Amount;
+use crate::bls::{PublicKey, SignatureVerifier, Unchecked};
+use crate::cross::{receipts_root, Claim, Receipts, TransferReceipt};
+use crate::deposit::{Deposit, Deposits};
+use crate::domain::Domain;
 use crate::error::Error;
+use crate::gas::Schedule;
+use crate::hash::H256;
+use crate::nonce::Nonce;
+use crate::receipt::{Balance, TxReceipt};
+use crate::settlement::Settlement;
+use crate::state::buffer::Buffer;
 use crate::state::State;
-use crate::transaction::{Transaction, Transfer};
+use crate::transaction::{Batch, ChangeKey, Transaction, Transfer};
+use crate::withdrawal::{withdrawals_root, WithdrawalReceipt};
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cell::Cell;
 
-pub fn process_transactions<'a, T: State>(
+/// Limits enforced while processing a block. A limit of `None` is unbounded.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Limits {
+    /// The maximum number of distinct accounts the block may touch.
+    pub accounts_touched: Option<usize>,
+
+    /// The maximum number of state operations the block may make before it is aborted with
+    /// `Error::Timeout`.
+    pub steps: Option<usize>,
+
+    /// The slot of the block, which transactions must be valid until to be applied. Blobs set
+    /// it from their header; when it is `None` expiry isn't checked.
+    pub slot: Option<u64>,
+
+    /// The beacon chain's deposits, which deposits must be proven against to be applied. The
+    /// host supplies it, since a root taken from the blob could claim any deposit; when it is
+    /// `None` every deposit is skipped.
+    pub deposits: Option<Deposits>,
+
+    /// The foreign receipts root which claims are proven against, supplied by the host for the
+    /// same reason; when it is `None` every claim is skipped.
+    pub receipts: Option<Receipts>,
+
+    /// The maximum gas the block's applied transactions may use before it is aborted with
+    /// `Error::GasLimitExceeded`.
+    pub gas: Option<u64>,
+
+    /// The prices gas is charged at.
+    pub schedule: Schedule,
+
+    /// Whether a transaction which can't be applied is skipped or rejects the block.
+    pub mode: Mode,
+
+    /// The chain transactions must be signed for.
+    pub domain: Domain,
+
+    /// How a transaction identical to one earlier in the block is treated.
+    pub duplicates: Duplicates,
+}
+
+/// How `execute` treats a transaction which can't be applied. Either way the transaction leaves
+/// the state unchanged, and exceeding a block limit always rejects the block.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Mode {
+    /// The transaction is skipped and the rest of the block is applied.
+    #[default]
+    Lenient,
+
+    /// The block is rejected with the transaction's error.
+    Strict,
+}
+
+/// How `execute` treats a copy of a transaction earlier in the same block. Copies are found by
+/// their `Transaction::hash` before any nonce is checked, so a copy is handled here rather than
+/// failing with `Error::NonceInvalid`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Duplicates {
+    /// The copy is skipped, whatever the `Mode`.
+    #[default]
+    Skip,
+
+    /// The block is rejected with `Error::TransactionDuplicate`.
+    Reject,
+}
+
+/// What happens to the fees a block collects once its transactions are applied.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum FeePolicy {
+    /// The fees are paid to the block's proposer, see `credit_fees`.
+    #[default]
+    Proposer,
+
+    /// The fees are taken from their senders and credited to no one.
+    Burn,
+}
+
+/// Metering collected while processing a block.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ExecutionResult {
+    /// The number of distinct accounts read or written by the block.
+    pub accounts_touched: usize,
+
+    /// The number of transactions that were applied to the state.
+    pub transactions_applied: usize,
+
+    /// The number of state operations made by the block.
+    pub steps: usize,
+
+    /// The total of the fees debited from the senders of the applied transactions, which are
+    /// left for the caller to credit to the block's proposer with `credit_fees`.
+    pub fees: Amount,
+
+    /// The number of deposits that were applied, which the host adds to `Deposits::index` for
+    /// the next block.
+    pub deposits_applied: usize,
+
+    /// The root of the receipts of the applied withdrawals, in the order they were applied, which
+    /// the withdrawals are credited against wherever their value moves to.
+    pub withdrawals_root: H256,
+
+    /// The root of the receipts of the applied cross transfers, in the order they were applied,
+    /// which their recipients claim against in the destination EE.
+    pub receipts_root: H256,
+
+    /// The total gas used by the applied transactions.
+    pub gas_used: u64,
+}
+
+/// Receipt of a transaction which was applied by `apply_transactions`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Receipt {
+    /// The sender's nonce after the transaction.
+    pub nonce: Nonce,
+
+    /// The sender's value after the transaction.
+    pub value: Amount,
+
+    /// The fee debited from the sender, which is owed to the block's proposer.
+    pub fee: Amount,
+
+    /// The gas used by the transaction, priced by the default `Schedule`.
+    pub gas_used: u64,
+}
+
+/// The receipt of a transaction, or the reason it was rejected. A rejected transaction leaves the
+/// state unchanged.
+pub type TxResult = Result<Receipt, Error>;
+
+/// Process `transactions` against `db` without any limits.
+pub fn process_transactions<T: State>(
     db: &mut T,
     transactions: &[Transaction],
 ) -> Result<(), Error> {
+    execute(db, transactions, &Limits::default()).map(|_| ())
+}
+
+/// Process `transactions` against `db`, enforcing `limits` and metering the work done. A
+/// transaction which can't be applied, e.g. because of its nonce or the sender's balance, is
+/// skipped without changing the state, or rejects the block if `limits.mode` is `Mode::Strict`.
+///
+/// Debits are aggregated per account and settled once all the transactions have been processed,
+/// so an account sending many transactions in a block only has its value written once.
+pub fn execute<T: State>(
+    db: &mut T,
+    transactions: &[Transaction],
+    limits: &Limits,
+) -> Result<ExecutionResult, Error> {
+    execute_with(db, transactions, limits, &Unchecked)
+}
+
+/// Like `execute`, but checks the signature of each transaction with `verifier` before applying
+/// it. A transaction with an invalid signature is treated like one with an invalid nonce.
+pub fn execute_with<T: State, V: SignatureVerifier>(
+    db: &mut T,
+    transactions: &[Transaction],
+    limits: &Limits,
+    verifier: &V,
+) -> Result<ExecutionResult, Error> {
+    run(db, transactions, limits, verifier, None)
+}
+
+/// Like `execute_with`, but also returns a receipt of every transaction, in order, whose root a
+/// block's header commits to. The balances in the receipts are read once each transaction's
+/// writes are made, so they don't count towards the block's steps or gas.
+pub fn execute_with_receipts<T: State, V: SignatureVerifier>(
+    db: &mut T,
+    transactions: &[Transaction],
+    limits: &Limits,
+    verifier: &V,
+) -> Result<(ExecutionResult, Vec<TxReceipt>), Error> {
+    let mut receipts = Vec::with_capacity(transactions.len());
+    let result = run(db, transactions, limits, verifier, Some(&mut receipts))?;
+
+    Ok((result, receipts))
+}
+
+fn run<T: State, V: SignatureVerifier>(
+    db: &mut T,
+    transactions: &[Transaction],
+    limits: &Limits,
+    verifier: &V,
+    mut tx_receipts: Option<&mut Vec<TxReceipt>>,
+) -> Result<ExecutionResult, Error> {
+    let meter = Meter::new(limits.steps);
+    let mut settlement = Settlement::new();
+    let mut touched: BTreeSet<Address> = BTreeSet::new();
+    let mut result = ExecutionResult::default();
+    let mut withdrawals: Vec<WithdrawalReceipt> = Vec::new();
+    let mut receipts: Vec<TransferReceipt> = Vec::new();
+    let mut seen: BTreeSet<H256> = BTreeSet::new();
+
     for tx in transactions {
-        if let Err(_) = tx.verify(db) {
+        if !seen.insert(tx.hash()) {
+            if limits.duplicates == Duplicates::Reject {
+                return Err(Error::TransactionDuplicate);
+            }
+
+            if let Some(tx_receipts) = tx_receipts.as_mut() {
+                tx_receipts.push(TxReceipt::skipped());
+            }
             continue;
         }
 
-        db.inc_nonce(tx.from())?;
+        let chunks = meter.chunks.get();
+
+        for address in tx.accounts().iter() {
+            touched.insert(*address);
+        }
+
+        if let Some(limit) = limits.accounts_touched {
+            if touched.len() > limit {
+                return Err(Error::AccountLimitExceeded);
+            }
+        }
+
+        let applied = apply_atomic(
+            db,
+            &meter,
+            &mut settlement,
+            tx,
+            limits,
+            result.deposits_applied,
+            verifier,
+        )?;
+
+        let receipt = match applied {
+            Ok(receipt) => receipt,
+            Err(Error::Timeout) => return Err(Error::Timeout),
+            Err(e) if limits.mode == Mode::Strict => return Err(e),
+            Err(_) => {
+                if let Some(tx_receipts) = tx_receipts.as_mut() {
+                    tx_receipts.push(TxReceipt::skipped());
+                }
+                continue;
+            }
+        };
+
+        result.transactions_applied += 1;
+        result.fees = result.fees.checked_add(receipt.fee)?;
+
+        // Only applied transactions are charged, for the chunks of their checks and writes.
+        let gas = limits.schedule.cost(meter.chunks.get() - chunks);
+        result.gas_used = result.gas_used.saturating_add(gas);
+        if let Some(limit) = limits.gas {
+            if result.gas_used > limit {
+                return Err(Error::GasLimitExceeded);
+            }
+        }
+
+        if let Some(tx_receipts) = tx_receipts.as_mut() {
+            // A claim's marker account holds no value, so its value needn't be in the witness.
+            let accounts = match tx {
+                Transaction::Claim(c) => vec![c.receipt.to],
+                _ => tx.accounts(),
+            };
+
+            let mut balances: Vec<Balance> = Vec::new();
+            for address in accounts {
+                if balances.iter().all(|b| b.address != address) {
+                    balances.push(Balance {
+                        address,
+                        value: settlement.value(db, address)?,
+                    });
+                }
+            }
+
+            tx_receipts.push(TxReceipt {
+                applied: true,
+                gas_used: gas,
+                balances,
+            });
+        }
 
         match tx {
-            Transaction::Transfer(t) => transfer(db, t)?,
-            Transaction::Deposit(_) => unimplemented!(),
-            Transaction::Withdrawal(_) => unimplemented!(),
+            Transaction::Deposit(_) => result.deposits_applied += 1,
+            Transaction::Withdrawal(w) => withdrawals.push(w.receipt()),
+            Transaction::CrossTransfer(c) => receipts.push(c.receipt()),
+            _ => (),
         }
     }
 
-    Ok(())
+    settlement.settle(&mut Metered::new(db, &meter))?;
+    result.withdrawals_root = withdrawals_root(&withdrawals);
+    result.receipts_root = receipts_root(&receipts);
+    result.accounts_touched = touched.len();
+    result.steps = meter.steps.get();
+
+    Ok(result)
+}
+
+/// Process `transactions` against `db`, returning a receipt for each one.
+///
+/// Unlike `execute`, a transaction which can't be applied is rejected rather than failing the
+/// block. An error is only returned if the pending debits can't be settled at the end of the
+/// block. As with `execute`, the fees in the receipts are debited but not credited. There is no
+/// block slot, so expiry isn't checked; callers which have one check `Transaction::verify_slot`.
+/// Nor is there a deposit root or foreign receipts root, so deposits are rejected with
+/// `Error::DepositInvalid` and claims with `Error::ReceiptInvalid`. The receipt of an applied
+/// withdrawal or cross transfer is its `receipt`. Gas is priced by the default `Schedule`, and
+/// there is no block gas limit. A copy of an earlier transaction is rejected with
+/// `Error::TransactionDuplicate`.
+pub fn apply_transactions<T: State>(
+    db: &mut T,
+    transactions: &[Transaction],
+) -> Result<Vec<TxResult>, Error> {
+    apply_transactions_with(db, transactions, &Unchecked)
+}
+
+/// Like `apply_transactions`, but rejects transactions whose signature `verifier` doesn't accept
+/// with `Error::SignatureInvalid`.
+pub fn apply_transactions_with<T: State, V: SignatureVerifier>(
+    db: &mut T,
+    transactions: &[Transaction],
+    verifier: &V,
+) -> Result<Vec<TxResult>, Error> {
+    let meter = Meter::new(None);
+    let mut settlement = Settlement::new();
+    let limits = Limits::default();
+
+    let mut seen: BTreeSet<H256> = BTreeSet::new();
+
+    let mut results = Vec::with_capacity(transactions.len());
+    for tx in transactions {
+        if !seen.insert(tx.hash()) {
+            results.push(Err(Error::TransactionDuplicate));
+            continue;
+        }
+
+        let chunks = meter.chunks.get();
+        let applied = apply_atomic(db, &meter, &mut settlement, tx, &limits, 0, verifier)?;
+
+        results.push(applied.map(|receipt| Receipt {
+            gas_used: limits.schedule.cost(meter.chunks.get() - chunks),
+            ..receipt
+        }));
+    }
+
+    settlement.settle(db)?;
+
+    Ok(results)
+}
+
+/// Check and apply `tx` to `db`, metering its state operations with `meter`. Its writes and
+/// debits are buffered until every step has succeeded, so a transaction which fails part way
+/// through leaves the state and `settlement` unchanged. The outer error is returned if the
+/// buffered writes can't be committed.
+fn apply_atomic<T: State, V: SignatureVerifier>(
+    db: &mut T,
+    meter: &Meter,
+    settlement: &mut Settlement,
+    tx: &Transaction,
+    limits: &Limits,
+    deposits_applied: usize,
+    verifier: &V,
+) -> Result<TxResult, Error> {
+    let mut buffer = Buffer::new(db);
+    let mut pending = settlement.clone();
+
+    let applied = {
+        let mut db = Metered::new(&mut buffer, meter);
+        check(&db, tx, limits, deposits_applied, verifier)
+            .and_then(|()| apply(&mut db, &mut pending, tx))
+    };
+
+    if applied.is_ok() {
+        buffer.commit()?;
+        *settlement = pending;
+    }
+
+    Ok(applied)
+}
+
+/// Check that `tx` can be applied in a block processed with `limits`, once `deposits_applied` of
+/// the block's deposits have been.
+fn check<T: State, V: SignatureVerifier>(
+    db: &T,
+    tx: &Transaction,
+    limits: &Limits,
+    deposits_applied: usize,
+    verifier: &V,
+) -> Result<(), Error> {
+    if let Some(slot) = limits.slot {
+        tx.verify_slot(slot)?;
+    }
+
+    match tx {
+        Transaction::Deposit(deposit) => {
+            // Each deposit must follow the last one applied, so none is applied twice.
+            let deposits = limits.deposits.ok_or(Error::DepositInvalid)?;
+            deposit.verify(&Deposits {
+                index: deposits.index + deposits_applied as u64,
+                ..deposits
+            })?;
+        }
+        Transaction::Claim(claim) => {
+            let receipts = limits.receipts.ok_or(Error::ReceiptInvalid)?;
+            claim.verify(db, &receipts)?;
+        }
+        _ => (),
+    }
+
+    tx.verify_with(db, verifier, &limits.domain)
+}
+
+fn apply<T: State>(db: &mut T, settlement: &mut Settlement, tx: &Transaction) -> TxResult {
+    match tx {
+        Transaction::Transfer(t) => transfer(db, settlement, t),
+        Transaction::Batch(b) => batch(db, settlement, b),
+        Transaction::Deposit(d) => deposit(db, settlement, d),
+        Transaction::Withdrawal(w) => send(db, settlement, w.from, w.amount, w.fee),
+        Transaction::CrossTransfer(c) => send(db, settlement, c.from, c.amount, c.fee),
+        Transaction::Claim(c) => claim(db, settlement, c),
+        Transaction::ChangeKey(k) => change_key(db, settlement, k),
+    }
+}
+
+/// Settle the `fees` collected by a block according to `policy`, either crediting them to
+/// `proposer` with `credit_fees` or burning them.
+pub fn settle_fees<T: State>(
+    db: &mut T,
+    proposer: Address,
+    fees: Amount,
+    policy: FeePolicy,
+) -> Result<(), Error> {
+    match policy {
+        FeePolicy::Proposer => credit_fees(db, proposer, fees),
+        FeePolicy::Burn => Ok(()),
+    }
 }
 
-fn transfer<'a, T: State>(db: &mut T, tx: &Transfer) -> Result<(), Error> {
-    db.sub_value(tx.from, tx.amount)?;
-    db.add_value(tx.to, tx.amount)?;
+/// Credit the `fees` collected by `execute` or `apply_transactions` to the block's `proposer`. The
+/// proposer is only read when there are fees, so blocks without them needn't prove its account.
+pub fn credit_fees<T: State>(db: &mut T, proposer: Address, fees: Amount) -> Result<(), Error> {
+    if fees != Amount::default() {
+        db.add_value(proposer, fees)?;
+    }
 
     Ok(())
 }
 
+fn transfer<T: State>(db: &mut T, settlement: &mut Settlement, tx: &Transfer) -> TxResult {
+    // Check that every write will succeed before making any, so a transfer which fails leaves
+    // the state unchanged. The sender is debited before the credit is checked, since it may also
+    // be the recipient, but debits are only recorded in `settlement`.
+    let total = tx.amount.checked_add(tx.fee)?;
+    if settlement.value(db, tx.from)? < total {
+        return Err(Error::BalanceInsufficient);
+    }
+
+    db.nonce(tx.from)?.next()?;
+    settlement.debit(db, tx.from, total)?;
+    settlement.value(db, tx.to)?.checked_add(tx.amount)?;
+
+    settlement.credit(db, tx.to, tx.amount)?;
+    let nonce = db.inc_nonce(tx.from)?;
+
+    Ok(Receipt {
+        nonce,
+        value: settlement.value(db, tx.from)?,
+        fee: tx.fee,
+        gas_used: 0,
+    })
+}
+
+fn batch<T: State>(db: &mut T, settlement: &mut Settlement, tx: &Batch) -> TxResult {
+    // Credits are totalled per recipient first, so a recipient paid more than once is checked
+    // for overflow against everything it will receive.
+    let mut credits: BTreeMap<Address, Amount> = BTreeMap::new();
+    for payment in tx.payments.iter() {
+        let credit = credits.entry(payment.to).or_default();
+        *credit = credit.checked_add(payment.amount)?;
+    }
+
+    let total = tx.total()?.checked_add(tx.fee)?;
+    if settlement.value(db, tx.from)? < total {
+        return Err(Error::BalanceInsufficient);
+    }
+
+    // As in `transfer`, the sender is debited before the credits are checked.
+    db.nonce(tx.from)?.next()?;
+    settlement.debit(db, tx.from, total)?;
+    for (to, amount) in credits.iter() {
+        settlement.value(db, *to)?.checked_add(*amount)?;
+    }
+
+    for (to, amount) in credits {
+        settlement.credit(db, to, amount)?;
+    }
+    let nonce = db.inc_nonce(tx.from)?;
+
+    Ok(Receipt {
+        nonce,
+        value: settlement.value(db, tx.from)?,
+        fee: tx.fee,
+        gas_used: 0,
+    })
+}
+
+/// Debit `amount` and `fee` from `from` for a withdrawal or cross transfer. The amount leaves the
+/// EE, so only the sender is written.
+fn send<T: State>(
+    db: &mut T,
+    settlement: &mut Settlement,
+    from: Address,
+    amount: Amount,
+    fee: Amount,
+) -> TxResult {
+    let total = amount.checked_add(fee)?;
+    if settlement.value(db, from)? < total {
+        return Err(Error::BalanceInsufficient);
+    }
+
+    db.nonce(from)?.next()?;
+
+    settlement.debit(db, from, total)?;
+    let nonce = db.inc_nonce(from)?;
+
+    Ok(Receipt {
+        nonce,
+        value: settlement.value(db, from)?,
+        fee,
+        gas_used: 0,
+    })
+}
+
+fn change_key<T: State>(db: &mut T, settlement: &mut Settlement, tx: &ChangeKey) -> TxResult {
+    if settlement.value(db, tx.from)? < tx.fee {
+        return Err(Error::BalanceInsufficient);
+    }
+
+    db.nonce(tx.from)?.next()?;
+
+    settlement.debit(db, tx.from, tx.fee)?;
+    db.set_pubkey(tx.from, tx.pubkey.clone())?;
+    let nonce = db.inc_nonce(tx.from)?;
+
+    Ok(Receipt {
+        nonce,
+        value: settlement.value(db, tx.from)?,
+        fee: tx.fee,
+        gas_used: 0,
+    })
+}
+
+fn claim<T: State>(db: &mut T, settlement: &mut Settlement, tx: &Claim) -> TxResult {
+    let to = tx.receipt.to;
+    let claimed = tx.claimed_address();
+
+    if db.nonce(claimed)? != Nonce::new(0) {
+        return Err(Error::ReceiptClaimed);
+    }
+    settlement.value(db, to)?.checked_add(tx.receipt.amount)?;
+
+    db.inc_nonce(claimed)?;
+    settlement.credit(db, to, tx.receipt.amount)?;
+
+    Ok(Receipt {
+        nonce: db.nonce(to)?,
+        value: settlement.value(db, to)?,
+        fee: Amount::zero(),
+        gas_used: 0,
+    })
+}
+
+fn deposit<T: State>(db: &mut T, settlement: &mut Settlement, tx: &Deposit) -> TxResult {
+    let to = tx.to();
+    settlement.value(db, to)?.checked_add(tx.amount)?;
+
+    // An empty account is created for the depositor's key, so it can spend what it receives.
+    // Otherwise the deposit is credited to whoever owns the account.
+    match db.create_account(to, tx.pubkey.clone()) {
+        Ok(()) | Err(Error::AccountExists) => (),
+        Err(e) => return Err(e),
+    }
+    settlement.credit(db, to, tx.amount)?;
+
+    Ok(Receipt {
+        nonce: db.nonce(to)?,
+        value: settlement.value(db, to)?,
+        fee: Amount::zero(),
+        gas_used: 0,
+    })
+}
+
+/// Counts the operations made on the state, so hostile blocks can be aborted once they exceed the
+/// host's budget, and the witness chunks they access, which gas is charged for.
+struct Meter {
+    steps: Cell<usize>,
+    chunks: Cell<usize>,
+    limit: Option<usize>,
+}
+
+impl Meter {
+    fn new(limit: Option<usize>) -> Self {
+        Self {
+            steps: Cell::new(0),
+            chunks: Cell::new(0),
+            limit,
+        }
+    }
+
+    /// Count an operation which accesses `chunks` witness chunks.
+    fn step(&self, chunks: usize) -> Result<(), Error> {
+        let steps = self.steps.get() + 1;
+        self.steps.set(steps);
+        self.chunks.set(self.chunks.get() + chunks);
+
+        match self.limit {
+            Some(limit) if steps > limit => Err(Error::Timeout),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Counts the operations made on `db` with a `Meter`, which is shared by every transaction in the
+/// block.
+struct Metered<'a, T: State> {
+    db: &'a mut T,
+    meter: &'a Meter,
+}
+
+impl<'a, T: State> Metered<'a, T> {
+    fn new(db: &'a mut T, meter: &'a Meter) -> Self {
+        Self { db, meter }
+    }
+
+    fn step(&self, chunks: usize) -> Result<(), Error> {
+        self.meter.step(chunks)
+    }
+}
+
+impl<'a, T: State> State for Metered<'a, T> {
+    fn root(&mut self) -> Result<H256, Error> {
+        self.step(0)?;
+        self.db.root()
+    }
+
+    fn value(&self, address: Address) -> Result<Amount, Error> {
+        self.step(1)?;
+        self.db.value(address)
+    }
+
+    fn nonce(&self, address: Address) -> Result<Nonce, Error> {
+        self.step(1)?;
+        self.db.nonce(address)
+    }
+
+    fn pubkey(&self, address: Address) -> Result<PublicKey, Error> {
+        self.step(2)?;
+        self.db.pubkey(address)
+    }
+
+    fn add_value(&mut self, address: Address, amount: Amount) -> Result<Amount, Error> {
+        self.step(1)?;
+        self.db.add_value(address, amount)
+    }
+
+    fn sub_value(&mut self, address: Address, amount: Amount) -> Result<Amount, Error> {
+        self.step(1)?;
+        self.db.sub_value(address, amount)
+    }
+
+    fn inc_nonce(&mut self, address: Address) -> Result<Nonce, Error> {
+        self.step(1)?;
+        self.db.inc_nonce(address)
+    }
+
+    fn set_pubkey(&mut self, address: Address, pubkey: PublicKey) -> Result<(), Error> {
+        self.step(2)?;
+        self.db.set_pubkey(address, pubkey)
+    }
+
+    fn create_account(&mut self, address: Address, pubkey: PublicKey) -> Result<(), Error> {
+        self.step(4)?;
+        self.db.create_account(address, pubkey)
+    }
+
+    fn delete_account(&mut self, address: Address) -> Result<Amount, Error> {
+        self.step(4)?;
+        self.db.delete_account(address)
+    }
+}
+
 #[cfg(feature = "std")]
 #[cfg(test)]
 mod test {
@@ -37,9 +709,17 @@ mod test {
     use crate::account::Account;
     use crate::address::Address;
     use crate::bls::PublicKey;
+    use crate::config::Config;
+    use crate::cross::{build_claim, CrossTransfer};
+    use crate::deposit::DEPOSIT_PROOF_LENGTH;
+    use crate::state::imp::new_checked;
     use crate::state::MockState;
-    use crate::transaction::{Transaction, Transfer};
+    use crate::transaction::ChangeKey;
+    use crate::transaction::{Payment, Transaction, Transfer};
+    use crate::withdrawal::Withdrawal;
     use bigint::U256;
+    use composer::accounts::AddressedAccount;
+    use composer::proof::cache::StateCache;
     use std::collections::BTreeMap;
 
     fn build_state() -> MockState {
@@ -70,22 +750,28 @@ mod test {
             Transaction::Transfer(Transfer {
                 to: U256::from(1).into(),
                 from: U256::from(0).into(),
-                nonce: 0,
-                amount: 2,
+                nonce: 0.into(),
+                amount: 2.into(),
+                fee: 0.into(),
+                valid_until: None,
                 signature: [0; 96],
             }),
             Transaction::Transfer(Transfer {
                 to: U256::from(1).into(),
                 from: U256::from(0).into(),
-                nonce: 1,
-                amount: 3,
+                nonce: 1.into(),
+                amount: 3.into(),
+                fee: 0.into(),
+                valid_until: None,
                 signature: [0; 96],
             }),
             Transaction::Transfer(Transfer {
                 to: U256::from(0).into(),
                 from: U256::from(1).into(),
-                nonce: 0,
-                amount: 5,
+                nonce: 0.into(),
+                amount: 5.into(),
+                fee: 0.into(),
+                valid_until: None,
                 signature: [0; 96],
             }),
         ];
@@ -97,13 +783,978 @@ mod test {
         let post_root = mem.root().unwrap();
 
         assert_eq!(
-            "000000000000000000000000000000000000000000000000babe8b8a8d142623",
+            "d3c21ec458d992dd803299a0b2eee6103905361e846bd55f103c1339616f81ae",
             hex::encode(pre_root)
         );
 
         assert_eq!(
-            "0000000000000000000000000000000000000000000000003265323fcea3d6a2",
+            "97a8dcc23e669a0264c554ffcb008d6768584071a51d91100e0e40f0b520e2fb",
             hex::encode(post_root)
         );
     }
+
+    fn build_transfer(from: usize, to: usize, nonce: u64) -> Transaction {
+        Transaction::Transfer(Transfer {
+            to: to.into(),
+            from: from.into(),
+            nonce: nonce.into(),
+            amount: 1.into(),
+            fee: 0.into(),
+            valid_until: None,
+            signature: [0; 96],
+        })
+    }
+
+    #[test]
+    fn credit_nets_pending_debit() {
+        let mut accounts: BTreeMap<Address, Account> = BTreeMap::new();
+        for (address, value) in [(0, Amount::MAX.get()), (1, 10), (2, 0)].iter() {
+            accounts.insert(
+                (*address).into(),
+                Account {
+                    pubkey: PublicKey::zero(),
+                    nonce: 0,
+                    value: *value,
+                },
+            );
+        }
+        let mut db = MockState::new(accounts);
+
+        // Account 0 can take the second transfer once the first is debited from it, although its
+        // value before the debit is settled can't.
+        let transfer = |from: usize, to: usize| match build_transfer(from, to, 0) {
+            Transaction::Transfer(t) => Transaction::Transfer(Transfer {
+                amount: 10.into(),
+                ..t
+            }),
+            _ => unreachable!(),
+        };
+        let transactions = [transfer(0, 2), transfer(1, 0)];
+
+        assert_eq!(
+            execute(&mut db, &transactions, &Limits::default())
+                .map(|result| result.transactions_applied),
+            Ok(2)
+        );
+        assert_eq!(db.value(0.into()), Ok(Amount::MAX));
+        assert_eq!(db.value(1.into()), Ok(0.into()));
+        assert_eq!(db.value(2.into()), Ok(10.into()));
+    }
+
+    #[test]
+    fn execute_meters_accounts() {
+        let transactions = vec![
+            build_transfer(0, 1, 0),
+            build_transfer(1, 1, 0),
+            build_transfer(0, 1, 5),
+        ];
+
+        let mut mem = build_state();
+
+        assert_eq!(
+            execute(&mut mem, &transactions, &Limits::default()),
+            Ok(ExecutionResult {
+                accounts_touched: 2,
+                transactions_applied: 2,
+                steps: 19,
+                fees: 0.into(),
+                deposits_applied: 0,
+                withdrawals_root: withdrawals_root(&[]),
+                receipts_root: receipts_root(&[]),
+                gas_used: 5200,
+            })
+        );
+    }
+
+    #[test]
+    fn execute_enforces_account_limit() {
+        let transactions = [build_transfer(0, 0, 0), build_transfer(0, 1, 1)];
+        let limits = Limits {
+            accounts_touched: Some(1),
+            ..Limits::default()
+        };
+
+        let mut mem = build_state();
+
+        assert_eq!(
+            execute(&mut mem, &transactions[0..1], &limits),
+            Ok(ExecutionResult {
+                accounts_touched: 1,
+                transactions_applied: 1,
+                steps: 9,
+                fees: 0.into(),
+                deposits_applied: 0,
+                withdrawals_root: withdrawals_root(&[]),
+                receipts_root: receipts_root(&[]),
+                gas_used: 2600,
+            })
+        );
+
+        assert_eq!(
+            execute(&mut mem, &transactions[1..], &limits),
+            Err(Error::AccountLimitExceeded)
+        );
+    }
+
+    #[test]
+    fn execute_enforces_step_limit() {
+        let transactions = [build_transfer(0, 1, 0), build_transfer(0, 1, 1)];
+        let limits = Limits {
+            steps: Some(9),
+            ..Limits::default()
+        };
+
+        let mut mem = build_state();
+        assert!(execute(&mut mem, &transactions[0..1], &limits).is_ok());
+
+        let mut mem = build_state();
+        assert_eq!(
+            execute(&mut mem, &transactions, &limits),
+            Err(Error::Timeout)
+        );
+    }
+
+    #[test]
+    fn execute_enforces_gas_limit() {
+        // Without a signature check each transfer accesses 8 chunks, the sender's nonce and value
+        // and the recipient's value.
+        let transactions = [build_transfer(0, 1, 0), build_transfer(0, 1, 1)];
+        let limits = Limits {
+            gas: Some(5_000),
+            schedule: Schedule {
+                base: 100,
+                per_chunk: 10,
+            },
+            ..Limits::default()
+        };
+
+        let mut mem = build_state();
+        assert_eq!(
+            execute(&mut mem, &transactions, &limits).map(|r| r.gas_used),
+            Ok(360)
+        );
+
+        let limits = Limits {
+            gas: Some(2_600),
+            ..Limits::default()
+        };
+
+        let mut mem = build_state();
+        assert!(execute(&mut mem, &transactions[0..1], &limits).is_ok());
+
+        let mut mem = build_state();
+        assert_eq!(
+            execute(&mut mem, &transactions, &limits),
+            Err(Error::GasLimitExceeded)
+        );
+    }
+
+    #[test]
+    fn execute_returns_tx_receipts() {
+        let transactions = [build_transfer(0, 1, 0), build_transfer(0, 1, 0)];
+
+        let mut mem = build_state();
+        let (result, receipts) =
+            execute_with_receipts(&mut mem, &transactions, &Limits::default(), &Unchecked).unwrap();
+
+        assert_eq!(result.transactions_applied, 1);
+        assert_eq!(
+            receipts,
+            vec![
+                TxReceipt {
+                    applied: true,
+                    gas_used: 2600,
+                    balances: vec![
+                        Balance {
+                            address: 0.into(),
+                            value: 4.into(),
+                        },
+                        Balance {
+                            address: 1.into(),
+                            value: 3.into(),
+                        },
+                    ],
+                },
+                TxReceipt::skipped(),
+            ]
+        );
+
+        // Reading the balances isn't metered.
+        let mut mem = build_state();
+        assert_eq!(
+            execute(&mut mem, &transactions, &Limits::default()),
+            Ok(result)
+        );
+    }
+
+    #[test]
+    fn apply_transactions_returns_receipts() {
+        let transactions = vec![
+            build_transfer(0, 1, 0),
+            // Reuses the first transfer's nonce.
+            build_transfer(0, 0, 0),
+            Transaction::Transfer(Transfer {
+                to: 0.into(),
+                from: 1.into(),
+                nonce: 0.into(),
+                amount: 4.into(),
+                fee: 0.into(),
+                valid_until: None,
+                signature: [0; 96],
+            }),
+            build_transfer(1, 0, 0),
+        ];
+
+        let mut mem = build_state();
+
+        assert_eq!(
+            apply_transactions(&mut mem, &transactions),
+            Ok(vec![
+                Ok(Receipt {
+                    nonce: 1.into(),
+                    value: 4.into(),
+                    fee: 0.into(),
+                    gas_used: 2600,
+                }),
+                Err(Error::NonceInvalid),
+                Err(Error::BalanceInsufficient),
+                Ok(Receipt {
+                    nonce: 1.into(),
+                    value: 2.into(),
+                    fee: 0.into(),
+                    gas_used: 2600,
+                }),
+            ])
+        );
+
+        assert_eq!(mem.value(0.into()), Ok(5.into()));
+        assert_eq!(mem.value(1.into()), Ok(2.into()));
+        assert_eq!(mem.nonce(1.into()), Ok(1.into()));
+    }
+
+    #[test]
+    fn rejected_transactions_leave_state_unchanged() {
+        let mut mem = build_state();
+        let mut expected = build_state();
+
+        let transactions = vec![Transaction::Transfer(Transfer {
+            to: 1.into(),
+            from: 0.into(),
+            nonce: 0.into(),
+            amount: 6.into(),
+            fee: 0.into(),
+            valid_until: None,
+            signature: [0; 96],
+        })];
+
+        assert_eq!(
+            apply_transactions(&mut mem, &transactions),
+            Ok(vec![Err(Error::BalanceInsufficient)])
+        );
+        assert_eq!(mem.root(), expected.root());
+    }
+
+    /// Answers `reads` value reads from the state it wraps, then fails as if the rest of the
+    /// witness were missing.
+    struct Truncated {
+        db: MockState,
+        reads: Cell<usize>,
+    }
+
+    impl State for Truncated {
+        fn root(&mut self) -> Result<H256, Error> {
+            self.db.root()
+        }
+
+        fn value(&self, address: Address) -> Result<Amount, Error> {
+            match self.reads.get() {
+                0 => Err(Error::StateIncomplete(address.into())),
+                n => {
+                    self.reads.set(n - 1);
+                    self.db.value(address)
+                }
+            }
+        }
+
+        fn nonce(&self, address: Address) -> Result<Nonce, Error> {
+            self.db.nonce(address)
+        }
+
+        fn pubkey(&self, address: Address) -> Result<PublicKey, Error> {
+            self.db.pubkey(address)
+        }
+
+        fn add_value(&mut self, address: Address, amount: Amount) -> Result<Amount, Error> {
+            self.db.add_value(address, amount)
+        }
+
+        fn sub_value(&mut self, address: Address, amount: Amount) -> Result<Amount, Error> {
+            self.db.sub_value(address, amount)
+        }
+
+        fn inc_nonce(&mut self, address: Address) -> Result<Nonce, Error> {
+            self.db.inc_nonce(address)
+        }
+
+        fn set_pubkey(&mut self, address: Address, pubkey: PublicKey) -> Result<(), Error> {
+            self.db.set_pubkey(address, pubkey)
+        }
+
+        fn create_account(&mut self, address: Address, pubkey: PublicKey) -> Result<(), Error> {
+            self.db.create_account(address, pubkey)
+        }
+
+        fn delete_account(&mut self, address: Address) -> Result<Amount, Error> {
+            self.db.delete_account(address)
+        }
+    }
+
+    #[test]
+    fn failed_transactions_are_rolled_back() {
+        // The transfer's debit is recorded and its recipient's value read before the read made
+        // by crediting the recipient fails.
+        let mut truncated = Truncated {
+            db: build_state(),
+            reads: Cell::new(3),
+        };
+
+        let transactions = [build_transfer(0, 1, 0)];
+        let results = apply_transactions(&mut truncated, &transactions[..]);
+        assert_eq!(
+            results,
+            Ok(vec![Err(Error::StateIncomplete(Address::from(1).into()))])
+        );
+
+        let mut expected = build_state();
+        assert_eq!(truncated.db.root(), expected.root());
+    }
+
+    /// Rejects every signature.
+    struct Reject;
+
+    impl SignatureVerifier for Reject {
+        fn verify(&self, _: &PublicKey, _: &H256, _: &[u8; 96]) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn invalid_signatures_are_not_applied() {
+        let transactions = vec![build_transfer(0, 1, 0), build_transfer(1, 0, 0)];
+
+        let mut mem = build_state();
+        let mut expected = build_state();
+
+        assert_eq!(
+            execute_with(&mut mem, &transactions, &Limits::default(), &Reject)
+                .map(|result| result.transactions_applied),
+            Ok(0)
+        );
+        assert_eq!(mem.root(), expected.root());
+
+        assert_eq!(
+            apply_transactions_with(&mut mem, &transactions, &Reject),
+            Ok(vec![
+                Err(Error::SignatureInvalid),
+                Err(Error::SignatureInvalid)
+            ])
+        );
+    }
+
+    fn build_batch(from: usize, nonce: u64, payments: &[(usize, u32)]) -> Transaction {
+        Transaction::Batch(Batch {
+            from: from.into(),
+            nonce: nonce.into(),
+            payments: payments
+                .iter()
+                .map(|(to, amount)| Payment {
+                    to: (*to).into(),
+                    amount: (*amount).into(),
+                })
+                .collect(),
+            fee: 0.into(),
+            valid_until: None,
+            signature: [0; 96],
+        })
+    }
+
+    #[test]
+    fn batch_pays_every_recipient() {
+        // Paying the same recipient twice and the sender itself are both allowed.
+        let transactions = vec![build_batch(0, 0, &[(1, 1), (1, 2), (0, 1)])];
+
+        let mut mem = build_state();
+        assert_eq!(
+            apply_transactions(&mut mem, &transactions),
+            Ok(vec![Ok(Receipt {
+                nonce: 1.into(),
+                value: 2.into(),
+                fee: 0.into(),
+                gas_used: 3000,
+            })])
+        );
+        assert_eq!(mem.value(1.into()), Ok(5.into()));
+        assert_eq!(mem.nonce(1.into()), Ok(0.into()));
+    }
+
+    #[test]
+    fn batch_is_atomic() {
+        let transactions = vec![build_batch(0, 0, &[(1, 3), (1, 3)])];
+
+        let mut mem = build_state();
+        let mut expected = build_state();
+
+        assert_eq!(
+            apply_transactions(&mut mem, &transactions),
+            Ok(vec![Err(Error::BalanceInsufficient)])
+        );
+        assert_eq!(mem.root(), expected.root());
+    }
+
+    #[test]
+    fn batch_touches_every_recipient() {
+        let transactions = vec![build_batch(0, 0, &[(1, 1), (2, 1)])];
+        let limits = Limits {
+            accounts_touched: Some(2),
+            ..Limits::default()
+        };
+
+        let mut mem = build_state();
+        assert_eq!(
+            execute(&mut mem, &transactions, &limits),
+            Err(Error::AccountLimitExceeded)
+        );
+    }
+
+    #[test]
+    fn fees_are_debited_and_credited() {
+        let with_fee = |amount: u32, fee: u32| {
+            Transaction::Transfer(Transfer {
+                to: 1.into(),
+                from: 0.into(),
+                nonce: 0.into(),
+                amount: amount.into(),
+                fee: fee.into(),
+                valid_until: None,
+                signature: [0; 96],
+            })
+        };
+
+        // The sender must cover the amount and the fee.
+        let mut mem = build_state();
+        assert_eq!(
+            apply_transactions(&mut mem, &[with_fee(5, 1)]),
+            Ok(vec![Err(Error::BalanceInsufficient)])
+        );
+
+        let mut mem = build_state();
+        let result = execute(&mut mem, &[with_fee(3, 2)], &Limits::default()).unwrap();
+        assert_eq!(result.fees, 2.into());
+        assert_eq!(mem.value(0.into()), Ok(0.into()));
+        assert_eq!(mem.value(1.into()), Ok(5.into()));
+
+        assert_eq!(credit_fees(&mut mem, 1.into(), result.fees), Ok(()));
+        assert_eq!(mem.value(1.into()), Ok(7.into()));
+
+        // Without fees the proposer isn't touched, so it needn't be in the state.
+        assert_eq!(credit_fees(&mut mem, 9.into(), 0.into()), Ok(()));
+    }
+
+    #[test]
+    fn execute_skips_replayed_and_future_nonces() {
+        // The second transfer replays the first, the third skips ahead of the sender's nonce.
+        let transactions = vec![
+            build_transfer(0, 1, 0),
+            build_transfer(0, 1, 0),
+            build_transfer(0, 1, 5),
+            build_batch(0, 0, &[(1, 1)]),
+            build_batch(0, 1, &[(1, 1)]),
+        ];
+
+        let mut mem = build_state();
+        assert_eq!(
+            execute(&mut mem, &transactions, &Limits::default())
+                .map(|result| result.transactions_applied),
+            Ok(2)
+        );
+        assert_eq!(mem.nonce(0.into()), Ok(2.into()));
+        assert_eq!(mem.value(1.into()), Ok(4.into()));
+    }
+
+    #[test]
+    fn execute_mode() {
+        // The first batch overdraws the sender, the second is fine.
+        let transactions = vec![
+            build_batch(0, 0, &[(1, 3), (1, 3)]),
+            build_batch(0, 0, &[(1, 3)]),
+        ];
+
+        let mut mem = build_state();
+        assert_eq!(
+            execute(&mut mem, &transactions, &Limits::default())
+                .map(|result| result.transactions_applied),
+            Ok(1)
+        );
+        assert_eq!(mem.value(0.into()), Ok(2.into()));
+        assert_eq!(mem.value(1.into()), Ok(5.into()));
+        assert_eq!(mem.nonce(0.into()), Ok(1.into()));
+
+        let limits = Limits {
+            mode: Mode::Strict,
+            ..Limits::default()
+        };
+
+        let mut mem = build_state();
+        assert_eq!(
+            execute(&mut mem, &transactions, &limits),
+            Err(Error::BalanceInsufficient)
+        );
+        assert!(execute(&mut mem, &transactions[1..], &limits).is_ok());
+    }
+
+    #[test]
+    fn execute_duplicates() {
+        let transfer = Transaction::Transfer(Transfer {
+            to: 1.into(),
+            from: 0.into(),
+            nonce: 0.into(),
+            amount: 1.into(),
+            fee: 0.into(),
+            valid_until: None,
+            signature: [0; 96],
+        });
+        let transactions = vec![transfer.clone(), transfer.clone()];
+
+        let mut mem = build_state();
+        assert_eq!(
+            execute(&mut mem, &transactions, &Limits::default())
+                .map(|result| result.transactions_applied),
+            Ok(1)
+        );
+        assert_eq!(mem.value(1.into()), Ok(3.into()));
+
+        // The copy is skipped even when a transaction which can't be applied rejects the block.
+        let limits = Limits {
+            mode: Mode::Strict,
+            ..Limits::default()
+        };
+        let mut mem = build_state();
+        assert!(execute(&mut mem, &transactions, &limits).is_ok());
+
+        let limits = Limits {
+            duplicates: Duplicates::Reject,
+            ..Limits::default()
+        };
+        let mut mem = build_state();
+        assert_eq!(
+            execute(&mut mem, &transactions, &limits),
+            Err(Error::TransactionDuplicate)
+        );
+
+        let mut mem = build_state();
+        let results = apply_transactions(&mut mem, &transactions).unwrap();
+        assert!(results[0].is_ok());
+        assert_eq!(results[1], Err(Error::TransactionDuplicate));
+    }
+
+    #[test]
+    fn execute_skips_expired_transactions() {
+        let expiring = |valid_until: u64| {
+            Transaction::Transfer(Transfer {
+                to: 1.into(),
+                from: 0.into(),
+                nonce: 0.into(),
+                amount: 1.into(),
+                fee: 0.into(),
+                valid_until: Some(valid_until),
+                signature: [0; 96],
+            })
+        };
+        let limits = Limits {
+            slot: Some(7),
+            ..Limits::default()
+        };
+
+        let mut mem = build_state();
+        assert_eq!(
+            execute(&mut mem, &[expiring(6)], &limits).map(|result| result.transactions_applied),
+            Ok(0)
+        );
+        assert_eq!(
+            execute(&mut mem, &[expiring(7)], &limits).map(|result| result.transactions_applied),
+            Ok(1)
+        );
+    }
+
+    fn build_deposit(to: usize, amount: u32, index: u64) -> Deposit {
+        Deposit {
+            pubkey: PublicKey::one(),
+            withdrawal_credentials: Address::from(to).into(),
+            amount: amount.into(),
+            signature: [0; 96],
+            index,
+            proof: vec![[0; 32]; DEPOSIT_PROOF_LENGTH],
+        }
+    }
+
+    #[test]
+    fn deposits_create_and_credit_accounts() {
+        // Account 2 is empty, as the tree would prove it.
+        let mut mem = build_state();
+        mem.create_account(2.into(), PublicKey::zero()).unwrap();
+
+        let deposits = vec![build_deposit(2, 3, 4), build_deposit(1, 1, 5)];
+        let limits = Limits {
+            deposits: Some(Deposits {
+                root: deposits[0].root(),
+                index: 4,
+            }),
+            ..Limits::default()
+        };
+
+        // Every deposit is proven against the same root, which only the first is in.
+        let transactions: Vec<Transaction> =
+            deposits.into_iter().map(Transaction::Deposit).collect();
+        assert_eq!(
+            execute(&mut mem, &transactions, &limits).map(|result| result.deposits_applied),
+            Ok(1)
+        );
+        assert_eq!(mem.value(2.into()), Ok(3.into()));
+        assert_eq!(mem.pubkey(2.into()), Ok(PublicKey::one()));
+
+        let deposit = build_deposit(1, 1, 5);
+        let limits = Limits {
+            deposits: Some(Deposits {
+                root: deposit.root(),
+                index: 5,
+            }),
+            ..Limits::default()
+        };
+
+        // An existing account keeps its key.
+        let transactions = vec![Transaction::Deposit(deposit)];
+        assert_eq!(
+            execute(&mut mem, &transactions, &limits).map(|result| result.deposits_applied),
+            Ok(1)
+        );
+        assert_eq!(mem.value(1.into()), Ok(3.into()));
+        assert_eq!(mem.pubkey(1.into()), Ok(PublicKey::zero()));
+    }
+
+    #[test]
+    fn deposits_are_applied_once_in_order() {
+        let deposit = build_deposit(1, 1, 0);
+        let root = deposit.root();
+        let transactions = vec![
+            Transaction::Deposit(deposit.clone()),
+            Transaction::Deposit(deposit),
+        ];
+
+        // Without a deposit root nothing can be proven.
+        let mut mem = build_state();
+        assert_eq!(
+            execute(&mut mem, &transactions, &Limits::default())
+                .map(|result| result.deposits_applied),
+            Ok(0)
+        );
+        assert_eq!(
+            apply_transactions(&mut mem, &transactions[0..1]),
+            Ok(vec![Err(Error::DepositInvalid)])
+        );
+
+        // The replayed deposit has the index the first one used.
+        let limits = Limits {
+            deposits: Some(Deposits { root, index: 0 }),
+            ..Limits::default()
+        };
+        assert_eq!(
+            execute(&mut mem, &transactions, &limits).map(|result| result.deposits_applied),
+            Ok(1)
+        );
+        assert_eq!(mem.value(1.into()), Ok(3.into()));
+    }
+
+    fn build_withdrawal(from: usize, nonce: u64, amount: u32) -> Transaction {
+        Transaction::Withdrawal(Withdrawal {
+            from: from.into(),
+            nonce: nonce.into(),
+            amount: amount.into(),
+            fee: 1.into(),
+            valid_until: None,
+            signature: [0; 96],
+        })
+    }
+
+    #[test]
+    fn withdrawals_burn_value_and_commit_receipts() {
+        // Account 1 can't cover the fee as well as the amount.
+        let mut mem = build_state();
+        assert_eq!(
+            apply_transactions(&mut mem, &[build_withdrawal(1, 0, 2)]),
+            Ok(vec![Err(Error::BalanceInsufficient)])
+        );
+
+        let transactions = vec![build_withdrawal(0, 0, 2), build_withdrawal(0, 1, 1)];
+        let result = execute(&mut mem, &transactions, &Limits::default()).unwrap();
+
+        assert_eq!(result.transactions_applied, 2);
+        assert_eq!(result.fees, 2.into());
+        assert_eq!(mem.value(0.into()), Ok(0.into()));
+        assert_eq!(mem.value(1.into()), Ok(2.into()));
+        assert_eq!(mem.nonce(0.into()), Ok(2.into()));
+
+        let receipts = [
+            WithdrawalReceipt {
+                address: 0.into(),
+                amount: 2.into(),
+                nonce: 0.into(),
+            },
+            WithdrawalReceipt {
+                address: 0.into(),
+                amount: 1.into(),
+                nonce: 1.into(),
+            },
+        ];
+        assert_eq!(result.withdrawals_root, withdrawals_root(&receipts));
+    }
+
+    #[test]
+    fn cross_transfers_are_claimed_once() {
+        let send = |nonce: u64, destination: u64| {
+            Transaction::CrossTransfer(CrossTransfer {
+                to: 1.into(),
+                from: 0.into(),
+                destination,
+                nonce: nonce.into(),
+                amount: 2.into(),
+                fee: 0.into(),
+                valid_until: None,
+                signature: [0; 96],
+            })
+        };
+
+        let mut source = build_state();
+        let transactions = vec![send(0, 7), send(1, 8)];
+        let result = execute(&mut source, &transactions, &Limits::default()).unwrap();
+        assert_eq!(source.value(0.into()), Ok(1.into()));
+        assert_eq!(source.value(1.into()), Ok(2.into()));
+
+        let receipts: Vec<TransferReceipt> = transactions
+            .iter()
+            .map(|tx| match tx {
+                Transaction::CrossTransfer(c) => c.receipt(),
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(result.receipts_root, receipts_root(&receipts));
+
+        // EE 7 credits the receipt sent to it once, and not the one sent to EE 8.
+        let claims = vec![
+            Transaction::Claim(build_claim(&receipts, 0)),
+            Transaction::Claim(build_claim(&receipts, 0)),
+            Transaction::Claim(build_claim(&receipts, 1)),
+        ];
+        // The claimed accounts are empty, as the tree would prove them.
+        let mut destination = build_state();
+        for tx in claims.iter() {
+            if let Transaction::Claim(c) = tx {
+                destination
+                    .create_account(c.claimed_address(), PublicKey::zero())
+                    .unwrap();
+            }
+        }
+
+        let limits = Limits {
+            receipts: Some(Receipts {
+                root: result.receipts_root,
+                destination: 7,
+            }),
+            ..Limits::default()
+        };
+        assert_eq!(
+            execute(&mut destination, &claims, &limits).map(|result| result.transactions_applied),
+            Ok(1)
+        );
+        assert_eq!(destination.value(1.into()), Ok(4.into()));
+
+        // Nor can it be claimed in a later block.
+        assert_eq!(
+            execute(&mut destination, &claims[0..1], &limits)
+                .map(|result| result.transactions_applied),
+            Ok(0)
+        );
+        assert_eq!(
+            apply_transactions(&mut destination, &claims[0..1]),
+            Ok(vec![Err(Error::ReceiptInvalid)])
+        );
+    }
+
+    #[test]
+    fn claims_are_not_replayed_through_collapsed_witnesses() {
+        let receipts = vec![CrossTransfer {
+            to: 1.into(),
+            from: 0.into(),
+            destination: 7,
+            nonce: 0.into(),
+            amount: 2.into(),
+            fee: 0.into(),
+            valid_until: None,
+            signature: [0; 96],
+        }
+        .receipt()];
+        let claim = build_claim(&receipts, 0);
+        let marker = U256::from(claim.claimed_address());
+
+        let limits = Limits {
+            receipts: Some(Receipts {
+                root: claim.root,
+                destination: 7,
+            }),
+            ..Limits::default()
+        };
+
+        // The receipt has already been claimed, so the nonce of its marker is 1. The composer's
+        // `Account` is its own build of this crate, so one of a generated blob's is copied.
+        let mut recipient = composer::blob::generate(1, 0, 256).accounts[0].1.clone();
+        recipient.value = 0;
+        recipient.nonce = 0;
+        let mut claimed = recipient.clone();
+        claimed.nonce = 1;
+
+        let mut state = StateCache::new(
+            vec![
+                AddressedAccount(1.into(), recipient),
+                AddressedAccount(marker, claimed),
+            ],
+            256,
+        );
+        let height = Config::default().proof_height();
+
+        let mut proof = state.witness(&[1.into(), marker]).compress();
+        let mem = new_checked(&mut proof, height).unwrap();
+        assert_eq!(
+            claim.verify(&mem, limits.receipts.as_ref().unwrap()),
+            Err(Error::ReceiptClaimed)
+        );
+
+        // A witness of only the recipient collapses the marker into the hash of its subtree,
+        // which can't be read as an unclaimed marker.
+        let mut proof = state.witness(&[1.into()]).compress();
+        let mut mem = new_checked(&mut proof, height).unwrap();
+        assert!(matches!(
+            claim.verify(&mem, limits.receipts.as_ref().unwrap()),
+            Err(Error::StateIncomplete(_))
+        ));
+
+        let claims = [Transaction::Claim(claim)];
+        assert_eq!(
+            execute(&mut mem, &claims, &limits).map(|result| result.transactions_applied),
+            Ok(0)
+        );
+        assert_eq!(mem.value(1.into()), Ok(0.into()));
+    }
+
+    #[test]
+    fn change_key_skipped_without_pubkey() {
+        // indexes = [8, 9, 10, 11, 3]: account 0's public key is the hash of its two chunks, and
+        // the rest of the tree is a single hash.
+        let mut value = [0u8; 32];
+        value[0] = 5;
+        let chunks = [[7u8; 32], [0u8; 32], value, [0u8; 32], [9u8; 32]];
+
+        let mut proof = vec![];
+        for offset in [5u64, 4, 2, 1, 1].iter() {
+            proof.extend(&offset.to_le_bytes());
+        }
+        for chunk in chunks.iter() {
+            proof.extend(chunk);
+        }
+        let expected = proof.clone();
+
+        let transactions = [Transaction::ChangeKey(ChangeKey {
+            from: 0.into(),
+            nonce: 0.into(),
+            pubkey: PublicKey::one(),
+            fee: 0.into(),
+            valid_until: None,
+            signature: [0; 96],
+        })];
+
+        let mut mem = new_checked(&mut proof, 4).unwrap();
+        let (result, receipts) =
+            execute_with_receipts(&mut mem, &transactions, &Limits::default(), &Unchecked).unwrap();
+        assert_eq!(result.transactions_applied, 0);
+        assert_eq!(receipts, vec![TxReceipt::skipped()]);
+        assert_eq!(proof, expected);
+    }
+
+    /// Accepts signatures which start with the signer's public key.
+    struct Keyed;
+
+    impl SignatureVerifier for Keyed {
+        fn verify(&self, pubkey: &PublicKey, _: &H256, signature: &[u8; 96]) -> bool {
+            signature[0..48] == pubkey.as_bytes()[..]
+        }
+    }
+
+    fn signed_by(pubkey: &PublicKey) -> [u8; 96] {
+        let mut signature = [0u8; 96];
+        signature[0..48].copy_from_slice(&pubkey.as_bytes());
+        signature
+    }
+
+    #[test]
+    fn keys_are_rotated() {
+        let rotate = Transaction::ChangeKey(ChangeKey {
+            from: 0.into(),
+            nonce: 0.into(),
+            pubkey: PublicKey::one(),
+            fee: 1.into(),
+            valid_until: None,
+            signature: signed_by(&PublicKey::zero()),
+        });
+        let send = |nonce: u64, pubkey: &PublicKey| {
+            Transaction::Transfer(Transfer {
+                to: 1.into(),
+                from: 0.into(),
+                nonce: nonce.into(),
+                amount: 1.into(),
+                fee: 0.into(),
+                valid_until: None,
+                signature: signed_by(pubkey),
+            })
+        };
+
+        let mut mem = build_state();
+        assert_eq!(
+            apply_transactions_with(
+                &mut mem,
+                &[
+                    rotate,
+                    send(1, &PublicKey::zero()),
+                    send(1, &PublicKey::one())
+                ],
+                &Keyed
+            ),
+            Ok(vec![
+                Ok(Receipt {
+                    nonce: 1.into(),
+                    value: 4.into(),
+                    fee: 1.into(),
+                    gas_used: 3000,
+                }),
+                Err(Error::SignatureInvalid),
+                Ok(Receipt {
+                    nonce: 2.into(),
+                    value: 3.into(),
+                    fee: 0.into(),
+                    gas_used: 3000,
+                }),
+            ])
+        );
+
+        // The account keeps its address and value.
+        assert_eq!(mem.pubkey(0.into()), Ok(PublicKey::one()));
+        assert_eq!(mem.value(0.into()), Ok(3.into()));
+    }
 }
@@ -0,0 +1,157 @@
+//! Receipts of the transactions in a processed block. A block's header commits to the root of its
+//! receipts, so a light client can check that a payment was received, and the balances it left,
+//! from the receipt and a proof rather than by replaying the block.
+
+use crate::address::Address;
+use crate::amount::Amount;
+use crate::block::{InclusionProof, TRANSACTIONS_TREE_DEPTH};
+use crate::hash::{branch_root, hash_pair, list_proof, list_root, H256};
+use alloc::vec::Vec;
+
+/// Depth of the tree of a receipt's balances, which limits it to `2**32` of them like the number
+/// of payments in a batch.
+pub const BALANCES_TREE_DEPTH: usize = 32;
+
+/// The value of an account after a transaction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Balance {
+    pub address: Address,
+    pub value: Amount,
+}
+
+impl Balance {
+    /// Returns the `hash_tree_root` of the balance as an SSZ container:
+    ///
+    /// ```text
+    /// root = hash(address, value)
+    /// ```
+    pub fn root(&self) -> H256 {
+        let mut value = [0u8; 32];
+        value[0..8].copy_from_slice(&self.value.get().to_le_bytes());
+
+        hash_pair(&<[u8; 32]>::from(self.address), &value)
+    }
+}
+
+/// The outcome of a transaction in a processed block.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TxReceipt {
+    /// Whether the transaction was applied. A skipped transaction changes nothing, so it uses no
+    /// gas and has no balances.
+    pub applied: bool,
+
+    /// The gas used by the transaction.
+    pub gas_used: u64,
+
+    /// The values of the accounts the transaction touched after it was applied, in the order of
+    /// `Transaction::accounts` and without repeats. A claim's marker account, which holds no
+    /// value, is left out.
+    pub balances: Vec<Balance>,
+}
+
+impl TxReceipt {
+    /// Returns the receipt of a transaction which was skipped.
+    pub fn skipped() -> Self {
+        TxReceipt {
+            applied: false,
+            gas_used: 0,
+            balances: Vec::new(),
+        }
+    }
+
+    /// Returns the `hash_tree_root` of the receipt as an SSZ container, whose balances are a list
+    /// with a limit of `2**BALANCES_TREE_DEPTH`:
+    ///
+    /// ```text
+    /// root = hash(hash(applied, gas_used), hash(balances_root, 0))
+    /// ```
+    pub fn root(&self) -> H256 {
+        let mut applied = [0u8; 32];
+        applied[0] = self.applied as u8;
+        let mut gas_used = [0u8; 32];
+        gas_used[0..8].copy_from_slice(&self.gas_used.to_le_bytes());
+
+        let balances: Vec<H256> = self.balances.iter().map(|b| b.root()).collect();
+
+        hash_pair(
+            &hash_pair(&applied, &gas_used),
+            &hash_pair(&list_root(&balances, BALANCES_TREE_DEPTH), &[0u8; 32]),
+        )
+    }
+}
+
+/// Returns the root of a block's `receipts`, the root of their SSZ list.
+pub fn tx_receipts_root(receipts: &[TxReceipt]) -> H256 {
+    let leaves: Vec<H256> = receipts.iter().map(|r| r.root()).collect();
+    list_root(&leaves, TRANSACTIONS_TREE_DEPTH)
+}
+
+/// Returns the proof that `receipts[index]` is in their `tx_receipts_root`, or `None` if there
+/// aren't that many receipts.
+pub fn prove_receipt_inclusion(receipts: &[TxReceipt], index: usize) -> Option<InclusionProof> {
+    if index >= receipts.len() {
+        return None;
+    }
+
+    let leaves: Vec<H256> = receipts.iter().map(|r| r.root()).collect();
+
+    Some(InclusionProof {
+        index: index as u64,
+        branch: list_proof(&leaves, TRANSACTIONS_TREE_DEPTH, index),
+    })
+}
+
+/// Returns whether `proof` shows that `receipt` is in the block whose receipts root is `root`.
+pub fn verify_receipt_inclusion(root: &H256, receipt: &TxReceipt, proof: &InclusionProof) -> bool {
+    proof.index >> TRANSACTIONS_TREE_DEPTH == 0
+        && proof.branch.len() == TRANSACTIONS_TREE_DEPTH + 1
+        && branch_root(receipt.root(), proof.index, &proof.branch) == *root
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn build_receipt() -> TxReceipt {
+        TxReceipt {
+            applied: true,
+            gas_used: 2_600,
+            balances: vec![
+                Balance {
+                    address: 1.into(),
+                    value: 4.into(),
+                },
+                Balance {
+                    address: 2.into(),
+                    value: 3.into(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn root() {
+        // hash_tree_root(TxReceipt) as computed by the SSZ spec.
+        assert_eq!(
+            hex::encode(build_receipt().root()),
+            "cf1d9d80a713a019b487eea8c84d4c4df5dd9cd7ca7478df7d55c61fc84f1259"
+        );
+        assert_ne!(build_receipt().root(), TxReceipt::skipped().root());
+    }
+
+    #[test]
+    fn receipt_inclusion() {
+        let receipts = vec![build_receipt(), TxReceipt::skipped(), build_receipt()];
+        let root = tx_receipts_root(&receipts);
+
+        for (i, receipt) in receipts.iter().enumerate() {
+            let proof = prove_receipt_inclusion(&receipts, i).unwrap();
+            assert!(verify_receipt_inclusion(&root, receipt, &proof));
+        }
+
+        let proof = prove_receipt_inclusion(&receipts, 1).unwrap();
+        assert!(!verify_receipt_inclusion(&root, &receipts[0], &proof));
+        assert_eq!(prove_receipt_inclusion(&receipts, 3), None);
+    }
+}
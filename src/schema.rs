@@ -0,0 +1,111 @@
+use crate::account::calc_leaf_index;
+use crate::address::Address;
+use crate::u264::U264;
+
+/// A field of a leaf, as the offset of its first chunk among the chunks at the bottom of the
+/// leaf's subtree and the number of consecutive chunks it spans.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Field {
+    pub offset: usize,
+    pub chunks: usize,
+}
+
+impl Field {
+    pub const fn new(offset: usize, chunks: usize) -> Self {
+        Field { offset, chunks }
+    }
+}
+
+/// Layout of the data stored at each leaf of the state tree.
+///
+/// The sparse merkle tree, multi-proofs and root calculation don't depend on what a leaf holds,
+/// so an execution environment with a different leaf can implement this trait and reuse them
+/// through `state::imp::read` and `state::imp::write`. `Account` is this crate's schema.
+pub trait LeafSchema {
+    /// Number of levels in the subtree below each leaf. Fields are laid out in the
+    /// `2**DEPTH` chunks at the bottom of it, and every other chunk is zero.
+    const DEPTH: usize;
+}
+
+/// Given an address and tree height, calculate the general index of the first chunk of `field`
+/// in the leaf at `address`.
+///
+/// ```text
+/// field_index = (first_leaf + address) * 2**DEPTH + field.offset
+/// ```
+#[inline]
+pub fn field_index<S: LeafSchema>(address: Address, height: usize, field: Field) -> U264 {
+    debug_assert!(
+        S::DEPTH <= 8 && field.offset + field.chunks <= 1 << S::DEPTH,
+        "field doesn't fit in the leaf"
+    );
+
+    (calc_leaf_index(address, height) << S::DEPTH) + (field.offset as u8).into()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::account::Account;
+    use crate::state::imp::{read, write};
+    use crate::state::root::imp_root;
+    use imp::Imp;
+
+    /// A leaf holding a single balance chunk and a single flags chunk.
+    struct Balance;
+
+    impl LeafSchema for Balance {
+        const DEPTH: usize = 1;
+    }
+
+    const AMOUNT: Field = Field::new(0, 1);
+    const FLAGS: Field = Field::new(1, 1);
+
+    #[test]
+    fn custom_schema() {
+        // A full tree of height 1, so indexes = [4, 5, 6, 7]
+        let mut proof = vec![];
+        for offset in [4u64, 2, 1, 1].iter() {
+            proof.extend(&offset.to_le_bytes());
+        }
+        for n in 0..4u8 {
+            proof.extend(&[n; 32]);
+        }
+
+        let mut mem = Imp::<U264>::new(&mut proof, 1 + Balance::DEPTH);
+        let pre_root = imp_root(&mem);
+
+        let mut chunk = [0u8; 32];
+        assert_eq!(read::<Balance>(&mem, 1.into(), FLAGS, &mut chunk), Ok(()));
+        assert_eq!(chunk, [3u8; 32]);
+
+        assert_eq!(
+            write::<Balance>(&mut mem, 1.into(), AMOUNT, &[9u8; 32]),
+            Ok(())
+        );
+        assert_eq!(read::<Balance>(&mem, 1.into(), AMOUNT, &mut chunk), Ok(()));
+        assert_eq!(chunk, [9u8; 32]);
+
+        assert_ne!(imp_root(&mem), pre_root);
+    }
+
+    #[test]
+    fn account_fields() {
+        // (first_leaf + account) = 16 + 5 = 21
+        let address = Address::from(5);
+        let leaf = U264::from(21u8);
+
+        assert_eq!(
+            field_index::<Account>(address, 4, Account::PUBKEY),
+            leaf << 3
+        );
+        assert_eq!(
+            field_index::<Account>(address, 4, Account::NONCE),
+            ((leaf << 2usize) + 1.into()) << 1
+        );
+        assert_eq!(
+            field_index::<Account>(address, 4, Account::VALUE),
+            ((leaf << 2usize) + 2.into()) << 1
+        );
+    }
+}
@@ -0,0 +1,97 @@
+//! The entry point of the EE under Scout and other hosts which implement the eth2 EE interface.
+//! Building with the `scout` feature for `wasm32-unknown-unknown` exports `main`, which processes
+//! the block data the host supplies against its pre-state root:
+//!
+//! ```text
+//! cargo build --lib --release --target wasm32-unknown-unknown --features scout
+//! ```
+//!
+//! The exports are only built for wasm32, since the host functions are only linked by a wasm
+//! runtime, so a native build with the feature still links.
+//!
+//! An invalid block traps, leaving the host's post-state root unsaved. Before trapping, the
+//! `Error::code` of its rejection is written to a designated location in the exported `memory`,
+//! which is the contract for finding out why: the exported global `ERROR_CODE` holds its address,
+//! and the little-endian `u32` there is the code, or 0 if the last block was accepted. Since the
+//! memory outlives the trap, a host can read it without calling back into the instance, though
+//! the exported `error_code` returns the same value for hosts which can.
+
+use crate::hash::H256;
+use crate::host::ExecutionHost;
+#[cfg(target_arch = "wasm32")]
+use crate::{config::Config, host::run};
+#[cfg(target_arch = "wasm32")]
+use core::sync::atomic::{AtomicU32, Ordering};
+
+// This is a list of functions that `ewasm` environments support. They provide additional data and
+// functionality to execution environments. Each function is implemented in the host environment.
+mod native {
+    extern "C" {
+        pub fn eth2_loadPreStateRoot(offset: *const u32);
+        pub fn eth2_blockDataSize() -> u32;
+        pub fn eth2_blockDataCopy(outputOfset: *const u32, offset: u32, length: u32);
+        pub fn eth2_savePostStateRoot(offset: *const u32);
+        pub fn debug_printMem(offset: *const u32, length: u32);
+    }
+}
+
+/// The host functions of the eth2 EE interface.
+pub struct ScoutHost;
+
+impl ExecutionHost for ScoutHost {
+    fn load_pre_state_root(&self) -> H256 {
+        let mut ret = [0u8; 32];
+        unsafe { native::eth2_loadPreStateRoot(ret.as_mut_ptr() as *const u32) }
+        ret
+    }
+
+    fn block_data_size(&self) -> usize {
+        unsafe { native::eth2_blockDataSize() as usize }
+    }
+
+    fn read_block_data(&self, offset: usize, buf: &mut [u8]) {
+        unsafe {
+            native::eth2_blockDataCopy(
+                buf.as_mut_ptr() as *const u32,
+                offset as u32,
+                buf.len() as u32,
+            )
+        }
+    }
+
+    fn save_post_state_root(&mut self, root: &H256) {
+        unsafe { native::eth2_savePostStateRoot(root.as_ptr() as *const u32) }
+    }
+
+    fn debug_print(&mut self, message: &str) {
+        unsafe { native::debug_printMem(message.as_ptr() as *const u32, message.len() as u32) }
+    }
+}
+
+/// The `Error::code` the last block was rejected with, or 0 if it was accepted. It is exported so
+/// the host can read it from memory after a trap.
+#[cfg(target_arch = "wasm32")]
+#[no_mangle]
+pub static ERROR_CODE: AtomicU32 = AtomicU32::new(0);
+
+#[cfg(target_arch = "wasm32")]
+#[no_mangle]
+pub extern "C" fn main() {
+    match run(&mut ScoutHost, &Config::default()) {
+        Ok(_) => ERROR_CODE.store(0, Ordering::Relaxed),
+        Err(e) => {
+            // Record why before trapping, so the host doesn't save a post-state root but can
+            // still read the code from memory.
+            ERROR_CODE.store(e.code(), Ordering::Relaxed);
+            panic!("Block to be valid");
+        }
+    }
+}
+
+/// Returns the code of the error the last call to `main` trapped with, see `Error::code`, or 0 if
+/// it didn't.
+#[cfg(target_arch = "wasm32")]
+#[no_mangle]
+pub extern "C" fn error_code() -> u32 {
+    ERROR_CODE.load(Ordering::Relaxed)
+}
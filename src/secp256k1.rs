@@ -0,0 +1,545 @@
+//! secp256k1 ECDSA signatures with public key recovery, as an alternative to BLS for deployments
+//! whose wallets already sign with secp256k1.
+//!
+//! The 96 byte signature of a transaction holds `r || s || v` followed by zeros, where `v` is
+//! the recovery id (`0`, `1`, `27` or `28`). The 48 byte public key of an account holds the
+//! compressed 33 byte secp256k1 key followed by zeros, see `pubkey`. Like eth1, signatures with
+//! `s` in the upper half of the curve order are rejected so they can't be malleated.
+//!
+//! A deployment selects these signatures with `SignatureScheme::Secp256k1` in its `Config`.
+//!
+//! The arithmetic is a straightforward implementation over `bigint::U256`, meant for verifying a
+//! block's worth of signatures rather than being fast or constant time.
+
+use crate::bls::{PublicKey, SignatureVerifier};
+use crate::hash::H256;
+use bigint::{U256, U512};
+
+/// The field prime, `2**256 - 2**32 - 977`.
+const P: U256 = U256([
+    0xffff_fffe_ffff_fc2f,
+    0xffff_ffff_ffff_ffff,
+    0xffff_ffff_ffff_ffff,
+    0xffff_ffff_ffff_ffff,
+]);
+
+/// The order of the curve's group.
+const N: U256 = U256([
+    0xbfd2_5e8c_d036_4141,
+    0xbaae_dce6_af48_a03b,
+    0xffff_ffff_ffff_fffe,
+    0xffff_ffff_ffff_ffff,
+]);
+
+/// `N / 2`, the greatest `s` which is accepted.
+const HALF_N: U256 = U256([
+    0xdfe9_2f46_681b_20a0,
+    0x5d57_6e73_57a4_501d,
+    0xffff_ffff_ffff_ffff,
+    0x7fff_ffff_ffff_ffff,
+]);
+
+const GX: U256 = U256([
+    0x59f2_815b_16f8_1798,
+    0x029b_fcdb_2dce_28d9,
+    0x55a0_6295_ce87_0b07,
+    0x79be_667e_f9dc_bbac,
+]);
+
+const GY: U256 = U256([
+    0x9c47_d08f_fb10_d4b8,
+    0xfd17_b448_a685_5419,
+    0x5da4_fbfc_0e11_08a8,
+    0x483a_da77_26a3_c465,
+]);
+
+/// Verifies secp256k1 signatures by recovering the signer's key and comparing it to the
+/// sender's public key in the state.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Secp256k1;
+
+impl SignatureVerifier for Secp256k1 {
+    fn verify(&self, pubkey: &PublicKey, message: &H256, signature: &[u8; 96]) -> bool {
+        let mut sig = [0u8; 65];
+        sig.copy_from_slice(&signature[0..65]);
+
+        if signature[65..].iter().any(|b| *b != 0) {
+            return false;
+        }
+
+        match recover(message, &sig) {
+            Some(key) => self::pubkey(&key) == *pubkey,
+            None => false,
+        }
+    }
+}
+
+/// Returns the account public key holding the compressed secp256k1 key `key`.
+pub fn pubkey(key: &[u8; 33]) -> PublicKey {
+    let mut buf = [0u8; 48];
+    buf[0..33].copy_from_slice(key);
+    PublicKey::new(buf)
+}
+
+/// Recover the compressed public key which signed `message` with `signature` (`r || s || v`),
+/// or `None` if the signature is malformed or doesn't belong to any key.
+pub fn recover(message: &H256, signature: &[u8; 65]) -> Option<[u8; 33]> {
+    let r = U256::from_big_endian(&signature[0..32]);
+    let s = U256::from_big_endian(&signature[32..64]);
+    let v = match signature[64] {
+        0 | 27 => 0,
+        1 | 28 => 1,
+        _ => return None,
+    };
+
+    if r.is_zero() || r >= N || s.is_zero() || s > HALF_N {
+        return None;
+    }
+
+    // The x coordinate of the nonce point is `r`, and `v` picks which of its two y coordinates.
+    // `r + N` is also a valid x coordinate when it is below `P`, but this is vanishingly rare
+    // and signers don't produce it.
+    let y = lift_x(r, v)?;
+    let point = Point::from_affine(r, y);
+
+    let e = U256::from_big_endian(message) % N;
+    let r_inv = scalar_inv(r);
+    let u1 = scalar_mul(N - e, r_inv) % N;
+    let u2 = scalar_mul(s, r_inv);
+
+    let q = Point::generator().mul(u1).add(&point.mul(u2));
+    q.compress()
+}
+
+/// Returns the y coordinate of the point with `x` whose parity is `parity`, if there is one.
+fn lift_x(x: U256, parity: u8) -> Option<U256> {
+    let rhs = fe_add(fe_mul(fe_mul(x, x), x), U256::from(7));
+    let y = fe_pow(rhs, (P + U256::one()) >> 2);
+
+    if fe_mul(y, y) != rhs {
+        return None;
+    }
+
+    if (y.low_u64() & 1) as u8 == parity {
+        Some(y)
+    } else {
+        Some(P - y)
+    }
+}
+
+fn fe_add(a: U256, b: U256) -> U256 {
+    let (sum, overflow) = a.overflowing_add(b);
+    if overflow || sum >= P {
+        sum.overflowing_sub(P).0
+    } else {
+        sum
+    }
+}
+
+fn fe_sub(a: U256, b: U256) -> U256 {
+    let (diff, underflow) = a.overflowing_sub(b);
+    if underflow {
+        diff.overflowing_add(P).0
+    } else {
+        diff
+    }
+}
+
+fn fe_mul(a: U256, b: U256) -> U256 {
+    // Since `2**256 = 2**32 + 977 (mod P)`, the upper half of the product can be folded into the
+    // lower half until it fits.
+    let fold = U256::from(0x1_0000_03d1u64);
+
+    let mut t = a.full_mul(b);
+    while t.0[4..8].iter().any(|limb| *limb != 0) {
+        let lo = U256([t.0[0], t.0[1], t.0[2], t.0[3]]);
+        let hi = U256([t.0[4], t.0[5], t.0[6], t.0[7]]);
+        t = U512::from(lo) + hi.full_mul(fold);
+    }
+
+    let t = U256([t.0[0], t.0[1], t.0[2], t.0[3]]);
+    if t >= P {
+        t - P
+    } else {
+        t
+    }
+}
+
+fn fe_pow(a: U256, e: U256) -> U256 {
+    let mut ret = U256::one();
+    for i in (0..e.bits()).rev() {
+        ret = fe_mul(ret, ret);
+        if e.bit(i) {
+            ret = fe_mul(ret, a);
+        }
+    }
+
+    ret
+}
+
+fn fe_inv(a: U256) -> U256 {
+    fe_pow(a, P - U256::from(2))
+}
+
+fn scalar_mul(a: U256, b: U256) -> U256 {
+    U256::from(a.full_mul(b) % U512::from(N))
+}
+
+fn scalar_inv(a: U256) -> U256 {
+    let e = N - U256::from(2);
+
+    let mut ret = U256::one();
+    for i in (0..e.bits()).rev() {
+        ret = scalar_mul(ret, ret);
+        if e.bit(i) {
+            ret = scalar_mul(ret, a);
+        }
+    }
+
+    ret
+}
+
+/// A point on the curve in Jacobian coordinates, where `(x, y) = (X / Z**2, Y / Z**3)` and the
+/// point at infinity has `Z = 0`.
+#[derive(Clone, Copy, Debug)]
+struct Point {
+    x: U256,
+    y: U256,
+    z: U256,
+}
+
+impl Point {
+    fn infinity() -> Self {
+        Point {
+            x: U256::one(),
+            y: U256::one(),
+            z: U256::zero(),
+        }
+    }
+
+    fn generator() -> Self {
+        Point::from_affine(GX, GY)
+    }
+
+    fn from_affine(x: U256, y: U256) -> Self {
+        Point {
+            x,
+            y,
+            z: U256::one(),
+        }
+    }
+
+    fn is_infinity(&self) -> bool {
+        self.z.is_zero()
+    }
+
+    fn affine(&self) -> Option<(U256, U256)> {
+        if self.is_infinity() {
+            return None;
+        }
+
+        let z_inv = fe_inv(self.z);
+        let z_inv2 = fe_mul(z_inv, z_inv);
+        Some((
+            fe_mul(self.x, z_inv2),
+            fe_mul(self.y, fe_mul(z_inv2, z_inv)),
+        ))
+    }
+
+    fn compress(&self) -> Option<[u8; 33]> {
+        let (x, y) = self.affine()?;
+
+        let mut ret = [0u8; 33];
+        ret[0] = 2 + (y.low_u64() & 1) as u8;
+        x.to_big_endian(&mut ret[1..33]);
+        Some(ret)
+    }
+
+    fn double(&self) -> Self {
+        if self.is_infinity() || self.y.is_zero() {
+            return Point::infinity();
+        }
+
+        let a = fe_mul(self.x, self.x);
+        let b = fe_mul(self.y, self.y);
+        let c = fe_mul(b, b);
+        let xb = fe_add(self.x, b);
+        let d = fe_sub(fe_sub(fe_mul(xb, xb), a), c);
+        let d = fe_add(d, d);
+        let e = fe_add(fe_add(a, a), a);
+        let f = fe_mul(e, e);
+
+        let x = fe_sub(f, fe_add(d, d));
+        let c8 = fe_add(c, c);
+        let c8 = fe_add(c8, c8);
+        let c8 = fe_add(c8, c8);
+        let y = fe_sub(fe_mul(e, fe_sub(d, x)), c8);
+        let yz = fe_mul(self.y, self.z);
+
+        Point {
+            x,
+            y,
+            z: fe_add(yz, yz),
+        }
+    }
+
+    fn add(&self, other: &Point) -> Self {
+        if self.is_infinity() {
+            return *other;
+        }
+        if other.is_infinity() {
+            return *self;
+        }
+
+        let z1z1 = fe_mul(self.z, self.z);
+        let z2z2 = fe_mul(other.z, other.z);
+        let u1 = fe_mul(self.x, z2z2);
+        let u2 = fe_mul(other.x, z1z1);
+        let s1 = fe_mul(fe_mul(self.y, other.z), z2z2);
+        let s2 = fe_mul(fe_mul(other.y, self.z), z1z1);
+
+        if u1 == u2 {
+            return if s1 == s2 {
+                self.double()
+            } else {
+                Point::infinity()
+            };
+        }
+
+        let h = fe_sub(u2, u1);
+        let h2 = fe_add(h, h);
+        let i = fe_mul(h2, h2);
+        let j = fe_mul(h, i);
+        let r = fe_sub(s2, s1);
+        let r = fe_add(r, r);
+        let v = fe_mul(u1, i);
+
+        let x = fe_sub(fe_sub(fe_mul(r, r), j), fe_add(v, v));
+        let s1j = fe_mul(s1, j);
+        let y = fe_sub(fe_mul(r, fe_sub(v, x)), fe_add(s1j, s1j));
+        let zs = fe_add(self.z, other.z);
+        let z = fe_mul(fe_sub(fe_sub(fe_mul(zs, zs), z1z1), z2z2), h);
+
+        Point { x, y, z }
+    }
+
+    fn mul(&self, k: U256) -> Self {
+        let mut ret = Point::infinity();
+        for i in (0..k.bits()).rev() {
+            ret = ret.double();
+            if k.bit(i) {
+                ret = ret.add(self);
+            }
+        }
+
+        ret
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bls::SignatureScheme;
+    use crate::hash::Incremental;
+
+    fn u256(hex: &str) -> U256 {
+        let mut buf = [0u8; 32];
+        for (i, byte) in buf.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).unwrap();
+        }
+        U256::from(buf)
+    }
+
+    /// Sign `message` with `secret`, deriving the nonce from both. Wallets use RFC 6979 instead,
+    /// but any nonce which isn't reused gives a valid signature.
+    fn sign(secret: U256, message: &H256) -> [u8; 65] {
+        let mut secret_bytes = [0u8; 32];
+        secret.to_big_endian(&mut secret_bytes);
+
+        let mut hasher = Incremental::new();
+        hasher.update(&secret_bytes);
+        hasher.update(message);
+        let k = U256::from(hasher.finalize()) % N;
+
+        let (x, y) = Point::generator().mul(k).affine().unwrap();
+        let r = x % N;
+        let e = U256::from_big_endian(message) % N;
+        let rd = scalar_mul(r, secret);
+        let sum = U256::from((U512::from(e) + U512::from(rd)) % U512::from(N));
+        let mut s = scalar_mul(scalar_inv(k), sum);
+        let mut v = (y.low_u64() & 1) as u8;
+
+        if s > HALF_N {
+            s = N - s;
+            v ^= 1;
+        }
+
+        let mut ret = [0u8; 65];
+        r.to_big_endian(&mut ret[0..32]);
+        s.to_big_endian(&mut ret[32..64]);
+        ret[64] = v;
+        ret
+    }
+
+    fn public(secret: U256) -> [u8; 33] {
+        Point::generator().mul(secret).compress().unwrap()
+    }
+
+    #[test]
+    fn known_points() {
+        let two = Point::generator().double().affine().unwrap();
+        assert_eq!(
+            two,
+            (
+                u256("c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5"),
+                u256("1ae168fea63dc339a3c58419466ceaeef7f632653266d0e1236431a950cfe52a")
+            )
+        );
+        assert_eq!(Point::generator().mul(U256::from(2)).affine(), Some(two));
+
+        // `(N - 1) * G = -G`, and `N * G` is the point at infinity.
+        assert_eq!(
+            Point::generator().mul(N - U256::one()).affine(),
+            Some((GX, P - GY))
+        );
+        assert!(Point::generator().mul(N).is_infinity());
+    }
+
+    #[cfg(feature = "eth1")]
+    #[test]
+    fn known_eth1_address() {
+        use crate::hash::keccak::keccak256;
+
+        let secret = u256("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318");
+        let (x, y) = Point::generator().mul(secret).affine().unwrap();
+
+        let mut buf = [0u8; 64];
+        x.to_big_endian(&mut buf[0..32]);
+        y.to_big_endian(&mut buf[32..64]);
+
+        let address = keccak256(&buf);
+        assert_eq!(
+            address[12..32],
+            [
+                0x2c, 0x75, 0x36, 0xe3, 0x60, 0x5d, 0x9c, 0x16, 0xa7, 0xa3, 0xd7, 0xb1, 0x89, 0x8e,
+                0x52, 0x93, 0x96, 0xa6, 0x5c, 0x23
+            ]
+        );
+    }
+
+    #[test]
+    fn sign_and_recover() {
+        for (secret, message) in [(1u64, [0u8; 32]), (0xdead_beef, [7u8; 32])].iter() {
+            let secret = U256::from(*secret);
+            let signature = sign(secret, message);
+
+            assert_eq!(recover(message, &signature), Some(public(secret)));
+
+            let mut other = *message;
+            other[0] ^= 1;
+            assert_ne!(recover(&other, &signature), Some(public(secret)));
+        }
+    }
+
+    fn h256(hex: &str) -> H256 {
+        let mut buf = [0u8; 32];
+        u256(hex).to_big_endian(&mut buf);
+        buf
+    }
+
+    /// Returns `r || s || v` from their hex.
+    fn signature(r: &str, s: &str, v: u8) -> [u8; 65] {
+        let mut ret = [0u8; 65];
+        ret[0..32].copy_from_slice(&h256(r));
+        ret[32..64].copy_from_slice(&h256(s));
+        ret[64] = v;
+        ret
+    }
+
+    fn key(hex: &str) -> [u8; 33] {
+        let mut ret = [0u8; 33];
+        for (i, byte) in ret.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).unwrap();
+        }
+        ret
+    }
+
+    #[test]
+    fn known_signatures() {
+        // The example transaction of EIP-155, signed by the key `0x4646..46` with `v = 37`.
+        // That is recovery id 0 on chain 1, given here in its pre-EIP-155 form of 27.
+        let eip155 = key("024bc2a31265153f07e70e0bab08724e6b85e217f8cd628ceb62974247bb493382");
+        assert_eq!(
+            recover(
+                &h256("daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53"),
+                &signature(
+                    "28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276",
+                    "67cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83",
+                    27,
+                )
+            ),
+            Some(eip155)
+        );
+        assert_eq!(
+            public(u256(
+                "4646464646464646464646464646464646464646464646464646464646464646"
+            )),
+            eip155
+        );
+
+        // Signed by OpenSSL with the key of `known_eth1_address`, with `s` normalised to the
+        // lower half of the order, for a message whose nonce point has an odd y coordinate.
+        let message = h256("e668db8d13219c51405b03c50dfe33f7b057dbe4dbe658673880d6a628ba1793");
+        let signature = signature(
+            "3f5f8b571ebe26325a9ae2cbc9ade4a7e96993b92f91ed5340c605108a6786cc",
+            "4ff037af4d2e00e74602a8f1098a839e78f29d84e712802a91015ab6587c7491",
+            1,
+        );
+        let openssl = key("024e3b81af9c2234cad09d679ce6035ed1392347ce64ce405f5dcd36228a25de6e");
+        assert_eq!(recover(&message, &signature), Some(openssl));
+
+        let mut scheme = [0u8; 96];
+        scheme[0..65].copy_from_slice(&signature);
+        assert!(SignatureScheme::Secp256k1.verify(&pubkey(&openssl), &message, &scheme));
+        assert!(!SignatureScheme::Secp256k1.verify(&pubkey(&eip155), &message, &scheme));
+    }
+
+    #[test]
+    fn verifier() {
+        let secret = U256::from(42);
+        let message = [9u8; 32];
+        let key = pubkey(&public(secret));
+
+        let mut signature = [0u8; 96];
+        signature[0..65].copy_from_slice(&sign(secret, &message));
+        assert!(Secp256k1.verify(&key, &message, &signature));
+
+        // Another signer, trailing bytes and an unknown recovery id are all rejected.
+        assert!(!Secp256k1.verify(&pubkey(&public(U256::from(43))), &message, &signature));
+
+        let mut trailing = signature;
+        trailing[95] = 1;
+        assert!(!Secp256k1.verify(&key, &message, &trailing));
+
+        let mut id = signature;
+        id[64] = 2;
+        assert!(!Secp256k1.verify(&key, &message, &id));
+    }
+
+    #[test]
+    fn malleated_signatures() {
+        let secret = U256::from(42);
+        let message = [9u8; 32];
+        let mut signature = sign(secret, &message);
+
+        // `(r, N - s)` with the other recovery id is also a valid ECDSA signature, but it isn't
+        // accepted.
+        let s = U256::from_big_endian(&signature[32..64]);
+        (N - s).to_big_endian(&mut signature[32..64]);
+        signature[64] ^= 1;
+        assert_eq!(recover(&message, &signature), None);
+
+        signature[32..64].copy_from_slice(&[0u8; 32]);
+        assert_eq!(recover(&message, &signature), None);
+    }
+}
@@ -0,0 +1,220 @@
+//! `serde` support for the types used by tooling around the EE (e.g. fixtures, RPC and
+//! explorers).
+//!
+//! Byte strings such as addresses, indexes and public keys are written as `0x` prefixed,
+//! big-endian hex, and amounts and nonces as plain integers. Amounts above `Amount::MAX` are
+//! rejected while deserializing. `H256` is an alias of `[u8; 32]`, which `serde` writes as an
+//! array of bytes, so fields holding a hash can opt into hex with `#[serde(with = "h256")]`.
+
+use crate::address::Address;
+use crate::amount::Amount;
+use crate::bls::PublicKey;
+use crate::u264::U264;
+use alloc::string::String;
+use core::convert::TryFrom;
+use core::fmt;
+use serde::de::{self, Deserializer, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+
+const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Returns `bytes` as `0x` prefixed hex.
+fn encode(bytes: &[u8]) -> String {
+    let mut ret = String::with_capacity(2 + bytes.len() * 2);
+    ret.push_str("0x");
+
+    for byte in bytes {
+        ret.push(DIGITS[(byte >> 4) as usize] as char);
+        ret.push(DIGITS[(byte & 0xf) as usize] as char);
+    }
+
+    ret
+}
+
+/// Decodes hex, with or without a `0x` prefix, into exactly `N` bytes.
+fn decode<const N: usize>(s: &str) -> Option<[u8; N]> {
+    let s = s.strip_prefix("0x").unwrap_or(s).as_bytes();
+    if s.len() != N * 2 {
+        return None;
+    }
+
+    let digit = |c: u8| (c as char).to_digit(16).map(|d| d as u8);
+
+    let mut ret = [0u8; N];
+    for (i, byte) in ret.iter_mut().enumerate() {
+        *byte = digit(s[i * 2])? << 4 | digit(s[i * 2 + 1])?;
+    }
+
+    Some(ret)
+}
+
+struct HexVisitor<const N: usize>;
+
+impl<'de, const N: usize> Visitor<'de> for HexVisitor<N> {
+    type Value = [u8; N];
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} bytes of hex", N)
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
+        decode(s).ok_or_else(|| E::invalid_value(de::Unexpected::Str(s), &self))
+    }
+}
+
+fn deserialize_hex<'de, D: Deserializer<'de>, const N: usize>(d: D) -> Result<[u8; N], D::Error> {
+    d.deserialize_str(HexVisitor::<N>)
+}
+
+/// Writes a 32 byte hash as hex, for use with `#[serde(with = "sheth::serialize::h256")]`.
+pub mod h256 {
+    use crate::hash::H256;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(hash: &H256, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&super::encode(hash))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<H256, D::Error> {
+        super::deserialize_hex(d)
+    }
+}
+
+/// Writes a transfer's 96 byte signature as hex.
+pub(crate) mod signature {
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(signature: &[u8; 96], s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&super::encode(signature))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<[u8; 96], D::Error> {
+        super::deserialize_hex(d)
+    }
+}
+
+impl Serialize for Address {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&encode(&<[u8; 32]>::from(*self)))
+    }
+}
+
+impl<'de> Deserialize<'de> for Address {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(d)?;
+        Address::try_from(&*s)
+            .map_err(|_| de::Error::invalid_value(de::Unexpected::Str(&s), &"an address"))
+    }
+}
+
+impl Serialize for U264 {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&encode(&self.to_be_bytes()))
+    }
+}
+
+impl<'de> Deserialize<'de> for U264 {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        deserialize_hex::<D, 33>(d).map(U264::from_be_bytes)
+    }
+}
+
+impl Serialize for PublicKey {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&encode(&self.as_bytes()))
+    }
+}
+
+impl<'de> Deserialize<'de> for PublicKey {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        deserialize_hex::<D, 48>(d).map(PublicKey::new)
+    }
+}
+
+impl Serialize for Amount {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_u64(self.get())
+    }
+}
+
+impl<'de> Deserialize<'de> for Amount {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let n = u64::deserialize(d)?;
+        Amount::new(n).map_err(|_| {
+            de::Error::invalid_value(de::Unexpected::Unsigned(n), &"an amount below Amount::MAX")
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::account::Account;
+    use crate::transaction::{Transaction, Transfer};
+
+    #[test]
+    fn round_trip() {
+        let address = Address::from(0x1234);
+        let json = serde_json::to_string(&address).unwrap();
+        assert_eq!(json, format!("\"0x{}1234\"", "0".repeat(60)));
+        assert_eq!(serde_json::from_str::<Address>(&json).unwrap(), address);
+
+        let index = U264::from(5u8) << 260;
+        let json = serde_json::to_string(&index).unwrap();
+        assert!(json.starts_with("\"0x50"));
+        assert_eq!(serde_json::from_str::<U264>(&json).unwrap(), index);
+
+        let account = Account {
+            pubkey: PublicKey::one(),
+            nonce: 3,
+            value: 42,
+        };
+        let json = serde_json::to_string(&account).unwrap();
+        assert_eq!(serde_json::from_str::<Account>(&json).unwrap(), account);
+    }
+
+    #[test]
+    fn hex_hash() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Root {
+            #[serde(with = "h256")]
+            root: crate::hash::H256,
+        }
+
+        let root = Root { root: [0xab; 32] };
+        let json = serde_json::to_string(&root).unwrap();
+        assert_eq!(json, format!("{{\"root\":\"0x{}\"}}", "ab".repeat(32)));
+        assert_eq!(serde_json::from_str::<Root>(&json).unwrap(), root);
+    }
+
+    #[test]
+    fn transaction() {
+        let tx = Transaction::Transfer(Transfer {
+            to: 1.into(),
+            from: 2.into(),
+            nonce: 3.into(),
+            amount: 4.into(),
+            fee: 1.into(),
+            valid_until: Some(5),
+            signature: [9u8; 96],
+        });
+
+        let json = serde_json::to_value(&tx).unwrap();
+        assert_eq!(json["Transfer"]["nonce"], 3);
+        assert_eq!(json["Transfer"]["amount"], 4);
+        assert_eq!(json["Transfer"]["fee"], 1);
+        assert_eq!(json["Transfer"]["valid_until"], 5);
+        assert_eq!(json["Transfer"]["signature"], encode(&[9u8; 96]));
+
+        let tx: Transaction = serde_json::from_value(json).unwrap();
+        assert_eq!(tx.nonce(), 3.into());
+        assert_eq!(tx.accounts(), [2.into(), 1.into()]);
+    }
+
+    #[test]
+    fn invalid_values() {
+        assert!(serde_json::from_str::<Amount>(&u64::MAX.to_string()).is_err());
+        assert!(serde_json::from_str::<Address>("\"0x1234\"").is_err());
+        assert!(serde_json::from_str::<PublicKey>(&format!("\"{}\"", "zz".repeat(48))).is_err());
+    }
+}
@@ -0,0 +1,166 @@
+use crate::address::Address;
+use crate::amount::Amount;
+use crate::error::Error;
+use crate::state::State;
+use alloc::collections::BTreeMap;
+
+/// Accumulates the debits made against each account during a block so they can be settled with
+/// a single write per account once processing is complete.
+///
+/// Each debit is checked against the account's current value less any debits which are already
+/// pending, so settling can never underflow. A credit to an account with pending debits is netted
+/// against them, so it is checked for overflow against the value the account will settle at
+/// rather than its current one.
+#[derive(Clone, Default)]
+pub struct Settlement {
+    debits: BTreeMap<Address, Amount>,
+}
+
+impl Settlement {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the total of the debits pending against `address`.
+    pub fn pending(&self, address: Address) -> Amount {
+        self.debits.get(&address).cloned().unwrap_or_default()
+    }
+
+    /// Returns the value of `address` once its pending debits are settled.
+    pub fn value<T: State>(&self, db: &T, address: Address) -> Result<Amount, Error> {
+        db.value(address)?
+            .checked_sub(self.pending(address))
+            .map_err(|_| Error::BalanceInsufficient)
+    }
+
+    /// Record a debit of `amount` against `address`.
+    pub fn debit<T: State>(
+        &mut self,
+        db: &T,
+        address: Address,
+        amount: Amount,
+    ) -> Result<(), Error> {
+        if self.value(db, address)? < amount {
+            return Err(Error::BalanceInsufficient);
+        }
+
+        let pending = self.debits.entry(address).or_default();
+        *pending = pending.checked_add(amount)?;
+
+        Ok(())
+    }
+
+    /// Credit `amount` to `address`, returning the value it will settle at. The credit cancels
+    /// the debits pending against `address` first, and only what is left over is added to its
+    /// value in `db`, so the write fails with `Error::Overflow` only if the settled value would.
+    pub fn credit<T: State>(
+        &mut self,
+        db: &mut T,
+        address: Address,
+        amount: Amount,
+    ) -> Result<Amount, Error> {
+        let pending = self.pending(address);
+        let cancelled = pending.min(amount);
+
+        let value = db.add_value(address, amount.checked_sub(cancelled)?)?;
+
+        // A debit cancelled in full is still settled, as a write of zero, so the block makes the
+        // same writes whether or not its debits are cancelled.
+        let pending = pending.checked_sub(cancelled)?;
+        if let Some(debit) = self.debits.get_mut(&address) {
+            *debit = pending;
+        }
+
+        value.checked_sub(pending)
+    }
+
+    /// Apply all pending debits to `db`.
+    pub fn settle<T: State>(self, db: &mut T) -> Result<(), Error> {
+        for (address, amount) in self.debits {
+            db.sub_value(address, amount)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::account::Account;
+    use crate::bls::PublicKey;
+    use crate::state::MockState;
+
+    fn build_state() -> MockState {
+        let mut accounts: BTreeMap<Address, Account> = BTreeMap::new();
+        accounts.insert(
+            0.into(),
+            Account {
+                pubkey: PublicKey::zero(),
+                nonce: 0,
+                value: 10,
+            },
+        );
+
+        MockState::new(accounts)
+    }
+
+    #[test]
+    fn aggregates_debits() {
+        let mut db = build_state();
+        let mut settlement = Settlement::new();
+
+        assert_eq!(settlement.debit(&db, 0.into(), 3.into()), Ok(()));
+        assert_eq!(settlement.debit(&db, 0.into(), 4.into()), Ok(()));
+        assert_eq!(settlement.pending(0.into()), 7.into());
+        assert_eq!(settlement.value(&db, 0.into()), Ok(3.into()));
+
+        // Nothing is written until the block is settled.
+        assert_eq!(db.value(0.into()), Ok(10.into()));
+        assert_eq!(settlement.settle(&mut db), Ok(()));
+        assert_eq!(db.value(0.into()), Ok(3.into()));
+    }
+
+    #[test]
+    fn rejects_debits_exceeding_value() {
+        let db = build_state();
+        let mut settlement = Settlement::new();
+
+        assert_eq!(settlement.debit(&db, 0.into(), 6.into()), Ok(()));
+        assert_eq!(
+            settlement.debit(&db, 0.into(), 5.into()),
+            Err(Error::BalanceInsufficient)
+        );
+        assert_eq!(settlement.pending(0.into()), 6.into());
+    }
+
+    #[test]
+    fn credits_net_against_debits() {
+        let mut db = build_state();
+        let mut settlement = Settlement::new();
+
+        assert_eq!(settlement.debit(&db, 0.into(), 6.into()), Ok(()));
+        assert_eq!(settlement.credit(&mut db, 0.into(), 4.into()), Ok(8.into()));
+        assert_eq!(settlement.pending(0.into()), 2.into());
+        assert_eq!(db.value(0.into()), Ok(10.into()));
+
+        // Once the debits are cancelled, the rest of a credit is written.
+        assert_eq!(
+            settlement.credit(&mut db, 0.into(), 5.into()),
+            Ok(13.into())
+        );
+        assert_eq!(settlement.pending(0.into()), 0.into());
+        assert_eq!(db.value(0.into()), Ok(13.into()));
+    }
+
+    #[test]
+    fn unknown_account() {
+        let db = build_state();
+        let mut settlement = Settlement::new();
+
+        assert_eq!(
+            settlement.debit(&db, 1.into(), 1.into()),
+            Err(Error::StateIncomplete(Address::from(1).into()))
+        );
+    }
+}
@@ -0,0 +1,242 @@
+use crate::address::Address;
+use crate::amount::Amount;
+use crate::bls::PublicKey;
+use crate::error::Error;
+use crate::hash::H256;
+use crate::nonce::Nonce;
+use crate::state::State;
+use alloc::vec::Vec;
+
+/// A change buffered by a `Buffer`.
+enum Write {
+    AddValue(Address, Amount),
+    SubValue(Address, Amount),
+    IncNonce(Address),
+    SetPubkey(Address, PublicKey),
+    CreateAccount(Address, PublicKey),
+    DeleteAccount(Address),
+}
+
+/// Buffers the changes made by a transaction, so they can be written to the base once every step
+/// has succeeded or dropped if any fails.
+///
+/// Unlike `Overlay`, which loads every chunk of an account it touches, reads are answered from
+/// the base and the changes buffered since. So only the chunks the base would have read are
+/// needed, and a buffer can run over a partial witness.
+pub struct Buffer<'a, T: State> {
+    base: &'a mut T,
+    writes: Vec<Write>,
+}
+
+impl<'a, T: State> Buffer<'a, T> {
+    pub fn new(base: &'a mut T) -> Self {
+        Self {
+            base,
+            writes: Vec::new(),
+        }
+    }
+
+    /// Write the buffered changes to the base in the order they were made. Each was checked
+    /// against the base when it was buffered, so this only fails if the base can't make a change
+    /// it allowed.
+    pub fn commit(self) -> Result<(), Error> {
+        for write in self.writes {
+            match write {
+                Write::AddValue(address, amount) => {
+                    self.base.add_value(address, amount).map(|_| ())
+                }
+                Write::SubValue(address, amount) => {
+                    self.base.sub_value(address, amount).map(|_| ())
+                }
+                Write::IncNonce(address) => self.base.inc_nonce(address).map(|_| ()),
+                Write::SetPubkey(address, pubkey) => self.base.set_pubkey(address, pubkey),
+                Write::CreateAccount(address, pubkey) => self.base.create_account(address, pubkey),
+                Write::DeleteAccount(address) => self.base.delete_account(address).map(|_| ()),
+            }?;
+        }
+
+        Ok(())
+    }
+
+    /// Drop the buffered changes, leaving the base untouched.
+    pub fn discard(self) {}
+}
+
+impl<'a, T: State> State for Buffer<'a, T> {
+    /// The root can only be calculated once every change has been committed to the base.
+    fn root(&mut self) -> Result<H256, Error> {
+        if !self.writes.is_empty() {
+            return Err(Error::Uncommitted);
+        }
+
+        self.base.root()
+    }
+
+    fn value(&self, address: Address) -> Result<Amount, Error> {
+        let mut value = self.base.value(address)?;
+
+        for write in self.writes.iter() {
+            value = match write {
+                Write::AddValue(a, amount) if *a == address => value.checked_add(*amount)?,
+                Write::SubValue(a, amount) if *a == address => value.checked_sub(*amount)?,
+                Write::DeleteAccount(a) if *a == address => Amount::zero(),
+                _ => value,
+            };
+        }
+
+        Ok(value)
+    }
+
+    fn nonce(&self, address: Address) -> Result<Nonce, Error> {
+        let mut nonce = self.base.nonce(address)?;
+
+        for write in self.writes.iter() {
+            nonce = match write {
+                Write::IncNonce(a) if *a == address => nonce.next()?,
+                Write::DeleteAccount(a) if *a == address => Nonce::new(0),
+                _ => nonce,
+            };
+        }
+
+        Ok(nonce)
+    }
+
+    fn pubkey(&self, address: Address) -> Result<PublicKey, Error> {
+        let mut pubkey = self.base.pubkey(address)?;
+
+        for write in self.writes.iter() {
+            match write {
+                Write::SetPubkey(a, p) | Write::CreateAccount(a, p) if *a == address => {
+                    pubkey = p.clone()
+                }
+                Write::DeleteAccount(a) if *a == address => pubkey = PublicKey::zero(),
+                _ => (),
+            }
+        }
+
+        Ok(pubkey)
+    }
+
+    fn add_value(&mut self, address: Address, amount: Amount) -> Result<Amount, Error> {
+        let value = self.value(address)?.checked_add(amount)?;
+        self.writes.push(Write::AddValue(address, amount));
+
+        Ok(value)
+    }
+
+    fn sub_value(&mut self, address: Address, amount: Amount) -> Result<Amount, Error> {
+        let value = self
+            .value(address)?
+            .checked_sub(amount)
+            .map_err(|_| Error::BalanceInsufficient)?;
+        self.writes.push(Write::SubValue(address, amount));
+
+        Ok(value)
+    }
+
+    fn inc_nonce(&mut self, address: Address) -> Result<Nonce, Error> {
+        let nonce = self.nonce(address)?.next()?;
+        self.writes.push(Write::IncNonce(address));
+
+        Ok(nonce)
+    }
+
+    fn set_pubkey(&mut self, address: Address, pubkey: PublicKey) -> Result<(), Error> {
+        self.pubkey(address)?;
+        self.writes.push(Write::SetPubkey(address, pubkey));
+
+        Ok(())
+    }
+
+    fn create_account(&mut self, address: Address, pubkey: PublicKey) -> Result<(), Error> {
+        if self.nonce(address)? != Nonce::new(0)
+            || self.value(address)? != Amount::zero()
+            || self.pubkey(address)? != PublicKey::zero()
+        {
+            return Err(Error::AccountExists);
+        }
+
+        self.writes.push(Write::CreateAccount(address, pubkey));
+
+        Ok(())
+    }
+
+    fn delete_account(&mut self, address: Address) -> Result<Amount, Error> {
+        let value = self.value(address)?;
+        self.nonce(address)?;
+        self.pubkey(address)?;
+        self.writes.push(Write::DeleteAccount(address));
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::account::Account;
+    use crate::state::MockState;
+    use alloc::collections::BTreeMap;
+
+    fn build_state() -> MockState {
+        let mut accounts: BTreeMap<Address, Account> = BTreeMap::new();
+        accounts.insert(
+            0.into(),
+            Account {
+                pubkey: PublicKey::one(),
+                nonce: 3,
+                value: 10,
+            },
+        );
+        accounts.insert(1.into(), Account::zero());
+
+        MockState::new(accounts)
+    }
+
+    #[test]
+    fn reads_include_buffered_writes() {
+        let mut base = build_state();
+        let mut buffer = Buffer::new(&mut base);
+
+        assert_eq!(buffer.sub_value(0.into(), 4.into()), Ok(6.into()));
+        assert_eq!(buffer.inc_nonce(0.into()), Ok(4.into()));
+        assert_eq!(buffer.value(0.into()), Ok(6.into()));
+        assert_eq!(buffer.nonce(0.into()), Ok(4.into()));
+        assert_eq!(
+            buffer.sub_value(0.into(), 7.into()),
+            Err(Error::BalanceInsufficient)
+        );
+        assert_eq!(
+            buffer.create_account(0.into(), PublicKey::zero()),
+            Err(Error::AccountExists)
+        );
+        assert_eq!(buffer.root(), Err(Error::Uncommitted));
+
+        buffer.discard();
+        assert_eq!(base.value(0.into()), Ok(10.into()));
+        assert_eq!(base.nonce(0.into()), Ok(3.into()));
+    }
+
+    #[test]
+    fn commit_matches_base() {
+        let mut base = build_state();
+        let mut expected = build_state();
+
+        let mut buffer = Buffer::new(&mut base);
+        for state in [&mut buffer as &mut dyn State, &mut expected].iter_mut() {
+            state.sub_value(0.into(), 4.into()).unwrap();
+            state.inc_nonce(0.into()).unwrap();
+            state.set_pubkey(0.into(), PublicKey::zero()).unwrap();
+            state.create_account(1.into(), PublicKey::one()).unwrap();
+            state.add_value(1.into(), 4.into()).unwrap();
+            state.delete_account(1.into()).unwrap();
+        }
+
+        assert_eq!(buffer.pubkey(0.into()), Ok(PublicKey::zero()));
+        assert_eq!(buffer.pubkey(1.into()), Ok(PublicKey::zero()));
+        assert_eq!(buffer.value(1.into()), Ok(0.into()));
+
+        buffer.commit().unwrap();
+        assert_eq!(base.root(), expected.root());
+    }
+}
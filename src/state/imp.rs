@@ -1,81 +1,314 @@
-use crate::account::{calc_nonce_index, calc_value_index};
+use crate::account::{check_address, Account};
 use crate::address::Address;
+use crate::amount::Amount;
+use crate::bls::PublicKey;
 use crate::error::Error;
+use crate::hash::H256;
+use crate::nonce::Nonce;
+use crate::schema::{field_index, Field, LeafSchema};
+#[cfg(feature = "alloc")]
+use crate::state::root::imp_root;
 use crate::state::State;
 use crate::u264::U264;
+#[cfg(feature = "alloc")]
+use alloc::vec;
 use arrayref::array_ref;
 use imp::Imp;
 
-impl<'a> State for Imp<'a, U264> {
-    fn root(&mut self) -> Result<[u8; 32], Error> {
-        Ok(self.root())
+/// Returns an `Imp` over `data` once its structure has been validated with `validate`. Unlike
+/// `Imp::new`, a malformed proof is rejected instead of panicking or producing the wrong root.
+///
+/// Bytes after the last chunk are ignored, as they are by `Imp::new`.
+#[cfg(feature = "alloc")]
+pub fn new_checked(data: &mut [u8], height: usize) -> Result<Imp<'_, U264>, Error> {
+    if data.len() < 8 {
+        return Err(Error::ProofTruncated);
     }
 
-    fn value(&self, address: Address) -> Result<u64, Error> {
-        let index = calc_value_index(address, self.height);
-        let chunk = self.get(index);
-        Ok(u64::from_le_bytes(*array_ref![&chunk, 0, 8]))
+    let len = u64::from_le_bytes(*array_ref![data, 0, 8]) as usize;
+    let end = len.checked_mul(40).ok_or(Error::ProofTruncated)?;
+    if len == 0 || data.len() < end {
+        return Err(Error::ProofTruncated);
+    }
+
+    validate(&data[8..len * 8], &data[len * 8..end])?;
+
+    Ok(Imp::new(data, height))
+}
+
+/// Checks that `offsets`, not including the leading length, and `chunks` form a multi-proof:
+///
+/// * `offsets` must be a whole number of `u64`s, or `Error::OffsetsLengthInvalid`.
+/// * `chunks` must hold one more chunk than there are offsets, or `Error::ChunksLengthInvalid`.
+/// * Each offset must split its subtree into two non-empty subtrees, and the positions of the
+///   internal nodes they describe must cover the offsets exactly once, or `Error::OffsetsInvalid`.
+///
+/// The shape of the tree isn't checked against the leaves it should hold, so a proof which
+/// collapses an account into its hash is valid here. Reading that account is rejected by `lookup`.
+#[cfg(feature = "alloc")]
+pub fn validate(offsets: &[u8], chunks: &[u8]) -> Result<(), Error> {
+    if !offsets.len().is_multiple_of(8) {
+        return Err(Error::OffsetsLengthInvalid);
     }
 
-    fn nonce(&self, address: Address) -> Result<u64, Error> {
-        let index = calc_nonce_index(address, self.height);
-        let chunk = self.get(index);
-        Ok(u64::from_le_bytes(*array_ref![&chunk, 0, 8]))
+    let len = offsets.len() / 8 + 1;
+    if chunks.len() != len * 32 {
+        return Err(Error::ChunksLengthInvalid);
     }
 
-    fn add_value(&mut self, address: Address, amount: u64) -> Result<u64, Error> {
-        let index = calc_value_index(address, self.height);
-        let chunk = self.get(index);
+    // The number of chunks below each internal node, found going forwards through the offsets
+    // in the same way as `root::nodes`.
+    let mut sizes = vec![0usize; len - 1];
+    if len > 1 {
+        sizes[0] = len;
+    }
 
-        let value = u64::from_le_bytes(*array_ref![&chunk, 0, 8]);
+    for position in 0..(len - 1) {
+        let size = sizes[position];
+        let left = u64::from_le_bytes(*array_ref![offsets, position * 8, 8]) as usize;
 
-        let (value, overflow) = value.overflowing_add(amount);
-        if overflow {
-            return Err(Error::Overflow);
+        if size == 0 || left == 0 || left >= size {
+            return Err(Error::OffsetsInvalid);
         }
 
-        let mut buf = [0u8; 32];
-        buf[0..8].copy_from_slice(&value.to_le_bytes());
-        self.update(index, buf);
+        for (child, child_size) in [(position + 1, left), (position + left, size - left)].iter() {
+            if *child_size > 1 {
+                if *child >= len - 1 || sizes[*child] != 0 {
+                    return Err(Error::OffsetsInvalid);
+                }
 
-        Ok(value)
+                sizes[*child] = *child_size;
+            }
+        }
     }
 
-    fn sub_value(&mut self, address: Address, amount: u64) -> Result<u64, Error> {
-        let index = calc_value_index(address, self.height);
-        let chunk = self.get(index);
+    Ok(())
+}
+
+/// Returns the offset of the chunk at `index` in a proof split into its `offsets` and `chunks`,
+/// following the same path as `Imp`. A proof which is too short for the path is rejected with
+/// `Error::ProofTruncated` rather than panicking.
+///
+/// Unlike `Imp`, the path must end at a single chunk. A chunk may be found one level above
+/// `index` when `index` is a left child, since that is where the nonce and value are stored with
+/// their zero sibling, but a path which reaches a chunk any higher is rejected with
+/// `Error::StateIncomplete` and the general index of that chunk. Otherwise a proof which collapses
+/// an account into its hash would read and write the chunks of whichever subtree follows it.
+pub(crate) fn lookup(
+    offsets: &[u8],
+    chunks: &[u8],
+    height: usize,
+    index: U264,
+) -> Result<usize, Error> {
+    let mut position = 0usize;
+    let mut offset = 0usize;
+    let mut size = chunks.len() / 32;
+
+    // The general index of the node the path has reached.
+    let mut node = U264::one();
+
+    // `height` is the height of the proof, so the bits below the marker bit of `index` are read
+    // from the most significant down.
+    for depth in (0..height).rev() {
+        let bit = (index >> depth) & U264::one();
+
+        if size == 1 {
+            if depth == 0 && bit == U264::zero() {
+                break;
+            }
+
+            return Err(Error::StateIncomplete(node));
+        }
+
+        let begin = position.checked_mul(8).ok_or(Error::ProofTruncated)?;
+        if begin.saturating_add(8) > offsets.len() {
+            return Err(Error::ProofTruncated);
+        }
 
-        let value = u64::from_le_bytes(*array_ref![chunk, 0, 8]);
+        let skip = u64::from_le_bytes(*array_ref![offsets, begin, 8]) as usize;
+        if skip == 0 || skip >= size {
+            return Err(Error::ProofTruncated);
+        }
 
-        let (value, overflow) = value.overflowing_sub(amount);
-        if overflow {
-            return Err(Error::Overflow);
+        if bit == U264::zero() {
+            position = position.saturating_add(1);
+            size = skip;
+        } else {
+            position = position.saturating_add(skip);
+            offset = offset.saturating_add(skip);
+            size -= skip;
         }
 
+        node = (node << 1usize) + bit;
+    }
+
+    // A path which ends above a chunk would return the first chunk below it.
+    if size != 1 {
+        return Err(Error::StateIncomplete(node));
+    }
+
+    if offset >= chunks.len() / 32 {
+        return Err(Error::ProofTruncated);
+    }
+
+    Ok(offset)
+}
+
+/// Returns the chunk of `imp` at `index`, or `Error::ProofTruncated` if it isn't in the proof.
+pub fn get(imp: &Imp<U264>, index: U264) -> Result<H256, Error> {
+    let offset = lookup(imp.offsets, imp.db, imp.height, index)?;
+    Ok(*array_ref![imp.db, offset * 32, 32])
+}
+
+/// Overwrite the chunk of `imp` at `index`, or return `Error::ProofTruncated` if it isn't in the
+/// proof.
+pub fn update(imp: &mut Imp<U264>, index: U264, chunk: H256) -> Result<(), Error> {
+    let offset = lookup(imp.offsets, imp.db, imp.height, index)?;
+    imp.db[offset * 32..offset * 32 + 32].copy_from_slice(&chunk);
+    Ok(())
+}
+
+/// Reads the chunks of `field` in the leaf at `address` into `buf`, which must be exactly as long
+/// as the field. An address beyond the last leaf is rejected with `Error::AddressInvalid`.
+pub fn read<S: LeafSchema>(
+    imp: &Imp<U264>,
+    address: Address,
+    field: Field,
+    buf: &mut [u8],
+) -> Result<(), Error> {
+    debug_assert_eq!(buf.len(), field.chunks * 32);
+    check_address(address, imp.height.saturating_sub(S::DEPTH))?;
+    let index = field_index::<S>(address, imp.height, field);
+
+    for (i, chunk) in buf.chunks_mut(32).enumerate() {
+        chunk.copy_from_slice(&get(imp, index + (i as u8).into())?);
+    }
+
+    Ok(())
+}
+
+/// Overwrites the chunks of `field` in the leaf at `address` with `buf`, which must be exactly as
+/// long as the field. Every chunk is looked up before any are written, so a truncated proof is
+/// left unchanged. An address beyond the last leaf is rejected with `Error::AddressInvalid`.
+pub fn write<S: LeafSchema>(
+    imp: &mut Imp<U264>,
+    address: Address,
+    field: Field,
+    buf: &[u8],
+) -> Result<(), Error> {
+    debug_assert_eq!(buf.len(), field.chunks * 32);
+    check_address(address, imp.height.saturating_sub(S::DEPTH))?;
+    let index = field_index::<S>(address, imp.height, field);
+
+    for i in 0..field.chunks {
+        lookup(imp.offsets, imp.db, imp.height, index + (i as u8).into())?;
+    }
+
+    for (i, chunk) in buf.chunks(32).enumerate() {
+        update(imp, index + (i as u8).into(), *array_ref![chunk, 0, 32])?;
+    }
+
+    Ok(())
+}
+
+impl<'a> State for Imp<'a, U264> {
+    #[cfg(feature = "alloc")]
+    fn root(&mut self) -> Result<[u8; 32], Error> {
+        Ok(imp_root(self))
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    fn root(&mut self) -> Result<[u8; 32], Error> {
+        crate::fixed::proof_root(self)
+    }
+
+    fn value(&self, address: Address) -> Result<Amount, Error> {
+        let mut chunk = [0u8; 32];
+        read::<Account>(self, address, Account::VALUE, &mut chunk)?;
+        Amount::new(u64::from_le_bytes(*array_ref![chunk, 0, 8]))
+    }
+
+    fn nonce(&self, address: Address) -> Result<Nonce, Error> {
+        let mut chunk = [0u8; 32];
+        read::<Account>(self, address, Account::NONCE, &mut chunk)?;
+        Ok(u64::from_le_bytes(*array_ref![chunk, 0, 8]).into())
+    }
+
+    fn pubkey(&self, address: Address) -> Result<PublicKey, Error> {
+        let mut buf = [0u8; 64];
+        read::<Account>(self, address, Account::PUBKEY, &mut buf)?;
+        Ok(PublicKey::new(*array_ref![buf, 0, 48]))
+    }
+
+    fn add_value(&mut self, address: Address, amount: Amount) -> Result<Amount, Error> {
+        let value = self.value(address)?.checked_add(amount)?;
+
         let mut buf = [0u8; 32];
-        buf[0..8].copy_from_slice(&value.to_le_bytes());
-        self.update(index, buf);
+        buf[0..8].copy_from_slice(&value.get().to_le_bytes());
+        write::<Account>(self, address, Account::VALUE, &buf)?;
 
         Ok(value)
     }
 
-    fn inc_nonce(&mut self, address: Address) -> Result<u64, Error> {
-        let index = calc_nonce_index(address, self.height);
-        let chunk = self.get(index);
+    fn sub_value(&mut self, address: Address, amount: Amount) -> Result<Amount, Error> {
+        let value = self
+            .value(address)?
+            .checked_sub(amount)
+            .map_err(|_| Error::BalanceInsufficient)?;
 
-        let nonce = u64::from_le_bytes(*array_ref![chunk, 0, 8]);
+        let mut buf = [0u8; 32];
+        buf[0..8].copy_from_slice(&value.get().to_le_bytes());
+        write::<Account>(self, address, Account::VALUE, &buf)?;
 
-        let (nonce, overflow) = nonce.overflowing_add(1);
-        if overflow {
-            return Err(Error::Overflow);
-        }
+        Ok(value)
+    }
+
+    fn inc_nonce(&mut self, address: Address) -> Result<Nonce, Error> {
+        let nonce = self.nonce(address)?.next()?;
 
         let mut buf = [0u8; 32];
-        buf[0..8].copy_from_slice(&nonce.to_le_bytes());
-        self.update(index, buf);
+        buf[0..8].copy_from_slice(&nonce.get().to_le_bytes());
+        write::<Account>(self, address, Account::NONCE, &buf)?;
 
         Ok(nonce)
     }
+
+    fn set_pubkey(&mut self, address: Address, pubkey: PublicKey) -> Result<(), Error> {
+        let mut buf = [0u8; 64];
+        buf[0..48].copy_from_slice(&pubkey.as_bytes());
+        write::<Account>(self, address, Account::PUBKEY, &buf)
+    }
+
+    /// `Imp` can't change the shape of its proof, so the account's chunks must already be part
+    /// of it, each at its own depth. The account is empty if all of them are zero. An account
+    /// collapsed into the hash of its subtree is rejected by `lookup`, even if it is the hash of
+    /// an empty subtree, since there would be nowhere to write the pubkey.
+    fn create_account(&mut self, address: Address, pubkey: PublicKey) -> Result<(), Error> {
+        if self.nonce(address)? != Nonce::new(0)
+            || self.value(address)? != Amount::zero()
+            || self.pubkey(address)? != PublicKey::zero()
+        {
+            return Err(Error::AccountExists);
+        }
+
+        let mut buf = [0u8; 64];
+        buf[0..48].copy_from_slice(&pubkey.as_bytes());
+        write::<Account>(self, address, Account::PUBKEY, &buf)
+    }
+
+    /// The chunks of the account are zeroed, but the shape of the proof is left unchanged.
+    fn delete_account(&mut self, address: Address) -> Result<Amount, Error> {
+        // Read every chunk first, so a truncated proof is rejected before any are zeroed.
+        let value = self.value(address)?;
+        self.nonce(address)?;
+        self.pubkey(address)?;
+
+        write::<Account>(self, address, Account::PUBKEY, &[0u8; 64])?;
+        write::<Account>(self, address, Account::NONCE, &[0u8; 32])?;
+        write::<Account>(self, address, Account::VALUE, &[0u8; 32])?;
+
+        Ok(value)
+    }
 }
 
 #[cfg(test)]
@@ -97,13 +330,13 @@ mod test {
 
     fn get_proof() -> Vec<u8> {
         // indexes = [16, 17, 9, 10, 11, 3]
-        let offsets: Vec<u8> = vec![6, 5, 3, 2, 1, 1].iter().fold(vec![], |mut acc, x| {
+        let offsets: Vec<u8> = [6, 5, 3, 2, 1, 1].iter().fold(vec![], |mut acc, x| {
             let x = *x as u64;
             acc.extend(&x.to_le_bytes());
             acc
         });
 
-        let proof: Vec<u8> = vec![h256(0), h256(0), h256(1), h256(1), zh(0), zh(0)]
+        let proof: Vec<u8> = [h256(0), h256(0), h256(1), h256(1), zh(0), zh(0)]
             .iter()
             .fold(vec![], |mut acc, x| {
                 acc.extend(x);
@@ -116,12 +349,53 @@ mod test {
         ret
     }
 
+    /// Returns a full proof of both accounts of a tree of height 1, and the same proof with
+    /// account 0 collapsed into the hash of its subtree.
+    fn get_collapsed_proofs() -> (Vec<u8>, Vec<u8>) {
+        let encode = |offsets: &[u64], chunks: &[H256]| {
+            let mut ret = vec![];
+            for offset in offsets.iter() {
+                ret.extend(&offset.to_le_bytes());
+            }
+            for chunk in chunks.iter() {
+                ret.extend(chunk);
+            }
+            ret
+        };
+
+        // indexes = [16, 17, 9, 10, 11, 24, 25, 13, 14, 15]
+        let full = encode(
+            &[10, 5, 3, 2, 1, 1, 3, 2, 1, 1],
+            &[
+                h256(0),
+                h256(0),
+                h256(1),
+                h256(1),
+                zh(0),
+                h256(220),
+                h256(221),
+                h256(2),
+                h256(2),
+                zh(0),
+            ],
+        );
+
+        // indexes = [2, 24, 25, 13, 14, 15]
+        let account = crate::state::root::nodes(&full)[1];
+        let collapsed = encode(
+            &[6, 1, 3, 2, 1, 1],
+            &[account, h256(220), h256(221), h256(2), h256(2), zh(0)],
+        );
+
+        (full, collapsed)
+    }
+
     #[test]
     fn add_value() {
         let mut proof = get_proof();
         let mut mem = Imp::new(&mut proof, 4);
 
-        assert_eq!(mem.add_value(0.into(), 1), Ok(2));
+        assert_eq!(mem.add_value(0.into(), 1.into()), Ok(2.into()));
         assert_eq!(mem.get((10 << 1).into()), h256(2));
     }
 
@@ -130,16 +404,205 @@ mod test {
         let mut proof = get_proof();
         let mut mem = Imp::new(&mut proof, 4);
 
-        assert_eq!(mem.sub_value(0.into(), 1), Ok(0));
+        assert_eq!(mem.sub_value(0.into(), 1.into()), Ok(0.into()));
         assert_eq!(mem.get((10 << 1).into()), h256(0));
     }
 
+    #[test]
+    fn pubkey() {
+        let mut proof = get_proof();
+        let mut mem = Imp::new(&mut proof, 4);
+
+        assert_eq!(mem.pubkey(0.into()), Ok(PublicKey::zero()));
+
+        mem.update(16.into(), [1u8; 32]);
+        mem.update(17.into(), [1u8; 32]);
+        assert_eq!(mem.pubkey(0.into()), Ok(PublicKey::one()));
+    }
+
     #[test]
     fn inc_nonce() {
         let mut proof = get_proof();
         let mut mem = Imp::new(&mut proof, 4);
 
-        assert_eq!(mem.inc_nonce(0.into()), Ok(2));
+        assert_eq!(mem.inc_nonce(0.into()), Ok(2.into()));
         assert_eq!(mem.get((9 << 1).into()), h256(2));
     }
+
+    #[test]
+    fn set_pubkey() {
+        let mut proof = get_proof();
+        let mut mem = Imp::new(&mut proof, 4);
+
+        assert_eq!(mem.set_pubkey(0.into(), PublicKey::one()), Ok(()));
+        assert_eq!(mem.pubkey(0.into()), Ok(PublicKey::one()));
+        assert_eq!(mem.nonce(0.into()), Ok(1.into()));
+        assert_eq!(mem.value(0.into()), Ok(1.into()));
+    }
+
+    #[test]
+    fn create_account() {
+        let mut proof = get_proof();
+        let mut mem = Imp::new(&mut proof, 4);
+
+        assert_eq!(
+            mem.create_account(0.into(), PublicKey::one()),
+            Err(Error::AccountExists)
+        );
+
+        mem.update((10 << 1).into(), h256(0));
+        mem.update((9 << 1).into(), h256(0));
+        assert_eq!(mem.create_account(0.into(), PublicKey::one()), Ok(()));
+        assert_eq!(mem.pubkey(0.into()), Ok(PublicKey::one()));
+    }
+
+    #[test]
+    fn delete_account() {
+        let mut proof = get_proof();
+        let mut mem = Imp::new(&mut proof, 4);
+
+        assert_eq!(mem.delete_account(0.into()), Ok(1.into()));
+        assert_eq!(mem.value(0.into()), Ok(0.into()));
+        assert_eq!(mem.nonce(0.into()), Ok(0.into()));
+        assert_eq!(mem.pubkey(0.into()), Ok(PublicKey::zero()));
+    }
+
+    #[test]
+    fn address_beyond_tree() {
+        let mut proof = get_proof();
+        let expected = proof.clone();
+        let mut mem = Imp::new(&mut proof, 4);
+
+        // The tree has a single level, so address 2 would alias account 0.
+        assert_eq!(mem.value(2.into()), Err(Error::AddressInvalid));
+        assert_eq!(
+            mem.add_value(2.into(), 1.into()),
+            Err(Error::AddressInvalid)
+        );
+        assert_eq!(proof, expected);
+    }
+
+    #[test]
+    fn truncated_proof() {
+        // Every skip points past the end of the proof.
+        let mut proof = get_proof();
+        for position in 1..6 {
+            proof[position * 8..position * 8 + 8].copy_from_slice(&100u64.to_le_bytes());
+        }
+
+        let expected = proof.clone();
+        let mut mem = Imp::new(&mut proof, 4);
+
+        assert_eq!(get(&mem, (10 << 1).into()), Err(Error::ProofTruncated));
+        assert_eq!(mem.value(0.into()), Err(Error::ProofTruncated));
+        assert_eq!(
+            mem.add_value(0.into(), 1.into()),
+            Err(Error::ProofTruncated)
+        );
+        assert_eq!(mem.delete_account(0.into()), Err(Error::ProofTruncated));
+        assert_eq!(proof, expected);
+    }
+
+    #[test]
+    fn new_checked_accepts_valid_proof() {
+        let mut proof = get_proof();
+        let mem = new_checked(&mut proof, 4).unwrap();
+        assert_eq!(mem.value(0.into()), Ok(1.into()));
+    }
+
+    #[test]
+    fn new_checked_rejects_malformed_proofs() {
+        let proof = get_proof();
+
+        assert_eq!(
+            new_checked(&mut [0u8; 4], 4).err(),
+            Some(Error::ProofTruncated)
+        );
+        assert_eq!(
+            new_checked(&mut proof[..proof.len() - 1].to_vec(), 4).err(),
+            Some(Error::ProofTruncated)
+        );
+
+        // The second offset skips past every chunk below its node.
+        let mut skip_too_far = proof.clone();
+        skip_too_far[16..24].copy_from_slice(&5u64.to_le_bytes());
+        assert_eq!(
+            new_checked(&mut skip_too_far, 4).err(),
+            Some(Error::OffsetsInvalid)
+        );
+
+        // A node with no chunks on its left.
+        let mut empty_subtree = proof;
+        empty_subtree[8..16].copy_from_slice(&0u64.to_le_bytes());
+        assert_eq!(
+            new_checked(&mut empty_subtree, 4).err(),
+            Some(Error::OffsetsInvalid)
+        );
+    }
+
+    #[test]
+    fn validate_lengths() {
+        let proof = get_proof();
+        let (offsets, chunks) = (&proof[8..48], &proof[48..]);
+
+        assert_eq!(validate(offsets, chunks), Ok(()));
+        assert_eq!(
+            validate(&offsets[1..], chunks),
+            Err(Error::OffsetsLengthInvalid)
+        );
+        assert_eq!(
+            validate(offsets, &chunks[32..]),
+            Err(Error::ChunksLengthInvalid)
+        );
+    }
+
+    #[test]
+    fn collapsed_account() {
+        let (mut full, mut collapsed) = get_collapsed_proofs();
+        let expected = collapsed.clone();
+
+        let full = new_checked(&mut full, 4).unwrap();
+        assert_eq!(full.nonce(0.into()), Ok(1.into()));
+
+        let mut mem = new_checked(&mut collapsed, 4).unwrap();
+        assert_eq!(imp_root(&mem), imp_root(&full));
+
+        // The path to account 0 ends at its hash, rather than continuing into account 1.
+        assert_eq!(mem.nonce(0.into()), Err(Error::StateIncomplete(2.into())));
+        assert_eq!(mem.pubkey(0.into()), Err(Error::StateIncomplete(2.into())));
+        assert_eq!(
+            mem.inc_nonce(0.into()),
+            Err(Error::StateIncomplete(2.into()))
+        );
+        assert_eq!(mem.nonce(1.into()), Ok(2.into()));
+        assert_eq!(collapsed, expected);
+    }
+
+    #[test]
+    fn create_collapsed_account() {
+        let (_, mut collapsed) = get_collapsed_proofs();
+        let expected = collapsed.clone();
+        let mut mem = new_checked(&mut collapsed, 4).unwrap();
+
+        // Account 0 exists, so its hash isn't a proof of absence, and the chunks of account 1
+        // which its path would otherwise reach are left alone.
+        assert_eq!(
+            mem.create_account(0.into(), PublicKey::one()),
+            Err(Error::StateIncomplete(2.into()))
+        );
+        assert_eq!(collapsed, expected);
+    }
+
+    #[test]
+    fn path_past_chunk() {
+        let mut proof = get_proof();
+        let mem = Imp::new(&mut proof, 4);
+
+        // The nonce is stored with its zero sibling, but its sibling isn't a chunk of the proof.
+        assert_eq!(get(&mem, (9 << 1).into()), Ok(h256(1)));
+        assert_eq!(
+            get(&mem, ((9 << 1) + 1).into()),
+            Err(Error::StateIncomplete(9.into()))
+        );
+    }
 }
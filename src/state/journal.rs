@@ -0,0 +1,191 @@
+use crate::account::{
+    calc_nonce_index, calc_pubkey_index, calc_value_index, check_address, Account,
+};
+use crate::address::Address;
+use crate::amount::Amount;
+use crate::bls::PublicKey;
+use crate::error::Error;
+use crate::hash::H256;
+use crate::nonce::Nonce;
+use crate::schema::LeafSchema;
+use crate::state::imp::{get, update};
+use crate::state::State;
+use crate::u264::U264;
+use alloc::vec::Vec;
+use imp::Imp;
+
+/// Identifies a point in a `Journaled` state which it can be reverted to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SnapshotId(usize);
+
+/// Wraps `Imp` with a journal of the chunks overwritten by each change, so a block builder can
+/// speculatively apply transactions and revert them without rebuilding the proof.
+pub struct Journaled<'a> {
+    pub imp: Imp<'a, U264>,
+    journal: Vec<(U264, H256)>,
+}
+
+impl<'a> Journaled<'a> {
+    pub fn new(imp: Imp<'a, U264>) -> Self {
+        Self {
+            imp,
+            journal: Vec::new(),
+        }
+    }
+
+    /// Returns an id which `revert_to` can use to undo every change made after this call.
+    pub fn snapshot(&self) -> SnapshotId {
+        SnapshotId(self.journal.len())
+    }
+
+    /// Restore the chunks overwritten since `id` was taken. Any snapshots taken after `id` are
+    /// invalidated.
+    pub fn revert_to(&mut self, id: SnapshotId) {
+        while self.journal.len() > id.0 {
+            let (index, chunk) = self.journal.pop().unwrap();
+            update(&mut self.imp, index, chunk).expect("Journaled chunks to be in the proof");
+        }
+    }
+
+    /// Record the current chunks of the account at `address` before it is changed.
+    fn record(&mut self, address: Address) -> Result<(), Error> {
+        let height = self.imp.height;
+        check_address(address, height.saturating_sub(Account::DEPTH))?;
+        let pubkey = calc_pubkey_index(address, height);
+
+        for index in [
+            pubkey,
+            pubkey + U264::one(),
+            calc_nonce_index(address, height),
+            calc_value_index(address, height),
+        ]
+        .iter()
+        {
+            self.journal.push((*index, get(&self.imp, *index)?));
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> State for Journaled<'a> {
+    fn root(&mut self) -> Result<H256, Error> {
+        State::root(&mut self.imp)
+    }
+
+    fn value(&self, address: Address) -> Result<Amount, Error> {
+        self.imp.value(address)
+    }
+
+    fn nonce(&self, address: Address) -> Result<Nonce, Error> {
+        self.imp.nonce(address)
+    }
+
+    fn pubkey(&self, address: Address) -> Result<PublicKey, Error> {
+        self.imp.pubkey(address)
+    }
+
+    fn add_value(&mut self, address: Address, amount: Amount) -> Result<Amount, Error> {
+        self.record(address)?;
+        self.imp.add_value(address, amount)
+    }
+
+    fn sub_value(&mut self, address: Address, amount: Amount) -> Result<Amount, Error> {
+        self.record(address)?;
+        self.imp.sub_value(address, amount)
+    }
+
+    fn inc_nonce(&mut self, address: Address) -> Result<Nonce, Error> {
+        self.record(address)?;
+        self.imp.inc_nonce(address)
+    }
+
+    fn set_pubkey(&mut self, address: Address, pubkey: PublicKey) -> Result<(), Error> {
+        self.record(address)?;
+        self.imp.set_pubkey(address, pubkey)
+    }
+
+    fn create_account(&mut self, address: Address, pubkey: PublicKey) -> Result<(), Error> {
+        self.record(address)?;
+        self.imp.create_account(address, pubkey)
+    }
+
+    fn delete_account(&mut self, address: Address) -> Result<Amount, Error> {
+        self.record(address)?;
+        self.imp.delete_account(address)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use arrayref::array_ref;
+
+    fn zh(depth: usize) -> H256 {
+        let mut buf = [0u8; 64];
+        crate::hash::zh(depth, &mut buf);
+        *array_ref![buf, 0, 32]
+    }
+
+    fn h256(n: u8) -> H256 {
+        let mut ret = [0u8; 32];
+        ret[0] = n;
+        ret
+    }
+
+    fn get_proof() -> Vec<u8> {
+        // indexes = [2, 24, 25, 13, 14, 15]
+        let mut proof: Vec<u8> = [6u64, 1, 3, 2, 1, 1].iter().fold(vec![], |mut acc, x| {
+            acc.extend(&x.to_le_bytes());
+            acc
+        });
+
+        for chunk in &[zh(0), h256(0), h256(0), h256(3), h256(9), h256(0)] {
+            proof.extend(chunk);
+        }
+
+        proof
+    }
+
+    #[test]
+    fn revert_to_snapshot() {
+        let mut proof = get_proof();
+        let mut state = Journaled::new(Imp::new(&mut proof, 4));
+        let pre_root = state.root();
+
+        let start = state.snapshot();
+        assert_eq!(state.sub_value(1.into(), 4.into()), Ok(5.into()));
+        assert_eq!(state.inc_nonce(1.into()), Ok(4.into()));
+        let mid_root = state.root();
+
+        let mid = state.snapshot();
+        assert_eq!(state.delete_account(1.into()), Ok(5.into()));
+        assert_eq!(state.value(1.into()), Ok(0.into()));
+
+        state.revert_to(mid);
+        assert_eq!(state.root(), mid_root);
+        assert_eq!(state.value(1.into()), Ok(5.into()));
+        assert_eq!(state.nonce(1.into()), Ok(4.into()));
+
+        state.revert_to(start);
+        assert_eq!(state.root(), pre_root);
+        assert_eq!(state.value(1.into()), Ok(9.into()));
+    }
+
+    #[test]
+    fn failed_changes_are_reverted() {
+        let mut proof = get_proof();
+        let mut state = Journaled::new(Imp::new(&mut proof, 4));
+        let pre_root = state.root();
+
+        let start = state.snapshot();
+        assert_eq!(
+            state.sub_value(1.into(), 10.into()),
+            Err(Error::BalanceInsufficient)
+        );
+        assert_eq!(state.add_value(1.into(), 1.into()), Ok(10.into()));
+
+        state.revert_to(start);
+        assert_eq!(state.root(), pre_root);
+    }
+}
@@ -1,12 +1,13 @@
 use crate::account::Account;
 use crate::address::Address;
+use crate::amount::Amount;
+use crate::bls::PublicKey;
 use crate::error::Error;
 use crate::hash::H256;
+use crate::nonce::Nonce;
 use crate::state::State;
-use bigint::U256;
-use std::collections::hash_map::DefaultHasher;
+use sha2::{Digest, Sha256};
 use std::collections::BTreeMap;
-use std::hash::{Hash, Hasher};
 
 pub struct MockState {
     accounts: BTreeMap<Address, Account>,
@@ -19,69 +20,160 @@ impl MockState {
 }
 
 impl State for MockState {
+    /// Returns the SHA-256 of each account's address, public key, nonce and value in address
+    /// order. It is not a merkle root, only a commitment to every account so tests can detect when
+    /// the state changes. Unlike `DefaultHasher`, whose output isn't specified and may change
+    /// between toolchains, it gives the same root everywhere, so tests can pin it.
     fn root(&mut self) -> Result<H256, Error> {
-        let mut s = DefaultHasher::new();
-        self.accounts.hash(&mut s);
-        let hash = U256::from(s.finish());
-        Ok(hash.into())
+        let mut hasher = Sha256::new();
+
+        for (address, account) in self.accounts.iter() {
+            hasher.input(<[u8; 32]>::from(*address));
+            hasher.input(&account.pubkey.as_bytes()[..]);
+            hasher.input(account.nonce.to_le_bytes());
+            hasher.input(account.value.to_le_bytes());
+        }
+
+        let mut root = [0u8; 32];
+        root.copy_from_slice(hasher.result().as_ref());
+        Ok(root)
     }
 
-    fn value(&self, address: Address) -> Result<u64, Error> {
+    fn value(&self, address: Address) -> Result<Amount, Error> {
         let value = self
             .accounts
             .get(&address)
             .ok_or(Error::StateIncomplete(address.into()))?
             .value;
 
-        Ok(value)
+        Amount::new(value)
     }
 
-    fn nonce(&self, address: Address) -> Result<u64, Error> {
+    fn nonce(&self, address: Address) -> Result<Nonce, Error> {
         let nonce = self
             .accounts
             .get(&address)
             .ok_or(Error::StateIncomplete(address.into()))?
             .nonce;
 
-        Ok(nonce)
+        Ok(nonce.into())
     }
 
-    fn add_value(&mut self, address: Address, amount: u64) -> Result<u64, Error> {
+    fn pubkey(&self, address: Address) -> Result<PublicKey, Error> {
+        let pubkey = self
+            .accounts
+            .get(&address)
+            .ok_or(Error::StateIncomplete(address.into()))?
+            .pubkey
+            .clone();
+
+        Ok(pubkey)
+    }
+
+    fn add_value(&mut self, address: Address, amount: Amount) -> Result<Amount, Error> {
         let mut account = self
             .accounts
             .get(&address)
             .ok_or(Error::StateIncomplete(address.into()))?
             .clone();
 
-        account.value += amount;
+        account.value = Amount::new(account.value)?.checked_add(amount)?.get();
         self.accounts.insert(address, account.clone());
 
-        Ok(account.value)
+        Amount::new(account.value)
     }
 
-    fn sub_value(&mut self, address: Address, amount: u64) -> Result<u64, Error> {
+    fn sub_value(&mut self, address: Address, amount: Amount) -> Result<Amount, Error> {
         let mut account = self
             .accounts
             .get(&address)
             .ok_or(Error::StateIncomplete(address.into()))?
             .clone();
 
-        account.value -= amount;
+        account.value = Amount::new(account.value)?
+            .checked_sub(amount)
+            .map_err(|_| Error::BalanceInsufficient)?
+            .get();
         self.accounts.insert(address, account.clone());
 
-        Ok(account.value)
+        Amount::new(account.value)
     }
 
-    fn inc_nonce(&mut self, address: Address) -> Result<u64, Error> {
+    fn inc_nonce(&mut self, address: Address) -> Result<Nonce, Error> {
         let mut account = self
             .accounts
             .get(&address)
             .ok_or(Error::StateIncomplete(address.into()))?
             .clone();
 
-        account.nonce += 1;
+        account.nonce = Nonce::from(account.nonce).next()?.get();
         self.accounts.insert(address, account.clone());
 
-        Ok(account.nonce)
+        Ok(account.nonce.into())
+    }
+
+    fn set_pubkey(&mut self, address: Address, pubkey: PublicKey) -> Result<(), Error> {
+        self.accounts
+            .get_mut(&address)
+            .ok_or(Error::StateIncomplete(address.into()))?
+            .pubkey = pubkey;
+
+        Ok(())
+    }
+
+    fn create_account(&mut self, address: Address, pubkey: PublicKey) -> Result<(), Error> {
+        // As in the tree, an account which is entirely zero is empty.
+        if let Some(account) = self.accounts.get(&address) {
+            if *account != Account::zero() {
+                return Err(Error::AccountExists);
+            }
+        }
+
+        self.accounts.insert(
+            address,
+            Account {
+                pubkey,
+                nonce: 0,
+                value: 0,
+            },
+        );
+
+        Ok(())
+    }
+
+    fn delete_account(&mut self, address: Address) -> Result<Amount, Error> {
+        let account = self
+            .accounts
+            .remove(&address)
+            .ok_or(Error::StateIncomplete(address.into()))?;
+
+        Amount::new(account.value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn stable_root() {
+        let account = Account {
+            pubkey: PublicKey::new([7u8; 48]),
+            nonce: 1,
+            value: 2,
+        };
+
+        let mut accounts = BTreeMap::new();
+        accounts.insert(Address::from(3), account);
+
+        let mut preimage = <[u8; 32]>::from(Address::from(3)).to_vec();
+        preimage.extend_from_slice(&[7u8; 48]);
+        preimage.extend_from_slice(&1u64.to_le_bytes());
+        preimage.extend_from_slice(&2u64.to_le_bytes());
+
+        assert_eq!(
+            MockState::new(accounts).root().unwrap()[..],
+            Sha256::digest(&preimage)[..]
+        );
     }
 }
@@ -1,4 +1,20 @@
+#[cfg(feature = "alloc")]
+pub mod buffer;
 pub mod imp;
+#[cfg(feature = "alloc")]
+pub mod journal;
+#[cfg(feature = "alloc")]
+pub mod overlay;
+#[cfg(feature = "alloc")]
+pub mod owned;
+#[cfg(feature = "alloc")]
+pub mod partial;
+#[cfg(feature = "alloc")]
+pub mod root;
+#[cfg(feature = "alloc")]
+pub mod tracked;
+#[cfg(feature = "alloc")]
+pub mod witness;
 
 #[cfg(test)]
 pub mod mock;
@@ -7,8 +23,11 @@ pub mod mock;
 pub use mock::MockState;
 
 use crate::address::Address;
+use crate::amount::Amount;
+use crate::bls::PublicKey;
 use crate::error::Error;
 use crate::hash::H256;
+use crate::nonce::Nonce;
 
 /// Interface for interacting with the state's Sparse Merkle Tree (SMT).
 ///
@@ -27,17 +46,117 @@ pub trait State {
     fn root(&mut self) -> Result<H256, Error>;
 
     /// Returns the value of a specified address.
-    fn value(&self, address: Address) -> Result<u64, Error>;
+    fn value(&self, address: Address) -> Result<Amount, Error>;
 
     /// Returns the nonce of a specified address.
-    fn nonce(&self, address: Address) -> Result<u64, Error>;
+    fn nonce(&self, address: Address) -> Result<Nonce, Error>;
+
+    /// Returns the public key of a specified address.
+    fn pubkey(&self, address: Address) -> Result<PublicKey, Error>;
 
     /// Increase the value of an account at `address`.
-    fn add_value(&mut self, address: Address, amount: u64) -> Result<u64, Error>;
+    fn add_value(&mut self, address: Address, amount: Amount) -> Result<Amount, Error>;
 
     /// Decrease the value of an account at `address`.
-    fn sub_value(&mut self, address: Address, amount: u64) -> Result<u64, Error>;
+    fn sub_value(&mut self, address: Address, amount: Amount) -> Result<Amount, Error>;
 
     /// Increment the `nonce` of the account at `address` by `1`.
-    fn inc_nonce(&mut self, address: Address) -> Result<u64, Error>;
+    fn inc_nonce(&mut self, address: Address) -> Result<Nonce, Error>;
+
+    /// Replace the public key of the account at `address` with `pubkey`, leaving its nonce and
+    /// value unchanged.
+    fn set_pubkey(&mut self, address: Address, pubkey: PublicKey) -> Result<(), Error>;
+
+    /// Create an account owned by `pubkey` at `address`, after verifying that the state proves
+    /// the account at `address` is empty.
+    fn create_account(&mut self, address: Address, pubkey: PublicKey) -> Result<(), Error>;
+
+    /// Reset the account at `address` to the empty account and return its final value.
+    fn delete_account(&mut self, address: Address) -> Result<Amount, Error>;
+
+    /// Move `amount` from `from` to `to` and increment the nonce of `from`, returning the new
+    /// nonce.
+    ///
+    /// The balance of `from`, the value of `to` and the nonce are all checked before any write
+    /// is made, so a transfer which fails leaves the state unchanged.
+    fn transfer(&mut self, from: Address, to: Address, amount: Amount) -> Result<Nonce, Error> {
+        if self.value(from)? < amount {
+            return Err(Error::BalanceInsufficient);
+        }
+
+        if from != to {
+            self.value(to)?.checked_add(amount)?;
+        }
+
+        self.nonce(from)?.next()?;
+
+        self.sub_value(from, amount)?;
+        self.add_value(to, amount)?;
+        self.inc_nonce(from)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::account::Account;
+    use alloc::collections::BTreeMap;
+
+    fn build_state() -> MockState {
+        let mut accounts: BTreeMap<Address, Account> = BTreeMap::new();
+        accounts.insert(
+            0.into(),
+            Account {
+                pubkey: PublicKey::zero(),
+                nonce: 0,
+                value: 5,
+            },
+        );
+        accounts.insert(
+            1.into(),
+            Account {
+                pubkey: PublicKey::zero(),
+                nonce: u64::MAX,
+                value: Amount::MAX.get(),
+            },
+        );
+
+        MockState::new(accounts)
+    }
+
+    #[test]
+    fn transfer() {
+        let mut state = build_state();
+
+        assert_eq!(state.transfer(0.into(), 0.into(), 5.into()), Ok(1.into()));
+        assert_eq!(state.value(0.into()), Ok(5.into()));
+
+        assert_eq!(
+            state.transfer(0.into(), 2.into(), 3.into()),
+            Err(Error::StateIncomplete(2.into()))
+        );
+        assert_eq!(state.value(0.into()), Ok(5.into()));
+    }
+
+    #[test]
+    fn failed_transfer_leaves_state_unchanged() {
+        let mut state = build_state();
+        let root = state.root();
+
+        // Insufficient balance, recipient overflow and nonce overflow.
+        assert_eq!(
+            state.transfer(0.into(), 1.into(), 6.into()),
+            Err(Error::BalanceInsufficient)
+        );
+        assert_eq!(
+            state.transfer(0.into(), 1.into(), 1.into()),
+            Err(Error::Overflow)
+        );
+        assert_eq!(
+            state.transfer(1.into(), 0.into(), 1.into()),
+            Err(Error::Overflow)
+        );
+
+        assert_eq!(state.root(), root);
+    }
 }
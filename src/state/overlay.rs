@@ -0,0 +1,285 @@
+use crate::account::Account;
+use crate::address::Address;
+use crate::amount::Amount;
+use crate::bls::PublicKey;
+use crate::error::Error;
+use crate::hash::H256;
+use crate::nonce::Nonce;
+use crate::state::State;
+use alloc::collections::BTreeMap;
+
+/// An account which has been changed in the overlay.
+struct Entry {
+    /// The account in the base state.
+    original: Account,
+
+    /// The account after the overlay's changes.
+    account: Account,
+
+    /// Whether the account was deleted at some point, in which case it must be deleted from the
+    /// base before its final value is written.
+    deleted: bool,
+}
+
+/// Buffers changes to a base state in memory, so speculative execution (e.g. validating
+/// transactions for the mempool) can run without touching the base until the overlay is
+/// committed.
+///
+/// Reads are answered by the overlay's buffer before falling back to the base.
+pub struct Overlay<'a, T: State> {
+    base: &'a mut T,
+    entries: BTreeMap<Address, Entry>,
+}
+
+impl<'a, T: State> Overlay<'a, T> {
+    pub fn new(base: &'a mut T) -> Self {
+        Self {
+            base,
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// Write the overlay's changes to the base.
+    pub fn commit(self) -> Result<(), Error> {
+        for (address, entry) in self.entries {
+            if entry.account == entry.original && !entry.deleted {
+                continue;
+            }
+
+            let mut current = entry.original;
+            if entry.deleted {
+                self.base.delete_account(address)?;
+                current = Account::zero();
+            }
+
+            // An empty account is created for its new key, while an existing one has its key
+            // replaced.
+            let recreated = entry.deleted && entry.account != Account::zero();
+            let rekeyed = entry.account.pubkey != current.pubkey;
+            if recreated || (rekeyed && current == Account::zero()) {
+                self.base
+                    .create_account(address, entry.account.pubkey.clone())?;
+            } else if rekeyed {
+                self.base
+                    .set_pubkey(address, entry.account.pubkey.clone())?;
+            }
+
+            let value = Amount::new(entry.account.value)?;
+            let current_value = Amount::new(current.value)?;
+            if value > current_value {
+                self.base
+                    .add_value(address, value.checked_sub(current_value)?)?;
+            } else if value < current_value {
+                self.base
+                    .sub_value(address, current_value.checked_sub(value)?)?;
+            }
+
+            for _ in current.nonce..entry.account.nonce {
+                self.base.inc_nonce(address)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Drop the overlay's changes, leaving the base untouched.
+    pub fn discard(self) {}
+
+    fn account(&self, address: Address) -> Result<Account, Error> {
+        match self.entries.get(&address) {
+            Some(entry) => Ok(entry.account.clone()),
+            None => Ok(Account {
+                pubkey: self.base.pubkey(address)?,
+                nonce: self.base.nonce(address)?.get(),
+                value: self.base.value(address)?.get(),
+            }),
+        }
+    }
+
+    fn entry(&mut self, address: Address) -> Result<&mut Entry, Error> {
+        if !self.entries.contains_key(&address) {
+            let original = self.account(address)?;
+
+            self.entries.insert(
+                address,
+                Entry {
+                    account: original.clone(),
+                    original,
+                    deleted: false,
+                },
+            );
+        }
+
+        Ok(self.entries.get_mut(&address).unwrap())
+    }
+}
+
+impl<'a, T: State> State for Overlay<'a, T> {
+    /// The root can only be calculated once every change has been committed to the base.
+    fn root(&mut self) -> Result<H256, Error> {
+        if self
+            .entries
+            .values()
+            .any(|e| e.account != e.original || e.deleted)
+        {
+            return Err(Error::Uncommitted);
+        }
+
+        self.base.root()
+    }
+
+    fn value(&self, address: Address) -> Result<Amount, Error> {
+        Amount::new(self.account(address)?.value)
+    }
+
+    fn nonce(&self, address: Address) -> Result<Nonce, Error> {
+        Ok(self.account(address)?.nonce.into())
+    }
+
+    fn pubkey(&self, address: Address) -> Result<PublicKey, Error> {
+        Ok(self.account(address)?.pubkey)
+    }
+
+    fn add_value(&mut self, address: Address, amount: Amount) -> Result<Amount, Error> {
+        let account = &mut self.entry(address)?.account;
+        let value = Amount::new(account.value)?.checked_add(amount)?;
+        account.value = value.get();
+
+        Ok(value)
+    }
+
+    fn sub_value(&mut self, address: Address, amount: Amount) -> Result<Amount, Error> {
+        let account = &mut self.entry(address)?.account;
+        let value = Amount::new(account.value)?
+            .checked_sub(amount)
+            .map_err(|_| Error::BalanceInsufficient)?;
+        account.value = value.get();
+
+        Ok(value)
+    }
+
+    fn inc_nonce(&mut self, address: Address) -> Result<Nonce, Error> {
+        let account = &mut self.entry(address)?.account;
+        let nonce = Nonce::from(account.nonce).next()?;
+        account.nonce = nonce.get();
+
+        Ok(nonce)
+    }
+
+    fn set_pubkey(&mut self, address: Address, pubkey: PublicKey) -> Result<(), Error> {
+        self.entry(address)?.account.pubkey = pubkey;
+
+        Ok(())
+    }
+
+    fn create_account(&mut self, address: Address, pubkey: PublicKey) -> Result<(), Error> {
+        let account = &mut self.entry(address)?.account;
+        if *account != Account::zero() {
+            return Err(Error::AccountExists);
+        }
+
+        account.pubkey = pubkey;
+
+        Ok(())
+    }
+
+    fn delete_account(&mut self, address: Address) -> Result<Amount, Error> {
+        let entry = self.entry(address)?;
+        let value = Amount::new(entry.account.value)?;
+
+        entry.account = Account::zero();
+        entry.deleted = true;
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::state::MockState;
+
+    fn build_state() -> MockState {
+        let mut accounts: BTreeMap<Address, Account> = BTreeMap::new();
+        accounts.insert(
+            0.into(),
+            Account {
+                pubkey: PublicKey::one(),
+                nonce: 3,
+                value: 10,
+            },
+        );
+        accounts.insert(1.into(), Account::zero());
+
+        MockState::new(accounts)
+    }
+
+    #[test]
+    fn reads_fall_back_to_base() {
+        let mut base = build_state();
+        let mut overlay = Overlay::new(&mut base);
+
+        assert_eq!(overlay.add_value(0.into(), 5.into()), Ok(15.into()));
+        assert_eq!(overlay.value(0.into()), Ok(15.into()));
+        assert_eq!(overlay.nonce(0.into()), Ok(3.into()));
+        assert_eq!(overlay.value(1.into()), Ok(0.into()));
+        assert_eq!(overlay.root(), Err(Error::Uncommitted));
+
+        overlay.discard();
+        assert_eq!(base.value(0.into()), Ok(10.into()));
+    }
+
+    #[test]
+    fn commit_matches_base() {
+        let mut base = build_state();
+        let mut expected = build_state();
+
+        let mut overlay = Overlay::new(&mut base);
+        for state in [&mut overlay as &mut dyn State, &mut expected].iter_mut() {
+            state.sub_value(0.into(), 4.into()).unwrap();
+            state.inc_nonce(0.into()).unwrap();
+            state.inc_nonce(0.into()).unwrap();
+            state.create_account(1.into(), PublicKey::one()).unwrap();
+            state.add_value(1.into(), 4.into()).unwrap();
+        }
+
+        overlay.commit().unwrap();
+        assert_eq!(base.root(), expected.root());
+    }
+
+    #[test]
+    fn commit_deleted_accounts() {
+        let mut base = build_state();
+        let mut expected = build_state();
+
+        let mut overlay = Overlay::new(&mut base);
+        assert_eq!(overlay.delete_account(0.into()), Ok(10.into()));
+        assert_eq!(overlay.create_account(0.into(), PublicKey::zero()), Ok(()));
+        assert_eq!(overlay.add_value(0.into(), 1.into()), Ok(1.into()));
+        overlay.commit().unwrap();
+
+        expected.delete_account(0.into()).unwrap();
+        expected
+            .create_account(0.into(), PublicKey::zero())
+            .unwrap();
+        expected.add_value(0.into(), 1.into()).unwrap();
+
+        assert_eq!(base.root(), expected.root());
+        assert_eq!(base.nonce(0.into()), Ok(0.into()));
+    }
+
+    #[test]
+    fn commit_changed_keys() {
+        let mut base = build_state();
+        let mut expected = build_state();
+
+        let mut overlay = Overlay::new(&mut base);
+        assert_eq!(overlay.set_pubkey(0.into(), PublicKey::zero()), Ok(()));
+        assert_eq!(overlay.pubkey(0.into()), Ok(PublicKey::zero()));
+        overlay.commit().unwrap();
+
+        expected.set_pubkey(0.into(), PublicKey::zero()).unwrap();
+        assert_eq!(base.root(), expected.root());
+        assert_eq!(base.value(0.into()), Ok(10.into()));
+    }
+}
@@ -0,0 +1,380 @@
+use crate::account::{
+    calc_nonce_index, calc_pubkey_index, calc_value_index, check_address, Account,
+};
+use crate::address::Address;
+use crate::amount::Amount;
+use crate::bls::PublicKey;
+use crate::error::Error;
+use crate::hash::{Hasher, Sha256, H256};
+use crate::nonce::Nonce;
+use crate::schema::LeafSchema;
+use crate::state::imp::{lookup, new_checked, update};
+use crate::state::root::nodes_with;
+use crate::state::State;
+use crate::u264::U264;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use arrayref::array_ref;
+use core::marker::PhantomData;
+use imp::Imp;
+
+/// A multi-proof which owns its buffer, so it can be returned from constructors and stored in
+/// other structs without borrowing the proof from somewhere else.
+///
+/// The proof has the same layout as the input to `Imp`. The hashes of its internal nodes are
+/// kept after the first call to `root`, and each update marks the nodes above the chunk dirty so
+/// later calls only rehash the branches which changed. Nodes are hashed with `H`.
+pub struct Owned<H: Hasher = Sha256> {
+    proof: Vec<u8>,
+    height: usize,
+
+    /// Hash of each internal node, by the node's position in the offsets.
+    nodes: Vec<H256>,
+
+    /// Internal nodes above the chunks changed since the last call to `root`, by position. Each
+    /// node's first chunk and number of chunks are kept so it can be rehashed.
+    dirty: BTreeMap<usize, (usize, usize)>,
+
+    /// Whether `nodes` must be rebuilt by a full pass over the proof.
+    stale: bool,
+
+    hasher: PhantomData<H>,
+}
+
+impl Owned {
+    pub fn new(proof: Vec<u8>, height: usize) -> Self {
+        Self::with_hasher(proof, height)
+    }
+
+    /// Returns the proof as an `Owned` once its structure has been validated, so a malformed proof
+    /// is rejected here rather than when it is first read.
+    pub fn new_checked(mut proof: Vec<u8>, height: usize) -> Result<Self, Error> {
+        new_checked(&mut proof, height)?;
+        Ok(Self::new(proof, height))
+    }
+}
+
+impl<H: Hasher> Owned<H> {
+    /// Returns an `Owned` which hashes nodes with `H` instead of SHA-256.
+    pub fn with_hasher(proof: Vec<u8>, height: usize) -> Self {
+        Self {
+            proof,
+            height,
+            nodes: Vec::new(),
+            dirty: BTreeMap::new(),
+            stale: true,
+            hasher: PhantomData,
+        }
+    }
+
+    /// Returns an `Imp` over the proof. Changes made through it aren't tracked, so the next call
+    /// to `root` makes a full pass.
+    pub fn imp(&mut self) -> Imp<'_, U264> {
+        self.stale = true;
+        self.inner()
+    }
+
+    pub fn into_inner(self) -> Vec<u8> {
+        self.proof
+    }
+
+    /// Returns the chunk at `index`, following the same path through the offsets as `Imp`.
+    pub fn get(&self, index: U264) -> Result<H256, Error> {
+        let (offsets, chunks) = self.parts()?;
+        let offset = lookup(offsets, chunks, self.height, index)?;
+        Ok(*array_ref![chunks, offset * 32, 32])
+    }
+
+    /// Overwrite the chunk at `index` and mark the nodes above it dirty.
+    pub fn update(&mut self, index: U264, chunk: H256) -> Result<(), Error> {
+        update(&mut self.inner(), index, chunk)?;
+        self.touch(index);
+        Ok(())
+    }
+
+    fn inner(&mut self) -> Imp<'_, U264> {
+        Imp::new(&mut self.proof, self.height)
+    }
+
+    /// Splits the proof into its offsets, not including the leading length, and its chunks.
+    fn parts(&self) -> Result<(&[u8], &[u8]), Error> {
+        let end = self.len().saturating_mul(8);
+        let offsets = self.proof.get(8..end).ok_or(Error::ProofTruncated)?;
+        let chunks = self.proof.get(end..).ok_or(Error::ProofTruncated)?;
+        Ok((offsets, chunks))
+    }
+
+    /// Returns `Error::AddressInvalid` unless `address` has a leaf in the tree.
+    fn check(&self, address: Address) -> Result<(), Error> {
+        check_address(address, self.height.saturating_sub(Account::DEPTH))
+    }
+
+    fn len(&self) -> usize {
+        u64::from_le_bytes(*array_ref![self.proof, 0, 8]) as usize
+    }
+
+    /// Returns the number of chunks below the left child of the internal node at `position`.
+    fn skip(&self, position: usize) -> usize {
+        u64::from_le_bytes(*array_ref![self.proof, 8 + position * 8, 8]) as usize
+    }
+
+    fn chunk(&self, offset: usize) -> H256 {
+        *array_ref![self.proof, self.len() * 8 + offset * 32, 32]
+    }
+
+    /// Mark the internal nodes on the path to `index` dirty.
+    fn touch(&mut self, index: U264) {
+        if self.stale {
+            return;
+        }
+
+        let mut position = 0;
+        let mut offset = 0;
+        let mut size = self.len();
+
+        for depth in (0..self.height).rev() {
+            if size == 1 {
+                break;
+            }
+
+            self.dirty.insert(position, (offset, size));
+
+            let left = self.skip(position);
+            if (index >> depth) & U264::one() == U264::zero() {
+                position += 1;
+                size = left;
+            } else {
+                position += left;
+                offset += left;
+                size -= left;
+            }
+        }
+    }
+
+    fn touch_account(&mut self, address: Address) {
+        let index = calc_pubkey_index(address, self.height);
+
+        self.touch(index);
+        self.touch(index + U264::one());
+        self.touch(calc_nonce_index(address, self.height));
+        self.touch(calc_value_index(address, self.height));
+    }
+
+    /// Returns the hash of the node at `position`, which is a chunk if it has no children.
+    fn node(&self, position: usize, offset: usize, size: usize) -> H256 {
+        if size == 1 {
+            self.chunk(offset)
+        } else {
+            self.nodes[position]
+        }
+    }
+
+    /// Hash the children of the internal node at `position`.
+    fn rehash(&mut self, position: usize, offset: usize, size: usize) {
+        let left = self.skip(position);
+
+        let mut buf = [0u8; 64];
+        buf[0..32].copy_from_slice(&self.node(position + 1, offset, left));
+        buf[32..64].copy_from_slice(&self.node(position + left, offset + left, size - left));
+        H::hash(&mut buf);
+
+        self.nodes[position] = *array_ref![buf, 0, 32];
+    }
+}
+
+impl<H: Hasher> State for Owned<H> {
+    fn root(&mut self) -> Result<H256, Error> {
+        let len = self.len();
+        if len == 1 {
+            return Ok(self.chunk(0));
+        }
+
+        if self.stale {
+            self.nodes = nodes_with::<H>(&self.proof);
+            self.stale = false;
+        } else {
+            // The children of a node are always at greater positions than the node itself.
+            let dirty = core::mem::take(&mut self.dirty);
+            for (position, (offset, size)) in dirty.into_iter().rev() {
+                self.rehash(position, offset, size);
+            }
+        }
+
+        self.dirty.clear();
+        Ok(self.nodes[0])
+    }
+
+    fn value(&self, address: Address) -> Result<Amount, Error> {
+        self.check(address)?;
+        let chunk = self.get(calc_value_index(address, self.height))?;
+        Amount::new(u64::from_le_bytes(*array_ref![chunk, 0, 8]))
+    }
+
+    fn nonce(&self, address: Address) -> Result<Nonce, Error> {
+        self.check(address)?;
+        let chunk = self.get(calc_nonce_index(address, self.height))?;
+        Ok(u64::from_le_bytes(*array_ref![chunk, 0, 8]).into())
+    }
+
+    fn pubkey(&self, address: Address) -> Result<PublicKey, Error> {
+        self.check(address)?;
+        let index = calc_pubkey_index(address, self.height);
+
+        let mut buf = [0u8; 64];
+        buf[0..32].copy_from_slice(&self.get(index)?);
+        buf[32..64].copy_from_slice(&self.get(index + U264::one())?);
+
+        Ok(PublicKey::new(*array_ref![buf, 0, 48]))
+    }
+
+    fn add_value(&mut self, address: Address, amount: Amount) -> Result<Amount, Error> {
+        let value = self.inner().add_value(address, amount)?;
+        self.touch(calc_value_index(address, self.height));
+        Ok(value)
+    }
+
+    fn sub_value(&mut self, address: Address, amount: Amount) -> Result<Amount, Error> {
+        let value = self.inner().sub_value(address, amount)?;
+        self.touch(calc_value_index(address, self.height));
+        Ok(value)
+    }
+
+    fn inc_nonce(&mut self, address: Address) -> Result<Nonce, Error> {
+        let nonce = self.inner().inc_nonce(address)?;
+        self.touch(calc_nonce_index(address, self.height));
+        Ok(nonce)
+    }
+
+    fn set_pubkey(&mut self, address: Address, pubkey: PublicKey) -> Result<(), Error> {
+        self.inner().set_pubkey(address, pubkey)?;
+
+        let index = calc_pubkey_index(address, self.height);
+        self.touch(index);
+        self.touch(index + U264::one());
+        Ok(())
+    }
+
+    fn create_account(&mut self, address: Address, pubkey: PublicKey) -> Result<(), Error> {
+        self.inner().create_account(address, pubkey)?;
+        self.touch_account(address);
+        Ok(())
+    }
+
+    fn delete_account(&mut self, address: Address) -> Result<Amount, Error> {
+        let value = self.inner().delete_account(address)?;
+        self.touch_account(address);
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::state::root::{imp_root, root_with};
+
+    fn zh(depth: usize) -> H256 {
+        let mut buf = [0u8; 64];
+        crate::hash::zh(depth, &mut buf);
+        *array_ref![buf, 0, 32]
+    }
+
+    fn h256(n: u8) -> H256 {
+        let mut ret = [0u8; 32];
+        ret[0] = n;
+        ret
+    }
+
+    fn get_proof() -> Vec<u8> {
+        // indexes = [2, 24, 25, 13, 14, 15]
+        let mut proof: Vec<u8> = [6u64, 1, 3, 2, 1, 1].iter().fold(vec![], |mut acc, x| {
+            acc.extend(&x.to_le_bytes());
+            acc
+        });
+
+        for chunk in &[zh(0), h256(0), h256(0), h256(3), h256(9), h256(0)] {
+            proof.extend(chunk);
+        }
+
+        proof
+    }
+
+    #[test]
+    fn matches_imp() {
+        let mut proof = get_proof();
+        let mut imp = Imp::<U264>::new(&mut proof, 4);
+        let mut owned = Owned::new(get_proof(), 4);
+
+        for state in [&mut imp as &mut dyn State, &mut owned].iter_mut() {
+            assert_eq!(state.value(1.into()), Ok(9.into()));
+            assert_eq!(state.nonce(1.into()), Ok(3.into()));
+            assert_eq!(state.pubkey(1.into()), Ok(PublicKey::zero()));
+
+            state.sub_value(1.into(), 4.into()).unwrap();
+            state.inc_nonce(1.into()).unwrap();
+            assert_eq!(state.value(1.into()), Ok(5.into()));
+        }
+
+        assert_eq!(State::root(&mut imp), owned.root());
+        assert_eq!(owned.into_inner(), proof);
+    }
+
+    #[test]
+    fn incremental_root() {
+        let mut owned = Owned::new(get_proof(), 4);
+        let pre_root = owned.root();
+
+        let mut proof = get_proof();
+        assert_eq!(
+            imp_root(&Imp::<U264>::new(&mut proof, 4)),
+            pre_root.unwrap()
+        );
+
+        // Only the path to the value is rehashed.
+        owned.add_value(1.into(), 1.into()).unwrap();
+        assert_eq!(owned.dirty.len(), 3);
+
+        let mut imp = Imp::<U264>::new(&mut proof, 4);
+        imp.add_value(1.into(), 1.into()).unwrap();
+        assert_eq!(owned.root(), State::root(&mut imp));
+        assert!(owned.dirty.is_empty());
+
+        assert_eq!(owned.update(U264::from(24), [1u8; 32]), Ok(()));
+        imp.update(U264::from(24), [1u8; 32]);
+        assert_eq!(owned.root(), State::root(&mut imp));
+
+        // Changes made through `imp` can't be tracked, so they force a full pass.
+        owned.imp().update(U264::from(2), [2u8; 32]);
+        imp.update(U264::from(2), [2u8; 32]);
+        assert_eq!(owned.root(), State::root(&mut imp));
+
+        owned.delete_account(1.into()).unwrap();
+        imp.delete_account(1.into()).unwrap();
+        assert_eq!(owned.root(), State::root(&mut imp));
+    }
+
+    /// A hasher which only XORs the two nodes together.
+    struct Xor;
+
+    impl Hasher for Xor {
+        fn hash(buf: &mut [u8; 64]) {
+            for i in 0..32 {
+                buf[i] ^= buf[i + 32];
+            }
+        }
+    }
+
+    #[test]
+    fn custom_hasher() {
+        let mut owned = Owned::<Xor>::with_hasher(get_proof(), 4);
+        assert_eq!(owned.root(), Ok(root_with::<Xor>(&get_proof())));
+        assert_ne!(owned.root(), Owned::new(get_proof(), 4).root());
+
+        owned.add_value(1.into(), 1.into()).unwrap();
+
+        let mut proof = get_proof();
+        Imp::<U264>::new(&mut proof, 4)
+            .add_value(1.into(), 1.into())
+            .unwrap();
+        assert_eq!(owned.root(), Ok(root_with::<Xor>(&proof)));
+    }
+}
@@ -0,0 +1,547 @@
+use crate::account::{calc_nonce_index, calc_pubkey_index, calc_value_index};
+use crate::address::Address;
+use crate::amount::Amount;
+use crate::bls::PublicKey;
+use crate::error::Error;
+use crate::gindex::GeneralizedIndex;
+use crate::hash::{Hasher, Sha256, H256};
+use crate::nonce::Nonce;
+use crate::state::State;
+use crate::u264::U264;
+use alloc::collections::BTreeMap;
+use arrayref::array_ref;
+use core::marker::PhantomData;
+
+/// Interface over merkle partials which store the known nodes of a tree by generalized index,
+/// such as the partials used by SSZ.
+///
+/// A partial may store interior nodes, up to the root, alongside the chunks below them. `Partial`
+/// removes the ancestors of every chunk it writes, so they are recalculated rather than read
+/// stale.
+///
+/// Implementing this trait for another crate's partial type allows it to be used as `sheth`'s
+/// state through `Partial`, without converting it to the `Imp` multi-proof format.
+pub trait MerklePartial {
+    /// Returns the chunk at `index`, if it is part of the partial.
+    fn chunk(&self, index: U264) -> Option<H256>;
+
+    /// Overwrite the chunk at `index`, which must already be part of the partial.
+    fn set_chunk(&mut self, index: U264, chunk: H256) -> Result<(), Error>;
+
+    /// Add the chunk at `index` to the partial.
+    fn insert_chunk(&mut self, index: U264, chunk: H256);
+
+    /// Remove the chunk at `index` from the partial.
+    fn remove_chunk(&mut self, index: U264);
+}
+
+impl MerklePartial for BTreeMap<U264, H256> {
+    fn chunk(&self, index: U264) -> Option<H256> {
+        self.get(&index).cloned()
+    }
+
+    fn set_chunk(&mut self, index: U264, chunk: H256) -> Result<(), Error> {
+        match self.get_mut(&index) {
+            Some(c) => {
+                *c = chunk;
+                Ok(())
+            }
+            None => Err(Error::StateIncomplete(index)),
+        }
+    }
+
+    fn insert_chunk(&mut self, index: U264, chunk: H256) {
+        self.insert(index, chunk);
+    }
+
+    fn remove_chunk(&mut self, index: U264) {
+        self.remove(&index);
+    }
+}
+
+/// Adapts a `MerklePartial` of the state tree to the `State` interface, hashing nodes with `H`.
+pub struct Partial<P: MerklePartial, H: Hasher = Sha256> {
+    pub partial: P,
+    pub height: usize,
+    hasher: PhantomData<H>,
+}
+
+impl<P: MerklePartial> Partial<P> {
+    /// `height` is the height of the state tree, not including the account subtrees.
+    pub fn new(partial: P, height: usize) -> Self {
+        Self::with_hasher(partial, height)
+    }
+}
+
+impl<P: MerklePartial, H: Hasher> Partial<P, H> {
+    /// Returns a `Partial` which hashes nodes with `H` instead of SHA-256.
+    pub fn with_hasher(partial: P, height: usize) -> Self {
+        Self {
+            partial,
+            height,
+            hasher: PhantomData,
+        }
+    }
+
+    // The `calc_*_index` functions return indexes padded to the depth of the pubkey chunks, as
+    // expected by `Imp`. Partials are keyed by the node's actual generalized index.
+    fn read(&self, index: U264) -> Result<u64, Error> {
+        let index = GeneralizedIndex::from(index).parent().get();
+        let chunk = self
+            .partial
+            .chunk(index)
+            .ok_or(Error::StateIncomplete(index))?;
+
+        Ok(u64::from_le_bytes(*array_ref![chunk, 0, 8]))
+    }
+
+    fn write(&mut self, index: U264, n: u64) -> Result<(), Error> {
+        let mut buf = [0u8; 32];
+        buf[0..8].copy_from_slice(&n.to_le_bytes());
+
+        let index = GeneralizedIndex::from(index).parent();
+        self.partial.set_chunk(index.get(), buf)?;
+        self.invalidate(index);
+
+        Ok(())
+    }
+
+    fn write_pubkey(&mut self, index: U264, pubkey: PublicKey) {
+        let mut buf = [0u8; 64];
+        buf[0..48].copy_from_slice(&pubkey.as_bytes());
+        self.partial.insert_chunk(index, *array_ref![buf, 0, 32]);
+        self.partial
+            .insert_chunk(index + U264::one(), *array_ref![buf, 32, 32]);
+        self.invalidate(GeneralizedIndex::from(index));
+    }
+
+    /// Remove the stored ancestors of `index`, whose hashes are stale once it has been written.
+    fn invalidate(&mut self, index: GeneralizedIndex) {
+        let mut node = index;
+        while !node.is_root() {
+            node = node.parent();
+            self.partial.remove_chunk(node.get());
+        }
+    }
+
+    fn node(&self, index: U264, depth: usize) -> Result<H256, Error> {
+        if let Some(chunk) = self.partial.chunk(index) {
+            return Ok(chunk);
+        }
+
+        // The deepest nodes in the tree are the pubkey chunks.
+        if depth == self.height + 3 {
+            return Err(Error::StateIncomplete(index));
+        }
+
+        let index = GeneralizedIndex::from(index);
+
+        let mut buf = [0u8; 64];
+        buf[0..32].copy_from_slice(&self.node(index.left_child().get(), depth + 1)?);
+        buf[32..64].copy_from_slice(&self.node(index.right_child().get(), depth + 1)?);
+        H::hash(&mut buf);
+
+        Ok(*array_ref![buf, 0, 32])
+    }
+}
+
+impl<P: MerklePartial, H: Hasher> State for Partial<P, H> {
+    fn root(&mut self) -> Result<H256, Error> {
+        self.node(GeneralizedIndex::root().get(), 0)
+    }
+
+    fn value(&self, address: Address) -> Result<Amount, Error> {
+        Amount::new(self.read(calc_value_index(address, self.height))?)
+    }
+
+    fn nonce(&self, address: Address) -> Result<Nonce, Error> {
+        Ok(self.read(calc_nonce_index(address, self.height))?.into())
+    }
+
+    fn pubkey(&self, address: Address) -> Result<PublicKey, Error> {
+        let index = calc_pubkey_index(address, self.height);
+
+        let mut buf = [0u8; 64];
+        for (i, index) in [index, index + U264::one()].iter().enumerate() {
+            let chunk = self
+                .partial
+                .chunk(*index)
+                .ok_or(Error::StateIncomplete(*index))?;
+
+            buf[i * 32..(i + 1) * 32].copy_from_slice(&chunk);
+        }
+
+        Ok(PublicKey::new(*array_ref![buf, 0, 48]))
+    }
+
+    fn add_value(&mut self, address: Address, amount: Amount) -> Result<Amount, Error> {
+        let index = calc_value_index(address, self.height);
+        let value = self.value(address)?.checked_add(amount)?;
+
+        self.write(index, value.get())?;
+
+        Ok(value)
+    }
+
+    fn sub_value(&mut self, address: Address, amount: Amount) -> Result<Amount, Error> {
+        let index = calc_value_index(address, self.height);
+        let value = self
+            .value(address)?
+            .checked_sub(amount)
+            .map_err(|_| Error::BalanceInsufficient)?;
+
+        self.write(index, value.get())?;
+
+        Ok(value)
+    }
+
+    fn inc_nonce(&mut self, address: Address) -> Result<Nonce, Error> {
+        let index = calc_nonce_index(address, self.height);
+        let nonce = self.nonce(address)?.next()?;
+
+        self.write(index, nonce.get())?;
+
+        Ok(nonce)
+    }
+
+    fn set_pubkey(&mut self, address: Address, pubkey: PublicKey) -> Result<(), Error> {
+        let index = calc_pubkey_index(address, self.height);
+
+        // Both chunks of the key must be part of the partial for it to be rewritten.
+        for index in [index, index + U264::one()].iter() {
+            self.partial
+                .chunk(*index)
+                .ok_or(Error::StateIncomplete(*index))?;
+        }
+
+        self.write_pubkey(index, pubkey);
+        Ok(())
+    }
+
+    fn create_account(&mut self, address: Address, pubkey: PublicKey) -> Result<(), Error> {
+        let index = calc_pubkey_index(address, self.height);
+        let account = GeneralizedIndex::account(address, self.height);
+
+        // When the account's chunks are part of the partial, it is empty if they are all zero.
+        if self.partial.chunk(index).is_some() {
+            if self.nonce(address)? != Nonce::new(0)
+                || self.value(address)? != Amount::zero()
+                || self.pubkey(address)? != PublicKey::zero()
+            {
+                return Err(Error::AccountExists);
+            }
+
+            self.write_pubkey(index, pubkey);
+            return Ok(());
+        }
+
+        // Otherwise the account must be below a node of the partial which is the root of an
+        // empty subtree.
+        let mut empty = account;
+        let mut depth = self.height;
+        let chunk = loop {
+            if let Some(chunk) = self.partial.chunk(empty.get()) {
+                break chunk;
+            }
+
+            if depth == 0 {
+                return Err(Error::StateIncomplete(account.get()));
+            }
+
+            empty = empty.parent();
+            depth -= 1;
+        };
+
+        if chunk != zh::<H>(self.height - depth) {
+            return Err(if depth == self.height {
+                Error::AccountExists
+            } else {
+                Error::StateIncomplete(account.get())
+            });
+        }
+
+        // Replace the empty subtree with the path down to the account and the empty siblings
+        // along it.
+        self.partial.remove_chunk(empty.get());
+
+        for depth in (depth + 1)..=self.height {
+            let sibling = account.ancestor(self.height - depth).sibling();
+            self.partial
+                .insert_chunk(sibling.get(), zh::<H>(self.height - depth));
+        }
+
+        self.write_pubkey(index, pubkey);
+        for i in 1..4u8 {
+            self.partial
+                .insert_chunk(account.descendant(2, i).get(), [0u8; 32]);
+        }
+
+        Ok(())
+    }
+
+    fn delete_account(&mut self, address: Address) -> Result<Amount, Error> {
+        let value = self.value(address)?;
+        let account = GeneralizedIndex::account(address, self.height);
+
+        // Replace the account's subtree with an empty account.
+        let subtree = [
+            account.descendant(3, 0),
+            account.descendant(3, 1),
+            account.descendant(2, 0),
+            account.left_child(),
+            account.right_child(),
+        ];
+
+        for index in subtree.iter() {
+            self.partial.remove_chunk(index.get());
+        }
+        for i in 1..4u8 {
+            self.partial.remove_chunk(account.descendant(2, i).get());
+        }
+
+        // Collapse the branch for as long as its sibling is also empty, so the partial doesn't keep
+        // the path to an account which no longer exists.
+        let mut node = account;
+        let mut depth = self.height;
+
+        while depth > 0 {
+            let sibling = node.sibling().get();
+            if self.partial.chunk(sibling) != Some(zh::<H>(self.height - depth)) {
+                break;
+            }
+
+            self.partial.remove_chunk(sibling);
+            node = node.parent();
+            depth -= 1;
+        }
+
+        self.partial
+            .insert_chunk(node.get(), zh::<H>(self.height - depth));
+        self.invalidate(node);
+
+        Ok(value)
+    }
+}
+
+/// Returns the root of an empty subtree whose leaves are `depth` levels below it.
+fn zh<H: Hasher>(depth: usize) -> H256 {
+    let mut buf = [0u8; 64];
+    H::zh(depth, &mut buf);
+    *array_ref![buf, 0, 32]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::state::root::imp_root;
+    use imp::Imp;
+
+    fn h256(n: u8) -> H256 {
+        let mut ret = [0u8; 32];
+        ret[0] = n;
+        ret
+    }
+
+    fn zh(depth: usize) -> H256 {
+        super::zh::<Sha256>(depth)
+    }
+
+    fn get_partial() -> Partial<BTreeMap<U264, H256>> {
+        let mut chunks = BTreeMap::new();
+
+        chunks.insert(16u8.into(), h256(0));
+        chunks.insert(17u8.into(), h256(0));
+        chunks.insert(9u8.into(), h256(1));
+        chunks.insert(10u8.into(), h256(1));
+        chunks.insert(11u8.into(), zh(0));
+        chunks.insert(3u8.into(), zh(0));
+
+        Partial::new(chunks, 1)
+    }
+
+    #[test]
+    fn root_matches_imp() {
+        // indexes = [16, 17, 9, 10, 11, 3]
+        let mut proof: Vec<u8> = [6u64, 5, 3, 2, 1, 1].iter().fold(vec![], |mut acc, x| {
+            acc.extend(&x.to_le_bytes());
+            acc
+        });
+
+        for chunk in &[h256(0), h256(0), h256(1), h256(1), zh(0), zh(0)] {
+            proof.extend(chunk);
+        }
+
+        let mem = Imp::<U264>::new(&mut proof, 4);
+        assert_eq!(get_partial().root(), Ok(imp_root(&mem)));
+    }
+
+    #[test]
+    fn interior_nodes() {
+        let mut plain = get_partial();
+        let mut partial = get_partial();
+
+        // Like an SSZ partial, also store the nodes above the chunks, up to the root.
+        for (index, depth) in [(1u8, 0), (2, 1), (4, 2), (5, 2)].iter() {
+            let node = partial.node((*index).into(), *depth).unwrap();
+            partial.partial.insert((*index).into(), node);
+        }
+        assert_eq!(partial.root(), plain.root());
+
+        let mut check = |f: &dyn Fn(&mut dyn State) -> Result<(), Error>| {
+            assert_eq!(f(&mut partial), Ok(()));
+            assert_eq!(f(&mut plain), Ok(()));
+            assert_eq!(partial.root(), plain.root());
+        };
+
+        check(&|db| db.add_value(0.into(), 1.into()).map(|_| ()));
+        check(&|db| db.inc_nonce(0.into()).map(|_| ()));
+        check(&|db| db.set_pubkey(0.into(), PublicKey::one()));
+        check(&|db| db.create_account(1.into(), PublicKey::one()));
+        check(&|db| db.delete_account(0.into()).map(|_| ()));
+    }
+
+    #[test]
+    fn add_value() {
+        let mut partial = get_partial();
+
+        assert_eq!(partial.add_value(0.into(), 1.into()), Ok(2.into()));
+        assert_eq!(partial.partial.chunk(10u8.into()), Some(h256(2)));
+    }
+
+    #[test]
+    fn sub_value() {
+        let mut partial = get_partial();
+
+        assert_eq!(partial.sub_value(0.into(), 1.into()), Ok(0.into()));
+        assert_eq!(
+            partial.sub_value(0.into(), 1.into()),
+            Err(Error::BalanceInsufficient)
+        );
+        assert_eq!(partial.partial.chunk(10u8.into()), Some(h256(0)));
+    }
+
+    #[test]
+    fn pubkey() {
+        let mut partial = get_partial();
+        assert_eq!(partial.pubkey(0.into()), Ok(PublicKey::zero()));
+
+        partial.partial.insert(16u8.into(), [1u8; 32]);
+        partial.partial.insert(17u8.into(), [1u8; 32]);
+        assert_eq!(partial.pubkey(0.into()), Ok(PublicKey::one()));
+    }
+
+    #[test]
+    fn inc_nonce() {
+        let mut partial = get_partial();
+
+        assert_eq!(partial.inc_nonce(0.into()), Ok(2.into()));
+        assert_eq!(partial.partial.chunk(9u8.into()), Some(h256(2)));
+    }
+
+    #[test]
+    fn incomplete_partial() {
+        let mut partial = get_partial();
+        partial.partial.remove(&3u8.into());
+
+        assert_eq!(
+            partial.root(),
+            Err(Error::StateIncomplete(U264::from(24u8)))
+        );
+        assert_eq!(
+            partial.value(1.into()),
+            Err(Error::StateIncomplete(U264::from(14u8)))
+        );
+    }
+
+    #[test]
+    fn create_account_in_empty_subtree() {
+        let mut partial = get_partial();
+        let mut expected = get_partial();
+
+        // Account 1 is proven empty by the zero hash at index 3.
+        assert_eq!(partial.create_account(1.into(), PublicKey::one()), Ok(()));
+        assert_eq!(partial.pubkey(1.into()), Ok(PublicKey::one()));
+        assert_eq!(partial.value(1.into()), Ok(0.into()));
+        assert_eq!(partial.partial.chunk(3u8.into()), None);
+
+        expected.partial.remove(&3u8.into());
+        expected.partial.insert(24u8.into(), [1u8; 32]);
+        expected.partial.insert(25u8.into(), {
+            let mut chunk = [0u8; 32];
+            chunk[0..16].copy_from_slice(&[1u8; 16]);
+            chunk
+        });
+        for i in 13..16u8 {
+            expected.partial.insert(i.into(), h256(0));
+        }
+        assert_eq!(partial.root(), expected.root());
+
+        assert_eq!(
+            partial.create_account(1.into(), PublicKey::one()),
+            Err(Error::AccountExists)
+        );
+    }
+
+    #[test]
+    fn create_account_in_deep_empty_subtree() {
+        let mut chunks = BTreeMap::new();
+        chunks.insert(2u8.into(), zh(1));
+        chunks.insert(3u8.into(), zh(1));
+        let mut partial = Partial::new(chunks, 2);
+
+        assert_eq!(partial.create_account(1.into(), PublicKey::one()), Ok(()));
+        assert_eq!(partial.partial.chunk(4u8.into()), Some(zh(0)));
+        assert_eq!(partial.partial.chunk(2u8.into()), None);
+        assert_eq!(partial.pubkey(1.into()), Ok(PublicKey::one()));
+        assert!(partial.root().is_ok());
+    }
+
+    #[test]
+    fn create_account_requires_proof_of_absence() {
+        let mut chunks = BTreeMap::new();
+        chunks.insert(2u8.into(), h256(1));
+        chunks.insert(3u8.into(), zh(0));
+        let mut partial = Partial::new(chunks, 1);
+
+        assert_eq!(
+            partial.create_account(0.into(), PublicKey::one()),
+            Err(Error::AccountExists)
+        );
+
+        partial.partial.remove(&2u8.into());
+        assert_eq!(
+            partial.create_account(0.into(), PublicKey::one()),
+            Err(Error::StateIncomplete(2u8.into()))
+        );
+    }
+
+    #[test]
+    fn delete_account() {
+        let mut partial = get_partial();
+
+        assert_eq!(partial.delete_account(0.into()), Ok(1.into()));
+        assert_eq!(partial.partial.len(), 1);
+        assert_eq!(partial.partial.chunk(1u8.into()), Some(zh(1)));
+        assert_eq!(partial.root(), Ok(zh(1)));
+        assert_eq!(
+            partial.value(0.into()),
+            Err(Error::StateIncomplete(U264::from(10u8)))
+        );
+    }
+
+    #[test]
+    fn delete_account_keeps_non_empty_siblings() {
+        let mut partial = get_partial();
+        partial.create_account(1.into(), PublicKey::one()).unwrap();
+
+        let mut expected = partial.partial.clone();
+        for i in &[16u8, 17, 9, 10, 11] {
+            expected.remove(&(*i).into());
+        }
+        expected.insert(2u8.into(), zh(0));
+
+        assert_eq!(partial.delete_account(0.into()), Ok(1.into()));
+        assert_eq!(partial.partial, expected);
+        assert_eq!(partial.pubkey(1.into()), Ok(PublicKey::one()));
+    }
+}
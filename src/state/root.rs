@@ -0,0 +1,291 @@
+use crate::hash::{Hasher, Sha256, H256};
+use crate::u264::U264;
+use alloc::vec;
+use alloc::vec::Vec;
+use arrayref::array_ref;
+use imp::Imp;
+
+/// Returns the hash of every internal node of the multi-proof `proof`, by the node's position in
+/// the offsets.
+///
+/// Unlike `Imp::root`, the nodes are hashed bottom-up in a single pass over the offsets rather
+/// than by recursing down the tree, so the stack depth doesn't depend on the proof. The children
+/// of a node are always at greater positions than the node itself, so the positions and sizes of
+/// every subtree are found going forwards through the offsets and the nodes are hashed going
+/// backwards.
+pub fn nodes(proof: &[u8]) -> Vec<H256> {
+    nodes_with::<Sha256>(proof)
+}
+
+/// Returns the hash of every internal node of `proof` in the same way as `nodes`, hashing with
+/// `H`.
+pub fn nodes_with<H: Hasher>(proof: &[u8]) -> Vec<H256> {
+    let len = u64::from_le_bytes(*array_ref![proof, 0, 8]) as usize;
+    if len < 2 {
+        return vec![];
+    }
+
+    hash_nodes::<H>(&proof[8..len * 8], &proof[len * 8..len * 40])
+}
+
+/// Hashes the internal nodes of a multi-proof which has been split into its `offsets`, not
+/// including the leading length, and its `chunks`. Each level of the tree is hashed with a
+/// single call to `Hasher::hash_many`.
+///
+/// Each offset is decoded from its little endian bytes exactly once, so neither slice needs to be
+/// aligned and the result doesn't depend on the endianness of the target.
+pub(crate) fn hash_nodes<H: Hasher>(offsets: &[u8], chunks: &[u8]) -> Vec<H256> {
+    let len = chunks.len() / 32;
+    if len < 2 {
+        return vec![];
+    }
+
+    let skips: Vec<usize> = (0..(len - 1))
+        .map(|position| u64::from_le_bytes(*array_ref![offsets, position * 8, 8]) as usize)
+        .collect();
+
+    let chunk = |offset: usize| -> H256 { *array_ref![chunks, offset * 32, 32] };
+
+    // The first chunk and number of chunks below each internal node.
+    let mut subtrees = vec![(0, 0); len - 1];
+    subtrees[0] = (0, len);
+
+    for position in 0..(len - 1) {
+        let (offset, size) = subtrees[position];
+        let left = skips[position];
+
+        if left > 1 {
+            subtrees[position + 1] = (offset, left);
+        }
+        if size - left > 1 {
+            subtrees[position + left] = (offset + left, size - left);
+        }
+    }
+
+    // Going backwards, the children of each node have already been given a level, so nodes are
+    // grouped by their height above the chunks. Nodes of the same level don't depend on each
+    // other and are hashed as one batch.
+    let mut levels = vec![0usize; len - 1];
+    let mut batches: Vec<Vec<usize>> = vec![];
+
+    for position in (0..(len - 1)).rev() {
+        let (_, size) = subtrees[position];
+        let left = skips[position];
+
+        let mut level = 0;
+        if left > 1 {
+            level = levels[position + 1] + 1;
+        }
+        if size - left > 1 {
+            level = level.max(levels[position + left] + 1);
+        }
+
+        levels[position] = level;
+        if batches.len() == level {
+            batches.push(vec![]);
+        }
+        batches[level].push(position);
+    }
+
+    let mut nodes = vec![[0u8; 32]; len - 1];
+    let mut bufs = vec![];
+
+    for batch in batches {
+        bufs.clear();
+
+        for position in batch.iter() {
+            let (offset, size) = subtrees[*position];
+            let left = skips[*position];
+
+            let mut buf = [0u8; 64];
+            if left == 1 {
+                buf[0..32].copy_from_slice(&chunk(offset));
+            } else {
+                buf[0..32].copy_from_slice(&nodes[position + 1]);
+            }
+            if size - left == 1 {
+                buf[32..64].copy_from_slice(&chunk(offset + left));
+            } else {
+                buf[32..64].copy_from_slice(&nodes[position + left]);
+            }
+
+            bufs.push(buf);
+        }
+
+        H::hash_many(&mut bufs);
+
+        for (position, buf) in batch.iter().zip(bufs.iter()) {
+            nodes[*position] = *array_ref![buf, 0, 32];
+        }
+    }
+
+    nodes
+}
+
+/// Calculates the root of the multi-proof `proof` without recursion.
+pub fn root(proof: &[u8]) -> H256 {
+    root_with::<Sha256>(proof)
+}
+
+/// Calculates the root of the multi-proof `proof` without recursion, hashing with `H`.
+pub fn root_with<H: Hasher>(proof: &[u8]) -> H256 {
+    let len = u64::from_le_bytes(*array_ref![proof, 0, 8]) as usize;
+    subtree_root::<H>(&proof[8..len * 8], &proof[len * 8..len * 40])
+}
+
+/// Calculates the root of `imp` from its offsets and chunks.
+///
+/// `Imp::root` reinterprets the offsets as a `&[u64]`, which is undefined behaviour when the proof
+/// isn't 8 byte aligned and gives the wrong root on big-endian targets. This should be used
+/// instead wherever the root of an `Imp` is needed.
+pub fn imp_root(imp: &Imp<U264>) -> H256 {
+    imp_root_with::<Sha256>(imp)
+}
+
+/// Calculates the root of `imp` in the same way as `imp_root`, hashing with `H`.
+pub fn imp_root_with<H: Hasher>(imp: &Imp<U264>) -> H256 {
+    subtree_root::<H>(imp.offsets, imp.db)
+}
+
+/// Minimum number of chunks in a subtree before its two children are hashed in parallel. Below
+/// this, the cost of scheduling the work outweighs the hashing.
+#[cfg(feature = "rayon")]
+pub const PARALLEL_THRESHOLD: usize = 1 << 12;
+
+/// Calculates the root of a multi-proof which has been split into its `offsets` and `chunks`.
+#[cfg(not(feature = "rayon"))]
+fn subtree_root<H: Hasher>(offsets: &[u8], chunks: &[u8]) -> H256 {
+    match hash_nodes::<H>(offsets, chunks).first() {
+        Some(root) => *root,
+        None => *array_ref![chunks, 0, 32],
+    }
+}
+
+/// Calculates the root of a multi-proof which has been split into its `offsets` and `chunks`,
+/// hashing the two subtrees below each sufficiently large node on separate threads.
+///
+/// The internal nodes and chunks of any subtree are contiguous, and its offsets are relative to
+/// its first internal node, so each subtree is itself a multi-proof and is sliced out without
+/// copying. The recursion is only as deep as the proof's largest subtrees, which is bounded by
+/// the height of the tree.
+#[cfg(feature = "rayon")]
+fn subtree_root<H: Hasher>(offsets: &[u8], chunks: &[u8]) -> H256 {
+    let len = chunks.len() / 32;
+
+    if len < PARALLEL_THRESHOLD {
+        return match hash_nodes::<H>(offsets, chunks).first() {
+            Some(root) => *root,
+            None => *array_ref![chunks, 0, 32],
+        };
+    }
+
+    let left = u64::from_le_bytes(*array_ref![offsets, 0, 8]) as usize;
+    let (l, r) = rayon::join(
+        || subtree_root::<H>(&offsets[8..left * 8], &chunks[..left * 32]),
+        || subtree_root::<H>(&offsets[left * 8..], &chunks[left * 32..]),
+    );
+
+    let mut buf = [0u8; 64];
+    buf[0..32].copy_from_slice(&l);
+    buf[32..64].copy_from_slice(&r);
+    H::hash(&mut buf);
+
+    *array_ref![buf, 0, 32]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::u264::U264;
+    use imp::Imp;
+
+    fn zh(depth: usize) -> H256 {
+        let mut buf = [0u8; 64];
+        crate::hash::zh(depth, &mut buf);
+        *array_ref![buf, 0, 32]
+    }
+
+    fn h256(n: u8) -> H256 {
+        let mut ret = [0u8; 32];
+        ret[0] = n;
+        ret
+    }
+
+    fn build_proof(offsets: &[u64], chunks: &[H256]) -> Vec<u8> {
+        let mut proof = vec![];
+        for offset in offsets {
+            proof.extend(&offset.to_le_bytes());
+        }
+        for chunk in chunks {
+            proof.extend(chunk);
+        }
+
+        proof
+    }
+
+    #[test]
+    fn root_matches_imp() {
+        let proofs = [
+            // indexes = [2, 24, 25, 13, 14, 15]
+            build_proof(
+                &[6, 1, 3, 2, 1, 1],
+                &[zh(0), h256(0), h256(0), h256(3), h256(9), h256(0)],
+            ),
+            // indexes = [16, 17, 9, 10, 11, 3]
+            build_proof(
+                &[6, 5, 3, 2, 1, 1],
+                &[h256(0), h256(0), h256(1), h256(1), zh(0), zh(0)],
+            ),
+            // indexes = [2, 3]
+            build_proof(&[2, 1], &[h256(1), h256(2)]),
+        ];
+
+        for proof in proofs.iter() {
+            let mut expected = proof.clone();
+            assert_eq!(root(proof), Imp::<U264>::new(&mut expected, 4).root());
+        }
+    }
+
+    #[test]
+    fn imp_root_of_misaligned_proof() {
+        // indexes = [16, 17, 9, 10, 11, 3]
+        let proof = build_proof(
+            &[6, 5, 3, 2, 1, 1],
+            &[h256(0), h256(0), h256(1), h256(1), zh(0), zh(0)],
+        );
+
+        // Shifting the proof by 4 bytes leaves its offsets misaligned.
+        let mut buf = vec![0u8; 4];
+        buf.extend(&proof);
+
+        let mem = Imp::<U264>::new(&mut buf[4..], 4);
+        assert_eq!(imp_root(&mem), root(&proof));
+    }
+
+    #[test]
+    fn root_of_single_chunk() {
+        assert_eq!(root(&build_proof(&[1], &[h256(7)])), h256(7));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_root_matches_sequential() {
+        // A full tree of 2**14 chunks, so the top levels are hashed in parallel.
+        fn offsets(size: usize, ret: &mut Vec<u64>) {
+            if size > 1 {
+                ret.push((size / 2) as u64);
+                offsets(size / 2, ret);
+                offsets(size / 2, ret);
+            }
+        }
+
+        let len = 1 << 14;
+        let mut skips = vec![len as u64];
+        offsets(len, &mut skips);
+
+        let chunks: Vec<H256> = (0..len).map(|i| h256(i as u8)).collect();
+        let proof = build_proof(&skips, &chunks);
+
+        assert_eq!(root(&proof), nodes(&proof)[0]);
+    }
+}
@@ -0,0 +1,222 @@
+use crate::account::Account;
+use crate::address::Address;
+use crate::amount::Amount;
+use crate::bls::PublicKey;
+use crate::error::Error;
+use crate::hash::H256;
+use crate::nonce::Nonce;
+use crate::schema::{field_index, Field};
+use crate::state::imp::lookup;
+use crate::state::State;
+use crate::u264::U264;
+use alloc::collections::BTreeMap;
+use core::cell::RefCell;
+use imp::Imp;
+
+/// How a chunk of the proof was accessed while it was tracked.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Access {
+    /// Offset of the chunk among the proof's chunks.
+    pub offset: usize,
+
+    /// Whether the chunk was overwritten, rather than only read.
+    pub written: bool,
+}
+
+/// Wraps `Imp` and records the general index and offset of every chunk which is read or written,
+/// so a builder can construct a minimal witness for the block it executed or see exactly which
+/// leaves it depended on.
+///
+/// Only operations which succeed are recorded.
+pub struct Tracked<'a> {
+    pub imp: Imp<'a, U264>,
+    touched: RefCell<BTreeMap<U264, Access>>,
+}
+
+impl<'a> Tracked<'a> {
+    pub fn new(imp: Imp<'a, U264>) -> Self {
+        Self {
+            imp,
+            touched: RefCell::new(BTreeMap::new()),
+        }
+    }
+
+    /// Returns every chunk touched so far by general index, in ascending order.
+    pub fn touched(&self) -> impl Iterator<Item = (U264, Access)> {
+        self.touched.borrow().clone().into_iter()
+    }
+
+    /// Returns the map from the general index of each touched chunk to its offset in the proof.
+    pub fn offsets(&self) -> BTreeMap<U264, usize> {
+        self.touched
+            .borrow()
+            .iter()
+            .map(|(index, access)| (*index, access.offset))
+            .collect()
+    }
+
+    /// Forget the chunks touched so far.
+    pub fn clear(&mut self) {
+        self.touched.borrow_mut().clear();
+    }
+
+    /// Record that the chunks of `field` in the account at `address` were accessed.
+    fn record(&self, address: Address, field: Field, written: bool) -> Result<(), Error> {
+        let index = field_index::<Account>(address, self.imp.height, field);
+        let mut touched = self.touched.borrow_mut();
+
+        for i in 0..field.chunks {
+            let index = index + (i as u8).into();
+            let offset = lookup(self.imp.offsets, self.imp.db, self.imp.height, index)?;
+
+            let access = touched.entry(index).or_insert(Access {
+                offset,
+                written: false,
+            });
+            access.written |= written;
+        }
+
+        Ok(())
+    }
+
+    /// Record that every chunk of the account at `address` was accessed.
+    fn record_account(&self, address: Address, written: bool) -> Result<(), Error> {
+        for field in [Account::PUBKEY, Account::NONCE, Account::VALUE].iter() {
+            self.record(address, *field, written)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> State for Tracked<'a> {
+    fn root(&mut self) -> Result<H256, Error> {
+        State::root(&mut self.imp)
+    }
+
+    fn value(&self, address: Address) -> Result<Amount, Error> {
+        let value = self.imp.value(address)?;
+        self.record(address, Account::VALUE, false)?;
+        Ok(value)
+    }
+
+    fn nonce(&self, address: Address) -> Result<Nonce, Error> {
+        let nonce = self.imp.nonce(address)?;
+        self.record(address, Account::NONCE, false)?;
+        Ok(nonce)
+    }
+
+    fn pubkey(&self, address: Address) -> Result<PublicKey, Error> {
+        let pubkey = self.imp.pubkey(address)?;
+        self.record(address, Account::PUBKEY, false)?;
+        Ok(pubkey)
+    }
+
+    fn add_value(&mut self, address: Address, amount: Amount) -> Result<Amount, Error> {
+        let value = self.imp.add_value(address, amount)?;
+        self.record(address, Account::VALUE, true)?;
+        Ok(value)
+    }
+
+    fn sub_value(&mut self, address: Address, amount: Amount) -> Result<Amount, Error> {
+        let value = self.imp.sub_value(address, amount)?;
+        self.record(address, Account::VALUE, true)?;
+        Ok(value)
+    }
+
+    fn inc_nonce(&mut self, address: Address) -> Result<Nonce, Error> {
+        let nonce = self.imp.inc_nonce(address)?;
+        self.record(address, Account::NONCE, true)?;
+        Ok(nonce)
+    }
+
+    fn set_pubkey(&mut self, address: Address, pubkey: PublicKey) -> Result<(), Error> {
+        self.imp.set_pubkey(address, pubkey)?;
+        self.record(address, Account::PUBKEY, true)
+    }
+
+    fn create_account(&mut self, address: Address, pubkey: PublicKey) -> Result<(), Error> {
+        self.imp.create_account(address, pubkey)?;
+        self.record_account(address, false)?;
+        self.record(address, Account::PUBKEY, true)
+    }
+
+    fn delete_account(&mut self, address: Address) -> Result<Amount, Error> {
+        let value = self.imp.delete_account(address)?;
+        self.record_account(address, true)?;
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::account::{calc_nonce_index, calc_value_index};
+
+    fn h256(n: u8) -> H256 {
+        let mut ret = [0u8; 32];
+        ret[0] = n;
+        ret
+    }
+
+    fn get_proof() -> Vec<u8> {
+        // indexes = [16, 17, 9, 10, 11, 3]
+        let mut proof = vec![];
+        for offset in [6u64, 5, 3, 2, 1, 1].iter() {
+            proof.extend(&offset.to_le_bytes());
+        }
+        for chunk in [h256(0), h256(0), h256(1), h256(1), [0u8; 32], [0u8; 32]].iter() {
+            proof.extend(chunk);
+        }
+
+        proof
+    }
+
+    #[test]
+    fn records_touched_chunks() {
+        let mut proof = get_proof();
+        let mut state = Tracked::new(Imp::new(&mut proof, 4));
+
+        assert_eq!(state.nonce(0.into()), Ok(1.into()));
+        assert_eq!(state.add_value(0.into(), 1.into()), Ok(2.into()));
+
+        let nonce = calc_nonce_index(0.into(), 4);
+        let value = calc_value_index(0.into(), 4);
+
+        assert_eq!(
+            state.touched().collect::<Vec<_>>(),
+            vec![
+                (
+                    nonce,
+                    Access {
+                        offset: 2,
+                        written: false
+                    }
+                ),
+                (
+                    value,
+                    Access {
+                        offset: 3,
+                        written: true
+                    }
+                ),
+            ]
+        );
+        assert_eq!(state.offsets().get(&value), Some(&3));
+
+        state.clear();
+        assert_eq!(state.touched().count(), 0);
+    }
+
+    #[test]
+    fn failed_operations_are_not_recorded() {
+        let mut proof = get_proof();
+        let mut state = Tracked::new(Imp::new(&mut proof, 4));
+
+        assert_eq!(
+            state.sub_value(0.into(), 2.into()),
+            Err(Error::BalanceInsufficient)
+        );
+        assert_eq!(state.touched().count(), 0);
+    }
+}
@@ -0,0 +1,297 @@
+use crate::account::{calc_leaf_index, check_address};
+use crate::address::Address;
+use crate::error::Error;
+use crate::gindex::GeneralizedIndex;
+use crate::hash::{Hasher, H256};
+use crate::schema::LeafSchema;
+use crate::state::root::hash_nodes;
+use crate::u264::U264;
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use arrayref::array_ref;
+use imp::Imp;
+
+/// A node of the extracted multi-proof, in the order the nodes are visited going down the tree.
+enum Node {
+    /// An internal node, followed by its left and then its right subtree.
+    Branch,
+
+    /// A chunk of the proof.
+    Chunk(H256),
+}
+
+/// Extracts a self-contained multi-proof of only the leaves at `addresses` from `imp`, in the
+/// same format that `Imp` reads.
+///
+/// Every subtree of `imp` which doesn't contain one of the leaves is replaced by its hash with `H`
+/// as of the current contents of `imp`, so the extracted proof authenticates against the same root
+/// even after transactions have been executed against it. If a leaf was omitted from `imp`,
+/// `Error::StateIncomplete` is returned with the general index of the chunk which omits it, and
+/// an address beyond the last leaf is rejected with `Error::AddressInvalid`.
+///
+/// `imp` is expected to have been created by `imp::new_checked`.
+pub fn extract<S: LeafSchema, H: Hasher>(
+    imp: &Imp<U264>,
+    addresses: &[Address],
+) -> Result<Vec<u8>, Error> {
+    let (offsets, chunks): (&[u8], &[u8]) = (imp.offsets, imp.db);
+    let len = chunks.len() / 32;
+    let height = imp.height - S::DEPTH;
+
+    let leaves: Vec<U264> = addresses
+        .iter()
+        .map(|address| {
+            check_address(*address, height)?;
+            Ok(calc_leaf_index(*address, height))
+        })
+        .collect::<Result<_, Error>>()?;
+
+    // Whether the node at `index` and `depth` is a leaf at one of `addresses`, or is above or
+    // below one.
+    let covers = |index: U264, depth: usize| {
+        leaves.iter().any(|leaf| {
+            if depth <= height {
+                *leaf >> (height - depth) == index
+            } else {
+                index >> (depth - height) == *leaf
+            }
+        })
+    };
+
+    let skip = |position: usize| u64::from_le_bytes(*array_ref![offsets, position * 8, 8]) as usize;
+    let chunk = |offset: usize| -> H256 { *array_ref![chunks, offset * 32, 32] };
+    let hashes = hash_nodes::<H>(offsets, chunks);
+
+    // Each entry is the first chunk, number of chunks, offset position, general index and depth
+    // of a subtree of `imp`.
+    let mut nodes = vec![];
+    let mut stack = vec![(0, len, 0, GeneralizedIndex::root().get(), 0)];
+
+    while let Some((first, size, position, index, depth)) = stack.pop() {
+        if !covers(index, depth) {
+            if size == 1 {
+                nodes.push(Node::Chunk(chunk(first)));
+            } else {
+                nodes.push(Node::Chunk(hashes[position]));
+            }
+        } else if size == 1 {
+            if depth <= height {
+                return Err(Error::StateIncomplete(index));
+            }
+
+            nodes.push(Node::Chunk(chunk(first)));
+        } else {
+            let left = skip(position);
+            let index = GeneralizedIndex::from(index);
+
+            nodes.push(Node::Branch);
+            stack.push((
+                first + left,
+                size - left,
+                position + left,
+                index.right_child().get(),
+                depth + 1,
+            ));
+            stack.push((
+                first,
+                left,
+                position + 1,
+                index.left_child().get(),
+                depth + 1,
+            ));
+        }
+    }
+
+    // The children of a branch come after it, so going backwards the number of chunks below both
+    // of its children is already known, with the left child's on top of the stack.
+    let mut sizes: Vec<usize> = vec![];
+    let mut skips = vec![];
+
+    for node in nodes.iter().rev() {
+        match node {
+            Node::Chunk(_) => sizes.push(1),
+            Node::Branch => {
+                let left = sizes.pop().expect("Branch to have a left subtree");
+                let right = sizes.pop().expect("Branch to have a right subtree");
+
+                skips.push(left as u64);
+                sizes.push(left + right);
+            }
+        }
+    }
+
+    let mut ret = (sizes[0] as u64).to_le_bytes().to_vec();
+    for left in skips.iter().rev() {
+        ret.extend(&left.to_le_bytes());
+    }
+    for node in nodes {
+        if let Node::Chunk(chunk) = node {
+            ret.extend(&chunk);
+        }
+    }
+
+    Ok(ret)
+}
+
+/// Brings `stale`, a proof of an earlier state, up to date with `fresh`, a proof of a later one.
+/// Every chunk of `stale` whose general index is a chunk or internal node of `fresh` is overwritten
+/// with `fresh`'s value for it, hashing with `H`.
+///
+/// The chunks which changed between the two states are all in `fresh`, and so is every node above
+/// them, so `stale` authenticates against the root of `fresh` afterwards as long as every change
+/// since the state `stale` was made for was made to `fresh`. Otherwise its root is left wrong,
+/// which the caller is expected to check.
+pub fn refresh<H: Hasher>(stale: &mut Imp<U264>, fresh: &Imp<U264>) {
+    let hashes = hash_nodes::<H>(fresh.offsets, fresh.db);
+    let mut known: BTreeMap<U264, H256> = BTreeMap::new();
+    walk(
+        fresh.offsets,
+        fresh.db.len() / 32,
+        |first, size, position, index| {
+            let node = if size == 1 {
+                *array_ref![fresh.db, first * 32, 32]
+            } else {
+                hashes[position]
+            };
+            known.insert(index, node);
+        },
+    );
+
+    let mut updates = vec![];
+    walk(
+        stale.offsets,
+        stale.db.len() / 32,
+        |first, size, _, index| {
+            if size == 1 {
+                if let Some(node) = known.get(&index) {
+                    updates.push((first, *node));
+                }
+            }
+        },
+    );
+
+    for (first, node) in updates {
+        stale.db[first * 32..first * 32 + 32].copy_from_slice(&node);
+    }
+}
+
+/// Calls `f` with the first chunk, number of chunks, offset position and general index of every
+/// subtree of a proof with `offsets` and `len` chunks, parents before their children.
+fn walk<F: FnMut(usize, usize, usize, U264)>(offsets: &[u8], len: usize, mut f: F) {
+    let skip = |position: usize| u64::from_le_bytes(*array_ref![offsets, position * 8, 8]) as usize;
+    let mut stack = vec![(0, len, 0, GeneralizedIndex::root().get())];
+
+    while let Some((first, size, position, index)) = stack.pop() {
+        f(first, size, position, index);
+
+        if size > 1 {
+            let left = skip(position);
+            let index = GeneralizedIndex::from(index);
+
+            stack.push((
+                first + left,
+                size - left,
+                position + left,
+                index.right_child().get(),
+            ));
+            stack.push((first, left, position + 1, index.left_child().get()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::account::Account;
+    use crate::hash::Sha256;
+    use crate::state::imp::new_checked;
+    use crate::state::root::imp_root;
+    use crate::state::State;
+
+    fn h256(n: u8) -> H256 {
+        let mut ret = [0u8; 32];
+        ret[0] = n;
+        ret
+    }
+
+    fn get_proof() -> Vec<u8> {
+        // indexes = [16, 17, 9, 10, 11, 24, 25, 13, 14, 15]
+        let mut proof = vec![];
+        for offset in [10u64, 5, 3, 2, 1, 1, 3, 2, 1, 1].iter() {
+            proof.extend(&offset.to_le_bytes());
+        }
+        for n in 0..2 {
+            for chunk in [h256(n), h256(n), h256(1), h256(n + 1), [0u8; 32]].iter() {
+                proof.extend(chunk);
+            }
+        }
+
+        proof
+    }
+
+    #[test]
+    fn extract_single_account() {
+        let mut proof = get_proof();
+        let mut mem = new_checked(&mut proof, 4).unwrap();
+        assert_eq!(mem.add_value(1.into(), 5.into()), Ok(7.into()));
+
+        let mut witness = extract::<Account, Sha256>(&mem, &[0.into()]).unwrap();
+        assert_eq!(witness.len(), 6 * 40);
+
+        let mut sub = new_checked(&mut witness, 4).unwrap();
+        assert_eq!(imp_root(&sub), imp_root(&mem));
+        assert_eq!(sub.value(0.into()), Ok(1.into()));
+        assert_eq!(sub.nonce(0.into()), Ok(1.into()));
+
+        // Executing against the extracted proof gives the same root as the full proof.
+        assert_eq!(sub.add_value(0.into(), 3.into()), Ok(4.into()));
+        assert_eq!(mem.add_value(0.into(), 3.into()), Ok(4.into()));
+        assert_eq!(imp_root(&sub), imp_root(&mem));
+    }
+
+    #[test]
+    fn extract_every_account() {
+        let mut proof = get_proof();
+        let mem = new_checked(&mut proof, 4).unwrap();
+
+        assert_eq!(
+            extract::<Account, Sha256>(&mem, &[1.into(), 0.into()]),
+            Ok(get_proof())
+        );
+        assert_eq!(
+            extract::<Account, Sha256>(&mem, &[]),
+            Ok([&1u64.to_le_bytes()[..], &imp_root(&mem)].concat())
+        );
+    }
+
+    #[test]
+    fn extract_omitted_account() {
+        let mut proof = get_proof();
+        let mem = new_checked(&mut proof, 4).unwrap();
+        let mut witness = extract::<Account, Sha256>(&mem, &[0.into()]).unwrap();
+        let sub = new_checked(&mut witness, 4).unwrap();
+
+        assert_eq!(
+            extract::<Account, Sha256>(&sub, &[1.into()]),
+            Err(Error::StateIncomplete(3.into()))
+        );
+    }
+
+    #[test]
+    fn refresh_stale_witness() {
+        let mut proof = get_proof();
+        let mut mem = new_checked(&mut proof, 4).unwrap();
+        let mut witness = extract::<Account, Sha256>(&mem, &[0.into()]).unwrap();
+        let mut sub = new_checked(&mut witness, 4).unwrap();
+
+        // Both accounts change after the witness of the first was extracted.
+        assert_eq!(mem.add_value(0.into(), 3.into()), Ok(4.into()));
+        assert_eq!(mem.add_value(1.into(), 5.into()), Ok(7.into()));
+        assert_ne!(imp_root(&sub), imp_root(&mem));
+
+        refresh::<Sha256>(&mut sub, &mem);
+        assert_eq!(imp_root(&sub), imp_root(&mem));
+        assert_eq!(sub.value(0.into()), Ok(4.into()));
+    }
+}
@@ -0,0 +1,149 @@
+//! Experimental one-time recipient addresses, to prototype basic payment privacy on the transfer
+//! EE.
+//!
+//! Rather than being paid at the address derived from their public key, a recipient is paid at
+//! an address derived from their public key and a salt provided by the block. Each payment
+//! therefore lands in a fresh account which can't be linked to the recipient's other accounts
+//! by address alone. The public key is still part of the transfer and of the new account, so
+//! this only prototypes the derivation and its validation.
+
+use crate::address::Address;
+use crate::amount::Amount;
+use crate::bls::PublicKey;
+use crate::error::Error;
+use crate::nonce::Nonce;
+use crate::state::State;
+use bigint::U256;
+use sha2::{Digest, Sha256};
+
+/// Derive the one-time address at which `pubkey` is paid in the block with `salt`.
+///
+/// ```text
+/// address = sha256(sha256(pubkey) || salt) mod 2**height
+/// ```
+pub fn derive_address(pubkey: &PublicKey, salt: &[u8; 32], height: usize) -> Address {
+    let mut hasher = Sha256::new();
+    hasher.input(Sha256::digest(&pubkey.as_bytes()[..]));
+    hasher.input(salt);
+
+    let address = U256::from(hasher.result().as_ref());
+    if height < 256 {
+        (address % (U256::one() << height)).into()
+    } else {
+        address.into()
+    }
+}
+
+/// A transfer to the one-time address of `recipient`.
+#[cfg_attr(feature = "std", derive(Clone))]
+pub struct StealthTransfer {
+    pub from: Address,
+    pub recipient: PublicKey,
+    pub nonce: Nonce,
+    pub amount: Amount,
+    pub signature: [u8; 96],
+}
+
+impl StealthTransfer {
+    /// Returns the address the transfer pays in the block with `salt`.
+    pub fn to(&self, salt: &[u8; 32], height: usize) -> Address {
+        derive_address(&self.recipient, salt, height)
+    }
+}
+
+/// Apply `tx` to `db` for the block with `salt`, creating the recipient's one-time account, and
+/// return its address.
+///
+/// The address is derived by the EE rather than taken from the transfer, so a sender can't pay
+/// an address which the recipient's key doesn't own. The account must be proven empty, which
+/// also keeps an address from being paid twice.
+pub fn apply<T: State>(
+    db: &mut T,
+    tx: &StealthTransfer,
+    salt: &[u8; 32],
+    height: usize,
+) -> Result<Address, Error> {
+    if db.nonce(tx.from)? != tx.nonce {
+        return Err(Error::NonceInvalid);
+    }
+
+    if db.value(tx.from)? < tx.amount {
+        return Err(Error::BalanceInsufficient);
+    }
+
+    db.nonce(tx.from)?.next()?;
+
+    let to = tx.to(salt, height);
+    db.create_account(to, tx.recipient.clone())?;
+
+    db.transfer(tx.from, to, tx.amount)?;
+
+    Ok(to)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::account::Account;
+    use crate::state::MockState;
+    use std::collections::BTreeMap;
+
+    fn build_state() -> MockState {
+        let mut accounts: BTreeMap<Address, Account> = BTreeMap::new();
+        accounts.insert(
+            0.into(),
+            Account {
+                pubkey: PublicKey::zero(),
+                nonce: 0,
+                value: 10,
+            },
+        );
+
+        MockState::new(accounts)
+    }
+
+    fn build_transfer() -> StealthTransfer {
+        StealthTransfer {
+            from: 0.into(),
+            recipient: PublicKey::one(),
+            nonce: 0.into(),
+            amount: 4.into(),
+            signature: [0u8; 96],
+        }
+    }
+
+    #[test]
+    fn salt_changes_address() {
+        let one = derive_address(&PublicKey::one(), &[1u8; 32], 16);
+        let two = derive_address(&PublicKey::one(), &[2u8; 32], 16);
+
+        assert_ne!(one, two);
+        assert_eq!(one, derive_address(&PublicKey::one(), &[1u8; 32], 16));
+        assert!(U256::from(<[u8; 32]>::from(one)) < U256::from(1 << 16));
+    }
+
+    #[test]
+    fn pay_one_time_address() {
+        let mut db = build_state();
+        let tx = build_transfer();
+
+        let to = apply(&mut db, &tx, &[1u8; 32], 16).unwrap();
+        assert_eq!(to, tx.to(&[1u8; 32], 16));
+        assert_eq!(db.value(to), Ok(4.into()));
+        assert_eq!(db.pubkey(to), Ok(PublicKey::one()));
+        assert_eq!(db.value(0.into()), Ok(6.into()));
+        assert_eq!(db.nonce(0.into()), Ok(1.into()));
+
+        // The same address can't be paid twice.
+        let tx = StealthTransfer {
+            nonce: 1.into(),
+            ..build_transfer()
+        };
+        assert_eq!(
+            apply(&mut db, &tx, &[1u8; 32], 16),
+            Err(Error::AccountExists)
+        );
+        assert_eq!(db.value(0.into()), Ok(6.into()));
+        assert_eq!(apply(&mut db, &tx, &[2u8; 32], 16).map(|_| ()), Ok(()));
+    }
+}
@@ -1,44 +1,193 @@
 use crate::address::Address;
+use crate::amount::Amount;
+use crate::bls::PublicKey;
+#[cfg(feature = "alloc")]
+use crate::bls::{SignatureVerifier, Unchecked};
+#[cfg(feature = "alloc")]
+use crate::cross::{Claim, CrossTransfer, CLAIM_LENGTH, CROSS_TRANSFER_LENGTH};
+#[cfg(feature = "alloc")]
+use crate::deposit::{Deposit, DEPOSIT_LENGTH};
+#[cfg(feature = "alloc")]
+use crate::domain::Domain;
 use crate::error::Error;
+use crate::hash::{Incremental, H256};
+use crate::nonce::Nonce;
+#[cfg(feature = "alloc")]
 use crate::state::State;
+#[cfg(feature = "alloc")]
+use crate::withdrawal::{Withdrawal, WITHDRAWAL_LENGTH};
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use arrayref::array_ref;
+use core::convert::TryFrom;
 
+#[cfg(feature = "alloc")]
 #[cfg_attr(feature = "std", derive(Clone, Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Transaction {
     Transfer(Transfer),
-    Withdrawal(Transfer),
+    Batch(Batch),
+    Withdrawal(Withdrawal),
     Deposit(Deposit),
+    CrossTransfer(CrossTransfer),
+    Claim(Claim),
+    ChangeKey(ChangeKey),
 }
 
+#[cfg(feature = "alloc")]
 impl Transaction {
     pub fn from(&self) -> Address {
         match self {
             Transaction::Transfer(t) => t.from,
-            Transaction::Withdrawal(_) => unimplemented!(),
-            Transaction::Deposit(_) => unimplemented!(),
+            Transaction::Batch(b) => b.from,
+            Transaction::Withdrawal(w) => w.from,
+            Transaction::Deposit(d) => d.to(),
+            Transaction::CrossTransfer(c) => c.from,
+            Transaction::Claim(c) => c.receipt.to,
+            Transaction::ChangeKey(k) => k.from,
         }
     }
 
-    pub fn nonce(&self) -> u64 {
+    /// Returns the addresses of the accounts the transaction reads or writes, starting with the
+    /// sender.
+    pub fn accounts(&self) -> Vec<Address> {
+        match self {
+            Transaction::Transfer(t) => vec![t.from, t.to],
+            Transaction::Batch(b) => core::iter::once(b.from)
+                .chain(b.payments.iter().map(|p| p.to))
+                .collect(),
+            Transaction::Withdrawal(w) => vec![w.from],
+            Transaction::Deposit(d) => vec![d.to()],
+            Transaction::CrossTransfer(c) => vec![c.from],
+            Transaction::Claim(c) => vec![c.receipt.to, c.claimed_address()],
+            Transaction::ChangeKey(k) => vec![k.from],
+        }
+    }
+
+    /// Returns the fee the sender pays the block's proposer.
+    pub fn fee(&self) -> Amount {
+        match self {
+            Transaction::Transfer(t) => t.fee,
+            Transaction::Batch(b) => b.fee,
+            Transaction::Withdrawal(w) => w.fee,
+            Transaction::Deposit(_) => Amount::zero(),
+            Transaction::CrossTransfer(c) => c.fee,
+            Transaction::Claim(_) => Amount::zero(),
+            Transaction::ChangeKey(k) => k.fee,
+        }
+    }
+
+    /// Returns the last slot the transaction can be included in, if it expires.
+    pub fn valid_until(&self) -> Option<u64> {
+        match self {
+            Transaction::Transfer(t) => t.valid_until,
+            Transaction::Batch(b) => b.valid_until,
+            Transaction::Withdrawal(w) => w.valid_until,
+            Transaction::Deposit(_) => None,
+            Transaction::CrossTransfer(c) => c.valid_until,
+            Transaction::Claim(_) => None,
+            Transaction::ChangeKey(k) => k.valid_until,
+        }
+    }
+
+    /// Returns the nonce the sender must be at. Deposits and claims don't come from an account in
+    /// the EE, so they have no nonce and return zero.
+    pub fn nonce(&self) -> Nonce {
         match self {
             Transaction::Transfer(t) => t.nonce,
-            Transaction::Withdrawal(_) => unimplemented!(),
-            Transaction::Deposit(_) => unimplemented!(),
+            Transaction::Batch(b) => b.nonce,
+            Transaction::Withdrawal(w) => w.nonce,
+            Transaction::Deposit(_) => Nonce::new(0),
+            Transaction::CrossTransfer(c) => c.nonce,
+            Transaction::Claim(_) => Nonce::new(0),
+            Transaction::ChangeKey(k) => k.nonce,
+        }
+    }
+
+    /// Returns the signing root of the transaction, or `None` for deposits and claims, which are
+    /// authorised by their proof rather than a signature.
+    pub fn signing_root(&self) -> Option<H256> {
+        match self {
+            Transaction::Transfer(t) => Some(t.signing_root()),
+            Transaction::Batch(b) => Some(b.signing_root()),
+            Transaction::Withdrawal(w) => Some(w.signing_root()),
+            Transaction::Deposit(_) | Transaction::Claim(_) => None,
+            Transaction::CrossTransfer(c) => Some(c.signing_root()),
+            Transaction::ChangeKey(k) => Some(k.signing_root()),
+        }
+    }
+
+    /// Returns the signature of the transaction, or `None` for deposits and claims.
+    pub fn signature(&self) -> Option<&[u8; 96]> {
+        match self {
+            Transaction::Transfer(t) => Some(&t.signature),
+            Transaction::Batch(b) => Some(&b.signature),
+            Transaction::Withdrawal(w) => Some(&w.signature),
+            Transaction::Deposit(_) | Transaction::Claim(_) => None,
+            Transaction::CrossTransfer(c) => Some(&c.signature),
+            Transaction::ChangeKey(k) => Some(&k.signature),
         }
     }
 
-    pub fn verify<'a, T: State>(&self, db: &T) -> Result<(), Error> {
-        self.verify_signature(db)?;
+    pub fn verify<T: State>(&self, db: &T) -> Result<(), Error> {
+        self.verify_with(db, &Unchecked, &Domain::default())
+    }
+
+    /// Verify the transaction against `db`, checking its signature for `domain` with `verifier`.
+    pub fn verify_with<T: State, V: SignatureVerifier>(
+        &self,
+        db: &T,
+        verifier: &V,
+        domain: &Domain,
+    ) -> Result<(), Error> {
+        self.verify_signature(db, verifier, domain)?;
         self.verify_nonce(db)?;
 
         Ok(())
     }
 
-    pub fn verify_signature<'a, T: State>(&self, _db: &T) -> Result<(), Error> {
-        // TODO: Implement BLS verification
-        Ok(())
+    /// Check the signature against the sender's public key in `db`. The signed message is the
+    /// transaction's signing root in `domain`, so a transaction signed for another chain is
+    /// rejected. Deposits and claims are checked by `Deposit::verify` and `Claim::verify`
+    /// instead.
+    pub fn verify_signature<T: State, V: SignatureVerifier>(
+        &self,
+        db: &T,
+        verifier: &V,
+        domain: &Domain,
+    ) -> Result<(), Error> {
+        if !verifier.enabled() {
+            return Ok(());
+        }
+
+        let (signing_root, signature) = match (self.signing_root(), self.signature()) {
+            (Some(signing_root), Some(signature)) => (signing_root, signature),
+            _ => return Ok(()),
+        };
+
+        let pubkey = db.pubkey(self.from())?;
+        if verifier.verify(&pubkey, &domain.message(&signing_root), signature) {
+            Ok(())
+        } else {
+            Err(Error::SignatureInvalid)
+        }
     }
 
-    pub fn verify_nonce<'a, T: State>(&self, db: &T) -> Result<(), Error> {
+    /// Check that the transaction hasn't expired by the block at `slot`.
+    pub fn verify_slot(&self, slot: u64) -> Result<(), Error> {
+        match self.valid_until() {
+            Some(valid_until) if valid_until < slot => Err(Error::TransactionExpired),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn verify_nonce<T: State>(&self, db: &T) -> Result<(), Error> {
+        if let Transaction::Deposit(_) | Transaction::Claim(_) = self {
+            return Ok(());
+        }
+
         let nonce = db.nonce(self.from())?;
 
         if nonce == self.nonce() {
@@ -50,11 +199,15 @@ impl Transaction {
 }
 
 #[cfg_attr(feature = "std", derive(Clone))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Transfer {
     pub to: Address,
     pub from: Address,
-    pub nonce: u64,
-    pub amount: u64,
+    pub nonce: Nonce,
+    pub amount: Amount,
+    pub fee: Amount,
+    pub valid_until: Option<u64>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::signature"))]
     pub signature: [u8; 96],
 }
 
@@ -63,21 +216,449 @@ impl std::fmt::Debug for Transfer {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(
             f,
-            "{{\n\tto: {:?},\n\tfrom: {:?},\n\tnonce: {},\n\tamount: {},\n\t, signature: {:?}\n}}",
+            "{{\n\tto: {:?},\n\tfrom: {:?},\n\tnonce: {},\n\tamount: {},\n\tfee: {},\n\tvalid_until: {:?},\n\t, signature: {:?}\n}}",
             self.to,
             self.from,
             self.nonce,
             self.amount,
+            self.fee,
+            self.valid_until,
             self.signature.to_vec()
         )
     }
 }
 
+impl Transfer {
+    /// Returns the message the sender signs, which is the hash of the transfer's fields in their
+    /// serialized order.
+    ///
+    /// ```text
+    /// signing_root = sha256(to || from || nonce || amount || fee || valid_until)
+    /// ```
+    pub fn signing_root(&self) -> H256 {
+        let mut hasher = Incremental::new();
+        hasher.update(&<[u8; 32]>::from(self.to));
+        hasher.update(&<[u8; 32]>::from(self.from));
+        hasher.update(&self.nonce.get().to_le_bytes());
+        hasher.update(&self.amount.get().to_le_bytes());
+        hasher.update(&self.fee.get().to_le_bytes());
+        hasher.update(&encode_valid_until(self.valid_until).to_le_bytes());
+        hasher.finalize()
+    }
+
+    /// Returns the SSZ encoding of the transfer, a fixed size container of its fields:
+    ///
+    /// ```text
+    /// to (32) || from (32) || nonce (8, LE) || amount (8, LE) || fee (8, LE)
+    ///     || valid_until (8, LE) || signature (96)
+    /// ```
+    ///
+    /// A `valid_until` of `None` is encoded as 0.
+    pub fn to_ssz(&self) -> [u8; TRANSFER_LENGTH] {
+        let mut ret = [0u8; TRANSFER_LENGTH];
+        ret[0..32].copy_from_slice(&<[u8; 32]>::from(self.to));
+        ret[32..64].copy_from_slice(&<[u8; 32]>::from(self.from));
+        ret[64..72].copy_from_slice(&self.nonce.get().to_le_bytes());
+        ret[72..80].copy_from_slice(&self.amount.get().to_le_bytes());
+        ret[80..88].copy_from_slice(&self.fee.get().to_le_bytes());
+        ret[88..96].copy_from_slice(&encode_valid_until(self.valid_until).to_le_bytes());
+        ret[96..192].copy_from_slice(&self.signature);
+        ret
+    }
+
+    /// Decodes a transfer encoded by `to_ssz`, returning `Error::AmountInvalid` if its amount or
+    /// fee is out of range.
+    pub fn from_ssz(bytes: &[u8; TRANSFER_LENGTH]) -> Result<Self, Error> {
+        Ok(Transfer {
+            to: (*array_ref![bytes, 0, 32]).into(),
+            from: (*array_ref![bytes, 32, 32]).into(),
+            nonce: u64::from_le_bytes(*array_ref![bytes, 64, 8]).into(),
+            amount: Amount::new(u64::from_le_bytes(*array_ref![bytes, 72, 8]))?,
+            fee: Amount::new(u64::from_le_bytes(*array_ref![bytes, 80, 8]))?,
+            valid_until: decode_valid_until(u64::from_le_bytes(*array_ref![bytes, 88, 8])),
+            signature: *array_ref![bytes, 96, 96],
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// A payment of `amount` to `to`, as one of the payments in a `Batch`.
+#[cfg_attr(feature = "std", derive(Clone, Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Payment {
+    pub to: Address,
+    pub amount: Amount,
+}
+
+#[cfg(feature = "alloc")]
+/// Sends from one sender to many recipients with a single signature and nonce, e.g. for payouts,
+/// so each payment costs one credit rather than a whole transfer's witness and signature.
+///
+/// The batch is applied atomically: if the sender can't cover every payment, or any recipient
+/// would overflow, none of them are made.
 #[cfg_attr(feature = "std", derive(Clone, Debug))]
-pub struct Withdrawal;
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Batch {
+    pub from: Address,
+    pub nonce: Nonce,
+    pub payments: Vec<Payment>,
+    pub fee: Amount,
+    pub valid_until: Option<u64>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::signature"))]
+    pub signature: [u8; 96],
+}
+
+#[cfg(feature = "alloc")]
+impl Batch {
+    /// Returns the message the sender signs. The number of payments is included so no batch has
+    /// the same length, and therefore the same root, as a transfer.
+    ///
+    /// ```text
+    /// signing_root = sha256(from || nonce || fee || valid_until || len(payments)
+    ///     || to_0 || amount_0 || ...)
+    /// ```
+    pub fn signing_root(&self) -> H256 {
+        let mut hasher = Incremental::new();
+        hasher.update(&<[u8; 32]>::from(self.from));
+        hasher.update(&self.nonce.get().to_le_bytes());
+        hasher.update(&self.fee.get().to_le_bytes());
+        hasher.update(&encode_valid_until(self.valid_until).to_le_bytes());
+        hasher.update(&(self.payments.len() as u32).to_le_bytes());
+        for payment in self.payments.iter() {
+            hasher.update(&<[u8; 32]>::from(payment.to));
+            hasher.update(&payment.amount.get().to_le_bytes());
+        }
+        hasher.finalize()
+    }
 
+    /// Returns the sum of the payments.
+    pub fn total(&self) -> Result<Amount, Error> {
+        self.payments
+            .iter()
+            .try_fold(Amount::default(), |acc, p| acc.checked_add(p.amount))
+    }
+
+    /// Returns the SSZ encoding of the batch. Its payments are the only variable size field, so
+    /// they are preceded by their count rather than an offset:
+    ///
+    /// ```text
+    /// from (32) || nonce (8, LE) || fee (8, LE) || valid_until (8, LE) || signature (96)
+    ///     || len(payments) (4, LE) || (to (32) || amount (8, LE)) * len(payments)
+    /// ```
+    pub fn to_ssz(&self) -> Vec<u8> {
+        let mut ret = Vec::with_capacity(BATCH_LENGTH + self.payments.len() * PAYMENT_LENGTH);
+        ret.extend_from_slice(&<[u8; 32]>::from(self.from));
+        ret.extend_from_slice(&self.nonce.get().to_le_bytes());
+        ret.extend_from_slice(&self.fee.get().to_le_bytes());
+        ret.extend_from_slice(&encode_valid_until(self.valid_until).to_le_bytes());
+        ret.extend_from_slice(&self.signature);
+        ret.extend_from_slice(&(self.payments.len() as u32).to_le_bytes());
+        for payment in self.payments.iter() {
+            ret.extend_from_slice(&<[u8; 32]>::from(payment.to));
+            ret.extend_from_slice(&payment.amount.get().to_le_bytes());
+        }
+        ret
+    }
+
+    /// Decodes the batch encoded by `to_ssz` at the start of `bytes`, returning it and the length
+    /// of its encoding.
+    pub fn from_ssz(bytes: &[u8]) -> Result<(Self, usize), Error> {
+        if bytes.len() < BATCH_LENGTH {
+            return Err(Error::TransactionsLengthInvalid);
+        }
+
+        let count = u32::from_le_bytes(*array_ref![bytes, 152, 4]) as usize;
+        let len = count
+            .checked_mul(PAYMENT_LENGTH)
+            .and_then(|len| len.checked_add(BATCH_LENGTH))
+            .filter(|len| *len <= bytes.len())
+            .ok_or(Error::TransactionsLengthInvalid)?;
+
+        let payments = bytes[BATCH_LENGTH..len]
+            .chunks_exact(PAYMENT_LENGTH)
+            .map(|p| {
+                Ok(Payment {
+                    to: (*array_ref![p, 0, 32]).into(),
+                    amount: Amount::new(u64::from_le_bytes(*array_ref![p, 32, 8]))?,
+                })
+            })
+            .collect::<Result<Vec<Payment>, Error>>()?;
+
+        let batch = Batch {
+            from: (*array_ref![bytes, 0, 32]).into(),
+            nonce: u64::from_le_bytes(*array_ref![bytes, 32, 8]).into(),
+            fee: Amount::new(u64::from_le_bytes(*array_ref![bytes, 40, 8]))?,
+            valid_until: decode_valid_until(u64::from_le_bytes(*array_ref![bytes, 48, 8])),
+            signature: *array_ref![bytes, 56, 96],
+            payments,
+        };
+
+        Ok((batch, len))
+    }
+}
+
+/// Replaces the public key of `from` with `pubkey`, so a compromised key can be rotated without
+/// moving the account's value to a new address. It is signed by the key being replaced.
 #[cfg_attr(feature = "std", derive(Clone, Debug))]
-pub struct Deposit;
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChangeKey {
+    pub from: Address,
+    pub nonce: Nonce,
+    pub pubkey: PublicKey,
+    pub fee: Amount,
+    pub valid_until: Option<u64>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::signature"))]
+    pub signature: [u8; 96],
+}
+
+impl ChangeKey {
+    /// Returns the message the sender signs. It starts with the change's type byte, so it can
+    /// never be the signing root of another kind of transaction.
+    pub fn signing_root(&self) -> H256 {
+        let mut hasher = Incremental::new();
+        hasher.update(&[TransactionKind::ChangeKey as u8]);
+        hasher.update(&<[u8; 32]>::from(self.from));
+        hasher.update(&self.nonce.get().to_le_bytes());
+        hasher.update(&self.pubkey.as_bytes());
+        hasher.update(&self.fee.get().to_le_bytes());
+        hasher.update(&encode_valid_until(self.valid_until).to_le_bytes());
+        hasher.finalize()
+    }
+
+    /// Returns the SSZ encoding of the change, a fixed size container of its fields:
+    ///
+    /// ```text
+    /// from (32) || nonce (8, LE) || pubkey (48) || fee (8, LE) || valid_until (8, LE)
+    ///     || signature (96)
+    /// ```
+    pub fn to_ssz(&self) -> [u8; CHANGE_KEY_LENGTH] {
+        let mut ret = [0u8; CHANGE_KEY_LENGTH];
+        ret[0..32].copy_from_slice(&<[u8; 32]>::from(self.from));
+        ret[32..40].copy_from_slice(&self.nonce.get().to_le_bytes());
+        ret[40..88].copy_from_slice(&self.pubkey.as_bytes());
+        ret[88..96].copy_from_slice(&self.fee.get().to_le_bytes());
+        ret[96..104].copy_from_slice(&encode_valid_until(self.valid_until).to_le_bytes());
+        ret[104..200].copy_from_slice(&self.signature);
+        ret
+    }
+
+    /// Decodes a change encoded by `to_ssz`.
+    pub fn from_ssz(bytes: &[u8; CHANGE_KEY_LENGTH]) -> Result<Self, Error> {
+        Ok(ChangeKey {
+            from: (*array_ref![bytes, 0, 32]).into(),
+            nonce: u64::from_le_bytes(*array_ref![bytes, 32, 8]).into(),
+            pubkey: PublicKey::new(*array_ref![bytes, 40, 48]),
+            fee: Amount::new(u64::from_le_bytes(*array_ref![bytes, 88, 8]))?,
+            valid_until: decode_valid_until(u64::from_le_bytes(*array_ref![bytes, 96, 8])),
+            signature: *array_ref![bytes, 104, 96],
+        })
+    }
+}
+
+/// Slot 0 is genesis, so a transaction which is only valid until then never needs encoding and 0
+/// stands for no expiry.
+pub(crate) fn encode_valid_until(valid_until: Option<u64>) -> u64 {
+    valid_until.unwrap_or(0)
+}
+
+pub(crate) fn decode_valid_until(valid_until: u64) -> Option<u64> {
+    Some(valid_until).filter(|slot| *slot != 0)
+}
+
+/// Length of a transfer's SSZ encoding.
+pub const TRANSFER_LENGTH: usize = 192;
+
+/// Length of a batch's SSZ encoding without its payments.
+pub const BATCH_LENGTH: usize = 156;
+
+/// Length of each payment in a batch's SSZ encoding.
+pub const PAYMENT_LENGTH: usize = 40;
+
+/// Length of a key change's SSZ encoding.
+pub const CHANGE_KEY_LENGTH: usize = 200;
+
+/// The type byte which prefixes each transaction in a list, so new kinds of transaction can be
+/// added without changing how the existing ones are encoded.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransactionKind {
+    Transfer = 0,
+    Batch = 1,
+    Withdrawal = 2,
+    Deposit = 3,
+    CrossTransfer = 4,
+    Claim = 5,
+    ChangeKey = 6,
+}
+
+impl TryFrom<u8> for TransactionKind {
+    type Error = Error;
+
+    fn try_from(kind: u8) -> Result<Self, Error> {
+        match kind {
+            0 => Ok(TransactionKind::Transfer),
+            1 => Ok(TransactionKind::Batch),
+            2 => Ok(TransactionKind::Withdrawal),
+            3 => Ok(TransactionKind::Deposit),
+            4 => Ok(TransactionKind::CrossTransfer),
+            5 => Ok(TransactionKind::Claim),
+            6 => Ok(TransactionKind::ChangeKey),
+            _ => Err(Error::TransactionKindInvalid),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Transaction {
+    pub fn kind(&self) -> TransactionKind {
+        match self {
+            Transaction::Transfer(_) => TransactionKind::Transfer,
+            Transaction::Batch(_) => TransactionKind::Batch,
+            Transaction::Withdrawal(_) => TransactionKind::Withdrawal,
+            Transaction::Deposit(_) => TransactionKind::Deposit,
+            Transaction::CrossTransfer(_) => TransactionKind::CrossTransfer,
+            Transaction::Claim(_) => TransactionKind::Claim,
+            Transaction::ChangeKey(_) => TransactionKind::ChangeKey,
+        }
+    }
+
+    /// Returns the transaction's type byte followed by the SSZ encoding of its body, like an SSZ
+    /// union.
+    pub fn to_ssz(&self) -> Vec<u8> {
+        let mut ret = vec![self.kind() as u8];
+
+        match self {
+            Transaction::Transfer(t) => ret.extend_from_slice(&t.to_ssz()),
+            Transaction::Batch(b) => ret.extend(b.to_ssz()),
+            Transaction::Withdrawal(w) => ret.extend_from_slice(&w.to_ssz()),
+            Transaction::Deposit(d) => ret.extend_from_slice(&d.to_ssz()),
+            Transaction::CrossTransfer(c) => ret.extend_from_slice(&c.to_ssz()),
+            Transaction::Claim(c) => ret.extend_from_slice(&c.to_ssz()),
+            Transaction::ChangeKey(k) => ret.extend_from_slice(&k.to_ssz()),
+        }
+
+        ret
+    }
+
+    /// Returns the transaction's id, the SHA-256 of its `to_ssz` encoding. Unlike the signing
+    /// root it covers the signature and type byte, so it identifies exactly the transaction which
+    /// was included rather than the message its sender signed.
+    pub fn hash(&self) -> H256 {
+        let mut hasher = Incremental::new();
+        hasher.update(&self.to_ssz());
+        hasher.finalize()
+    }
+
+    /// Decodes the transaction at the start of `bytes`, returning it and the length of its
+    /// encoding. Returns `Error::TransactionKindInvalid` if its type byte is unknown or can't be
+    /// processed, and `Error::TransactionsLengthInvalid` if `bytes` ends before it does.
+    pub fn from_ssz(bytes: &[u8]) -> Result<(Transaction, usize), Error> {
+        let (kind, body) = bytes
+            .split_first()
+            .ok_or(Error::TransactionsLengthInvalid)?;
+
+        match TransactionKind::try_from(*kind)? {
+            TransactionKind::Transfer => {
+                if body.len() < TRANSFER_LENGTH {
+                    return Err(Error::TransactionsLengthInvalid);
+                }
+
+                let transfer = Transfer::from_ssz(array_ref![body, 0, TRANSFER_LENGTH])?;
+                Ok((Transaction::Transfer(transfer), 1 + TRANSFER_LENGTH))
+            }
+            TransactionKind::Batch => {
+                let (batch, len) = Batch::from_ssz(body)?;
+                Ok((Transaction::Batch(batch), 1 + len))
+            }
+            TransactionKind::Deposit => {
+                if body.len() < DEPOSIT_LENGTH {
+                    return Err(Error::TransactionsLengthInvalid);
+                }
+
+                let deposit = Deposit::from_ssz(array_ref![body, 0, DEPOSIT_LENGTH])?;
+                Ok((Transaction::Deposit(deposit), 1 + DEPOSIT_LENGTH))
+            }
+            TransactionKind::Withdrawal => {
+                if body.len() < WITHDRAWAL_LENGTH {
+                    return Err(Error::TransactionsLengthInvalid);
+                }
+
+                let withdrawal = Withdrawal::from_ssz(array_ref![body, 0, WITHDRAWAL_LENGTH])?;
+                Ok((Transaction::Withdrawal(withdrawal), 1 + WITHDRAWAL_LENGTH))
+            }
+            TransactionKind::CrossTransfer => {
+                if body.len() < CROSS_TRANSFER_LENGTH {
+                    return Err(Error::TransactionsLengthInvalid);
+                }
+
+                let transfer = CrossTransfer::from_ssz(array_ref![body, 0, CROSS_TRANSFER_LENGTH])?;
+                Ok((
+                    Transaction::CrossTransfer(transfer),
+                    1 + CROSS_TRANSFER_LENGTH,
+                ))
+            }
+            TransactionKind::Claim => {
+                if body.len() < CLAIM_LENGTH {
+                    return Err(Error::TransactionsLengthInvalid);
+                }
+
+                let claim = Claim::from_ssz(array_ref![body, 0, CLAIM_LENGTH])?;
+                Ok((Transaction::Claim(claim), 1 + CLAIM_LENGTH))
+            }
+            TransactionKind::ChangeKey => {
+                if body.len() < CHANGE_KEY_LENGTH {
+                    return Err(Error::TransactionsLengthInvalid);
+                }
+
+                let change = ChangeKey::from_ssz(array_ref![body, 0, CHANGE_KEY_LENGTH])?;
+                Ok((Transaction::ChangeKey(change), 1 + CHANGE_KEY_LENGTH))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// Returns the encoding of a list of transactions, which is the concatenation of each one's
+/// `Transaction::to_ssz`. Each encoding knows its own length, so the list needs no offsets.
+pub fn encode_transactions(transactions: &[Transaction]) -> Vec<u8> {
+    transactions.iter().flat_map(|tx| tx.to_ssz()).collect()
+}
+
+#[cfg(feature = "alloc")]
+/// Decodes the first `count` transactions encoded at the start of `bytes`, returning them and the
+/// length of their encoding.
+pub fn decode_transactions_prefix(
+    bytes: &[u8],
+    count: usize,
+) -> Result<(Vec<Transaction>, usize), Error> {
+    // `count` is untrusted, but every transaction is at least a byte long.
+    let mut ret = Vec::with_capacity(count.min(bytes.len()));
+    let mut offset = 0;
+
+    for _ in 0..count {
+        let (tx, len) = Transaction::from_ssz(&bytes[offset..])?;
+        ret.push(tx);
+        offset += len;
+    }
+
+    Ok((ret, offset))
+}
+
+#[cfg(feature = "alloc")]
+/// Decodes a list of transactions encoded by `encode_transactions`, returning
+/// `Error::TransactionsLengthInvalid` if `bytes` doesn't end with the last one.
+pub fn decode_transactions(bytes: &[u8]) -> Result<Vec<Transaction>, Error> {
+    let mut ret = Vec::new();
+    let mut offset = 0;
+
+    while offset < bytes.len() {
+        let (tx, len) = Transaction::from_ssz(&bytes[offset..])?;
+        ret.push(tx);
+        offset += len;
+    }
+
+    Ok(ret)
+}
 
 #[cfg(test)]
 mod test {
@@ -92,12 +673,34 @@ mod test {
         Transaction::Transfer(Transfer {
             to: 0.into(),
             from: 1.into(),
-            nonce: 3,
-            amount: 4,
+            nonce: 3.into(),
+            amount: 4.into(),
+            fee: 1.into(),
+            valid_until: Some(10),
             signature: [0u8; 96],
         })
     }
 
+    #[test]
+    fn hash() {
+        let transfer = build_transfer();
+        assert_eq!(
+            hex::encode(transfer.hash()),
+            "b4fe291ea5ee0ee374a8940e3b49d99b0f839e235fbb8e824624f4cada2473b5"
+        );
+
+        // Decoding and re-encoding doesn't change the id.
+        let decoded = decode_transactions(&transfer.to_ssz()).unwrap();
+        assert_eq!(decoded[0].hash(), transfer.hash());
+
+        // The signature is part of the id but not the signing root.
+        let mut resigned = transfer.clone();
+        if let Transaction::Transfer(t) = &mut resigned {
+            t.signature = [1u8; 96];
+        }
+        assert_ne!(resigned.hash(), transfer.hash());
+    }
+
     #[test]
     fn general_from() {
         let transfer = build_transfer();
@@ -107,7 +710,27 @@ mod test {
     #[test]
     fn general_nonce() {
         let transfer = build_transfer();
-        assert_eq!(transfer.nonce(), 3);
+        assert_eq!(transfer.nonce(), 3.into());
+    }
+
+    #[test]
+    fn general_fee() {
+        let transfer = build_transfer();
+        assert_eq!(transfer.fee(), 1.into());
+    }
+
+    #[test]
+    fn verify_slot() {
+        let transfer = build_transfer();
+        assert_eq!(transfer.verify_slot(10), Ok(()));
+        assert_eq!(transfer.verify_slot(11), Err(Error::TransactionExpired));
+
+        let mut t = match transfer {
+            Transaction::Transfer(t) => t,
+            _ => unreachable!(),
+        };
+        t.valid_until = None;
+        assert_eq!(Transaction::Transfer(t).verify_slot(u64::MAX), Ok(()));
     }
 
     #[test]
@@ -130,4 +753,219 @@ mod test {
         let mem = MockState::new(accounts);
         assert_eq!(transfer.verify_nonce(&mem), Ok(()));
     }
+
+    /// Accepts signatures which are the sender's public key followed by the signing root.
+    struct Echo;
+
+    impl SignatureVerifier for Echo {
+        fn verify(&self, pubkey: &PublicKey, message: &H256, signature: &[u8; 96]) -> bool {
+            signature[0..48] == pubkey.as_bytes()[..] && signature[48..80] == message[..]
+        }
+    }
+
+    #[test]
+    fn verify_signature() {
+        let mut accounts: BTreeMap<Address, Account> = BTreeMap::new();
+        accounts.insert(
+            1.into(),
+            Account {
+                pubkey: PublicKey::one(),
+                nonce: 3,
+                value: 0,
+            },
+        );
+        let mem = MockState::new(accounts);
+
+        let mut transfer = match build_transfer() {
+            Transaction::Transfer(t) => t,
+            _ => unreachable!(),
+        };
+        let domain = Domain::default();
+        assert_eq!(
+            Transaction::Transfer(transfer.clone()).verify_with(&mem, &Echo, &domain),
+            Err(Error::SignatureInvalid)
+        );
+        assert_eq!(Transaction::Transfer(transfer.clone()).verify(&mem), Ok(()));
+
+        transfer.signature[0..48].copy_from_slice(&PublicKey::one().as_bytes());
+        let message = domain.message(&transfer.signing_root());
+        transfer.signature[48..80].copy_from_slice(&message);
+        let transfer = Transaction::Transfer(transfer);
+        assert_eq!(transfer.verify_with(&mem, &Echo, &domain), Ok(()));
+
+        // The same signature isn't valid on another chain.
+        let testnet = Domain {
+            chain_id: 5,
+            ..domain
+        };
+        assert_eq!(
+            transfer.verify_with(&mem, &Echo, &testnet),
+            Err(Error::SignatureInvalid)
+        );
+    }
+
+    #[test]
+    fn signing_root_covers_every_field() {
+        let transfer = match build_transfer() {
+            Transaction::Transfer(t) => t,
+            _ => unreachable!(),
+        };
+
+        let mut other = transfer.clone();
+        other.amount = 5.into();
+        assert_ne!(transfer.signing_root(), other.signing_root());
+
+        let mut other = transfer.clone();
+        other.fee = 2.into();
+        assert_ne!(transfer.signing_root(), other.signing_root());
+
+        let mut other = transfer.clone();
+        other.valid_until = None;
+        assert_ne!(transfer.signing_root(), other.signing_root());
+
+        let mut other = transfer.clone();
+        other.signature = [1u8; 96];
+        assert_eq!(transfer.signing_root(), other.signing_root());
+    }
+
+    #[test]
+    fn ssz_round_trip() {
+        let mut transfer = match build_transfer() {
+            Transaction::Transfer(t) => t,
+            _ => unreachable!(),
+        };
+        transfer.signature = [9u8; 96];
+
+        let bytes = transfer.to_ssz();
+        assert_eq!(bytes[32..64], <[u8; 32]>::from(Address::from(1))[..]);
+        assert_eq!(bytes[64..72], 3u64.to_le_bytes());
+        assert_eq!(bytes[72..80], 4u64.to_le_bytes());
+        assert_eq!(bytes[80..88], 1u64.to_le_bytes());
+        assert_eq!(bytes[88..96], 10u64.to_le_bytes());
+        assert_eq!(bytes[96..], [9u8; 96][..]);
+        assert_eq!(
+            Transfer::from_ssz(&bytes).map(|t| t.to_ssz()[..] == bytes[..]),
+            Ok(true)
+        );
+
+        transfer.valid_until = None;
+        assert_eq!(
+            Transfer::from_ssz(&transfer.to_ssz()).map(|t| t.valid_until),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn ssz_list() {
+        let batch = Transaction::Batch(Batch {
+            from: 1.into(),
+            nonce: 3.into(),
+            payments: vec![
+                Payment {
+                    to: 0.into(),
+                    amount: 4.into(),
+                },
+                Payment {
+                    to: 2.into(),
+                    amount: 5.into(),
+                },
+            ],
+            fee: 1.into(),
+            valid_until: None,
+            signature: [7u8; 96],
+        });
+        let transactions = vec![build_transfer(), batch, build_transfer()];
+        let bytes = encode_transactions(&transactions);
+
+        assert_eq!(bytes[0], TransactionKind::Transfer as u8);
+        assert_eq!(bytes[1 + TRANSFER_LENGTH], TransactionKind::Batch as u8);
+        assert_eq!(
+            bytes.len(),
+            3 + 2 * TRANSFER_LENGTH + BATCH_LENGTH + 2 * PAYMENT_LENGTH
+        );
+        assert_eq!(
+            decode_transactions(&bytes).map(|txs| encode_transactions(&txs)),
+            Ok(bytes.clone())
+        );
+        assert_eq!(decode_transactions(&[]).map(|txs| txs.len()), Ok(0));
+        assert_eq!(
+            decode_transactions(&bytes[..bytes.len() - 1]).map(|txs| txs.len()),
+            Err(Error::TransactionsLengthInvalid)
+        );
+
+        // A prefix stops after `count` transactions, wherever the list continues.
+        let (prefix, len) = decode_transactions_prefix(&bytes, 2).unwrap();
+        assert_eq!(prefix.len(), 2);
+        assert_eq!(len, bytes.len() - 1 - TRANSFER_LENGTH);
+    }
+
+    #[test]
+    fn unknown_kinds() {
+        let mut bytes = build_transfer().to_ssz();
+
+        for kind in [7u8, 255].iter() {
+            bytes[0] = *kind;
+            assert_eq!(
+                decode_transactions(&bytes).map(|txs| txs.len()),
+                Err(Error::TransactionKindInvalid)
+            );
+        }
+
+        // A transfer's body is too short to be a deposit.
+        bytes[0] = TransactionKind::Deposit as u8;
+        assert_eq!(
+            decode_transactions(&bytes).map(|txs| txs.len()),
+            Err(Error::TransactionsLengthInvalid)
+        );
+        assert_eq!(
+            TransactionKind::try_from(7),
+            Err(Error::TransactionKindInvalid)
+        );
+    }
+
+    #[test]
+    fn truncated_batch() {
+        let batch = Batch::from_ssz(&[0u8; BATCH_LENGTH]).map(|(b, len)| (b.payments.len(), len));
+        assert_eq!(batch, Ok((0, BATCH_LENGTH)));
+
+        // A count of payments beyond the end of the data.
+        let mut bytes = [0u8; BATCH_LENGTH + PAYMENT_LENGTH];
+        bytes[152] = 2;
+        assert_eq!(
+            Batch::from_ssz(&bytes).map(|(_, len)| len),
+            Err(Error::TransactionsLengthInvalid)
+        );
+    }
+
+    #[test]
+    fn batch_signing_root() {
+        let batch = Batch {
+            from: 1.into(),
+            nonce: 3.into(),
+            payments: vec![Payment {
+                to: 0.into(),
+                amount: 4.into(),
+            }],
+            fee: 1.into(),
+            valid_until: None,
+            signature: [0u8; 96],
+        };
+        let transfer = match build_transfer() {
+            Transaction::Transfer(t) => t,
+            _ => unreachable!(),
+        };
+        assert_ne!(batch.signing_root(), transfer.signing_root());
+
+        let mut other = batch.clone();
+        other.payments.push(Payment {
+            to: 2.into(),
+            amount: 0.into(),
+        });
+        assert_ne!(batch.signing_root(), other.signing_root());
+        assert_eq!(other.total(), Ok(4.into()));
+        assert_eq!(
+            Transaction::Batch(other).accounts(),
+            [1.into(), 0.into(), 2.into()]
+        );
+    }
 }
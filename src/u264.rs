@@ -1,5 +1,9 @@
-use arrayref::array_refs;
-use bigint::U256;
+#[cfg(feature = "alloc")]
+use alloc::format;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use arrayref::{array_ref, array_refs};
+use bigint::{U256, U512};
 
 #[cfg(feature = "std")]
 use std::cmp::{Eq, Ordering};
@@ -8,7 +12,7 @@ use std::fmt;
 #[cfg(feature = "std")]
 use std::hash::{Hash, Hasher};
 #[cfg(feature = "std")]
-use std::ops::{Add, BitAnd, Not, Shl, Shr, Sub};
+use std::ops::{Add, BitAnd, BitOr, BitXor, Not, Shl, Shr, Sub};
 
 #[cfg(not(feature = "std"))]
 use core::cmp::{Eq, Ordering};
@@ -17,7 +21,7 @@ use core::fmt;
 #[cfg(not(feature = "std"))]
 use core::hash::{Hash, Hasher};
 #[cfg(not(feature = "std"))]
-use core::ops::{Add, BitAnd, Not, Shl, Shr, Sub};
+use core::ops::{Add, BitAnd, BitOr, BitXor, Not, Shl, Shr, Sub};
 
 #[repr(C)]
 #[derive(Copy, Clone)]
@@ -149,14 +153,157 @@ impl U264 {
         (U264(ret), carry)
     }
 
+    /// Multiplies the two values, returning the low 264 bits of the product and whether any of
+    /// the higher bits were set.
+    pub fn overflowing_mul(self, other: Self) -> (Self, bool) {
+        let Self(ref me) = self;
+        let Self(ref you) = other;
+
+        // Sum the partial products of each pair of bytes into the column of their combined
+        // weight. A column holds at most 33 products of two bytes, so it can't overflow a `u32`.
+        let mut columns = [0u32; 66];
+        for (i, a) in me.iter().enumerate() {
+            for (j, b) in you.iter().enumerate() {
+                columns[i + j] += u32::from(*a) * u32::from(*b);
+            }
+        }
+
+        let mut ret = [0u8; 33];
+        let mut carry = 0u32;
+        let mut overflow = false;
+
+        for (i, column) in columns.iter().enumerate() {
+            let v = column + carry;
+            carry = v >> 8;
+
+            if i < 33 {
+                ret[i] = v as u8;
+            } else {
+                overflow |= v as u8 != 0;
+            }
+        }
+
+        (U264(ret), overflow || carry != 0)
+    }
+
+    /// Returns `self + other`, or `None` if the sum doesn't fit in 264 bits.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        match self.overflowing_add(other) {
+            (v, false) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns `self - other`, or `None` if `other` is greater than `self`.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        match self.overflowing_sub(other) {
+            (v, false) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns `self * other`, or `None` if the product doesn't fit in 264 bits.
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        match self.overflowing_mul(other) {
+            (v, false) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns `self + other` modulo `2**264`.
+    pub fn wrapping_add(self, other: Self) -> Self {
+        self.overflowing_add(other).0
+    }
+
+    /// Returns `self - other` modulo `2**264`.
+    pub fn wrapping_sub(self, other: Self) -> Self {
+        self.overflowing_sub(other).0
+    }
+
+    /// Returns `self * other` modulo `2**264`.
+    pub fn wrapping_mul(self, other: Self) -> Self {
+        self.overflowing_mul(other).0
+    }
+
+    /// Returns `self + other`, or the greatest value if the sum doesn't fit in 264 bits.
+    pub fn saturating_add(self, other: Self) -> Self {
+        self.checked_add(other).unwrap_or_else(Self::max_value)
+    }
+
+    /// Returns `self - other`, or zero if `other` is greater than `self`.
+    pub fn saturating_sub(self, other: Self) -> Self {
+        self.checked_sub(other).unwrap_or_else(Self::zero)
+    }
+
+    pub fn max_value() -> Self {
+        Self([255; 33])
+    }
+
+    /// Returns the number of bits needed to represent the value, i.e. the position of the most
+    /// significant set bit plus one, or zero for zero.
+    pub fn bits(&self) -> usize {
+        match self.0.iter().rposition(|b| *b != 0) {
+            Some(i) => i * 8 + 8 - self.0[i].leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    /// Returns whether the bit at `index` is set, counting from the least significant bit.
+    /// Panics if `index` is 264 or greater.
+    pub fn bit(&self, index: usize) -> bool {
+        self.0[index / 8] & (1 << (index % 8)) != 0
+    }
+
+    pub const fn from_le_bytes(bytes: [u8; 33]) -> Self {
+        Self(bytes)
+    }
+
+    pub fn from_be_bytes(mut bytes: [u8; 33]) -> Self {
+        bytes.reverse();
+        Self(bytes)
+    }
+
+    pub const fn to_le_bytes(self) -> [u8; 33] {
+        self.0
+    }
+
+    pub fn to_be_bytes(self) -> [u8; 33] {
+        let mut ret = self.0;
+        ret.reverse();
+        ret
+    }
+
+    /// Parses up to 66 hex digits, with or without a `0x` prefix, or returns `None` if `s` isn't
+    /// hex or doesn't fit in 264 bits.
+    pub fn from_hex_str(s: &str) -> Option<Self> {
+        let s = s.strip_prefix("0x").unwrap_or(s);
+        if s.is_empty() || s.len() > 66 {
+            return None;
+        }
+
+        let mut ret = [0u8; 33];
+        for (i, c) in s.bytes().rev().enumerate() {
+            let digit = (c as char).to_digit(16)? as u8;
+            ret[i / 2] |= digit << (4 * (i % 2));
+        }
+
+        Some(Self(ret))
+    }
+
+    /// Returns the value as `0x` prefixed hex without leading zeros, e.g. `0x1a` for 26.
+    #[cfg(feature = "alloc")]
+    pub fn to_hex(&self) -> String {
+        format!("{:#x}", self)
+    }
+
     pub fn low_u32(&self) -> u32 {
-        let &Self(ref arr) = self;
+        let Self(ref arr) = self;
         let (arr, _) = array_refs!(arr, 4, 29);
         u32::from_le_bytes(*arr)
     }
 
     pub fn as_le_bytes(&self) -> &[u8; 33] {
-        let &U264(ref me) = self;
+        let U264(ref me) = self;
         me
     }
 }
@@ -166,7 +313,7 @@ impl Add for U264 {
 
     fn add(self, other: U264) -> U264 {
         let (o, v) = self.overflowing_add(other);
-        assert!(v == false);
+        assert!(!v);
         o
     }
 }
@@ -176,7 +323,7 @@ impl Sub for U264 {
 
     fn sub(self, other: U264) -> U264 {
         let (o, v) = self.overflowing_sub(other);
-        assert!(v == false);
+        assert!(!v);
         o
     }
 }
@@ -229,6 +376,36 @@ impl Shl<usize> for U264 {
     }
 }
 
+/// Shifts by any other primitive integer in the same way as by a `usize`. Panics if the shift is
+/// negative.
+macro_rules! impl_shifts {
+    ($($t:ty),*) => {
+        $(
+            impl Shl<$t> for U264 {
+                type Output = U264;
+
+                #[allow(clippy::unnecessary_cast)]
+                fn shl(self, shift: $t) -> U264 {
+                    assert!(shift >= 0 as $t, "shift is negative");
+                    self << (shift as usize)
+                }
+            }
+
+            impl Shr<$t> for U264 {
+                type Output = U264;
+
+                #[allow(clippy::unnecessary_cast)]
+                fn shr(self, shift: $t) -> U264 {
+                    assert!(shift >= 0 as $t, "shift is negative");
+                    self >> (shift as usize)
+                }
+            }
+        )*
+    };
+}
+
+impl_shifts!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, isize);
+
 impl BitAnd for U264 {
     type Output = Self;
 
@@ -259,6 +436,32 @@ impl BitAnd for U264 {
     }
 }
 
+impl BitOr for U264 {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        let Self(mut ret) = self;
+        for (a, b) in ret.iter_mut().zip(rhs.0.iter()) {
+            *a |= b;
+        }
+
+        ret.into()
+    }
+}
+
+impl BitXor for U264 {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        let Self(mut ret) = self;
+        for (a, b) in ret.iter_mut().zip(rhs.0.iter()) {
+            *a ^= b;
+        }
+
+        ret.into()
+    }
+}
+
 impl Not for U264 {
     type Output = Self;
 
@@ -350,18 +553,16 @@ impl PartialEq for U264 {
 
 impl PartialOrd for U264 {
     fn partial_cmp(&self, other: &U264) -> Option<Ordering> {
-        let Self(ref me) = self;
-        let Self(ref you) = other;
-
-        Some(me.cmp(you))
+        Some(self.cmp(other))
     }
 }
 
+/// The bytes are little endian, so they're compared starting from the most significant.
 impl Ord for U264 {
     fn cmp(&self, other: &Self) -> Ordering {
         let Self(ref me) = self;
         let Self(ref you) = other;
-        me.cmp(you)
+        me.iter().rev().cmp(you.iter().rev())
     }
 }
 
@@ -381,6 +582,23 @@ impl From<U256> for U264 {
     }
 }
 
+// Chops off everything above the least significant 33 bytes
+impl From<U512> for U264 {
+    fn from(n: U512) -> U264 {
+        let mut buf = [0u8; 64];
+        n.to_little_endian(&mut buf);
+        U264(*array_ref![buf, 0, 33])
+    }
+}
+
+impl From<U264> for U512 {
+    fn from(n: U264) -> U512 {
+        let mut buf = [0u8; 64];
+        buf[0..33].copy_from_slice(&n.0);
+        U512::from_little_endian(&buf)
+    }
+}
+
 impl From<u8> for U264 {
     fn from(n: u8) -> U264 {
         U264([
@@ -404,7 +622,68 @@ impl From<U264> for [u8; 33] {
 
 impl fmt::Debug for U264 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", &self.0[..])
+        write!(f, "{:#x}", self)
+    }
+}
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+impl fmt::LowerHex for U264 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bytes = self.to_be_bytes();
+
+        // Every byte is written with two digits, and then the leading zeros are dropped, leaving
+        // one digit for zero.
+        let mut digits = [0u8; 66];
+        for (i, b) in bytes.iter().enumerate() {
+            digits[2 * i] = HEX_DIGITS[(b >> 4) as usize];
+            digits[2 * i + 1] = HEX_DIGITS[(b & 0xf) as usize];
+        }
+
+        let first = digits
+            .iter()
+            .position(|d| *d != b'0')
+            .unwrap_or(digits.len() - 1);
+
+        f.pad_integral(
+            true,
+            "0x",
+            core::str::from_utf8(&digits[first..]).expect("Digits to be ASCII"),
+        )
+    }
+}
+
+/// Writes the value in decimal, by repeatedly dividing it by ten.
+impl fmt::Display for U264 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut n = self.to_be_bytes();
+
+        // Values below 2**264 have at most 80 decimal digits, which are found from the least
+        // significant.
+        let mut digits = [0u8; 80];
+        let mut first = digits.len();
+
+        loop {
+            let mut remainder = 0u16;
+            for byte in n.iter_mut() {
+                let v = (remainder << 8) | u16::from(*byte);
+                *byte = (v / 10) as u8;
+                remainder = v % 10;
+            }
+
+            first -= 1;
+            digits[first] = b'0' + remainder as u8;
+
+            if n.iter().all(|b| *b == 0) {
+                break;
+            }
+        }
+
+        f.pad_integral(
+            true,
+            "",
+            core::str::from_utf8(&digits[first..]).expect("Digits to be ASCII"),
+        )
     }
 }
 
@@ -456,4 +735,174 @@ mod tests {
         assert_eq!(x.shl(1), U264::from(2));
     }
 
+    #[test]
+    fn overflowing_sub() {
+        let x = U264::one();
+        assert_eq!(x.overflowing_sub(x), (U264::zero(), false));
+        assert_eq!(U264::zero().overflowing_sub(x), (U264::max_value(), true));
+    }
+
+    #[test]
+    fn overflowing_mul() {
+        let x = U264::from(200);
+        assert_eq!(x.overflowing_mul(U264::zero()), (U264::zero(), false));
+        // 200 * 200 = 0x9c40
+        assert_eq!(
+            x.overflowing_mul(x),
+            (U264::from(0x40) + (U264::from(0x9c) << 8), false)
+        );
+
+        // The product of the largest values is `2**528 - 2**265 + 1`.
+        assert_eq!(
+            U264::max_value().overflowing_mul(U264::max_value()),
+            (U264::one(), true)
+        );
+
+        // Carries out of the top byte are detected as well as set high bytes.
+        let top: U264 = U264::one() << 263;
+        assert_eq!(top.overflowing_mul(U264::from(2)), (U264::zero(), true));
+        assert_eq!(top.overflowing_mul(U264::one()), (top, false));
+    }
+
+    #[test]
+    fn checked() {
+        let max = U264::max_value();
+        let one = U264::one();
+
+        assert_eq!(max.checked_add(one), None);
+        assert_eq!(max.checked_add(U264::zero()), Some(max));
+        assert_eq!(U264::zero().checked_sub(one), None);
+        assert_eq!(max.checked_sub(max), Some(U264::zero()));
+        assert_eq!(max.checked_mul(U264::from(2)), None);
+        assert_eq!((one << 131usize).checked_mul(one << 132), Some(one << 263));
+        assert_eq!((one << 132usize).checked_mul(one << 132), None);
+    }
+
+    #[test]
+    fn wrapping_and_saturating() {
+        let max = U264::max_value();
+        let one = U264::one();
+
+        assert_eq!(max.wrapping_add(one), U264::zero());
+        assert_eq!(U264::zero().wrapping_sub(one), max);
+        assert_eq!(max.wrapping_mul(max), one);
+        assert_eq!(max.saturating_add(one), max);
+        assert_eq!(U264::zero().saturating_sub(one), U264::zero());
+        assert_eq!(U264::from(7).saturating_sub(one), U264::from(6));
+    }
+
+    #[test]
+    fn hex() {
+        let x = (U264::one() << 256usize) + U264::from(0x1a);
+
+        assert_eq!(x.to_hex(), format!("0x1{}1a", "0".repeat(62)));
+        assert_eq!(format!("{:x}", U264::from(0x1a)), "1a");
+        assert_eq!(format!("{:#x}", U264::zero()), "0x0");
+        assert_eq!(format!("{:?}", U264::from(16)), "0x10");
+        assert_eq!(format!("{:#06x}", U264::from(16)), "0x0010");
+        assert_eq!(format!("{:#x}", crate::address::Address::from(255)), "0xff");
+
+        assert_eq!(U264::from_hex_str(&x.to_hex()), Some(x));
+        assert_eq!(U264::from_hex_str("1A"), Some(U264::from(26)));
+        assert_eq!(U264::from_hex_str(&"f".repeat(66)), Some(U264::max_value()));
+        assert_eq!(U264::from_hex_str(&"f".repeat(67)), None);
+        assert_eq!(U264::from_hex_str("0x"), None);
+        assert_eq!(U264::from_hex_str("0xg"), None);
+    }
+
+    #[test]
+    fn decimal() {
+        assert_eq!(U264::zero().to_string(), "0");
+        assert_eq!(U264::from(200).to_string(), "200");
+        assert_eq!((U264::one() << 64usize).to_string(), "18446744073709551616");
+        assert_eq!(format!("{:>5}", U264::from(42)), "   42");
+    }
+
+    #[test]
+    fn byte_order() {
+        let mut be = [0u8; 33];
+        be[31] = 1;
+        be[32] = 2;
+
+        let x = U264::from_be_bytes(be);
+        assert_eq!(x, U264::from(2) + (U264::one() << 8));
+        assert_eq!(x.to_be_bytes(), be);
+        assert_eq!(U264::from_le_bytes(x.to_le_bytes()), x);
+        assert_eq!(x.to_le_bytes()[0], 2);
+    }
+
+    #[test]
+    fn ordering() {
+        let mut values = vec![
+            U264::one() << 256,
+            U264::from(2),
+            U264::from(1) << 8,
+            U264::zero(),
+            U264::from(255),
+        ];
+        values.sort();
+
+        assert_eq!(
+            values,
+            vec![
+                U264::zero(),
+                U264::from(2),
+                U264::from(255),
+                U264::from(1) << 8,
+                U264::one() << 256,
+            ]
+        );
+        assert!(U264::max_value() > U264::one() << 263);
+    }
+
+    #[test]
+    fn bit_operations() {
+        let x = U264::from(0b1100) + (U264::one() << 260);
+        let y = U264::from(0b1010);
+
+        assert_eq!(x & y, U264::from(0b1000));
+        assert_eq!(x | y, U264::from(0b1110) + (U264::one() << 260));
+        assert_eq!(x ^ y, U264::from(0b0110) + (U264::one() << 260));
+        assert_eq!(!U264::zero(), U264::max_value());
+        assert_eq!(x ^ x, U264::zero());
+    }
+
+    #[test]
+    fn bits() {
+        assert_eq!(U264::zero().bits(), 0);
+        assert_eq!(U264::one().bits(), 1);
+        assert_eq!(U264::from(255).bits(), 8);
+        assert_eq!((U264::one() << 8usize).bits(), 9);
+        assert_eq!(U264::max_value().bits(), 264);
+
+        let n = U264::from(0b101) + (U264::one() << 263usize);
+        assert!(n.bit(0) && !n.bit(1) && n.bit(2) && n.bit(263));
+        assert!(!n.bit(262));
+    }
+
+    #[test]
+    fn u512_conversions() {
+        let n = (U264::one() << 263usize) + U264::from(7);
+        assert_eq!(U264::from(U512::from(n)), n);
+        assert_eq!(U512::from(n), (U512::one() << 263) + U512::from(7));
+        assert_eq!(U264::from(U512::one() << 264), U264::zero());
+    }
+
+    #[test]
+    fn shift_by_any_integer() {
+        let x = U264::one();
+
+        assert_eq!(x << 9u8, x << 9usize);
+        assert_eq!(x << 9u32, x << 9usize);
+        assert_eq!(x << 9i32, x << 9usize);
+        assert_eq!((x << 200u64) >> 200u128, x);
+        assert_eq!((x << 263i64) >> 263isize, x);
+        assert_eq!(x << 264u16, U264::zero());
+    }
+
+    #[test]
+    #[should_panic(expected = "shift is negative")]
+    fn negative_shift() {
+        let _ = U264::one() << -1i32;
+    }
 }
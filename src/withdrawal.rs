@@ -0,0 +1,180 @@
+//! Withdrawals move value out of the EE, e.g. back to the beacon chain or to another shard. A
+//! withdrawal burns `amount` from its sender like a transfer to nobody, and emits a
+//! `WithdrawalReceipt` which the destination credits once it is proven against the block's
+//! withdrawals root.
+//!
+//! An account's address is the same wherever its owner's public key is used, so a receipt only
+//! names the account the value left.
+
+use crate::address::Address;
+use crate::amount::Amount;
+use crate::error::Error;
+use crate::hash::{hash_pair, list_root, Incremental, H256};
+use crate::nonce::Nonce;
+use crate::transaction::{decode_valid_until, encode_valid_until, TransactionKind};
+use alloc::vec::Vec;
+use arrayref::array_ref;
+
+/// Length of a withdrawal's SSZ encoding.
+pub const WITHDRAWAL_LENGTH: usize = 160;
+
+/// Depth of the tree of a block's withdrawal receipts, which limits a block to `2**32` of them.
+pub const WITHDRAWALS_TREE_DEPTH: usize = 32;
+
+/// A withdrawal of `amount` from `from`.
+#[cfg_attr(feature = "std", derive(Clone, Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Withdrawal {
+    pub from: Address,
+    pub nonce: Nonce,
+    pub amount: Amount,
+    pub fee: Amount,
+    pub valid_until: Option<u64>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::signature"))]
+    pub signature: [u8; 96],
+}
+
+impl Withdrawal {
+    /// Returns the message the sender signs. It starts with the withdrawal's type byte, so it can
+    /// never be the signing root of a transfer.
+    pub fn signing_root(&self) -> H256 {
+        let mut hasher = Incremental::new();
+        hasher.update(&[TransactionKind::Withdrawal as u8]);
+        hasher.update(&<[u8; 32]>::from(self.from));
+        hasher.update(&self.nonce.get().to_le_bytes());
+        hasher.update(&self.amount.get().to_le_bytes());
+        hasher.update(&self.fee.get().to_le_bytes());
+        hasher.update(&encode_valid_until(self.valid_until).to_le_bytes());
+        hasher.finalize()
+    }
+
+    /// Returns the receipt the withdrawal emits once it is applied.
+    pub fn receipt(&self) -> WithdrawalReceipt {
+        WithdrawalReceipt {
+            address: self.from,
+            amount: self.amount,
+            nonce: self.nonce,
+        }
+    }
+
+    /// Returns the SSZ encoding of the withdrawal, a fixed size container of its fields:
+    ///
+    /// ```text
+    /// from (32) || nonce (8, LE) || amount (8, LE) || fee (8, LE) || valid_until (8, LE)
+    ///     || signature (96)
+    /// ```
+    pub fn to_ssz(&self) -> [u8; WITHDRAWAL_LENGTH] {
+        let mut ret = [0u8; WITHDRAWAL_LENGTH];
+        ret[0..32].copy_from_slice(&<[u8; 32]>::from(self.from));
+        ret[32..40].copy_from_slice(&self.nonce.get().to_le_bytes());
+        ret[40..48].copy_from_slice(&self.amount.get().to_le_bytes());
+        ret[48..56].copy_from_slice(&self.fee.get().to_le_bytes());
+        ret[56..64].copy_from_slice(&encode_valid_until(self.valid_until).to_le_bytes());
+        ret[64..160].copy_from_slice(&self.signature);
+        ret
+    }
+
+    /// Decodes a withdrawal encoded by `to_ssz`.
+    pub fn from_ssz(bytes: &[u8; WITHDRAWAL_LENGTH]) -> Result<Self, Error> {
+        Ok(Withdrawal {
+            from: (*array_ref![bytes, 0, 32]).into(),
+            nonce: u64::from_le_bytes(*array_ref![bytes, 32, 8]).into(),
+            amount: Amount::new(u64::from_le_bytes(*array_ref![bytes, 40, 8]))?,
+            fee: Amount::new(u64::from_le_bytes(*array_ref![bytes, 48, 8]))?,
+            valid_until: decode_valid_until(u64::from_le_bytes(*array_ref![bytes, 56, 8])),
+            signature: *array_ref![bytes, 64, 96],
+        })
+    }
+}
+
+/// The record of a withdrawal of `amount` from `address` at `nonce`. The sender's nonce only ever
+/// increases, so no two receipts from the EE are the same.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WithdrawalReceipt {
+    pub address: Address,
+    pub amount: Amount,
+    pub nonce: Nonce,
+}
+
+impl WithdrawalReceipt {
+    /// Returns the `hash_tree_root` of the receipt as an SSZ container:
+    ///
+    /// ```text
+    /// root = hash(hash(address, amount), hash(nonce, 0))
+    /// ```
+    pub fn root(&self) -> H256 {
+        let mut amount = [0u8; 32];
+        amount[0..8].copy_from_slice(&self.amount.get().to_le_bytes());
+        let mut nonce = [0u8; 32];
+        nonce[0..8].copy_from_slice(&self.nonce.get().to_le_bytes());
+
+        hash_pair(
+            &hash_pair(&<[u8; 32]>::from(self.address), &amount),
+            &hash_pair(&nonce, &[0u8; 32]),
+        )
+    }
+}
+
+/// Returns the withdrawals root of a block's `receipts`, the root of their SSZ list.
+pub fn withdrawals_root<'a, I>(receipts: I) -> H256
+where
+    I: IntoIterator<Item = &'a WithdrawalReceipt>,
+{
+    let leaves: Vec<H256> = receipts.into_iter().map(|r| r.root()).collect();
+    list_root(&leaves, WITHDRAWALS_TREE_DEPTH)
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn build_withdrawal() -> Withdrawal {
+        Withdrawal {
+            from: 3.into(),
+            nonce: 2.into(),
+            amount: 5.into(),
+            fee: 1.into(),
+            valid_until: Some(9),
+            signature: [7u8; 96],
+        }
+    }
+
+    #[test]
+    fn ssz_round_trip() {
+        let withdrawal = build_withdrawal();
+        let bytes = withdrawal.to_ssz();
+        let decoded = Withdrawal::from_ssz(&bytes).unwrap();
+
+        assert_eq!(decoded.to_ssz()[..], bytes[..]);
+        assert_eq!(decoded.signing_root(), withdrawal.signing_root());
+        assert_eq!(decoded.valid_until, Some(9));
+    }
+
+    #[test]
+    fn receipts_root() {
+        let receipt = build_withdrawal().receipt();
+        assert_eq!(
+            receipt,
+            WithdrawalReceipt {
+                address: 3.into(),
+                amount: 5.into(),
+                nonce: 2.into(),
+            }
+        );
+
+        // hash_tree_root(List[WithdrawalReceipt, 2**32]) as computed by the SSZ spec.
+        assert_eq!(
+            hex::encode(receipt.root()),
+            "b22018931b3138b413bb16e29a3a257ffa109cc874c889c93efd7cdcf4a5c448"
+        );
+        assert_eq!(
+            hex::encode(withdrawals_root(&[])),
+            "d70a234731285c6804c2a4f56711ddb8c82c99740f207854891028af34e27e5e"
+        );
+        assert_eq!(
+            hex::encode(withdrawals_root(&[receipt, receipt])),
+            "819b3917559bb924366497e69846a5149f450f90886bec04096f29efd49d6cc7"
+        );
+    }
+}
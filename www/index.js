@@ -0,0 +1,34 @@
+import init, { run } from "./pkg/www.js";
+
+const output = document.getElementById("output");
+
+function render(report) {
+  const check = (valid) => (valid ? "valid" : "INVALID");
+  const rows = report.balances
+    .map((b) => `<tr><td>0x${b.address}</td><td>${b.before}</td><td>${b.after}</td></tr>`)
+    .join("");
+  const txs = report.transactions
+    .map((tx, i) => `<li>${i} (0x${tx.hash}): ${tx.applied ? `applied, nonce ${tx.nonce}` : tx.error}</li>`)
+    .join("");
+
+  output.innerHTML = `
+    <p>pre root: 0x${report.pre_root} (${check(report.pre_root_valid)})</p>
+    <p>post root: 0x${report.post_root} (${check(report.post_root_valid)})</p>
+    <h2>transactions</h2>
+    <ul>${txs}</ul>
+    <h2>balances</h2>
+    <table><tr><td>address</td><td>before</td><td>after</td></tr>${rows}</table>`;
+}
+
+init().then(() => {
+  document.getElementById("run").addEventListener("click", () => {
+    const pkg = document.getElementById("package").value;
+    const height = parseInt(document.getElementById("height").value, 10);
+
+    try {
+      render(JSON.parse(run(pkg, height)));
+    } catch (e) {
+      output.textContent = e;
+    }
+  });
+});
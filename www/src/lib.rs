@@ -0,0 +1,106 @@
+use arrayref::array_ref;
+use sheth::address::Address;
+use sheth::amount::Amount;
+use sheth::process::{apply_transactions, credit_fees};
+use sheth::state::imp::new_checked;
+use sheth::state::root::imp_root;
+use sheth::state::State;
+use sheth::{deserialize_proposer, deserialize_transactions, BLOB_HEADER_LENGTH};
+use std::collections::BTreeSet;
+use wasm_bindgen::prelude::*;
+
+/// Runs a package built by `client package` (e.g. `<pre root> <post root> <blob>` in hex) and
+/// returns a JSON report of the roots and the balances of every account the block touched.
+#[wasm_bindgen]
+pub fn run(package: &str, height: usize) -> Result<String, JsValue> {
+    let parts: Vec<Vec<u8>> = package
+        .split_whitespace()
+        .map(hex::decode)
+        .collect::<Result<_, _>>()
+        .map_err(|_| JsValue::from_str("package is not valid hex"))?;
+
+    if parts.len() != 3
+        || parts[0].len() != 32
+        || parts[1].len() != 32
+        || parts[2].len() < BLOB_HEADER_LENGTH
+    {
+        return Err(JsValue::from_str(
+            "expected a package of the form `<pre root> <post root> <blob>`",
+        ));
+    }
+
+    let (pre_root, post_root, mut blob) = (parts[0].clone(), parts[1].clone(), parts[2].clone());
+
+    let tx_count = u32::from_le_bytes(*array_ref![blob, 0, 4]) as usize;
+    let (transactions, offset) = deserialize_transactions(&blob, tx_count)
+        .map_err(|e| JsValue::from_str(&format!("{:?}", e)))?;
+    let proposer = deserialize_proposer(&blob);
+    let mut mem = new_checked(&mut blob[offset..], height + 3)
+        .map_err(|e| JsValue::from_str(&format!("{:?}", e)))?;
+
+    let addresses: BTreeSet<Address> = transactions
+        .iter()
+        .flat_map(|tx| tx.accounts())
+        .chain(core::iter::once(proposer))
+        .collect();
+
+    let before: Vec<Amount> = addresses
+        .iter()
+        .map(|a| mem.value(*a).unwrap_or_default())
+        .collect();
+
+    let computed_pre_root = imp_root(&mem);
+    let results = apply_transactions(&mut mem, &transactions)
+        .map_err(|e| JsValue::from_str(&format!("{:?}", e)))?;
+    results
+        .iter()
+        .flatten()
+        .try_fold(Amount::default(), |acc, receipt| {
+            acc.checked_add(receipt.fee)
+        })
+        .and_then(|fees| credit_fees(&mut mem, proposer, fees))
+        .map_err(|e| JsValue::from_str(&format!("{:?}", e)))?;
+    let computed_post_root = imp_root(&mem);
+
+    let balances: Vec<String> = addresses
+        .iter()
+        .zip(before.iter())
+        .map(|(address, before)| {
+            format!(
+                "{{\"address\":\"{}\",\"before\":{},\"after\":{}}}",
+                hex::encode(<[u8; 32]>::from(*address)),
+                before,
+                mem.value(*address).unwrap_or_default()
+            )
+        })
+        .collect();
+
+    let transactions: Vec<String> = transactions
+        .iter()
+        .zip(results.iter())
+        .map(|(tx, result)| match result {
+            Ok(receipt) => format!(
+                "{{\"hash\":\"{}\",\"applied\":true,\"nonce\":{},\"value\":{}}}",
+                hex::encode(tx.hash()),
+                receipt.nonce,
+                receipt.value
+            ),
+            Err(e) => format!(
+                "{{\"hash\":\"{}\",\"applied\":false,\"error\":\"{:?}\"}}",
+                hex::encode(tx.hash()),
+                e
+            ),
+        })
+        .collect();
+
+    Ok(format!(
+        "{{\"pre_root\":\"{}\",\"post_root\":\"{}\",\"pre_root_valid\":{},\
+         \"post_root_valid\":{},\"transactions\":[{}],\"balances\":[{}]}}",
+        hex::encode(computed_pre_root),
+        hex::encode(computed_post_root),
+        computed_pre_root[..] == pre_root[..],
+        computed_post_root[..] == post_root[..],
+        transactions.join(","),
+        balances.join(",")
+    ))
+}